@@ -0,0 +1,117 @@
+//! Measures time and heap allocations of parsing a game with many nested variations
+//!
+//! Run with `cargo bench --bench branches`. Parsing used to copy the parsed text of every
+//! variation once for each variation containing it, so the cost grew with the nesting depth.
+//! The baseline repeats that work by cloning each variation once per level above it.
+
+use std::fmt::Write;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+use sgf_parser::*;
+
+#[allow(dead_code)]
+mod common;
+
+use common::{CountingAllocator, ALLOCATIONS};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const RUNS: u32 = 20;
+
+/// Variations at each branch point
+const WIDTH: usize = 3;
+
+/// Nesting depth of the variations
+const DEPTH: usize = 6;
+
+/// Nodes in each sequence between two branch points
+const SEQUENCE: usize = 5;
+
+/// Writes a sequence of `SEQUENCE` moves, followed by `WIDTH` variations down to `depth`
+fn write_variation(out: &mut String, depth: usize, seed: &mut usize) {
+    out.push('(');
+    for _ in 0..SEQUENCE {
+        *seed = (*seed * 31 + 7) % 361;
+        let color = if seed.is_multiple_of(2) { 'B' } else { 'W' };
+        let (x, y) = (
+            (b'a' + (*seed % 19) as u8) as char,
+            (b'a' + (*seed / 19) as u8) as char,
+        );
+        write!(out, ";{}[{}{}]", color, x, y).unwrap();
+        if seed.is_multiple_of(7) {
+            write!(out, "C[Variation at depth {}]", depth).unwrap();
+        }
+    }
+    if depth > 0 {
+        for _ in 0..WIDTH {
+            write_variation(out, depth - 1, seed);
+        }
+    }
+    out.push(')');
+}
+
+/// Builds an SGF game of nested variations, with `SEQUENCE` nodes between branch points
+fn build_sgf() -> String {
+    let mut out = String::from("(;GM[1]FF[4]SZ[19]KM[6.5]");
+    let mut seed = 1;
+    for _ in 0..WIDTH {
+        write_variation(&mut out, DEPTH, &mut seed);
+    }
+    out.push(')');
+    out
+}
+
+/// Clones each variation once for each level above it, like parsing did before
+fn clone_variations(tree: &GameTree) -> usize {
+    tree.variations
+        .iter()
+        .map(|variation| variation.clone().total_node_count() + clone_variations(variation))
+        .sum()
+}
+
+fn measure(name: &str, f: impl Fn() -> usize) {
+    let mut elapsed = Duration::default();
+    let mut allocations = 0;
+    for _ in 0..RUNS {
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        let count = f();
+        elapsed += start.elapsed();
+        allocations += ALLOCATIONS.load(Ordering::Relaxed) - before;
+        assert!(count > 0);
+    }
+    println!(
+        "{:<16} {:>10.1?} {:>10} allocations",
+        name,
+        elapsed / RUNS,
+        allocations / RUNS as usize,
+    );
+}
+
+fn main() {
+    let input = build_sgf();
+    let tree = parse(&input).unwrap();
+    println!(
+        "Parsing {} nodes, {} bytes, average of {} runs",
+        tree.total_node_count(),
+        input.len(),
+        RUNS
+    );
+    measure("baseline", || {
+        let tree = parse(&input).unwrap();
+        tree.total_node_count() + clone_variations(&tree)
+    });
+    measure("parse", || parse(&input).unwrap().total_node_count());
+    measure("parse_bytes", || {
+        parse_bytes(input.as_bytes()).unwrap().total_node_count()
+    });
+    measure("intern_strings", || {
+        let options = ParserOptions::new().intern_strings(true);
+        parse_with_options(&input, &options)
+            .unwrap()
+            .tree
+            .total_node_count()
+    });
+}
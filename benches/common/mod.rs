@@ -0,0 +1,83 @@
+//! Shared setup of the benchmarks
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use sgf_parser::*;
+
+/// Number of nodes in the tree from `build_tree`
+pub const NODES: usize = 5000;
+
+/// Allocator counting all allocations and reallocations in `ALLOCATIONS`
+///
+/// Each benchmark installs it with `#[global_allocator]`.
+pub struct CountingAllocator;
+
+pub static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Builds a game of `NODES` nodes with a comment and a label every ten moves, and a short
+/// variation every hundred moves
+pub fn build_tree() -> GameTree {
+    let mut root = GameNode::from(vec![
+        SgfToken::Size(19, 19),
+        SgfToken::Komi(Real::new(6.5).unwrap()),
+        SgfToken::Event(TokenString::from_static("Benchmark")),
+    ]);
+    root.tokens.push(SgfToken::Charset(Encoding::UTF8));
+    let mut nodes = vec![root];
+    let mut variations = vec![];
+    for index in 1..NODES {
+        let color = if index % 2 == 0 {
+            Color::White
+        } else {
+            Color::Black
+        };
+        let coordinate = ((index % 19 + 1) as u8, (index / 19 % 19 + 1) as u8);
+        let mut tokens = vec![SgfToken::Move {
+            color,
+            action: Action::Move(coordinate.0, coordinate.1),
+        }];
+        if index % 10 == 0 {
+            tokens.push(SgfToken::Comment(format!("Move {} [note]", index).into()));
+            tokens.push(SgfToken::Label {
+                label: TokenString::from_static("A"),
+                coordinate,
+            });
+            tokens.push(SgfToken::Triangle { coordinate });
+        }
+        if index % 100 == 0 {
+            variations.push(GameTree {
+                nodes: vec![GameNode::from(vec![SgfToken::Move {
+                    color,
+                    action: Action::Pass,
+                }])],
+                variations: vec![],
+            });
+        }
+        nodes.push(GameNode::from(tokens));
+    }
+    variations.insert(
+        0,
+        GameTree {
+            nodes: nodes.split_off(NODES / 2),
+            variations: vec![],
+        },
+    );
+    GameTree { nodes, variations }
+}
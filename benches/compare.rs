@@ -0,0 +1,193 @@
+//! Compares the throughput and heap allocations of parsing and serializing SGF over a bundled
+//! corpus, against the `sgf-parse` and `goban` crates
+//!
+//! Run with `cargo bench --bench compare`. The corpus is the games in `tests/sgf`, and the
+//! generated game of the `serialize` benchmark. Every way of reading SGF offered by this crate
+//! is measured next to `sgf-parse`, so their costs can be compared with each other. `goban`
+//! replays the moves on a board while reading, so it is measured separately, on the recorded
+//! games only, since the generated game plays on occupied points.
+//!
+//! To measure another crate on the same corpus, add it as a dev-dependency, and push a
+//! `Contender` for it. Contenders return a count from their result, like the number of nodes,
+//! so the work isn't optimized away, and must be able to read every game they are given.
+
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+use sgf_parser::*;
+
+mod common;
+
+use common::{build_tree, CountingAllocator, ALLOCATIONS};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const RUNS: u32 = 20;
+
+/// A named function to measure, run once for each game of the corpus
+struct Contender<T: ?Sized> {
+    name: &'static str,
+    run: fn(&T) -> usize,
+}
+
+fn parsers() -> Vec<Contender<str>> {
+    vec![
+        Contender {
+            name: "parse",
+            run: |input| parse(input).unwrap().total_node_count(),
+        },
+        Contender {
+            name: "preserve_format",
+            run: |input| {
+                let options = ParserOptions::new().preserve_format(true);
+                let outcome = parse_with_options(input, &options).unwrap();
+                outcome.tree.total_node_count()
+            },
+        },
+        Contender {
+            name: "parse_bytes",
+            run: |input| parse_bytes(input.as_bytes()).unwrap().total_node_count(),
+        },
+        Contender {
+            name: "Parser",
+            run: |input| {
+                let mut parser = Parser::new();
+                let mut events = parser.feed(input.as_bytes()).unwrap();
+                events.extend(parser.finish().unwrap());
+                events.len()
+            },
+        },
+        Contender {
+            name: "parse_syntax",
+            run: |input| parse_syntax(input).unwrap().len(),
+        },
+        Contender {
+            name: "sgf-parse",
+            run: |input| {
+                let trees = sgf_parse::go::parse(input).unwrap();
+                trees.iter().map(sgf_parse_node_count).sum()
+            },
+        },
+    ]
+}
+
+/// Contenders that replay the moves of a game on a board while reading it
+fn replayers() -> Vec<Contender<str>> {
+    vec![
+        Contender {
+            name: "Board::replay",
+            run: |input| {
+                let board = Board::replay(&parse(input).unwrap()).unwrap();
+                let (black, white) = board.estimate_area();
+                (black + white) as usize
+            },
+        },
+        Contender {
+            name: "goban",
+            run: |input| {
+                let game = goban::rules::game::Game::from_sgf(input).unwrap();
+                let (black, white) = game.goban().number_of_stones();
+                (black + white) as usize
+            },
+        },
+    ]
+}
+
+fn sgf_parse_node_count(node: &sgf_parse::SgfNode<sgf_parse::go::Prop>) -> usize {
+    1 + node.children().map(sgf_parse_node_count).sum::<usize>()
+}
+
+fn serializers() -> Vec<Contender<GameTree>> {
+    vec![
+        Contender {
+            name: "to_string",
+            run: |tree| tree.to_string().len(),
+        },
+        Contender {
+            name: "serialize_with",
+            run: |tree| {
+                let options = WriterOptions::new().token_order(TokenOrder::Canonical);
+                tree.serialize_with(&options).len()
+            },
+        },
+    ]
+}
+
+/// Runs `contender` over all `inputs`, and prints the average time, throughput of `bytes`,
+/// and allocations of a run
+fn measure<T: ?Sized>(contender: &Contender<T>, inputs: &[&T], bytes: usize) {
+    let mut elapsed = Duration::default();
+    let mut allocations = 0;
+    for _ in 0..RUNS {
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        let count: usize = inputs.iter().map(|input| (contender.run)(input)).sum();
+        elapsed += start.elapsed();
+        allocations += ALLOCATIONS.load(Ordering::Relaxed) - before;
+        assert!(count > 0, "{} gave no result", contender.name);
+    }
+    let average = elapsed / RUNS;
+    println!(
+        "{:<16} {:>10.1?} {:>8.1} MB/s {:>10} allocations",
+        contender.name,
+        average,
+        bytes as f64 / average.as_secs_f64() / 1e6,
+        allocations / RUNS as usize,
+    );
+}
+
+fn main() {
+    let generated = build_tree().to_string();
+    let inputs = vec![
+        include_str!("../tests/sgf/ShusakuvsInseki.sgf"),
+        include_str!("../tests/sgf/empty_node.sgf"),
+        generated.as_str(),
+    ];
+    let bytes = inputs.iter().map(|input| input.len()).sum();
+    println!(
+        "Parsing {} games of {} bytes, average of {} runs",
+        inputs.len(),
+        bytes,
+        RUNS
+    );
+    for contender in parsers() {
+        measure(&contender, &inputs, bytes);
+    }
+
+    let games = &inputs[..2];
+    let bytes = games.iter().map(|input| input.len()).sum();
+    println!(
+        "Replaying {} games of {} bytes, average of {} runs",
+        games.len(),
+        bytes,
+        RUNS
+    );
+    for contender in replayers() {
+        measure(&contender, games, bytes);
+    }
+
+    let trees: Vec<GameTree> = inputs.iter().map(|input| parse(input).unwrap()).collect();
+    let trees: Vec<&GameTree> = trees.iter().collect();
+    let bytes = trees.iter().map(|tree| tree.to_string().len()).sum();
+    println!(
+        "Serializing {} games of {} bytes, average of {} runs",
+        trees.len(),
+        bytes,
+        RUNS
+    );
+    for contender in serializers() {
+        measure(&contender, &trees, bytes);
+    }
+
+    let nodes: Vec<_> = inputs
+        .iter()
+        .flat_map(|input| sgf_parse::go::parse(input).unwrap())
+        .collect();
+    let nodes: Vec<&sgf_parse::SgfNode<sgf_parse::go::Prop>> = nodes.iter().collect();
+    let contender = Contender {
+        name: "sgf-parse",
+        run: |node: &sgf_parse::SgfNode<sgf_parse::go::Prop>| node.serialize().len(),
+    };
+    measure(&contender, &nodes, bytes);
+}
@@ -0,0 +1,72 @@
+//! Measures time and heap allocations of serializing a tree with 5000 nodes
+//!
+//! Run with `cargo bench --bench serialize`. The per-node approach used before serialization
+//! wrote into a single buffer is kept here as a baseline.
+
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+use sgf_parser::*;
+
+mod common;
+
+use common::{build_tree, CountingAllocator, ALLOCATIONS, NODES};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const RUNS: u32 = 20;
+
+/// Serializes like the writer did before, one `String` per token and node
+fn baseline(tree: &GameTree) -> String {
+    let nodes = tree
+        .nodes
+        .iter()
+        .map(|node| {
+            let mut tokens: Vec<String> = node.tokens.iter().map(String::from).collect();
+            tokens.sort();
+            let mut out = vec![";".to_string()];
+            out.extend(tokens);
+            out.join("")
+        })
+        .collect::<String>();
+    let variations = tree.variations.iter().map(baseline).collect::<String>();
+    format!("({}{})", nodes, variations)
+}
+
+fn measure(name: &str, f: impl Fn() -> String) {
+    let mut elapsed = Duration::default();
+    let mut allocations = 0;
+    let mut len = 0;
+    for _ in 0..RUNS {
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        let out = f();
+        elapsed += start.elapsed();
+        allocations += ALLOCATIONS.load(Ordering::Relaxed) - before;
+        len = out.len();
+    }
+    println!(
+        "{:<16} {:>10.1?} {:>10} allocations {:>10} bytes",
+        name,
+        elapsed / RUNS,
+        allocations / RUNS as usize,
+        len
+    );
+}
+
+fn main() {
+    let tree = build_tree();
+    assert_eq!(baseline(&tree), tree.to_string());
+    println!("Serializing {} nodes, average of {} runs", NODES, RUNS);
+    measure("baseline", || baseline(&tree));
+    measure("to_string", || tree.to_string());
+    measure("serialize_with", || {
+        tree.serialize_with(&WriterOptions::default())
+    });
+    measure("write_to", || {
+        let mut out = String::with_capacity(tree.estimated_sgf_len());
+        tree.write_to(&mut out).unwrap();
+        out
+    });
+}
@@ -0,0 +1,92 @@
+use crate::{Color, Emphasis, GameTree, SgfToken, TreePath};
+
+/// Judgement of a position, from the `GB`, `GW`, `DM` and `UC` properties
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Judgement {
+    /// The position is good for `color`
+    GoodFor(Color, Emphasis),
+    /// The position is even
+    Even(Emphasis),
+    /// The position is unclear
+    Unclear(Emphasis),
+}
+
+impl GameTree {
+    /// Finds all nodes marked as hotspots with `HO`, in all variations
+    ///
+    /// Nodes are listed in the order they appear in the SGF text.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee]HO[1](;W[cc]HO[2])(;W[gg]))").unwrap();
+    /// assert_eq!(
+    ///     tree.hotspots(),
+    ///     vec![
+    ///         (TreePath::new(vec![], 1), Emphasis::Normal),
+    ///         (TreePath::new(vec![0], 0), Emphasis::Emphasized),
+    ///     ]
+    /// );
+    /// ```
+    pub fn hotspots(&self) -> Vec<(TreePath, Emphasis)> {
+        annotated_nodes(self, &|token| match token {
+            SgfToken::Hotspot(emphasis) => Some(*emphasis),
+            _ => None,
+        })
+    }
+
+    /// Finds all nodes with a position judgement, in all variations
+    ///
+    /// A node with several judgements is listed once for each of them.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee]GB[1];W[cc];B[gg]DM[2])").unwrap();
+    /// assert_eq!(
+    ///     tree.judged_positions(),
+    ///     vec![
+    ///         (TreePath::new(vec![], 1), Judgement::GoodFor(Color::Black, Emphasis::Normal)),
+    ///         (TreePath::new(vec![], 3), Judgement::Even(Emphasis::Emphasized)),
+    ///     ]
+    /// );
+    /// ```
+    pub fn judged_positions(&self) -> Vec<(TreePath, Judgement)> {
+        annotated_nodes(self, &|token| match token {
+            SgfToken::GoodFor { color, emphasis } => Some(Judgement::GoodFor(*color, *emphasis)),
+            SgfToken::Even(emphasis) => Some(Judgement::Even(*emphasis)),
+            SgfToken::Unclear(emphasis) => Some(Judgement::Unclear(*emphasis)),
+            _ => None,
+        })
+    }
+}
+
+fn annotated_nodes<T>(
+    tree: &GameTree,
+    annotation: &dyn Fn(&SgfToken) -> Option<T>,
+) -> Vec<(TreePath, T)> {
+    let mut found = vec![];
+    collect_annotations(tree, &mut vec![], annotation, &mut found);
+    found
+}
+
+fn collect_annotations<T>(
+    tree: &GameTree,
+    variations: &mut Vec<usize>,
+    annotation: &dyn Fn(&SgfToken) -> Option<T>,
+    found: &mut Vec<(TreePath, T)>,
+) {
+    for (index, node) in tree.nodes.iter().enumerate() {
+        found.extend(
+            node.tokens
+                .iter()
+                .filter_map(annotation)
+                .map(|value| (TreePath::new(variations.clone(), index), value)),
+        );
+    }
+    for (index, variation) in tree.variations.iter().enumerate() {
+        variations.push(index);
+        collect_annotations(variation, variations, annotation, found);
+        variations.pop();
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{property_info, Color, GameTree, SgfToken, TokenString};
+
+/// Selects which information `GameTree::anonymize` removes
+///
+/// Only properties holding identifying text are touched, so the position and the moves of the
+/// game stay the same. All categories except `private` are enabled by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AnonymizeOptions {
+    /// Replace `PB` and `PW` with `Black` and `White`, and remove `BT` and `WT`
+    pub players: bool,
+    /// Remove `BR` and `WR`
+    pub ranks: bool,
+    /// Remove `EV`, `PC` and `GN`
+    pub event: bool,
+    /// Remove `C`, `GC` and `N`
+    pub comments: bool,
+    /// Remove `SO`, `US`, `AN` and `CP`
+    pub sources: bool,
+    /// Remove all properties not defined by FF[4], like application specific properties
+    pub private: bool,
+}
+
+impl Default for AnonymizeOptions {
+    fn default() -> Self {
+        AnonymizeOptions {
+            players: true,
+            ranks: true,
+            event: true,
+            comments: true,
+            sources: true,
+            private: false,
+        }
+    }
+}
+
+impl GameTree {
+    /// Removes or replaces personal information in all nodes, including all variations
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree =
+    ///     parse("(;PB[Shusaku]BR[6d]PC[Edo]AB[dd];C[Nice move]N[Ear-reddening]B[aa])").unwrap();
+    /// tree.anonymize(&AnonymizeOptions::default());
+    ///
+    /// let sgf: String = tree.into();
+    /// assert_eq!(sgf, "(;AB[dd]PB[Black];B[aa])");
+    /// ```
+    pub fn anonymize(&mut self, options: &AnonymizeOptions) {
+        for node in &mut self.nodes {
+            node.tokens.retain(|token| !is_removed(token, options));
+            if options.players {
+                for token in &mut node.tokens {
+                    if let SgfToken::PlayerName { color, name } = token {
+                        *name = match color {
+                            Color::Black => TokenString::from_static("Black"),
+                            Color::White => TokenString::from_static("White"),
+                        };
+                    }
+                }
+            }
+        }
+        for variation in &mut self.variations {
+            variation.anonymize(options);
+        }
+    }
+}
+
+fn is_removed(token: &SgfToken, options: &AnonymizeOptions) -> bool {
+    match token {
+        SgfToken::PlayerRank { .. } => options.ranks,
+        SgfToken::Event(_) | SgfToken::Place(_) | SgfToken::GameName(_) => options.event,
+        SgfToken::Comment(_) | SgfToken::NodeName(_) => options.comments,
+        SgfToken::Copyright(_) => options.sources,
+        SgfToken::Unknown((identifier, _)) => match identifier.as_str() {
+            "BT" | "WT" => options.players,
+            "GC" => options.comments,
+            "SO" | "US" | "AN" => options.sources,
+            identifier => options.private && property_info(identifier).is_none(),
+        },
+        _ => false,
+    }
+}
@@ -0,0 +1,109 @@
+use std::panic;
+use std::path::Path;
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::task;
+
+use crate::{parse_bytes_with, Event, GameTree, Parser, ParserOptions, SgfError};
+
+/// Size of the chunks read by `AsyncEventReader`
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Reads and parses an SGF file without blocking the async runtime, with the default options
+///
+/// See `parse_file_async_with`.
+///
+/// ```rust,no_run
+/// # async fn run() -> Result<(), sgf_parser::SgfError> {
+/// let tree = sgf_parser::parse_file_async("game.sgf").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn parse_file_async(path: impl AsRef<Path>) -> Result<GameTree, SgfError> {
+    parse_file_async_with(path, &ParserOptions::default()).await
+}
+
+/// Reads and parses an SGF file like `parse_bytes_with`, without blocking the async runtime
+///
+/// The file is read asynchronously, then decoded and parsed on the blocking thread pool of
+/// the runtime, since parsing large files takes a while.
+///
+/// ```rust,no_run
+/// # async fn run() -> Result<(), sgf_parser::SgfError> {
+/// use sgf_parser::*;
+///
+/// let options = ParserOptions::new().charset_policy(CharsetPolicy::TranscodeKnown);
+/// let tree = parse_file_async_with("game.sgf", &options).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn parse_file_async_with(
+    path: impl AsRef<Path>,
+    options: &ParserOptions,
+) -> Result<GameTree, SgfError> {
+    let content = tokio::fs::read(path).await.map_err(SgfError::io_error)?;
+    let options = options.clone();
+    let parsed = task::spawn_blocking(move || parse_bytes_with(&content, &options));
+    match parsed.await {
+        Ok(result) => result,
+        Err(err) if err.is_panic() => panic::resume_unwind(err.into_panic()),
+        Err(err) => Err(SgfError::io_error(err)),
+    }
+}
+
+/// Reads SGF events from an `AsyncRead` source, one chunk at a time
+///
+/// ```rust,no_run
+/// # async fn run() -> Result<(), sgf_parser::SgfError> {
+/// use sgf_parser::*;
+///
+/// let file = tokio::fs::File::open("game.sgf").await.unwrap();
+/// let mut reader = AsyncEventReader::new(file);
+/// while let Some(events) = reader.next().await? {
+///     for event in events {
+///         if let Event::Node(node) = event {
+///             println!("{:?}", node);
+///         }
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncEventReader<R> {
+    reader: R,
+    parser: Parser,
+    buffer: Vec<u8>,
+    done: bool,
+}
+
+impl<R: AsyncRead + Unpin> AsyncEventReader<R> {
+    /// Creates a new event reader for `reader`
+    pub fn new(reader: R) -> Self {
+        AsyncEventReader {
+            reader,
+            parser: Parser::new(),
+            buffer: vec![0; CHUNK_SIZE],
+            done: false,
+        }
+    }
+
+    /// Reads the next chunk, and returns the events it completed
+    ///
+    /// Returns `None` once the input has been exhausted
+    pub async fn next(&mut self) -> Result<Option<Vec<Event>>, SgfError> {
+        if self.done {
+            return Ok(None);
+        }
+        let read = self
+            .reader
+            .read(&mut self.buffer)
+            .await
+            .map_err(SgfError::io_error)?;
+        if read == 0 {
+            self.done = true;
+            self.parser.finish().map(Some)
+        } else {
+            self.parser.feed(&self.buffer[..read]).map(Some)
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Command line tool to inspect, lint and convert SGF files, built with the `cli` feature
+//!
+//! ```text
+//! sgf-tool info <file>
+//! sgf-tool lint <file>
+//! sgf-tool to-json <file> [output]
+//! sgf-tool normalize <file> [output]
+//! sgf-tool mainline <file> [output]
+//! sgf-tool split <file> <directory>
+//! ```
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use sgf_parser::*;
+
+const USAGE: &str = "Usage: sgf-tool <info|lint|to-json|normalize|mainline|split> <file> [output]";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["info", file] => read_game(file).and_then(|tree| info(&tree)),
+        ["lint", file] => read_text(file).and_then(|source| lint(&source)),
+        ["to-json", file] => read_game(file).and_then(|tree| to_json(&tree, None)),
+        ["to-json", file, output] => read_game(file).and_then(|tree| to_json(&tree, Some(output))),
+        ["normalize", file] => read_game(file).and_then(|tree| normalize(&tree, None)),
+        ["normalize", file, output] => {
+            read_game(file).and_then(|tree| normalize(&tree, Some(output)))
+        }
+        ["mainline", file] => read_game(file).and_then(|tree| mainline(&tree, None)),
+        ["mainline", file, output] => {
+            read_game(file).and_then(|tree| mainline(&tree, Some(output)))
+        }
+        ["split", file, directory] => {
+            read_text(file).and_then(|source| split(&source, file, directory))
+        }
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+    }
+}
+
+/// Options for reading files, transcoding the charsets named by `CA` that the crate knows
+fn options() -> ParserOptions {
+    ParserOptions::new().charset_policy(CharsetPolicy::TranscodeKnown)
+}
+
+/// Reads and parses the first game of a file
+fn read_game(file: &str) -> Result<GameTree, String> {
+    let content = fs::read(file).map_err(|err| format!("{}: {}", file, err))?;
+    parse_bytes_with(&content, &options()).map_err(|err| format!("{}: {}", file, err))
+}
+
+/// Reads a file as text, for the commands needing all games or the parse warnings
+fn read_text(file: &str) -> Result<String, String> {
+    let content = fs::read(file).map_err(|err| format!("{}: {}", file, err))?;
+    decode_bytes(&content, &options()).map_err(|err| format!("{}: {}", file, err))
+}
+
+/// Writes `text` to `output`, or to the standard output without one
+fn write(text: &str, output: Option<&str>) -> Result<bool, String> {
+    match output {
+        Some(output) => fs::write(output, text).map_err(|err| format!("{}: {}", output, err))?,
+        None => println!("{}", text),
+    }
+    Ok(true)
+}
+
+fn info(tree: &GameTree) -> Result<bool, String> {
+    let record = GameRecord::from_tree(tree);
+    for (column, value) in GAME_RECORD_COLUMNS.iter().zip(record.to_row()) {
+        if !value.is_empty() {
+            println!("{}: {}", column, value);
+        }
+    }
+    if let Some((width, height)) = tree.board_size() {
+        println!("size: {}x{}", width, height);
+    }
+    println!("nodes: {}", tree.total_node_count());
+    println!("nodes in longest variation: {}", tree.count_max_nodes());
+    println!("branch points: {}", tree.branch_points().len());
+    println!("unknown nodes: {}", tree.get_unknown_nodes().len());
+    println!("invalid nodes: {}", tree.get_invalid_nodes().len());
+    Ok(true)
+}
+
+fn lint(source: &str) -> Result<bool, String> {
+    let outcome = parse_with_options(source, &options())
+        .map_err(|err| format!("Could not parse input: {}", err))?;
+    let mut clean = true;
+    for warning in &outcome.warnings {
+        clean = false;
+        println!(
+            "variation {:?}, node {}: {:?}",
+            warning.variations, warning.node, warning.kind
+        );
+    }
+    for node in outcome.tree.get_invalid_nodes() {
+        for token in node.get_invalid_tokens() {
+            clean = false;
+            println!("invalid token: {}", token);
+        }
+    }
+    for problem in outcome.tree.check_moves() {
+        clean = false;
+        println!(
+            "{}: {:?} move: {:?}",
+            problem.path, problem.color, problem.issue
+        );
+    }
+    let report = verify_results(&[outcome.tree]);
+    for check in report.flagged() {
+        for issue in &check.issues {
+            clean = false;
+            println!("result: {:?}", issue);
+        }
+    }
+    Ok(clean)
+}
+
+fn to_json(tree: &GameTree, output: Option<&str>) -> Result<bool, String> {
+    write(&tree.to_json(), output)
+}
+
+/// Repairs the game and splits nodes holding several moves
+fn normalize(tree: &GameTree, output: Option<&str>) -> Result<bool, String> {
+    let mut tree = tree.repair(&RepairOptions::default()).tree;
+    tree.normalize_moves();
+    write(&tree.to_string(), output)
+}
+
+/// Keeps only the main variation
+fn mainline(tree: &GameTree, output: Option<&str>) -> Result<bool, String> {
+    let tree = GameTree {
+        nodes: tree.iter().cloned().collect(),
+        variations: vec![],
+    };
+    write(&tree.to_string(), output)
+}
+
+/// Writes each game of a collection to its own file in `directory`, named after `file`
+fn split(source: &str, file: &str, directory: &str) -> Result<bool, String> {
+    let collection =
+        parse_collection(source).map_err(|err| format!("Could not parse input: {}", err))?;
+    let stem = Path::new(file)
+        .file_stem()
+        .map_or("game".into(), |stem| stem.to_string_lossy());
+    fs::create_dir_all(directory).map_err(|err| format!("{}: {}", directory, err))?;
+    for (index, game) in collection.iter().enumerate() {
+        let path = Path::new(directory).join(format!("{}-{}.sgf", stem, index + 1));
+        fs::write(&path, game.to_string()).map_err(|err| format!("{}: {}", path.display(), err))?;
+        println!("{}", path.display());
+    }
+    Ok(true)
+}
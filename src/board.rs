@@ -0,0 +1,609 @@
+//! Goban positions, and the standard board sizes, star points and komi values
+//!
+//! Coordinates are the same 1-based `(x, y)` pairs used by `SgfToken`, so `(1, 1)` is the `aa`
+//! point in the top left corner.
+//!
+//! ```rust
+//! use sgf_parser::board::*;
+//! use sgf_parser::RuleSet;
+//!
+//! assert_eq!(STAR_POINTS_9[4], (5, 5));
+//! assert_eq!(star_points(19, 19).len(), 9);
+//! assert_eq!(handicap_points(19, 2), Some(vec![(16, 4), (4, 16)]));
+//! assert_eq!(default_komi(&RuleSet::Chinese), Some(7.5));
+//! assert!(is_valid_size(19, 19));
+//! assert!(!is_valid_size(53, 19));
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use derive_more::Display;
+
+use crate::{Action, Color, GameNode, GameTree, RuleSet, SgfError, SgfErrorKind, SgfToken};
+
+/// Board size used when a game does not specify `SZ`
+pub const DEFAULT_SIZE: u8 = 19;
+
+/// Largest board side that can be written with SGF coordinates
+pub const MAX_SIZE: u8 = 52;
+
+/// The board sizes commonly played
+pub const STANDARD_SIZES: [u8; 3] = [9, 13, 19];
+
+/// Star points of the 9x9 board, corners first and then the center
+pub const STAR_POINTS_9: [(u8, u8); 5] = [(3, 3), (7, 3), (3, 7), (7, 7), (5, 5)];
+
+/// Star points of the 13x13 board, corners first and then the center and the sides
+pub const STAR_POINTS_13: [(u8, u8); 9] = [
+    (4, 4),
+    (10, 4),
+    (4, 10),
+    (10, 10),
+    (7, 7),
+    (7, 4),
+    (7, 10),
+    (4, 7),
+    (10, 7),
+];
+
+/// Star points of the 19x19 board, corners first and then the center and the sides
+pub const STAR_POINTS_19: [(u8, u8); 9] = [
+    (4, 4),
+    (16, 4),
+    (4, 16),
+    (16, 16),
+    (10, 10),
+    (10, 4),
+    (10, 16),
+    (4, 10),
+    (16, 10),
+];
+
+/// Checks if a board of `width` by `height` can be written in SGF
+pub fn is_valid_size(width: u32, height: u32) -> bool {
+    let range = 1..=u32::from(MAX_SIZE);
+    range.contains(&width) && range.contains(&height)
+}
+
+/// Checks if the board is one of the `STANDARD_SIZES`
+pub fn is_standard_size(width: u32, height: u32) -> bool {
+    width == height && STANDARD_SIZES.iter().any(|size| u32::from(*size) == width)
+}
+
+/// Checks if the 1-based `point` is on a board of `width` by `height`
+pub fn is_on_board(point: (u8, u8), width: u8, height: u8) -> bool {
+    let (x, y) = point;
+    (1..=width).contains(&x) && (1..=height).contains(&y)
+}
+
+/// Gets the star points of square boards of size 7 and up, corners first and then the center
+/// and the sides
+///
+/// Boards below 13x13 have no side star points, and boards of even size have no center.
+pub fn star_points(width: u8, height: u8) -> Vec<(u8, u8)> {
+    if width != height || width < 7 {
+        return vec![];
+    }
+    let edge = if width >= 13 { 4 } else { 3 };
+    let far = width - edge + 1;
+    let center = width.div_ceil(2);
+    let mut points = vec![(edge, edge), (far, edge), (edge, far), (far, far)];
+    if width % 2 == 1 {
+        points.push((center, center));
+        if width >= 13 {
+            points.extend(&[(center, edge), (center, far), (edge, center), (far, center)]);
+        }
+    }
+    points
+}
+
+/// Gets the points of the fixed handicap stones on a square board of `size`
+///
+/// Returns `None` when the handicap is not between 2 and 9, or the board does not have enough
+/// star points for it.
+///
+/// ```rust
+/// use sgf_parser::board::handicap_points;
+///
+/// assert_eq!(
+///     handicap_points(19, 3),
+///     Some(vec![(16, 4), (4, 16), (16, 16)])
+/// );
+/// assert_eq!(handicap_points(19, 6).unwrap().len(), 6);
+/// assert_eq!(handicap_points(9, 6), None);
+/// ```
+pub fn handicap_points(size: u8, handicap: u32) -> Option<Vec<(u8, u8)>> {
+    let points = star_points(size, size);
+    let (top_left, top_right, bottom_left, bottom_right) =
+        (*points.first()?, points[1], points[2], points[3]);
+    let center = points.get(4).copied();
+    let sides = points.get(5..9);
+    let mut stones = vec![top_right, bottom_left];
+    match handicap {
+        2 => {}
+        3 => stones.push(bottom_right),
+        4 | 5 => {
+            stones.extend(&[bottom_right, top_left]);
+            if handicap == 5 {
+                stones.push(center?);
+            }
+        }
+        6 | 7 => {
+            let sides = sides?;
+            stones.extend(&[bottom_right, top_left, sides[2], sides[3]]);
+            if handicap == 7 {
+                stones.push(center?);
+            }
+        }
+        8 | 9 => {
+            stones.extend(&[bottom_right, top_left]);
+            stones.extend(sides?);
+            if handicap == 9 {
+                stones.push(center?);
+            }
+        }
+        _ => return None,
+    }
+    Some(stones)
+}
+
+/// Gets the komi most often used for even games under `rules`
+pub fn default_komi(rules: &RuleSet) -> Option<f32> {
+    match rules {
+        RuleSet::Japanese => Some(6.5),
+        RuleSet::Chinese | RuleSet::AGA => Some(7.5),
+        RuleSet::NZ => Some(7.0),
+        RuleSet::GOE => Some(8.0),
+        RuleSet::Unknown(_) => None,
+    }
+}
+
+/// Which repeated positions are forbidden, used by `GameTree::check_moves_with`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KoRule {
+    /// Only retaking a ko right away is forbidden
+    Simple,
+    /// No move may repeat an earlier position of the game
+    PositionalSuperko,
+    /// No move may repeat an earlier position with the same player to move
+    SituationalSuperko,
+}
+
+/// Gets the ko rule of `rules`, defaulting to simple ko for unknown rule sets
+///
+/// ```rust
+/// use sgf_parser::board::{ko_rule, KoRule};
+/// use sgf_parser::RuleSet;
+///
+/// assert_eq!(ko_rule(&RuleSet::Chinese), KoRule::PositionalSuperko);
+/// assert_eq!(ko_rule(&RuleSet::Japanese), KoRule::Simple);
+/// ```
+pub fn ko_rule(rules: &RuleSet) -> KoRule {
+    match rules {
+        RuleSet::Chinese => KoRule::PositionalSuperko,
+        RuleSet::AGA | RuleSet::NZ | RuleSet::GOE => KoRule::SituationalSuperko,
+        RuleSet::Japanese | RuleSet::Unknown(_) => KoRule::Simple,
+    }
+}
+
+/// How the final position is counted, used by `GameTree::score`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScoringMethod {
+    /// Stones on the board and surrounded points
+    Area,
+    /// Surrounded points and captured stones
+    Territory,
+}
+
+/// Gets the scoring method of `rules`, defaulting to area scoring for unknown rule sets
+pub fn scoring_method(rules: &RuleSet) -> ScoringMethod {
+    match rules {
+        RuleSet::Japanese => ScoringMethod::Territory,
+        _ => ScoringMethod::Area,
+    }
+}
+
+/// Why `Board::play_action` rejected a move
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IllegalMove {
+    #[display(fmt = "Point is not on the board")]
+    OffBoard,
+    #[display(fmt = "Point is occupied")]
+    Occupied,
+    #[display(fmt = "Move would capture its own group")]
+    Suicide,
+    #[display(fmt = "Move retakes a ko")]
+    Ko,
+}
+
+impl std::error::Error for IllegalMove {}
+
+impl From<IllegalMove> for SgfError {
+    fn from(err: IllegalMove) -> SgfError {
+        SgfError::illegal_move(err)
+    }
+}
+
+/// A goban position, used to replay the moves of a `GameTree`
+///
+/// Coordinates are the same 1-based `(x, y)` pairs used by `SgfToken`, so `(1, 1)` is the
+/// `aa` point in the top left corner.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let tree: GameTree = parse("(;SZ[9];B[ba];W[aa];B[ab])").unwrap();
+/// let board = Board::replay(&tree).unwrap();
+///
+/// assert_eq!(board.get((1, 1)), None);
+/// assert_eq!(board.get((2, 1)), Some(Color::Black));
+/// assert_eq!(board.captures(Color::Black), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Board {
+    width: u8,
+    height: u8,
+    stones: Vec<Option<Color>>,
+    black_captures: u32,
+    white_captures: u32,
+    /// The color not allowed to play at the point of a stone just captured in a ko
+    ko: Option<(Color, (u8, u8))>,
+}
+
+impl Board {
+    /// Creates an empty board with the given dimensions
+    pub fn new(width: u8, height: u8) -> Self {
+        Board {
+            width,
+            height,
+            stones: vec![None; width as usize * height as usize],
+            black_captures: 0,
+            white_captures: 0,
+            ko: None,
+        }
+    }
+
+    /// Creates an empty board sized according to `GameTree::board_size`
+    ///
+    /// Fails with `SgfErrorKind::InvalidTokenValue` if the size is not valid, see
+    /// `is_valid_size`.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[13])").unwrap();
+    /// assert_eq!(Board::for_tree(&tree).unwrap().size(), (13, 13));
+    ///
+    /// let tree: GameTree = parse("(;SZ[300])").unwrap();
+    /// assert!(Board::for_tree(&tree).is_err());
+    /// ```
+    pub fn for_tree(tree: &GameTree) -> Result<Self, SgfError> {
+        match tree.board_size() {
+            Some((width, height)) if is_valid_size(width, height) => {
+                Ok(Board::new(width as u8, height as u8))
+            }
+            Some(_) => Err(SgfErrorKind::InvalidTokenValue.into()),
+            None => Ok(Board::new(DEFAULT_SIZE, DEFAULT_SIZE)),
+        }
+    }
+
+    /// Replays the main variation of `tree`, returning the final position
+    pub fn replay(tree: &GameTree) -> Result<Self, SgfError> {
+        let mut board = Board::for_tree(tree)?;
+        for node in tree.iter() {
+            board.apply_node(node)?;
+        }
+        Ok(board)
+    }
+
+    /// Gets the width and height of the board
+    pub fn size(&self) -> (u8, u8) {
+        (self.width, self.height)
+    }
+
+    /// Gets the stone at `coordinate`, if any
+    pub fn get(&self, coordinate: (u8, u8)) -> Option<Color> {
+        self.index(coordinate).and_then(|index| self.stones[index])
+    }
+
+    /// Number of stones captured by `color`
+    pub fn captures(&self, color: Color) -> u32 {
+        match color {
+            Color::Black => self.black_captures,
+            Color::White => self.white_captures,
+        }
+    }
+
+    /// Gets a hash of the stones on the board, ignoring captures and ko
+    ///
+    /// Equal positions have equal hashes, so repeated positions can be found by comparing
+    /// hashes alone, with a negligible chance of a collision.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut board = Board::new(9, 9);
+    /// let empty = board.position_hash();
+    /// board.play(Color::Black, (5, 5)).unwrap();
+    /// assert_ne!(board.position_hash(), empty);
+    /// assert_eq!(board.position_hash(), board.clone().position_hash());
+    /// ```
+    pub fn position_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.width.hash(&mut hasher);
+        self.height.hash(&mut hasher);
+        self.stones.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Places a setup stone, without checking for captures
+    pub fn place(&mut self, color: Color, coordinate: (u8, u8)) -> Result<(), SgfError> {
+        let index = self
+            .index(coordinate)
+            .ok_or_else(|| SgfError::from(SgfErrorKind::IllegalMove))?;
+        self.stones[index] = Some(color);
+        self.ko = None;
+        Ok(())
+    }
+
+    /// Checks if `color` may play at `coordinate`, which must be an empty point on the board
+    /// that isn't suicide or an immediate retake of a ko
+    pub fn is_legal(&self, color: Color, coordinate: (u8, u8)) -> bool {
+        self.check_move(color, coordinate).is_ok()
+    }
+
+    /// Plays a move or a pass for `color` following the rules, and returns the coordinates of
+    /// the captured stones
+    ///
+    /// Unlike `play`, which replays recorded games as they are, suicide and retaking a ko
+    /// right away are rejected, leaving the board unchanged. Moves played this way can be added
+    /// to a game with `Sandbox::push_move` and written back with `GameTree::commit_sandbox`.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9]AB[ba][ab][bc][cb]AW[ca][db][cc])").unwrap();
+    /// let mut board = Board::replay(&tree).unwrap();
+    /// assert_eq!(board.play_action(Color::White, Action::Move(1, 1)), Err(IllegalMove::Suicide));
+    /// assert_eq!(board.play_action(Color::White, Action::Move(2, 2)), Ok(vec![(3, 2)]));
+    /// assert_eq!(board.play_action(Color::Black, Action::Move(2, 2)), Err(IllegalMove::Occupied));
+    /// assert_eq!(board.play_action(Color::Black, Action::Move(3, 2)), Err(IllegalMove::Ko));
+    /// assert!(!board.is_legal(Color::Black, (3, 2)));
+    ///
+    /// board.play_action(Color::Black, Action::Move(7, 7)).unwrap();
+    /// board.play_action(Color::White, Action::Pass).unwrap();
+    /// assert_eq!(board.play_action(Color::Black, Action::Move(3, 2)), Ok(vec![(2, 2)]));
+    /// ```
+    pub fn play_action(
+        &mut self,
+        color: Color,
+        action: Action,
+    ) -> Result<Vec<(u8, u8)>, IllegalMove> {
+        match action {
+            Action::Pass => {
+                self.ko = None;
+                Ok(vec![])
+            }
+            Action::Move(x, y) => {
+                self.check_move(color, (x, y))?;
+                self.play(color, (x, y)).map_err(|_| IllegalMove::Occupied)
+            }
+        }
+    }
+
+    /// Plays a stone at `coordinate`, and returns the coordinates of all captured stones
+    ///
+    /// Fails if the point is outside the board, or already occupied. Suicide is allowed, and
+    /// the removed stones are counted as captured by the opponent.
+    pub fn play(&mut self, color: Color, coordinate: (u8, u8)) -> Result<Vec<(u8, u8)>, SgfError> {
+        let index = self
+            .index(coordinate)
+            .ok_or_else(|| SgfError::from(SgfErrorKind::IllegalMove))?;
+        if self.stones[index].is_some() {
+            return Err(SgfErrorKind::IllegalMove.into());
+        }
+        self.stones[index] = Some(color);
+
+        let mut captured = vec![];
+        for neighbour in self.neighbours(coordinate) {
+            if self.get(neighbour) == Some(!color) {
+                let (group, liberties) = self.group(neighbour);
+                if liberties == 0 {
+                    captured.extend(group);
+                }
+            }
+        }
+        captured.sort_unstable();
+        captured.dedup();
+        self.remove(&captured, color);
+
+        self.ko = None;
+        if captured.is_empty() {
+            let (group, liberties) = self.group(coordinate);
+            if liberties == 0 {
+                self.remove(&group, !color);
+                return Ok(group);
+            }
+        } else if captured.len() == 1 {
+            let (group, liberties) = self.group(coordinate);
+            if group.len() == 1 && liberties == 1 {
+                self.ko = Some((!color, captured[0]));
+            }
+        }
+        Ok(captured)
+    }
+
+    /// Applies all moves and setup stones in `node` to the board
+    ///
+    /// A pass ends any ko, like it does in `play_action`.
+    pub fn apply_node(&mut self, node: &GameNode) -> Result<(), SgfError> {
+        for token in &node.tokens {
+            match token {
+                SgfToken::Add { color, coordinate } => self.place(*color, *coordinate)?,
+                SgfToken::Move {
+                    color,
+                    action: Action::Move(x, y),
+                } => {
+                    self.play(*color, (*x, *y))?;
+                }
+                SgfToken::Move {
+                    action: Action::Pass,
+                    ..
+                } => self.ko = None,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks if a ko forbids retaking a stone right away
+    pub(crate) fn has_ko(&self) -> bool {
+        self.ko.is_some()
+    }
+
+    /// Estimates the area held by black and white, as `(black, white)`
+    ///
+    /// Counts stones on the board, and empty regions bordered by a single color. All stones are
+    /// treated as alive.
+    pub fn estimate_area(&self) -> (u32, u32) {
+        let area = |color| {
+            let stones = self.stones.iter().filter(|&&stone| stone == Some(color));
+            (stones.count() + self.territory(color).len()) as u32
+        };
+        (area(Color::Black), area(Color::White))
+    }
+
+    /// Gets the empty points in regions bordered only by stones of `color`
+    ///
+    /// All stones are treated as alive, so dead stones should be removed with `remove_group`
+    /// first.
+    pub fn territory(&self, color: Color) -> Vec<(u8, u8)> {
+        let mut territory = vec![];
+        let mut visited = vec![false; self.stones.len()];
+        for y in 1..=self.height {
+            for x in 1..=self.width {
+                let index = self.index((x, y)).expect("Coordinate is on the board");
+                if self.stones[index].is_none() && !visited[index] {
+                    let (region, borders) = self.region((x, y), &mut visited);
+                    if borders == [color] {
+                        territory.extend(region);
+                    }
+                }
+            }
+        }
+        territory.sort_unstable_by_key(|&(x, y)| (y, x));
+        territory
+    }
+
+    /// Removes the group containing `coordinate` as captured by the opponent, and returns its
+    /// stones
+    ///
+    /// Used to take dead stones off the board before counting. Returns no stones if the point
+    /// is empty or not on the board.
+    pub fn remove_group(&mut self, coordinate: (u8, u8)) -> Vec<(u8, u8)> {
+        let color = match self.get(coordinate) {
+            Some(color) => color,
+            None => return vec![],
+        };
+        let (group, _) = self.group(coordinate);
+        self.remove(&group, !color);
+        group
+    }
+
+    fn check_move(&self, color: Color, coordinate: (u8, u8)) -> Result<(), IllegalMove> {
+        let index = self.index(coordinate).ok_or(IllegalMove::OffBoard)?;
+        if self.stones[index].is_some() {
+            return Err(IllegalMove::Occupied);
+        }
+        if self.ko == Some((color, coordinate)) {
+            return Err(IllegalMove::Ko);
+        }
+        let mut board = self.clone();
+        match board.play(color, coordinate) {
+            Ok(removed) if removed.contains(&coordinate) => Err(IllegalMove::Suicide),
+            _ => Ok(()),
+        }
+    }
+
+    fn index(&self, (x, y): (u8, u8)) -> Option<usize> {
+        if x == 0 || y == 0 || x > self.width || y > self.height {
+            None
+        } else {
+            Some((y as usize - 1) * self.width as usize + (x as usize - 1))
+        }
+    }
+
+    fn neighbours(&self, (x, y): (u8, u8)) -> Vec<(u8, u8)> {
+        let mut neighbours = vec![];
+        if x > 1 {
+            neighbours.push((x - 1, y));
+        }
+        if x < self.width {
+            neighbours.push((x + 1, y));
+        }
+        if y > 1 {
+            neighbours.push((x, y - 1));
+        }
+        if y < self.height {
+            neighbours.push((x, y + 1));
+        }
+        neighbours
+    }
+
+    /// Finds the group containing `coordinate`, and counts its liberties
+    fn group(&self, coordinate: (u8, u8)) -> (Vec<(u8, u8)>, usize) {
+        let color = self.get(coordinate);
+        let mut group = vec![coordinate];
+        let mut liberties = vec![];
+        let mut stack = vec![coordinate];
+        while let Some(current) = stack.pop() {
+            for neighbour in self.neighbours(current) {
+                match self.get(neighbour) {
+                    None if !liberties.contains(&neighbour) => liberties.push(neighbour),
+                    stone if stone == color && !group.contains(&neighbour) => {
+                        group.push(neighbour);
+                        stack.push(neighbour);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        (group, liberties.len())
+    }
+
+    /// Finds the empty region containing `coordinate`, and collects the colors bordering it
+    fn region(&self, coordinate: (u8, u8), visited: &mut [bool]) -> (Vec<(u8, u8)>, Vec<Color>) {
+        let mut region = vec![];
+        let mut borders = vec![];
+        let mut stack = vec![coordinate];
+        visited[self.index(coordinate).expect("Coordinate is on the board")] = true;
+        while let Some(current) = stack.pop() {
+            region.push(current);
+            for neighbour in self.neighbours(current) {
+                let index = self.index(neighbour).expect("Neighbours are on the board");
+                match self.stones[index] {
+                    Some(color) if !borders.contains(&color) => borders.push(color),
+                    None if !visited[index] => {
+                        visited[index] = true;
+                        stack.push(neighbour);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        (region, borders)
+    }
+
+    fn remove(&mut self, stones: &[(u8, u8)], captured_by: Color) {
+        for stone in stones {
+            if let Some(index) = self.index(*stone) {
+                self.stones[index] = None;
+            }
+        }
+        match captured_by {
+            Color::Black => self.black_captures += stones.len() as u32,
+            Color::White => self.white_captures += stones.len() as u32,
+        }
+    }
+}
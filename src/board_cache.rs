@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::{Action, Board, GameNode, GameTree, SgfError, SgfToken, TreePath};
+
+/// Board positions of the nodes of a tree, computed on first use, made by
+/// `GameTree::board_cache`
+///
+/// Getting the board of a node replays only the nodes after its closest cached ancestor, and
+/// stores the boards of all nodes on the way. Boards are shared behind an `Arc` between a node
+/// and the nodes after it that leave the board unchanged, and between the cache and its
+/// callers, so nodes holding only comments or markup cost no extra board. A pass changes the
+/// board when it ends a ko.
+///
+/// The cache borrows the tree, so the tree can't change while cached positions are in use.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let tree: GameTree = parse("(;SZ[9];B[ba];W[aa];B[ab](;C[Capture])(;W[ca]))").unwrap();
+/// let mut cache = tree.board_cache();
+///
+/// let board = cache.board_at(&TreePath::new(vec![0], 0)).unwrap();
+/// assert_eq!(board.captures(Color::Black), 1);
+/// assert_eq!(cache.len(), 5);
+///
+/// let board = cache.board_at(&TreePath::new(vec![1], 0)).unwrap();
+/// assert_eq!(board.get((3, 1)), Some(Color::White));
+/// assert_eq!(cache.len(), 6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BoardCache<'a> {
+    tree: &'a GameTree,
+    boards: HashMap<TreePath, Arc<Board>>,
+}
+
+impl GameTree {
+    /// Creates an empty `BoardCache` for the tree
+    pub fn board_cache(&self) -> BoardCache<'_> {
+        BoardCache {
+            tree: self,
+            boards: HashMap::new(),
+        }
+    }
+}
+
+impl<'a> BoardCache<'a> {
+    /// Gets the tree the boards are computed for
+    pub fn tree(&self) -> &'a GameTree {
+        self.tree
+    }
+
+    /// Gets the board after the node at `path`, replaying the nodes that are not cached yet
+    pub fn board_at(&mut self, path: &TreePath) -> Result<Arc<Board>, SgfError> {
+        if let Some(board) = self.boards.get(path) {
+            return Ok(Arc::clone(board));
+        }
+        let line = line_to(self.tree, path)?;
+        let start = line
+            .iter()
+            .rposition(|(path, _)| self.boards.contains_key(path));
+        let mut board = match start {
+            Some(index) => Arc::clone(&self.boards[&line[index].0]),
+            None => Arc::new(Board::for_tree(self.tree)?),
+        };
+        let replayed = start.map_or(0, |index| index + 1);
+        for (path, node) in line.into_iter().skip(replayed) {
+            if changes_board(node, &board) {
+                let mut next = (*board).clone();
+                next.apply_node(node)?;
+                board = Arc::new(next);
+            }
+            self.boards.insert(path, Arc::clone(&board));
+        }
+        Ok(board)
+    }
+
+    /// Gets the cached board after the node at `path`, without replaying anything
+    pub fn get(&self, path: &TreePath) -> Option<Arc<Board>> {
+        self.boards.get(path).cloned()
+    }
+
+    /// Number of nodes with a cached board
+    pub fn len(&self) -> usize {
+        self.boards.len()
+    }
+
+    /// Checks if no board has been computed yet
+    pub fn is_empty(&self) -> bool {
+        self.boards.is_empty()
+    }
+
+    /// Drops all cached boards
+    pub fn clear(&mut self) {
+        self.boards.clear();
+    }
+}
+
+/// Gets the paths and nodes from the root up to, and including, the node at `path`
+fn line_to<'a>(
+    tree: &'a GameTree,
+    path: &TreePath,
+) -> Result<Vec<(TreePath, &'a GameNode)>, SgfError> {
+    tree.get_node(path)?;
+    let mut line = vec![];
+    let mut sequence = tree;
+    for (depth, variation) in path.variations.iter().enumerate() {
+        let variations = &path.variations[..depth];
+        line.extend(
+            sequence
+                .nodes
+                .iter()
+                .enumerate()
+                .map(|(index, node)| (TreePath::new(variations.to_vec(), index), node)),
+        );
+        sequence = &sequence.variations[*variation];
+    }
+    line.extend(
+        sequence.nodes[..=path.node]
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (TreePath::new(path.variations.clone(), index), node)),
+    );
+    Ok(line)
+}
+
+/// Checks if the node changes `board`, by placing stones or by passing while there is a ko
+fn changes_board(node: &GameNode, board: &Board) -> bool {
+    places_stones(node)
+        || (board.has_ko()
+            && node.tokens.iter().any(|token| {
+                matches!(
+                    token,
+                    SgfToken::Move {
+                        action: Action::Pass,
+                        ..
+                    }
+                )
+            }))
+}
+
+/// Checks if the node places stones on the board, passes don't
+pub(crate) fn places_stones(node: &GameNode) -> bool {
+    node.tokens.iter().any(|token| match token {
+        SgfToken::Add { .. } => true,
+        SgfToken::Move { action, .. } => !action.is_pass(),
+        _ => false,
+    })
+}
@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+
+use crate::{Action, Collection, Color, GameTree, SgfToken};
+
+/// A move played from a position of an `OpeningBook`, with the results of the games
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BookMove {
+    pub color: Color,
+    pub action: Action,
+    /// Number of games where the move was played
+    pub games: u32,
+    pub black_wins: u32,
+    pub white_wins: u32,
+}
+
+impl BookMove {
+    /// Gets the share of games with a known winner won by `color`
+    pub fn win_rate(&self, color: Color) -> Option<f32> {
+        let decided = self.black_wins + self.white_wins;
+        if decided == 0 {
+            return None;
+        }
+        let wins = match color {
+            Color::Black => self.black_wins,
+            Color::White => self.white_wins,
+        };
+        Some(wins as f32 / decided as f32)
+    }
+}
+
+/// A position of an `OpeningBook`, identified by the moves leading to it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BookPosition {
+    pub moves: Vec<(Color, Action)>,
+    /// Moves played from the position, most played first
+    pub candidates: Vec<BookMove>,
+}
+
+/// Move statistics for the openings of a collection of games
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct OpeningBook {
+    /// All positions, ordered by the moves leading to them
+    pub positions: Vec<BookPosition>,
+}
+
+impl OpeningBook {
+    /// Collects the first `depth` moves of the main variation of every game
+    ///
+    /// Winners are taken from the `RE` token of each game. Positions reached by different move
+    /// orders are kept apart.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let collection =
+    ///     parse_collection("(;RE[B+R];B[pd];W[dp])(;RE[W+2.5];B[pd];W[dd])(;B[dd])").unwrap();
+    /// let book = OpeningBook::from_collection(&collection, 2);
+    ///
+    /// let start = &book.positions[0];
+    /// assert!(start.moves.is_empty());
+    /// assert_eq!(start.candidates[0].action, Action::Move(16, 4));
+    /// assert_eq!(start.candidates[0].games, 2);
+    /// assert_eq!(start.candidates[0].win_rate(Color::Black), Some(0.5));
+    /// ```
+    pub fn from_collection(collection: &Collection, depth: usize) -> Self {
+        let mut positions: BTreeMap<Vec<(Color, Action)>, Vec<BookMove>> = BTreeMap::new();
+        for game in collection {
+            let winner = game_winner(game);
+            let mut moves = vec![];
+            for (color, action) in main_line_moves(game).into_iter().take(depth) {
+                let candidates = positions.entry(moves.clone()).or_default();
+                let index = match candidates
+                    .iter()
+                    .position(|c| c.color == color && c.action == action)
+                {
+                    Some(index) => index,
+                    None => {
+                        candidates.push(BookMove {
+                            color,
+                            action,
+                            games: 0,
+                            black_wins: 0,
+                            white_wins: 0,
+                        });
+                        candidates.len() - 1
+                    }
+                };
+                let candidate = &mut candidates[index];
+                candidate.games += 1;
+                match winner {
+                    Some(Color::Black) => candidate.black_wins += 1,
+                    Some(Color::White) => candidate.white_wins += 1,
+                    None => {}
+                }
+                moves.push((color, action));
+            }
+        }
+        let positions = positions
+            .into_iter()
+            .map(|(moves, mut candidates)| {
+                candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.games));
+                BookPosition { moves, candidates }
+            })
+            .collect();
+        OpeningBook { positions }
+    }
+
+    /// Gets the position reached by `moves`
+    pub fn get(&self, moves: &[(Color, Action)]) -> Option<&BookPosition> {
+        self.positions
+            .iter()
+            .find(|position| position.moves == moves)
+    }
+
+    /// Serializes the book as JSON, with moves written as SGF properties like `B[pd]`
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let collection = parse_collection("(;RE[B+R];B[pd])").unwrap();
+    /// let book = OpeningBook::from_collection(&collection, 1);
+    /// assert_eq!(
+    ///     book.to_json(),
+    ///     r#"{"positions":[{"moves":[],"candidates":[{"move":"B[pd]","games":1,"black_wins":1,"white_wins":0}]}]}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let positions: Vec<String> = self
+            .positions
+            .iter()
+            .map(|position| {
+                let moves: Vec<String> = position
+                    .moves
+                    .iter()
+                    .map(|(color, action)| format!("\"{}\"", move_text(*color, *action)))
+                    .collect();
+                let candidates: Vec<String> = position
+                    .candidates
+                    .iter()
+                    .map(|candidate| {
+                        format!(
+                            "{{\"move\":\"{}\",\"games\":{},\"black_wins\":{},\"white_wins\":{}}}",
+                            move_text(candidate.color, candidate.action),
+                            candidate.games,
+                            candidate.black_wins,
+                            candidate.white_wins
+                        )
+                    })
+                    .collect();
+                format!(
+                    "{{\"moves\":[{}],\"candidates\":[{}]}}",
+                    moves.join(","),
+                    candidates.join(",")
+                )
+            })
+            .collect();
+        format!("{{\"positions\":[{}]}}", positions.join(","))
+    }
+}
+
+fn move_text(color: Color, action: Action) -> String {
+    SgfToken::Move { color, action }.to_string()
+}
+
+fn game_winner(game: &GameTree) -> Option<Color> {
+    game.nodes.first().and_then(|root| {
+        root.tokens.iter().find_map(|token| match token {
+            SgfToken::Result(outcome) => outcome.get_winner(),
+            _ => None,
+        })
+    })
+}
+
+fn main_line_moves(game: &GameTree) -> Vec<(Color, Action)> {
+    game.iter()
+        .filter_map(|node| {
+            node.tokens.iter().find_map(|token| match token {
+                SgfToken::Move { color, action } => Some((*color, *action)),
+                _ => None,
+            })
+        })
+        .collect()
+}
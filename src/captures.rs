@@ -0,0 +1,94 @@
+use crate::{Board, Color, GameNode, GameTree, SgfError, SgfToken, TreePath};
+
+impl GameTree {
+    /// Gets the number of stones captured by black and white, as `(black, white)`, after the
+    /// node at `path`
+    ///
+    /// The counts come from replaying the line to the node with `Board`, so suicides count as
+    /// captured by the opponent.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ba];W[aa];B[ab](;W[ca])(;W[bb]))").unwrap();
+    /// assert_eq!(tree.captures_at(&TreePath::new(vec![], 2)).unwrap(), (0, 0));
+    /// assert_eq!(tree.captures_at(&TreePath::new(vec![], 3)).unwrap(), (1, 0));
+    /// assert!(tree.captures_at(&TreePath::new(vec![2], 0)).is_err());
+    /// ```
+    pub fn captures_at(&self, path: &TreePath) -> Result<(u32, u32), SgfError> {
+        let mut board = Board::for_tree(self)?;
+        for node in self.get_line_to(path)? {
+            board.apply_node(node)?;
+        }
+        Ok((board.captures(Color::Black), board.captures(Color::White)))
+    }
+
+    /// Writes the number of stones captured so far by black and white as the private
+    /// properties `black` and `white` of every node with a move, and returns the number of
+    /// nodes written
+    ///
+    /// Servers export prisoner counts under their own identifiers, so these are picked by the
+    /// caller. Existing tokens of both properties are replaced, and the counts can be read back
+    /// with `GameNode::recorded_captures`.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree = parse("(;SZ[9];B[ba];W[aa];B[ab]C[Capture])").unwrap();
+    /// assert_eq!(tree.write_captures("CB", "CW").unwrap(), 3);
+    /// assert_eq!(
+    ///     tree.to_string(),
+    ///     "(;SZ[9];B[ba]CB[0]CW[0];CB[0]CW[0]W[aa];B[ab]CB[1]CW[0]C[Capture])"
+    /// );
+    /// assert_eq!(tree.nodes[3].recorded_captures("CB", "CW"), Some((1, 0)));
+    /// ```
+    pub fn write_captures(&mut self, black: &str, white: &str) -> Result<usize, SgfError> {
+        let board = Board::for_tree(self)?;
+        write_tree_captures(self, board, black, white)
+    }
+}
+
+impl GameNode {
+    /// Reads prisoner counts stored in the private properties `black` and `white`, as
+    /// `(black, white)`
+    ///
+    /// Returns `None` unless both properties hold a number.
+    pub fn recorded_captures(&self, black: &str, white: &str) -> Option<(u32, u32)> {
+        let count = |identifier: &str| {
+            self.tokens.iter().find_map(|token| match token {
+                SgfToken::Unknown((id, value)) if id == identifier => value.trim().parse().ok(),
+                _ => None,
+            })
+        };
+        Some((count(black)?, count(white)?))
+    }
+}
+
+fn write_tree_captures(
+    tree: &mut GameTree,
+    mut board: Board,
+    black: &str,
+    white: &str,
+) -> Result<usize, SgfError> {
+    let mut written = 0;
+    for node in tree.nodes.iter_mut() {
+        board.apply_node(node)?;
+        if node
+            .tokens
+            .iter()
+            .any(|token| matches!(token, SgfToken::Move { .. }))
+        {
+            node.tokens
+                .retain(|token| token.identifier() != black && token.identifier() != white);
+            for (identifier, color) in [(black, Color::Black), (white, Color::White)] {
+                let count = board.captures(color).to_string();
+                node.tokens.push(SgfToken::from_pair(identifier, &count));
+            }
+            written += 1;
+        }
+    }
+    for variation in tree.variations.iter_mut() {
+        written += write_tree_captures(variation, board.clone(), black, white)?;
+    }
+    Ok(written)
+}
@@ -0,0 +1,132 @@
+use std::ops::BitOr;
+
+use crate::{GameTree, SgfToken};
+
+/// Property classes of the SGF specification, following `GroupedToken`
+///
+/// Categories combine with `|` into `TokenCategories`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TokenCategory {
+    Root,
+    GameInfo,
+    Setup,
+    Move,
+    Timing,
+    Annotation,
+    Markup,
+    Unknown,
+    Invalid,
+}
+
+/// A set of `TokenCategory` values
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let categories = TokenCategory::Markup | TokenCategory::Timing;
+/// assert!(categories.contains(TokenCategory::Timing));
+/// assert!(!categories.contains(TokenCategory::Move));
+/// assert!(TokenCategories::all().contains(TokenCategory::Invalid));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TokenCategories {
+    bits: u16,
+}
+
+impl TokenCategories {
+    /// Creates a set without any category
+    pub fn empty() -> Self {
+        TokenCategories::default()
+    }
+
+    /// Creates a set of every category
+    pub fn all() -> Self {
+        TokenCategories { bits: (1 << 9) - 1 }
+    }
+
+    pub fn contains(self, category: TokenCategory) -> bool {
+        self.bits & TokenCategories::from(category).bits != 0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.bits == 0
+    }
+}
+
+impl From<TokenCategory> for TokenCategories {
+    fn from(category: TokenCategory) -> Self {
+        TokenCategories {
+            bits: 1 << category as u16,
+        }
+    }
+}
+
+impl BitOr for TokenCategory {
+    type Output = TokenCategories;
+
+    fn bitor(self, other: TokenCategory) -> TokenCategories {
+        TokenCategories::from(self) | other
+    }
+}
+
+impl<T: Into<TokenCategories>> BitOr<T> for TokenCategories {
+    type Output = TokenCategories;
+
+    fn bitor(self, other: T) -> TokenCategories {
+        TokenCategories {
+            bits: self.bits | other.into().bits,
+        }
+    }
+}
+
+impl SgfToken {
+    /// Gets the property class of the token, the group `SgfToken::grouped` puts it in
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// assert_eq!(SgfToken::from_pair("TR", "aa").category(), TokenCategory::Markup);
+    /// assert_eq!(SgfToken::from_pair("KM", "6.5").category(), TokenCategory::GameInfo);
+    /// ```
+    pub fn category(&self) -> TokenCategory {
+        self.clone().grouped().category()
+    }
+}
+
+impl GameTree {
+    /// Removes all tokens in `categories` from every node, including all variations, and
+    /// returns the number of tokens removed
+    ///
+    /// Nodes left without tokens are kept, so paths into the tree stay valid.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree = parse("(;SZ[9]PB[Lee];B[ee]BL[30]TR[ee]C[Hi];W[cc]LB[cc:A])").unwrap();
+    /// assert_eq!(
+    ///     tree.strip(TokenCategory::Markup | TokenCategory::Timing | TokenCategory::Annotation),
+    ///     4
+    /// );
+    /// assert_eq!(tree.to_string(), "(;PB[Lee]SZ[9];B[ee];W[cc])");
+    /// ```
+    pub fn strip(&mut self, categories: impl Into<TokenCategories>) -> usize {
+        let categories = categories.into();
+        strip_tree(self, categories)
+    }
+}
+
+fn strip_tree(tree: &mut GameTree, categories: TokenCategories) -> usize {
+    let mut removed = 0;
+    for node in tree.nodes.iter_mut() {
+        let count = node.tokens.len();
+        node.tokens
+            .retain(|token| !categories.contains(token.category()));
+        removed += count - node.tokens.len();
+    }
+    removed
+        + tree
+            .variations
+            .iter_mut()
+            .map(|variation| strip_tree(variation, categories))
+            .sum::<usize>()
+}
@@ -0,0 +1,155 @@
+use crate::preserve;
+use crate::{
+    Encoding, Event, GameTree, Parser, ParserOptions, SgfError, SgfErrorKind, SgfToken, TreeBuilder,
+};
+
+/// How `parse_bytes_with` decodes input that is not plain UTF-8
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CharsetPolicy {
+    /// Only accept valid UTF-8, with `CA` missing or naming UTF-8
+    #[default]
+    Strict,
+    /// Transcode the charsets the crate knows to UTF-8, and reject any other `CA` value
+    ///
+    /// Known charsets are UTF-8, US-ASCII, ISO-8859-1 and Windows-1252.
+    TranscodeKnown,
+    /// Ignore `CA`, and replace invalid UTF-8 sequences with `U+FFFD`
+    AssumeUtf8Lossy,
+}
+
+/// Parses SGF bytes like `parse_bytes`, decoding them according to `options`
+///
+/// The charset is taken from the `CA` token of the root node. Input is decoded as a whole
+/// before parsing, so property values never hold partial characters.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let input = b"(;CA[ISO-8859-1]PB[Jos\xe9])";
+/// let options = ParserOptions::new().charset_policy(CharsetPolicy::TranscodeKnown);
+/// let tree = parse_bytes_with(input, &options).unwrap();
+/// assert_eq!(tree.to_string(), "(;CA[UTF-8]PB[José])");
+///
+/// let err = parse_bytes_with(input, &ParserOptions::default()).unwrap_err();
+/// assert_eq!(err.kind, SgfErrorKind::UnsupportedCharset);
+/// ```
+pub fn parse_bytes_with(input: &[u8], options: &ParserOptions) -> Result<GameTree, SgfError> {
+    let text = decode_bytes(input, options)?;
+    let mut parser = Parser::with_options(options);
+    let mut builder = TreeBuilder::with_options(options);
+    for event in parser
+        .feed(text.as_bytes())?
+        .into_iter()
+        .chain(parser.finish()?)
+    {
+        builder.push(event)?;
+    }
+    let mut tree = builder.finish()?.into_iter().next().unwrap_or_default();
+    if options.preserve_format {
+        preserve::record_source(&text, &mut tree)?;
+    }
+    if options.apply_quirks {
+        tree.apply_quirks();
+    }
+    Ok(tree)
+}
+
+/// Decodes SGF bytes to text the way `parse_bytes_with` does, following
+/// `options.charset_policy` and the `CA` token of the first root node
+///
+/// Useful to read files for the parsers taking text, like `parse_with_options` to get
+/// warnings, or `parse_collection` for files holding several games, which are all decoded
+/// with the charset of the first game.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let input = b"(;CA[ISO-8859-1]PB[Jos\xe9])(;PB[Honinbo])";
+/// let options = ParserOptions::new().charset_policy(CharsetPolicy::TranscodeKnown);
+/// let text = decode_bytes(input, &options).unwrap();
+/// assert_eq!(text, "(;CA[ISO-8859-1]PB[José])(;PB[Honinbo])");
+/// assert_eq!(parse_collection(&text).unwrap().iter().count(), 2);
+/// ```
+pub fn decode_bytes(input: &[u8], options: &ParserOptions) -> Result<String, SgfError> {
+    let text = match options.charset_policy {
+        CharsetPolicy::AssumeUtf8Lossy => {
+            debug_event!("decoding {} bytes as lossy UTF-8", input.len());
+            String::from_utf8_lossy(input).into_owned()
+        }
+        policy => {
+            let charset = match root_charset(input)? {
+                Some(Encoding::Other(name)) if !is_utf8(&name) => Some(name),
+                _ => None,
+            };
+            match charset {
+                None => {
+                    debug_event!("decoding {} bytes as UTF-8", input.len());
+                    decode_utf8(input)?
+                }
+                Some(name) if policy == CharsetPolicy::TranscodeKnown => {
+                    debug_event!("transcoding {} bytes from {}", input.len(), name);
+                    transcode(input, &name)?
+                }
+                Some(_name) => {
+                    debug_event!("rejected charset {} with {:?} policy", _name, policy);
+                    return Err(SgfErrorKind::UnsupportedCharset.into());
+                }
+            }
+        }
+    };
+    Ok(text)
+}
+
+/// Finds the charset of the root node, by parsing the input until the root node is complete
+fn root_charset(input: &[u8]) -> Result<Option<Encoding>, SgfError> {
+    let mut parser = Parser::new();
+    for byte in input.chunks(1) {
+        for event in parser.feed(byte)? {
+            if let Event::Node(node) = event {
+                return Ok(node.tokens.into_iter().find_map(|token| match token {
+                    SgfToken::Charset(encoding) => Some(encoding),
+                    _ => None,
+                }));
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn decode_utf8(input: &[u8]) -> Result<String, SgfError> {
+    String::from_utf8(input.to_vec()).map_err(SgfError::parse_error)
+}
+
+/// Windows-1252 characters for the bytes `0x80..=0x9F`, where it differs from ISO-8859-1
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+/// Normalizes a charset name for comparisons, like `UTF_8` to `utf-8`
+fn charset_key(name: &str) -> String {
+    name.trim().to_lowercase().replace('_', "-")
+}
+
+fn is_utf8(name: &str) -> bool {
+    matches!(charset_key(name).as_str(), "utf-8" | "utf8")
+}
+
+/// Converts `input` from the charset `name` to UTF-8
+fn transcode(input: &[u8], name: &str) -> Result<String, SgfError> {
+    match charset_key(name).as_str() {
+        "us-ascii" | "ascii" if !input.is_ascii() => Err(SgfErrorKind::ParseError.into()),
+        "us-ascii" | "ascii" => decode_utf8(input),
+        "iso-8859-1" | "iso8859-1" | "latin1" | "latin-1" | "l1" => {
+            Ok(input.iter().map(|byte| char::from(*byte)).collect())
+        }
+        "windows-1252" | "cp1252" => Ok(input
+            .iter()
+            .map(|byte| match byte {
+                0x80..=0x9f => WINDOWS_1252[(byte - 0x80) as usize],
+                byte => char::from(*byte),
+            })
+            .collect()),
+        _ => Err(SgfErrorKind::UnsupportedCharset.into()),
+    }
+}
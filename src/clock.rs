@@ -0,0 +1,254 @@
+use crate::time::move_color;
+use crate::{Color, GameNode, GameTree, SgfToken};
+
+/// Problem found in the `BL` and `WL` clock values of a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClockIssue {
+    /// The move has no remaining time, while other moves of the same color do
+    Missing,
+    /// The remaining time is negative
+    Negative,
+    /// The remaining time went up, without any overtime being recorded
+    Increased { previous: u32, remaining: u32 },
+}
+
+/// A clock problem at a single move of the main variation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClockProblem {
+    /// 1-based number of the move, counting moves of both colors
+    pub move_number: usize,
+    pub color: Color,
+    pub issue: ClockIssue,
+}
+
+/// Clock value of a single move, as found in the node
+enum ClockValue {
+    Missing,
+    Negative,
+    Remaining(u32),
+}
+
+struct ClockEntry {
+    node: usize,
+    move_number: usize,
+    value: ClockValue,
+    overtime: bool,
+}
+
+impl GameTree {
+    /// Checks the clock values of the main variation for impossible sequences
+    ///
+    /// Increasing time is allowed when the root node has an `OT` overtime description, or when
+    /// the move records the moves left in an overtime period. Colors without any clock values
+    /// are not checked.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;B[aa]BL[50];B[bb];B[cc]BL[60])").unwrap();
+    /// let problems = tree.check_clock();
+    /// assert_eq!(problems[0].issue, ClockIssue::Missing);
+    /// assert_eq!(
+    ///     problems[1].issue,
+    ///     ClockIssue::Increased { previous: 50, remaining: 60 }
+    /// );
+    /// ```
+    pub fn check_clock(&self) -> Vec<ClockProblem> {
+        let nodes: Vec<&GameNode> = self.iter().collect();
+        let mut problems = vec![];
+        for color in [Color::Black, Color::White].iter() {
+            let entries = clock_entries(self, &nodes, *color);
+            problems.extend(find_problems(&entries, *color).into_iter().map(|(_, p)| p));
+        }
+        problems.sort_by_key(|problem| problem.move_number);
+        problems
+    }
+
+    /// Repairs the clock problems found by `check_clock`, and returns them
+    ///
+    /// Broken values are replaced by interpolating between the closest valid values of the same
+    /// color. Values before the first, or after the last, valid value are copied from it.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree = parse("(;B[aa]BL[50];B[bb];B[cc]BL[30])").unwrap();
+    /// assert_eq!(tree.repair_clock().len(), 1);
+    /// assert!(tree.check_clock().is_empty());
+    ///
+    /// let sgf: String = tree.into();
+    /// assert_eq!(sgf, "(;BL[50]B[aa];BL[40]B[bb];BL[30]B[cc])");
+    /// ```
+    pub fn repair_clock(&mut self) -> Vec<ClockProblem> {
+        let mut repairs = vec![];
+        let mut problems = vec![];
+        {
+            let nodes: Vec<&GameNode> = self.iter().collect();
+            for color in [Color::Black, Color::White].iter() {
+                let entries = clock_entries(self, &nodes, *color);
+                let found = find_problems(&entries, *color);
+                let broken: Vec<usize> = found.iter().map(|(index, _)| *index).collect();
+                let valid: Vec<(usize, u32)> = entries
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| !broken.contains(index))
+                    .filter_map(|(index, entry)| match entry.value {
+                        ClockValue::Remaining(time) => Some((index, time)),
+                        _ => None,
+                    })
+                    .collect();
+                for index in broken {
+                    if let Some(time) = interpolate(&valid, index) {
+                        repairs.push((entries[index].node, *color, time));
+                    }
+                }
+                problems.extend(found.into_iter().map(|(_, problem)| problem));
+            }
+        }
+
+        let mut nodes = main_line_mut(self);
+        for (node, color, time) in repairs {
+            let tokens = &mut nodes[node].tokens;
+            tokens.retain(|token| !is_clock_token(token, color));
+            tokens.push(SgfToken::Time { color, time });
+        }
+        problems.sort_by_key(|problem| problem.move_number);
+        problems
+    }
+}
+
+fn clock_entries(tree: &GameTree, nodes: &[&GameNode], color: Color) -> Vec<ClockEntry> {
+    let overtime = tree.nodes.first().is_some_and(|node| {
+        node.tokens
+            .iter()
+            .any(|token| matches!(token, SgfToken::Overtime(_)))
+    });
+    let mut entries = vec![];
+    let mut move_number = 0;
+    for (index, node) in nodes.iter().enumerate() {
+        let node_color = match move_color(node) {
+            Some(node_color) => node_color,
+            None => continue,
+        };
+        move_number += 1;
+        if node_color != color {
+            continue;
+        }
+        let value = node
+            .tokens
+            .iter()
+            .find_map(|token| clock_value(token, color))
+            .unwrap_or(ClockValue::Missing);
+        let moves_remaining = node
+            .tokens
+            .iter()
+            .any(|token| matches!(token, SgfToken::MovesRemaining { color: c, .. } if *c == color));
+        entries.push(ClockEntry {
+            node: index,
+            move_number,
+            value,
+            overtime: overtime || moves_remaining,
+        });
+    }
+    let has_clock = entries
+        .iter()
+        .any(|entry| !matches!(entry.value, ClockValue::Missing));
+    if has_clock {
+        entries
+    } else {
+        vec![]
+    }
+}
+
+/// Finds the broken entries, returning their indexes together with the problem
+fn find_problems(entries: &[ClockEntry], color: Color) -> Vec<(usize, ClockProblem)> {
+    let mut problems = vec![];
+    let mut previous = None;
+    for (index, entry) in entries.iter().enumerate() {
+        let issue = match entry.value {
+            ClockValue::Missing => Some(ClockIssue::Missing),
+            ClockValue::Negative => Some(ClockIssue::Negative),
+            ClockValue::Remaining(remaining) => match previous {
+                Some(previous) if remaining > previous && !entry.overtime => {
+                    Some(ClockIssue::Increased {
+                        previous,
+                        remaining,
+                    })
+                }
+                _ => {
+                    previous = Some(remaining);
+                    None
+                }
+            },
+        };
+        if let Some(issue) = issue {
+            problems.push((
+                index,
+                ClockProblem {
+                    move_number: entry.move_number,
+                    color,
+                    issue,
+                },
+            ));
+        }
+    }
+    problems
+}
+
+fn interpolate(valid: &[(usize, u32)], index: usize) -> Option<u32> {
+    let before = valid.iter().rev().find(|(i, _)| *i < index);
+    let after = valid.iter().find(|(i, _)| *i > index);
+    match (before, after) {
+        (Some(&(i, a)), Some(&(j, b))) => {
+            let offset = (b as i64 - a as i64) * (index - i) as i64 / (j - i) as i64;
+            Some((a as i64 + offset) as u32)
+        }
+        (Some(&(_, time)), None) | (None, Some(&(_, time))) => Some(time),
+        (None, None) => None,
+    }
+}
+
+fn clock_value(token: &SgfToken, color: Color) -> Option<ClockValue> {
+    match token {
+        SgfToken::Time { color: c, time } if *c == color => Some(ClockValue::Remaining(*time)),
+        SgfToken::Invalid((identifier, value)) if is_clock_identifier(identifier, color) => {
+            match value.trim().parse::<f64>() {
+                Ok(time) if time < 0.0 => Some(ClockValue::Negative),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn is_clock_token(token: &SgfToken, color: Color) -> bool {
+    match token {
+        SgfToken::Time { color: c, .. } => *c == color,
+        SgfToken::Invalid((identifier, _)) => is_clock_identifier(identifier, color),
+        _ => false,
+    }
+}
+
+fn is_clock_identifier(identifier: &str, color: Color) -> bool {
+    match color {
+        Color::Black => identifier == "BL",
+        Color::White => identifier == "WL",
+    }
+}
+
+/// Gets mutable references to all nodes in the main variation
+fn main_line_mut(tree: &mut GameTree) -> Vec<&mut GameNode> {
+    let mut nodes = vec![];
+    let mut current = tree;
+    loop {
+        let GameTree {
+            nodes: sequence,
+            variations,
+        } = current;
+        nodes.extend(sequence.iter_mut());
+        match variations.first_mut() {
+            Some(variation) => current = variation,
+            None => return nodes,
+        }
+    }
+}
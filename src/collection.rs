@@ -0,0 +1,81 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{parse_collection, GameTree, SgfError};
+
+/// All games of an SGF file
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Collection {
+    pub games: Vec<GameTree>,
+}
+
+impl Collection {
+    /// Creates a collection holding `games`
+    pub fn new(games: Vec<GameTree>) -> Self {
+        Collection { games }
+    }
+
+    /// Gets an iterator over the games
+    pub fn iter(&self) -> std::slice::Iter<'_, GameTree> {
+        self.games.iter()
+    }
+}
+
+impl From<Vec<GameTree>> for Collection {
+    fn from(games: Vec<GameTree>) -> Self {
+        Collection { games }
+    }
+}
+
+impl std::iter::FromIterator<GameTree> for Collection {
+    fn from_iter<I: IntoIterator<Item = GameTree>>(iter: I) -> Self {
+        Collection {
+            games: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for Collection {
+    type Item = GameTree;
+    type IntoIter = std::vec::IntoIter<GameTree>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.games.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Collection {
+    type Item = &'a GameTree;
+    type IntoIter = std::slice::Iter<'a, GameTree>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.games.iter()
+    }
+}
+
+impl From<&Collection> for String {
+    fn from(collection: &Collection) -> String {
+        collection.games.iter().map(String::from).collect()
+    }
+}
+
+impl From<Collection> for String {
+    fn from(collection: Collection) -> String {
+        (&collection).into()
+    }
+}
+
+impl fmt::Display for Collection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let collection: String = self.into();
+        f.write_str(&collection)
+    }
+}
+
+impl FromStr for Collection {
+    type Err = SgfError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_collection(input)
+    }
+}
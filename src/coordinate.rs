@@ -0,0 +1,280 @@
+use crate::{Action, Color, GameTree, SgfError, SgfErrorKind, SgfToken};
+
+/// Corner of the board that coordinates are counted from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Origin {
+    TopLeft,
+    BottomLeft,
+}
+
+/// A coordinate system, describing the origin and the index of the first point
+///
+/// Tokens always store coordinates in the `CoordinateSystem::SGF` system, where `aa` is `(1, 1)`
+/// in the top left corner. Conversions need the height of the board when the origin changes,
+/// and return `None` for coordinates outside of a board of that height.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let token = SgfToken::from_pair("B", "cd");
+/// if let SgfToken::Move { action: Action::Move(x, y), .. } = token {
+///     assert_eq!(CoordinateSystem::ZERO_BASED.from_sgf((x, y), 19), Some((2, 3)));
+///     assert_eq!(CoordinateSystem::CARTESIAN.from_sgf((x, y), 19), Some((3, 16)));
+///     assert_eq!(CoordinateSystem::CARTESIAN.from_sgf((x, y), 3), None);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CoordinateSystem {
+    pub origin: Origin,
+    pub zero_based: bool,
+}
+
+impl CoordinateSystem {
+    /// System used by `SgfToken`, 1-based from the top left corner
+    pub const SGF: CoordinateSystem = CoordinateSystem {
+        origin: Origin::TopLeft,
+        zero_based: false,
+    };
+
+    /// 0-based from the top left corner, suitable for indexing arrays
+    pub const ZERO_BASED: CoordinateSystem = CoordinateSystem {
+        origin: Origin::TopLeft,
+        zero_based: true,
+    };
+
+    /// 1-based from the bottom left corner, as used by GTP and printed board diagrams
+    pub const CARTESIAN: CoordinateSystem = CoordinateSystem {
+        origin: Origin::BottomLeft,
+        zero_based: false,
+    };
+
+    /// Converts a coordinate from the SGF system to this system
+    ///
+    /// Returns `None` when the coordinate is not on a board with `height` rows
+    pub fn from_sgf(self, coordinate: (u8, u8), height: u8) -> Option<(u8, u8)> {
+        let point = Point::from(coordinate);
+        if point.x == 0 || point.y == 0 || point.y > height {
+            return None;
+        }
+        let point = match self.origin {
+            Origin::TopLeft => point,
+            Origin::BottomLeft => point.flip_y(height)?,
+        };
+        if self.zero_based {
+            point.to_zero_based()
+        } else {
+            Some(point.into())
+        }
+    }
+
+    /// Converts a coordinate from this system to the SGF system
+    ///
+    /// Returns `None` when the coordinate is not on a board with `height` rows
+    pub fn to_sgf(self, coordinate: (u8, u8), height: u8) -> Option<(u8, u8)> {
+        let point = if self.zero_based {
+            Point::from_zero_based(coordinate)?
+        } else {
+            Point::from(coordinate)
+        };
+        if point.x == 0 || point.y == 0 || point.y > height {
+            return None;
+        }
+        match self.origin {
+            Origin::TopLeft => Some(point.into()),
+            Origin::BottomLeft => point.flip_y(height).map(Into::into),
+        }
+    }
+
+    /// Converts a coordinate from this system to `other`
+    pub fn convert(
+        self,
+        coordinate: (u8, u8),
+        height: u8,
+        other: CoordinateSystem,
+    ) -> Option<(u8, u8)> {
+        other.from_sgf(self.to_sgf(coordinate, height)?, height)
+    }
+}
+
+/// A point on the board in the SGF system, 1-based from the top left corner
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let point = Point::new(4, 16);
+/// assert_eq!(point.to_zero_based(), Some((3, 15)));
+/// assert_eq!(point.flip_y(19), Some(Point::new(4, 4)));
+/// assert_eq!(point.flip_y(9), None);
+/// assert_eq!(Point::new(0, 1).to_zero_based(), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: u8,
+    pub y: u8,
+}
+
+impl Point {
+    pub fn new(x: u8, y: u8) -> Point {
+        Point { x, y }
+    }
+
+    /// Creates a point from 0-based coordinates, `None` if they don't fit in a point
+    pub fn from_zero_based((x, y): (u8, u8)) -> Option<Point> {
+        Some(Point::new(x.checked_add(1)?, y.checked_add(1)?))
+    }
+
+    /// Gets the 0-based coordinates of the point, `None` for coordinates of 0
+    pub fn to_zero_based(self) -> Option<(u8, u8)> {
+        Some((self.x.checked_sub(1)?, self.y.checked_sub(1)?))
+    }
+
+    /// Mirrors the point vertically on a board with `board_size` rows
+    ///
+    /// Returns `None` when the point is not on such a board.
+    pub fn flip_y(self, board_size: u8) -> Option<Point> {
+        if self.y == 0 {
+            return None;
+        }
+        let y = board_size.checked_sub(self.y)?.checked_add(1)?;
+        Some(Point::new(self.x, y))
+    }
+
+    /// Converts the point to `system`, see `CoordinateSystem::from_sgf`
+    pub fn to_system(self, system: CoordinateSystem, height: u8) -> Option<(u8, u8)> {
+        system.from_sgf(self.into(), height)
+    }
+}
+
+impl From<(u8, u8)> for Point {
+    fn from((x, y): (u8, u8)) -> Point {
+        Point::new(x, y)
+    }
+}
+
+impl From<Point> for (u8, u8) {
+    fn from(point: Point) -> (u8, u8) {
+        (point.x, point.y)
+    }
+}
+
+impl GameTree {
+    /// Gets the moves of the main variation with coordinates in `system`
+    ///
+    /// The height of the board comes from the `SZ` token of the root node, 19 without one.
+    /// Moves outside of the board fail with `SgfErrorKind::InvalidTokenValue`.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ce];W[];B[ai])").unwrap();
+    /// assert_eq!(
+    ///     tree.moves_in(CoordinateSystem::ZERO_BASED).unwrap(),
+    ///     vec![
+    ///         (Color::Black, Action::Move(2, 4)),
+    ///         (Color::White, Action::Pass),
+    ///         (Color::Black, Action::Move(0, 8)),
+    ///     ]
+    /// );
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[kk])").unwrap();
+    /// assert!(tree.moves_in(CoordinateSystem::CARTESIAN).is_err());
+    /// ```
+    pub fn moves_in(&self, system: CoordinateSystem) -> Converted<Action> {
+        let height = board_height(self);
+        self.iter()
+            .flat_map(|node| node.tokens.iter())
+            .filter_map(|token| match token {
+                SgfToken::Move { color, action } => Some((*color, *action)),
+                _ => None,
+            })
+            .map(|(color, action)| match action {
+                Action::Move(x, y) => system
+                    .from_sgf((x, y), height)
+                    .map(|(x, y)| (color, Action::Move(x, y)))
+                    .ok_or_else(|| SgfErrorKind::InvalidTokenValue.into()),
+                Action::Pass => Ok((color, Action::Pass)),
+            })
+            .collect()
+    }
+
+    /// Gets the setup stones of all nodes in the main variation with coordinates in `system`
+    ///
+    /// The height of the board comes from the `SZ` token of the root node, 19 without one.
+    /// Stones outside of the board fail with `SgfErrorKind::InvalidTokenValue`.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9]AB[cc][gg];AW[ce])").unwrap();
+    /// assert_eq!(
+    ///     tree.stones_in(CoordinateSystem::CARTESIAN).unwrap(),
+    ///     vec![(Color::Black, (3, 7)), (Color::Black, (7, 3)), (Color::White, (3, 5))]
+    /// );
+    /// ```
+    pub fn stones_in(&self, system: CoordinateSystem) -> Converted<(u8, u8)> {
+        let height = board_height(self);
+        self.iter()
+            .flat_map(|node| node.tokens.iter())
+            .filter_map(|token| match token {
+                SgfToken::Add { color, coordinate } => Some((*color, *coordinate)),
+                _ => None,
+            })
+            .map(|(color, coordinate)| {
+                system
+                    .from_sgf(coordinate, height)
+                    .map(|point| (color, point))
+                    .ok_or_else(|| SgfErrorKind::InvalidTokenValue.into())
+            })
+            .collect()
+    }
+}
+
+/// Colored moves or stones with coordinates converted to a coordinate system
+type Converted<T> = Result<Vec<(Color, T)>, SgfError>;
+
+/// Height of the board of `tree`, saturating at the largest height a coordinate can hold
+fn board_height(tree: &GameTree) -> u8 {
+    let (_, height) = tree.board_size().unwrap_or((19, 19));
+    height.min(u32::from(u8::MAX)) as u8
+}
+
+/// GTP column letters, which skip `I`
+const GTP_COLUMNS: &[u8] = b"ABCDEFGHJKLMNOPQRSTUVWXYZ";
+
+/// Converts an SGF coordinate to a GTP vertex like `D4`
+///
+/// Returns `None` for columns beyond the 25 letters GTP supports, and for coordinates outside
+/// of a board with `height` rows
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// assert_eq!(to_gtp_vertex((4, 16), 19), Some("D4".to_string()));
+/// assert_eq!(to_gtp_vertex((9, 1), 19), Some("J19".to_string()));
+/// assert_eq!(to_gtp_vertex((1, 30), 19), None);
+/// ```
+pub fn to_gtp_vertex(coordinate: (u8, u8), height: u8) -> Option<String> {
+    let (x, y) = CoordinateSystem::CARTESIAN.from_sgf(coordinate, height)?;
+    let column = GTP_COLUMNS.get((x as usize).checked_sub(1)?)?;
+    Some(format!("{}{}", *column as char, y))
+}
+
+/// Converts a GTP vertex like `D4` to an SGF coordinate
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// assert_eq!(from_gtp_vertex("D4", 19), Some((4, 16)));
+/// assert_eq!(from_gtp_vertex("I4", 19), None);
+/// assert_eq!(from_gtp_vertex("D20", 19), None);
+/// ```
+pub fn from_gtp_vertex(vertex: &str, height: u8) -> Option<(u8, u8)> {
+    let mut chars = vertex.chars();
+    let column = chars.next()?.to_ascii_uppercase();
+    let x = GTP_COLUMNS.iter().position(|c| *c as char == column)? as u8 + 1;
+    let y: u8 = chars.as_str().parse().ok()?;
+    if y == 0 || y > height {
+        return None;
+    }
+    CoordinateSystem::CARTESIAN.to_sgf((x, y), height)
+}
@@ -0,0 +1,135 @@
+//! Conversions between SGF point values and numeric coordinates
+//!
+//! These are the conversions used by the parser and the writer. Coordinates are 1-based
+//! `(x, y)` pairs counted from the top left corner, so `aa` is `(1, 1)`. Letters `a` to `z`
+//! are 1 to 26, and `A` to `Z` are 27 to 52. Unlike GTP vertices, the letter `i` is not
+//! skipped.
+//!
+//! ```rust
+//! use sgf_parser::coords::*;
+//! use sgf_parser::Action;
+//!
+//! assert_eq!(str_to_coordinates("ia").unwrap(), (9, 1));
+//! assert_eq!(coordinate_to_str((27, 52)).unwrap(), "AZ");
+//! assert_eq!(str_to_action("").unwrap(), Action::Pass);
+//! assert_eq!(action_to_str(Action::Move(4, 16)).unwrap(), "dp");
+//! ```
+
+use std::fmt;
+
+use crate::{Action, SgfError, SgfErrorKind};
+
+/// Converts a point value, like `dp`, to numeric coordinates
+///
+/// Fails with `SgfErrorKind::ParseError` unless the value is exactly two ASCII letters.
+///
+/// ```rust
+/// use sgf_parser::coords::str_to_coordinates;
+///
+/// assert_eq!(str_to_coordinates("dp").unwrap(), (4, 16));
+/// assert!(str_to_coordinates("d").is_err());
+/// assert!(str_to_coordinates("d1").is_err());
+/// ```
+pub fn str_to_coordinates(input: &str) -> Result<(u8, u8), SgfError> {
+    if input.len() != 2 {
+        Err(SgfErrorKind::ParseError.into())
+    } else {
+        let coords = input
+            .as_bytes()
+            .iter()
+            .map(|c| convert_u8_to_coordinate(*c))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| SgfError::from(SgfErrorKind::ParseError))?;
+        Ok((coords[0], coords[1]))
+    }
+}
+
+/// Converts numeric coordinates to a point value
+///
+/// Returns `None` for coordinates outside of `1..=52`, which can not be represented.
+///
+/// ```rust
+/// use sgf_parser::coords::coordinate_to_str;
+///
+/// assert_eq!(coordinate_to_str((4, 16)), Some("dp".to_string()));
+/// assert_eq!(coordinate_to_str((0, 16)), None);
+/// assert_eq!(coordinate_to_str((53, 16)), None);
+/// ```
+pub fn coordinate_to_str(coordinate: (u8, u8)) -> Option<String> {
+    if is_point(coordinate) {
+        Some(point_value(coordinate))
+    } else {
+        None
+    }
+}
+
+/// Converts coordinates known to be in `1..=52`, like those of a `Board`, to a point value
+pub(crate) fn point_value(coordinate: (u8, u8)) -> String {
+    let mut out = String::with_capacity(2);
+    let _ = write_coordinate(coordinate, &mut out);
+    out
+}
+
+/// Writes a coordinate like `coordinate_to_str`, without allocating
+///
+/// Coordinates outside of `1..=52` are a bug in the caller. They are caught by a debug
+/// assertion, and wrap around in release builds.
+pub(crate) fn write_coordinate<W: fmt::Write>(coordinate: (u8, u8), out: &mut W) -> fmt::Result {
+    fn to_char(c: u8) -> char {
+        c.wrapping_add(if c < 27 { 96 } else { 38 }) as char
+    }
+
+    debug_assert!(
+        is_point(coordinate),
+        "coordinate {:?} can not be written as an SGF point",
+        coordinate
+    );
+    out.write_char(to_char(coordinate.0))?;
+    out.write_char(to_char(coordinate.1))
+}
+
+/// Converts the value of a `B` or `W` property to an `Action`
+///
+/// An empty value is a pass. `tt` is kept as the point `(20, 20)`, since it is only a pass on
+/// boards up to 19x19.
+///
+/// ```rust
+/// use sgf_parser::coords::str_to_action;
+/// use sgf_parser::Action;
+///
+/// assert_eq!(str_to_action("").unwrap(), Action::Pass);
+/// assert_eq!(str_to_action("tt").unwrap(), Action::Move(20, 20));
+/// ```
+pub fn str_to_action(input: &str) -> Result<Action, SgfError> {
+    if input.is_empty() {
+        Ok(Action::Pass)
+    } else {
+        let (x, y) = str_to_coordinates(input)?;
+        Ok(Action::Move(x, y))
+    }
+}
+
+/// Converts an `Action` to the value of a `B` or `W` property, with passes as empty values
+///
+/// Returns `None` for moves that `coordinate_to_str` can't convert.
+pub fn action_to_str(action: Action) -> Option<String> {
+    match action {
+        Action::Move(x, y) => coordinate_to_str((x, y)),
+        Action::Pass => Some(String::new()),
+    }
+}
+
+fn is_point((x, y): (u8, u8)) -> bool {
+    (1..=52).contains(&x) && (1..=52).contains(&y)
+}
+
+/// Converts a u8 char to numeric coordinates
+///
+#[inline]
+fn convert_u8_to_coordinate(c: u8) -> Option<u8> {
+    match c {
+        b'a'..=b'z' => Some(c - 96),
+        b'A'..=b'Z' => Some(c - 38),
+        _ => None,
+    }
+}
@@ -0,0 +1,165 @@
+use crate::coords::{point_value, str_to_coordinates};
+use crate::{
+    Action, Board, GameNode, GameTree, SgfError, SgfErrorKind, SgfToken, TokenString, ValueType,
+};
+
+/// A rectangle of the board, from the top left to the bottom right point, both included
+#[derive(Debug, Clone, Copy)]
+struct Region {
+    from: (u8, u8),
+    to: (u8, u8),
+}
+
+impl Region {
+    fn contains(self, (x, y): (u8, u8)) -> bool {
+        (self.from.0..=self.to.0).contains(&x) && (self.from.1..=self.to.1).contains(&y)
+    }
+
+    fn points(self) -> impl Iterator<Item = (u8, u8)> {
+        (self.from.1..=self.to.1).flat_map(move |y| (self.from.0..=self.to.0).map(move |x| (x, y)))
+    }
+}
+
+impl GameTree {
+    /// Creates a new tree showing only the rectangle from `from` to `to`, with a `VW` token
+    /// limiting the view to it
+    ///
+    /// Moves, setup stones and markup outside the rectangle are removed, along with nodes
+    /// left without tokens. All lines are replayed, and where the stones in the rectangle
+    /// would end up different from the original, like after a capture by a stone outside it,
+    /// setup tokens are added to restore them: after the move in a node of its own, or in the
+    /// node itself for nodes without a move. Removed stones use an `AE` token.
+    ///
+    /// Fails with `SgfErrorKind::InvalidTokenValue` if the rectangle isn't on the board, and
+    /// with `SgfErrorKind::IllegalMove` for moves that can't be replayed.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree =
+    ///     parse("(;SZ[9]AB[gg];B[cc]C[Corner];W[dc];B[gc];W[cd](;B[bd])(;B[ge]))").unwrap();
+    /// let cropped = tree.crop((1, 1), (5, 5)).unwrap();
+    /// assert_eq!(
+    ///     cropped.to_string(),
+    ///     "(;SZ[9]VW[aa:ee];B[cc]C[Corner];W[dc];W[cd](;B[bd]))"
+    /// );
+    /// ```
+    pub fn crop(&self, from: (u8, u8), to: (u8, u8)) -> Result<GameTree, SgfError> {
+        let original = Board::for_tree(self)?;
+        let (width, height) = original.size();
+        let on_board = |(x, y): (u8, u8)| x >= 1 && y >= 1 && x <= width && y <= height;
+        if !on_board(from) || !on_board(to) || from.0 > to.0 || from.1 > to.1 {
+            return Err(SgfErrorKind::InvalidTokenValue.into());
+        }
+        let region = Region { from, to };
+        let view = original.clone();
+        let mut tree = crop_tree(self, region, original, view, true)?;
+        if let Some(root) = tree.nodes.first_mut() {
+            root.tokens.retain(|token| token.identifier() != "VW");
+            root.tokens.push(SgfToken::Unknown((
+                TokenString::from_static("VW"),
+                format!("{}:{}", point_value(from), point_value(to)).into(),
+            )));
+        } else {
+            return Err(SgfErrorKind::NodeNotFound.into());
+        }
+        Ok(tree)
+    }
+}
+
+/// Crops `tree`, replaying the original nodes on `original`, and the cropped nodes on `view`
+///
+/// The root node is kept even without tokens, so the `VW` token can be added to it.
+/// Before each node, the stones of `view` in the region are the same as on `original`, and
+/// there are no stones outside it.
+fn crop_tree(
+    tree: &GameTree,
+    region: Region,
+    mut original: Board,
+    mut view: Board,
+    is_root: bool,
+) -> Result<GameTree, SgfError> {
+    let (width, height) = original.size();
+    let mut nodes = vec![];
+    for (index, node) in tree.nodes.iter().enumerate() {
+        let mut cropped = GameNode {
+            tokens: node
+                .tokens
+                .iter()
+                .filter(
+                    |token| !matches!(token_point(token), Some(point) if !region.contains(point)),
+                )
+                .cloned()
+                .collect(),
+            extras: node.extras.clone(),
+        };
+        original.apply_node(node)?;
+        view.apply_node(&cropped)?;
+        let fixes: Vec<SgfToken> = region
+            .points()
+            .filter(|point| original.get(*point) != view.get(*point))
+            .map(|point| match original.get(point) {
+                Some(color) => SgfToken::Add {
+                    color,
+                    coordinate: point,
+                },
+                None => {
+                    SgfToken::Unknown((TokenString::from_static("AE"), point_value(point).into()))
+                }
+            })
+            .collect();
+        let has_move = cropped
+            .tokens
+            .iter()
+            .any(|token| matches!(token, SgfToken::Move { .. }));
+        if has_move && !fixes.is_empty() {
+            nodes.push(cropped);
+            cropped = GameNode::from(fixes);
+        } else {
+            cropped.tokens.extend(fixes);
+        }
+        if (is_root && index == 0) || !cropped.tokens.is_empty() || node.tokens.is_empty() {
+            nodes.push(cropped);
+        }
+        view = Board::new(width, height);
+        for point in region.points() {
+            if let Some(color) = original.get(point) {
+                view.place(color, point)?;
+            }
+        }
+    }
+    let mut variations = vec![];
+    for variation in &tree.variations {
+        let variation = crop_tree(variation, region, original.clone(), view.clone(), false)?;
+        if variation.nodes.is_empty() {
+            variations.extend(variation.variations);
+        } else {
+            variations.push(variation);
+        }
+    }
+    Ok(GameTree { nodes, variations })
+}
+
+/// Gets the point of a move, setup stone or markup token
+///
+/// Unknown tokens of properties taking points, like `AE` or `MA`, are included.
+fn token_point(token: &SgfToken) -> Option<(u8, u8)> {
+    match token {
+        SgfToken::Add { coordinate, .. }
+        | SgfToken::Square { coordinate }
+        | SgfToken::Circle { coordinate }
+        | SgfToken::Triangle { coordinate }
+        | SgfToken::Label { coordinate, .. } => Some(*coordinate),
+        SgfToken::Move {
+            action: Action::Move(x, y),
+            ..
+        } => Some((*x, *y)),
+        SgfToken::Unknown((identifier, value)) => match SgfToken::value_type_of(identifier) {
+            Some(ValueType::List(element)) if *element == ValueType::Point => {
+                str_to_coordinates(value).ok()
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
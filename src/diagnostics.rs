@@ -0,0 +1,34 @@
+//! Debug events emitted through the `log` crate when the `log` feature is enabled
+//!
+//! All events use the `sgf_parser` target, so they can be filtered separately from the
+//! application's own logs.
+
+use crate::SgfToken;
+
+/// Emits a debug event with the `sgf_parser` target, or nothing without the `log` feature
+macro_rules! debug_event {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "log")]
+        log::debug!(target: "sgf_parser", $($arg)+);
+    };
+}
+
+/// Reports properties that were not parsed into a known token
+#[cfg(feature = "log")]
+pub(crate) fn token_created(identifier: &str, value: &str, token: &SgfToken) {
+    match token {
+        SgfToken::Invalid(_) if crate::token::normalize_identifier(identifier).is_empty() => {
+            debug_event!("malformed identifier {}: no uppercase letters", identifier);
+        }
+        SgfToken::Invalid(_) => {
+            debug_event!("invalid value for {}: [{}]", identifier, value);
+        }
+        SgfToken::Unknown(_) => {
+            debug_event!("unknown property {}: [{}]", identifier, value);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(not(feature = "log"))]
+pub(crate) fn token_created(_identifier: &str, _value: &str, _token: &SgfToken) {}
@@ -0,0 +1,300 @@
+use crate::{format_real, Color, GameTree, Outcome, Rank, Real, RuleSet, SgfToken, TokenString};
+
+/// SGF variants written by Go servers, see `GameTree::normalize_dialect`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dialect {
+    /// Fox Weiqi, with Chinese labels and komi in hundredths
+    Fox,
+    /// Tygem, with Korean labels
+    Tygem,
+}
+
+impl GameTree {
+    /// Converts the game information written by `dialect` to standard tokens, and returns the
+    /// number of tokens that were changed
+    ///
+    /// Results and ranks written as Chinese or Korean labels, like `黑中盘胜` or `흑 3집반승`,
+    /// are recognized for both dialects. For `Dialect::Fox`, komi values of 50 and up are read
+    /// as hundredths of a point, or of a stone under Chinese rules, so `KM[375]` with
+    /// `RU[Chinese]` is 7.5 points.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree = parse("(;RU[cn]KM[375]BR[职业9段];B[pd])").unwrap();
+    /// assert_eq!(tree.normalize_dialect(Dialect::Fox), 3);
+    /// assert_eq!(tree.to_string(), "(;BR[9p]KM[7.5]RU[Chinese];B[pd])");
+    /// ```
+    pub fn normalize_dialect(&mut self, dialect: Dialect) -> usize {
+        let root = match self.nodes.first_mut() {
+            Some(root) => root,
+            None => return 0,
+        };
+        let chinese = root.tokens.iter().any(is_chinese_rules);
+        let mut changes = 0;
+        for token in root.tokens.iter_mut() {
+            let normalized = match token {
+                SgfToken::Invalid((identifier, value)) if identifier == "RE" => {
+                    parse_result_label(value).map(SgfToken::Result)
+                }
+                SgfToken::Rule(rule @ RuleSet::Unknown(_)) if is_chinese_rule_name(rule) => {
+                    Some(SgfToken::Rule(RuleSet::Chinese))
+                }
+                SgfToken::Komi(komi) if dialect == Dialect::Fox && komi.get() >= 50.0 => {
+                    let scale = if chinese { 50.0 } else { 100.0 };
+                    Real::new(komi.get() / scale).map(SgfToken::Komi)
+                }
+                SgfToken::PlayerRank { color, rank } => parse_rank_label(rank)
+                    .map(|rank| rank.to_string())
+                    .filter(|normalized| normalized != rank)
+                    .map(|rank| SgfToken::PlayerRank {
+                        color: *color,
+                        rank,
+                    }),
+                _ => None,
+            };
+            if let Some(normalized) = normalized {
+                *token = normalized;
+                changes += 1;
+            }
+        }
+        changes
+    }
+
+    /// Rewrites the `BR` and `WR` ranks of the root node in the short form of `Rank`, like
+    /// `5k` or `9d`, and returns the number of ranks that were changed
+    ///
+    /// Ranks are otherwise kept as written. Ranks that `Rank::from` can not read are left alone.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree = parse("(;BR[9段]WR[5 kyu];B[pd])").unwrap();
+    /// assert_eq!(tree.to_string(), "(;BR[9段]WR[5 kyu];B[pd])");
+    /// assert_eq!(tree.normalize_ranks(), 2);
+    /// assert_eq!(tree.to_string(), "(;BR[9d]WR[5k];B[pd])");
+    /// ```
+    pub fn normalize_ranks(&mut self) -> usize {
+        let root = match self.nodes.first_mut() {
+            Some(root) => root,
+            None => return 0,
+        };
+        let mut changes = 0;
+        for token in root.tokens.iter_mut() {
+            if let SgfToken::PlayerRank { rank, .. } = token {
+                let normalized = Rank::from(rank.as_str()).to_string();
+                if normalized != *rank {
+                    *rank = normalized;
+                    changes += 1;
+                }
+            }
+        }
+        changes
+    }
+
+    /// Serializes the tree with the game information written the way `dialect` writes it
+    ///
+    /// This is the reverse of `normalize_dialect`, for uploading games back to the server.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;KM[6.5]RE[W+3.5]WR[2d];W[pd])").unwrap();
+    /// assert_eq!(
+    ///     tree.serialize_dialect(Dialect::Tygem),
+    ///     "(;KM[6.5]RE[백 3집반승]WR[2단];W[pd])"
+    /// );
+    /// assert_eq!(
+    ///     tree.serialize_dialect(Dialect::Fox),
+    ///     "(;KM[650]RE[白胜3.5目]WR[2段];W[pd])"
+    /// );
+    /// ```
+    pub fn serialize_dialect(&self, dialect: Dialect) -> String {
+        let mut tree = self.clone();
+        if let Some(root) = tree.nodes.first_mut() {
+            let chinese = root.tokens.iter().any(is_chinese_rules);
+            for token in root.tokens.iter_mut() {
+                let written = match token {
+                    SgfToken::Result(outcome) => Some(("RE", result_label(*outcome, dialect))),
+                    SgfToken::PlayerRank { color, rank } => {
+                        let identifier = match color {
+                            Color::Black => "BR",
+                            Color::White => "WR",
+                        };
+                        Some((identifier, rank_label(&Rank::from(rank.as_str()), dialect)))
+                    }
+                    SgfToken::Komi(komi) if dialect == Dialect::Fox => {
+                        let scale = if chinese { 50.0 } else { 100.0 };
+                        let hundredths = komi.get() * scale;
+                        Some(("KM", format_real(hundredths)))
+                    }
+                    _ => None,
+                };
+                if let Some((identifier, value)) = written {
+                    *token =
+                        SgfToken::Unknown((TokenString::from_static(identifier), value.into()));
+                }
+            }
+        }
+        tree.to_string()
+    }
+}
+
+fn is_chinese_rules(token: &SgfToken) -> bool {
+    match token {
+        SgfToken::Rule(RuleSet::Chinese) => true,
+        SgfToken::Rule(rule) => is_chinese_rule_name(rule),
+        _ => false,
+    }
+}
+
+fn is_chinese_rule_name(rule: &RuleSet) -> bool {
+    match rule {
+        RuleSet::Unknown(name) => matches!(
+            name.to_lowercase().as_str(),
+            "cn" | "chinese" | "中国规则" | "中国"
+        ),
+        _ => false,
+    }
+}
+
+/// Reads ranks like `3级`, `职业9段`, `P9段` or `프로9단`, on top of the ranks `Rank::from` reads
+fn parse_rank_label(rank: &str) -> Option<Rank> {
+    let pro = ["职业", "職業", "프로", "P"]
+        .iter()
+        .find_map(|prefix| rank.trim().strip_prefix(prefix));
+    match (pro.map(Rank::from), Rank::from(rank)) {
+        (Some(Rank::Dan(dan)), _) => Some(Rank::Pro(dan)),
+        (_, Rank::Other(_)) => None,
+        (_, rank) => Some(rank),
+    }
+}
+
+fn rank_label(rank: &Rank, dialect: Dialect) -> String {
+    match (rank, dialect) {
+        (Rank::Kyu(kyu), Dialect::Fox) => format!("{}级", kyu),
+        (Rank::Dan(dan), Dialect::Fox) => format!("{}段", dan),
+        (Rank::Pro(pro), Dialect::Fox) => format!("职业{}段", pro),
+        (Rank::Kyu(kyu), Dialect::Tygem) => format!("{}급", kyu),
+        (Rank::Dan(dan), Dialect::Tygem) => format!("{}단", dan),
+        (Rank::Pro(pro), Dialect::Tygem) => format!("프로{}단", pro),
+        (Rank::Other(rank), _) => rank.clone(),
+    }
+}
+
+/// Reads result labels like `黑中盘胜`, `白胜3.5目`, `黑胜1又3/4子`, `黒3目半勝ち`, `흑 3집반승` or
+/// `B+中押し`
+pub(crate) fn parse_result_label(label: &str) -> Option<Outcome> {
+    let label: String = label.chars().filter(|c| !c.is_whitespace()).collect();
+    if matches!(
+        label.as_str(),
+        "和棋" | "和局" | "平局" | "持碁" | "ジゴ" | "무승부"
+    ) {
+        return Some(Outcome::Draw);
+    }
+    let mut chars = label.chars();
+    let color = match chars.next()? {
+        '黑' | '黒' | '흑' | 'B' => Color::Black,
+        '白' | '백' | 'W' => Color::White,
+        _ => return None,
+    };
+    let rest = chars.as_str();
+    let rest = rest
+        .strip_prefix('+')
+        .or_else(|| rest.strip_prefix('の'))
+        .unwrap_or(rest);
+    match rest {
+        "中盘胜" | "中盤勝" | "中押し勝ち" | "中押勝" | "中盘" | "中盤" | "中押し" | "불계승"
+        | "불계" => return Some(Outcome::WinnerByResign(color)),
+        "中盘负" | "中盤負" | "中押し負け" | "불계패" => {
+            return Some(Outcome::WinnerByResign(!color))
+        }
+        "超时胜" | "时间胜" | "時間勝ち" | "시간승" => {
+            return Some(Outcome::WinnerByTime(color))
+        }
+        "超时负" | "时间负" | "時間切れ負け" | "시간패" | "시간초과패" => {
+            return Some(Outcome::WinnerByTime(!color))
+        }
+        "不戦勝" | "기권승" => return Some(Outcome::WinnerByForfeit(color)),
+        _ => {}
+    }
+    let points = if let Some(score) = rest.strip_prefix('胜').or_else(|| rest.strip_prefix('勝'))
+    {
+        if let Some(stones) = score.strip_suffix('子') {
+            parse_fraction(stones)? * 2.0
+        } else {
+            score
+                .strip_suffix('目')
+                .or_else(|| score.strip_suffix('点'))?
+                .parse()
+                .ok()?
+        }
+    } else {
+        let score = ["勝ち", "勝", "승"]
+            .iter()
+            .find_map(|suffix| rest.strip_suffix(suffix))
+            .unwrap_or(rest);
+        match score
+            .strip_suffix("目半")
+            .or_else(|| score.strip_suffix("집반"))
+        {
+            Some(whole) => whole.parse::<f32>().ok()? + 0.5,
+            None => score
+                .strip_suffix('目')
+                .or_else(|| score.strip_suffix('집'))?
+                .parse()
+                .ok()?,
+        }
+    };
+    Some(Outcome::WinnerByPoints(color, Real::new(points)?))
+}
+
+/// Reads numbers like `2`, `1/4` or `2又1/4`
+fn parse_fraction(value: &str) -> Option<f32> {
+    let (whole, fraction) = match value.find('又') {
+        Some(index) => (&value[..index], &value[index + '又'.len_utf8()..]),
+        None if value.contains('/') => ("0", value),
+        None => (value, "0/1"),
+    };
+    let (numerator, denominator) = fraction.split_at(fraction.find('/')?);
+    let denominator: f32 = denominator[1..].parse().ok()?;
+    if denominator == 0.0 {
+        return None;
+    }
+    Some(whole.parse::<f32>().ok()? + numerator.parse::<f32>().ok()? / denominator)
+}
+
+fn result_label(outcome: Outcome, dialect: Dialect) -> String {
+    let color = |color: Color| match (color, dialect) {
+        (Color::Black, Dialect::Fox) => "黑",
+        (Color::White, Dialect::Fox) => "白",
+        (Color::Black, Dialect::Tygem) => "흑",
+        (Color::White, Dialect::Tygem) => "백",
+    };
+    match (outcome, dialect) {
+        (Outcome::Draw, Dialect::Fox) => "和棋".to_string(),
+        (Outcome::Draw, Dialect::Tygem) => "무승부".to_string(),
+        (Outcome::WinnerByResign(winner), Dialect::Fox) => format!("{}中盘胜", color(winner)),
+        (Outcome::WinnerByResign(winner), Dialect::Tygem) => format!("{} 불계승", color(winner)),
+        (Outcome::WinnerByTime(winner), Dialect::Fox) => format!("{}超时胜", color(winner)),
+        (Outcome::WinnerByTime(winner), Dialect::Tygem) => format!("{} 시간승", color(winner)),
+        (Outcome::WinnerByForfeit(winner), Dialect::Tygem) => format!("{} 기권승", color(winner)),
+        (Outcome::WinnerByForfeit(Color::Black), Dialect::Fox) => "B+F".to_string(),
+        (Outcome::WinnerByForfeit(Color::White), Dialect::Fox) => "W+F".to_string(),
+        (Outcome::WinnerByPoints(winner, points), Dialect::Fox) => {
+            format!("{}胜{}目", color(winner), points)
+        }
+        (Outcome::WinnerByPoints(winner, points), Dialect::Tygem)
+            if points.get().fract() == 0.5 =>
+        {
+            format!(
+                "{} {}집반승",
+                color(winner),
+                format_real(points.get().trunc())
+            )
+        }
+        (Outcome::WinnerByPoints(winner, points), Dialect::Tygem) => {
+            format!("{} {}집승", color(winner), points)
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{GameTree, SgfToken};
+
+impl GameTree {
+    /// Exports the node and variation structure as a Graphviz DOT graph
+    ///
+    /// Each node is labelled with its move and move number, like `12: B[dd]`. Nodes without a
+    /// move, like the root node, get an empty label.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9]C[Start];B[dc](;W[aa])(;W[]))").unwrap();
+    /// assert_eq!(
+    ///     tree.to_dot(),
+    ///     "digraph {\n    n0 [label=\"\"];\n    n1 [label=\"1: B[dc]\"];\n    n0 -> n1;\n    n2 [label=\"2: W[aa]\"];\n    n1 -> n2;\n    n3 [label=\"2: W[]\"];\n    n1 -> n3;\n}\n"
+    /// );
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph {\n");
+        let mut next_id = 0;
+        write_dot(self, None, 0, &mut next_id, &mut out);
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Writes the nodes of `tree`, connecting the first node to `parent`, and numbering moves
+/// after `moves` earlier moves
+fn write_dot(
+    tree: &GameTree,
+    parent: Option<usize>,
+    mut moves: usize,
+    next_id: &mut usize,
+    out: &mut String,
+) {
+    let mut parent = parent;
+    for node in &tree.nodes {
+        let id = *next_id;
+        *next_id += 1;
+        let label = node
+            .tokens
+            .iter()
+            .filter(|token| matches!(token, SgfToken::Move { .. }))
+            .map(|token| {
+                moves += 1;
+                format!("{}: {}", moves, token)
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        out.push_str(&format!("    n{} [label=\"{}\"];\n", id, label));
+        if let Some(parent) = parent {
+            out.push_str(&format!("    n{} -> n{};\n", parent, id));
+        }
+        parent = Some(id);
+    }
+    for variation in &tree.variations {
+        write_dot(variation, parent, moves, next_id, out);
+    }
+}
@@ -11,14 +11,35 @@ pub struct SgfError {
 }
 
 /// Describes what kind of error we're dealing with
-#[derive(Debug, Display, Eq, PartialEq)]
+#[derive(Debug, Display, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum SgfErrorKind {
     #[display(fmt = "Error parsing SGF file")]
     ParseError,
     #[display(fmt = "Variation not found")]
     VariationNotFound,
+    #[display(
+        fmt = "Variation {} picked, but there are {} variations",
+        requested,
+        available
+    )]
+    VariationOutOfRange { requested: usize, available: usize },
     #[display(fmt = "Root token found in a non root node")]
     InvalidRootTokenPlacement,
+    #[display(fmt = "Illegal move")]
+    IllegalMove,
+    #[display(fmt = "Error reading SGF input")]
+    IoError,
+    #[display(fmt = "Invalid token value")]
+    InvalidTokenValue,
+    #[display(fmt = "Node not found")]
+    NodeNotFound,
+    #[display(fmt = "Property identifier repeated in a node")]
+    DuplicateIdentifier,
+    #[display(fmt = "Unsupported charset")]
+    UnsupportedCharset,
+    #[display(fmt = "Game has variations")]
+    UnexpectedVariation,
 }
 
 impl Error for SgfError {
@@ -50,10 +71,31 @@ impl SgfError {
         }
     }
 
+    pub fn io_error(err: impl Error + Send + Sync + 'static) -> Self {
+        SgfError {
+            kind: SgfErrorKind::IoError,
+            source: Some(Box::new(err)),
+        }
+    }
+
+    pub fn illegal_move(err: impl Error + Send + Sync + 'static) -> Self {
+        SgfError {
+            kind: SgfErrorKind::IllegalMove,
+            source: Some(Box::new(err)),
+        }
+    }
+
     pub fn invalid_root_token_placment(err: impl Error + Send + Sync + 'static) -> Self {
         SgfError {
             kind: SgfErrorKind::InvalidRootTokenPlacement,
             source: Some(Box::new(err)),
         }
     }
+
+    pub fn unexpected_variation(err: impl Error + Send + Sync + 'static) -> Self {
+        SgfError {
+            kind: SgfErrorKind::UnexpectedVariation,
+            source: Some(Box::new(err)),
+        }
+    }
 }
@@ -0,0 +1,129 @@
+use std::fmt;
+
+use crate::{Action, GameNode, GameTree, SgfToken};
+
+impl GameTree {
+    /// Estimates the length in bytes of the tree written as SGF, without serializing it
+    ///
+    /// The estimate is usually a little above the real length, since repeated identifiers,
+    /// like in `AB[aa][bb]`, are counted for every value, and escapes in text values are not
+    /// counted.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[19]C[Opening];B[pd](;W[dp])(;W[dd]))").unwrap();
+    /// assert_eq!(tree.estimated_sgf_len(), tree.to_string().len());
+    /// assert_eq!(tree.token_count(), 5);
+    /// ```
+    pub fn estimated_sgf_len(&self) -> usize {
+        2 + self
+            .nodes
+            .iter()
+            .map(GameNode::estimated_len)
+            .sum::<usize>()
+            + self
+                .variations
+                .iter()
+                .map(GameTree::estimated_sgf_len)
+                .sum::<usize>()
+    }
+
+    /// Counts the tokens of all nodes, including every variation
+    pub fn token_count(&self) -> usize {
+        self.nodes
+            .iter()
+            .map(|node| node.tokens.len())
+            .sum::<usize>()
+            + self
+                .variations
+                .iter()
+                .map(GameTree::token_count)
+                .sum::<usize>()
+    }
+}
+
+impl GameNode {
+    /// Estimates the length in bytes of the node written as SGF, like
+    /// `GameTree::estimated_sgf_len`
+    pub fn estimated_len(&self) -> usize {
+        1 + self
+            .tokens
+            .iter()
+            .map(SgfToken::estimated_len)
+            .sum::<usize>()
+    }
+}
+
+impl SgfToken {
+    /// Estimates the length in bytes of the token written as SGF, without serializing it
+    ///
+    /// Text values are counted without escapes, and the few values that are neither text,
+    /// numbers or points are serialized to get their length.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// assert_eq!(SgfToken::from_pair("B", "pd").estimated_len(), 5);
+    /// assert_eq!(SgfToken::from_pair("C", "a]b").estimated_len(), 6);
+    /// ```
+    pub fn estimated_len(&self) -> usize {
+        let value = match self {
+            SgfToken::Move {
+                action: Action::Pass,
+                ..
+            } => 0,
+            SgfToken::Move { .. }
+            | SgfToken::Add { .. }
+            | SgfToken::Square { .. }
+            | SgfToken::Circle { .. }
+            | SgfToken::Triangle { .. } => 2,
+            SgfToken::Label { label, .. } => 3 + label.len(),
+            SgfToken::Time { time: number, .. }
+            | SgfToken::MovesRemaining { moves: number, .. }
+            | SgfToken::TimeLimit(number)
+            | SgfToken::Handicap(number) => digits(*number),
+            SgfToken::Size(width, height) if width == height => digits(*width),
+            SgfToken::Size(width, height) => digits(*width) + 1 + digits(*height),
+            SgfToken::PlayerName { name: text, .. }
+            | SgfToken::Event(text)
+            | SgfToken::Copyright(text)
+            | SgfToken::GameName(text)
+            | SgfToken::Place(text)
+            | SgfToken::Date(text)
+            | SgfToken::Overtime(text)
+            | SgfToken::Comment(text)
+            | SgfToken::NodeName(text)
+            | SgfToken::Unknown((_, text))
+            | SgfToken::Invalid((_, text)) => text.len(),
+            SgfToken::Application { name, version } => name.len() + 1 + version.len(),
+            SgfToken::EmptyList(_) => 0,
+            SgfToken::Even(_)
+            | SgfToken::GoodFor { .. }
+            | SgfToken::Hotspot(_)
+            | SgfToken::Unclear(_)
+            | SgfToken::BadMove(_)
+            | SgfToken::Tesuji(_) => 1,
+            token => {
+                let mut count = ByteCount(0);
+                let _ = token.write_to(&mut count);
+                return count.0;
+            }
+        };
+        self.identifier().len() + 2 + value
+    }
+}
+
+fn digits(number: u32) -> usize {
+    number.checked_ilog10().unwrap_or(0) as usize + 1
+}
+
+/// Counts the bytes written to it, to measure text without allocating it
+struct ByteCount(usize);
+
+impl fmt::Write for ByteCount {
+    fn write_str(&mut self, value: &str) -> fmt::Result {
+        self.0 += value.len();
+        Ok(())
+    }
+}
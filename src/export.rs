@@ -0,0 +1,258 @@
+use std::io::Write;
+
+use crate::{format_real, Collection, Color, GameTree, Outcome, Rank, SgfError, SgfToken};
+
+/// Column names of the exported game information, in export order
+pub const GAME_RECORD_COLUMNS: [&str; 10] = [
+    "black",
+    "white",
+    "black_rank",
+    "white_rank",
+    "result",
+    "date",
+    "event",
+    "moves",
+    "komi",
+    "handicap",
+];
+
+/// Game information of a single game, as exported by `write_csv`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GameRecord {
+    pub black: Option<String>,
+    pub white: Option<String>,
+    pub black_rank: Option<Rank>,
+    pub white_rank: Option<Rank>,
+    pub result: Option<Outcome>,
+    pub date: Option<String>,
+    pub event: Option<String>,
+    /// Number of moves in the main variation, including passes
+    pub moves: usize,
+    pub komi: Option<f32>,
+    pub handicap: Option<u32>,
+}
+
+impl GameRecord {
+    /// Collects the game information of `tree`, from its root node and main variation
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;PB[Shusaku]BR[4d]RE[B+2]KM[0];B[qd];W[dc];B[pq])").unwrap();
+    /// let record = GameRecord::from_tree(&tree);
+    /// assert_eq!(record.black, Some("Shusaku".to_string()));
+    /// assert_eq!(record.black_rank, Some(Rank::Dan(4)));
+    /// assert_eq!(record.moves, 3);
+    /// assert_eq!(record.white, None);
+    /// ```
+    pub fn from_tree(tree: &GameTree) -> Self {
+        let mut record = GameRecord::default();
+        if let Some(root) = tree.nodes.first() {
+            for token in &root.tokens {
+                match token {
+                    SgfToken::PlayerName { color, name } => match color {
+                        Color::Black => record.black = Some(name.to_string()),
+                        Color::White => record.white = Some(name.to_string()),
+                    },
+                    SgfToken::PlayerRank { color, rank } => match color {
+                        Color::Black => record.black_rank = Some(Rank::from(rank.as_str())),
+                        Color::White => record.white_rank = Some(Rank::from(rank.as_str())),
+                    },
+                    SgfToken::Result(outcome) => record.result = Some(*outcome),
+                    SgfToken::Date(date) => record.date = Some(date.to_string()),
+                    SgfToken::Event(event) => record.event = Some(event.to_string()),
+                    SgfToken::Komi(komi) => record.komi = Some(komi.get()),
+                    SgfToken::Handicap(handicap) => record.handicap = Some(*handicap),
+                    _ => {}
+                }
+            }
+        }
+        record.moves = tree
+            .iter()
+            .flat_map(|node| node.tokens.iter())
+            .filter(|token| matches!(token, SgfToken::Move { .. }))
+            .count();
+        record
+    }
+
+    /// Gets the values of the record as text, in the order of `GAME_RECORD_COLUMNS`
+    ///
+    /// Missing values are empty strings.
+    pub fn to_row(&self) -> Vec<String> {
+        fn text<T: ToString>(value: &Option<T>) -> String {
+            value.as_ref().map(T::to_string).unwrap_or_default()
+        }
+        vec![
+            text(&self.black),
+            text(&self.white),
+            text(&self.black_rank),
+            text(&self.white_rank),
+            self.result.map(outcome_text).unwrap_or_default(),
+            text(&self.date),
+            text(&self.event),
+            self.moves.to_string(),
+            self.komi.map(format_real).unwrap_or_default(),
+            text(&self.handicap),
+        ]
+    }
+}
+
+/// The value of an `RE` property for `outcome`
+fn outcome_text(outcome: Outcome) -> String {
+    let token: String = SgfToken::Result(outcome).into();
+    token[3..token.len() - 1].to_string()
+}
+
+impl Collection {
+    /// Collects the game information of every game
+    pub fn records(&self) -> Vec<GameRecord> {
+        self.iter().map(GameRecord::from_tree).collect()
+    }
+
+    /// Exports the game information as CSV, with a header row and one row per game
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let collection = parse_collection("(;PB[Shusaku]PW[Gennan]RE[B+2]EV[Castle, game];B[qd])").unwrap();
+    /// assert_eq!(
+    ///     collection.to_csv(),
+    ///     "black,white,black_rank,white_rank,result,date,event,moves,komi,handicap\n\
+    ///      Shusaku,Gennan,,,B+2,,\"Castle, game\",1,,\n"
+    /// );
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let mut output = vec![];
+        self.write_csv(&mut output)
+            .expect("Writing to a Vec does not fail");
+        String::from_utf8(output).expect("CSV output is valid UTF-8")
+    }
+
+    /// Writes the game information as CSV to `writer`
+    ///
+    /// Fields are quoted when they contain commas, quotes or line breaks.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> Result<(), SgfError> {
+        write_csv_row(&mut writer, GAME_RECORD_COLUMNS.iter().copied())?;
+        for record in self.records() {
+            let row = record.to_row();
+            write_csv_row(&mut writer, row.iter().map(String::as_str))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the game information as a Parquet file to `writer`
+    ///
+    /// Uses the columns of `GAME_RECORD_COLUMNS`. `moves` is a required `INT32` column, `komi`
+    /// an optional `FLOAT`, `handicap` an optional `INT32`, and all other columns are optional
+    /// UTF-8 strings.
+    #[cfg(feature = "parquet")]
+    pub fn write_parquet<W: Write + Send>(&self, writer: W) -> Result<(), SgfError> {
+        parquet_export::write(&self.records(), writer).map_err(SgfError::io_error)
+    }
+}
+
+fn write_csv_row<'a, W: Write>(
+    writer: &mut W,
+    fields: impl Iterator<Item = &'a str>,
+) -> Result<(), SgfError> {
+    let line = fields
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    writeln!(writer, "{}", line).map_err(SgfError::io_error)
+}
+
+#[cfg(feature = "parquet")]
+mod parquet_export {
+    use std::io::Write;
+    use std::sync::Arc;
+
+    use parquet::data_type::{ByteArray, ByteArrayType, FloatType, Int32Type};
+    use parquet::errors::Result;
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+
+    use super::GameRecord;
+
+    const SCHEMA: &str = "
+        message game {
+            OPTIONAL BYTE_ARRAY black (UTF8);
+            OPTIONAL BYTE_ARRAY white (UTF8);
+            OPTIONAL BYTE_ARRAY black_rank (UTF8);
+            OPTIONAL BYTE_ARRAY white_rank (UTF8);
+            OPTIONAL BYTE_ARRAY result (UTF8);
+            OPTIONAL BYTE_ARRAY date (UTF8);
+            OPTIONAL BYTE_ARRAY event (UTF8);
+            REQUIRED INT32 moves;
+            OPTIONAL FLOAT komi;
+            OPTIONAL INT32 handicap;
+        }
+    ";
+
+    /// Splits optional values into the present values and their definition levels
+    fn optional<T>(values: impl Iterator<Item = Option<T>>) -> (Vec<T>, Vec<i16>) {
+        let mut present = vec![];
+        let mut levels = vec![];
+        for value in values {
+            levels.push(i16::from(value.is_some()));
+            present.extend(value);
+        }
+        (present, levels)
+    }
+
+    pub(super) fn write<W: Write + Send>(records: &[GameRecord], writer: W) -> Result<()> {
+        let schema = Arc::new(parse_message_type(SCHEMA)?);
+        let properties = Arc::new(WriterProperties::builder().build());
+        let mut writer = SerializedFileWriter::new(writer, schema, properties)?;
+        let mut row_group = writer.next_row_group()?;
+        let mut column_index = 0;
+        while let Some(mut column) = row_group.next_column()? {
+            match column_index {
+                7 => {
+                    let moves: Vec<i32> = records.iter().map(|r| r.moves as i32).collect();
+                    column
+                        .typed::<Int32Type>()
+                        .write_batch(&moves, None, None)?;
+                }
+                8 => {
+                    let (values, levels) = optional(records.iter().map(|r| r.komi));
+                    column
+                        .typed::<FloatType>()
+                        .write_batch(&values, Some(&levels), None)?;
+                }
+                9 => {
+                    let (values, levels) =
+                        optional(records.iter().map(|r| r.handicap.map(|h| h as i32)));
+                    column
+                        .typed::<Int32Type>()
+                        .write_batch(&values, Some(&levels), None)?;
+                }
+                index => {
+                    let (values, levels) = optional(records.iter().map(|record| {
+                        let text = record.to_row().swap_remove(index);
+                        Some(text).filter(|text| !text.is_empty())
+                    }));
+                    let values: Vec<ByteArray> = values
+                        .into_iter()
+                        .map(|text| ByteArray::from(text.into_bytes()))
+                        .collect();
+                    column
+                        .typed::<ByteArrayType>()
+                        .write_batch(&values, Some(&levels), None)?;
+                }
+            }
+            column.close()?;
+            column_index += 1;
+        }
+        row_group.close()?;
+        writer.close()?;
+        Ok(())
+    }
+}
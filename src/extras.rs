@@ -0,0 +1,102 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// Application data attached to a `GameNode`, like engine evaluations or render cache keys
+///
+/// Values can be of any type, and are looked up by key and type. Extras are never written to
+/// SGF, use `GameNode::write_extra` to store a value as a property. Comparing or hashing nodes
+/// ignores their extras, so trees holding the same SGF data are equal. Cloning a node shares
+/// the values instead of copying them.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let mut tree: GameTree = parse("(;SZ[9];B[ee])").unwrap();
+/// tree.nodes[1].extras_mut().insert("winrate", 0.48f64);
+///
+/// assert_eq!(tree.nodes[1].extras().get::<f64>("winrate"), Some(&0.48));
+/// assert_eq!(tree.nodes[1].extras().get::<f32>("winrate"), None);
+/// assert_eq!(tree.to_string(), "(;SZ[9];B[ee])");
+/// ```
+#[derive(Clone, Default)]
+pub struct NodeExtras {
+    values: HashMap<String, Arc<dyn Any + Send + Sync>>,
+}
+
+impl NodeExtras {
+    /// Stores `value` under `key`, replacing any earlier value
+    pub fn insert<T: Any + Send + Sync>(&mut self, key: impl Into<String>, value: T) {
+        self.values.insert(key.into(), Arc::new(value));
+    }
+
+    /// Gets the value stored under `key`, if it is a `T`
+    pub fn get<T: Any>(&self, key: &str) -> Option<&T> {
+        self.values
+            .get(key)
+            .and_then(|value| value.as_ref().downcast_ref())
+    }
+
+    /// Removes the value stored under `key`, and returns if there was one
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.values.remove(key).is_some()
+    }
+
+    /// Checks if a value is stored under `key`
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    /// Gets the keys of all stored values, in no particular order
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear()
+    }
+}
+
+impl fmt::Debug for NodeExtras {
+    /// Lists the keys, since the values are not required to implement `Debug`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut keys: Vec<&str> = self.keys().collect();
+        keys.sort_unstable();
+        f.debug_set().entries(keys).finish()
+    }
+}
+
+/// All extras are equal, so that comparing nodes only compares their tokens
+///
+/// Values are not required to implement `PartialEq`, so use `NodeExtras::get` to compare the
+/// values themselves.
+impl PartialEq for NodeExtras {
+    fn eq(&self, _: &NodeExtras) -> bool {
+        true
+    }
+}
+
+impl Eq for NodeExtras {}
+
+/// Hashes nothing, matching `PartialEq`, so that nodes with the same tokens hash the same
+impl Hash for NodeExtras {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+/// Always creates empty extras, since the values can be of any type
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for NodeExtras {
+    fn arbitrary(_: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(NodeExtras::default())
+    }
+}
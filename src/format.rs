@@ -0,0 +1,138 @@
+use crate::{parse_syntax, SgfError, SgfToken, SyntaxNode, SyntaxProperty, SyntaxTree, TokenOrder};
+
+/// Layout used by `format_sgf`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormatStyle {
+    /// Spaces added before the `(` of each level of variations
+    pub indent: usize,
+    /// Width lines are kept within by wrapping between nodes, and between the properties of
+    /// long nodes, or 0 to never wrap
+    ///
+    /// Lines can still be longer when a single property value is wider than this.
+    pub max_width: usize,
+    /// Order of the properties of each node, or `None` to keep the order of the input
+    pub token_order: Option<TokenOrder>,
+}
+
+impl Default for FormatStyle {
+    fn default() -> Self {
+        FormatStyle {
+            indent: 2,
+            max_width: 80,
+            token_order: None,
+        }
+    }
+}
+
+/// Reformats SGF text according to `style`, without changing what it means
+///
+/// The root node of each game gets a line of its own, and the rest of the nodes follow each
+/// other on lines indented to the `(` of their variation. Identifiers and values are kept as
+/// written, including escapes and the line breaks inside values, so the result parses into
+/// the same game trees as the input. Every game of a collection ends with a line break.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let input = "( ;SZ[9]FF[4] ; B[ee] ;W[cc] (;B[gg] ) (;B[cg]\n;W[gc] ))";
+/// let style = FormatStyle {
+///     token_order: Some(TokenOrder::Identifier),
+///     ..FormatStyle::default()
+/// };
+/// let formatted = format_sgf(input, &style).unwrap();
+/// assert_eq!(
+///     formatted,
+///     "(;FF[4]SZ[9]\n;B[ee];W[cc]\n  (;B[gg])\n  (;B[cg];W[gc]))\n"
+/// );
+/// assert_eq!(
+///     parse(&formatted).unwrap().to_string(),
+///     parse(input).unwrap().to_string()
+/// );
+/// ```
+pub fn format_sgf(input: &str, style: &FormatStyle) -> Result<String, SgfError> {
+    let mut formatter = Formatter {
+        input,
+        style,
+        out: String::with_capacity(input.len()),
+    };
+    for tree in parse_syntax(input)? {
+        formatter.write_tree(&tree, 0);
+        formatter.out.push('\n');
+    }
+    Ok(formatter.out)
+}
+
+struct Formatter<'a> {
+    input: &'a str,
+    style: &'a FormatStyle,
+    out: String,
+}
+
+impl<'a> Formatter<'a> {
+    fn write_tree(&mut self, tree: &SyntaxTree, depth: usize) {
+        let column = depth * self.style.indent;
+        if depth > 0 {
+            self.out.push('\n');
+            self.out.push_str(&" ".repeat(column));
+        }
+        self.out.push('(');
+        for (index, node) in tree.nodes.iter().enumerate() {
+            if depth == 0 && index == 1 {
+                self.out.push('\n');
+            }
+            self.write_node(node, column);
+        }
+        if depth == 0 && tree.nodes.len() == 1 && !tree.variations.is_empty() {
+            self.out.push('\n');
+        }
+        for variation in &tree.variations {
+            self.write_tree(variation, depth + 1);
+        }
+        self.out.push(')');
+    }
+
+    fn write_node(&mut self, node: &SyntaxNode, column: usize) {
+        let mut properties: Vec<(&SyntaxProperty, String)> = node
+            .properties
+            .iter()
+            .map(|property| (property, self.property_text(property)))
+            .collect();
+        if let Some(order) = self.style.token_order {
+            properties.sort_by_cached_key(|(property, _)| {
+                let token = SgfToken::from_pair(
+                    &self.input[property.identifier.clone()],
+                    &self.input[property.values[0].clone()],
+                );
+                (order.rank(&token), token.identifier().to_string())
+            });
+        }
+        let width = 1 + properties.iter().map(|(_, text)| text.len()).sum::<usize>();
+        self.wrap(width, column);
+        self.out.push(';');
+        for (_, text) in properties {
+            self.wrap(text.len(), column);
+            self.out.push_str(&text);
+        }
+    }
+
+    /// Gets the text of a property, without whitespace between its values
+    fn property_text(&self, property: &SyntaxProperty) -> String {
+        let mut text = self.input[property.identifier.clone()].to_string();
+        for value in &property.values {
+            text.push('[');
+            text.push_str(&self.input[value.clone()]);
+            text.push(']');
+        }
+        text
+    }
+
+    /// Starts a new line at `column` if `width` more characters don't fit on the current one
+    fn wrap(&mut self, width: usize, column: usize) {
+        let line = self.out.rsplit('\n').next().unwrap_or_default();
+        let used = line.chars().count();
+        if self.style.max_width > 0 && used > column + 1 && used + width > self.style.max_width {
+            self.out.push('\n');
+            self.out.push_str(&" ".repeat(column));
+        }
+    }
+}
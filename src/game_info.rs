@@ -0,0 +1,145 @@
+use crate::{Color, Game, GameTree, RuleSet, SgfToken};
+
+/// Problems found when cross-checking the game info of a Go game
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameInfoIssue {
+    /// `HA` is below 2, which the specification does not allow
+    InvalidHandicap(u32),
+    /// `HA` is set, but the root node has no black setup stones
+    HandicapWithoutStones { handicap: u32 },
+    /// The number of black setup stones in the root node differs from `HA`
+    HandicapStoneMismatch { handicap: u32, stones: usize },
+    /// The komi is not one commonly used with the rule set and handicap
+    UnusualKomi {
+        komi: f32,
+        rules: Option<RuleSet>,
+        handicap: u32,
+    },
+}
+
+impl GameTree {
+    /// Gets the game played, from the `GM` token in the root node, defaulting to Go
+    pub fn game(&self) -> Game {
+        self.nodes
+            .first()
+            .and_then(|node| {
+                node.tokens.iter().find_map(|token| match token {
+                    SgfToken::Game(game) => Some(*game),
+                    _ => None,
+                })
+            })
+            .unwrap_or(Game::Go)
+    }
+
+    /// Gets the board size, from the `SZ` token in the root node, or the default size of the
+    /// game
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;GM[2])").unwrap();
+    /// assert_eq!(tree.board_size(), Some((8, 8)));
+    ///
+    /// let tree: GameTree = parse("(;SZ[9:13])").unwrap();
+    /// assert_eq!(tree.board_size(), Some((9, 13)));
+    /// ```
+    pub fn board_size(&self) -> Option<(u32, u32)> {
+        let size = self.nodes.first().and_then(|node| {
+            node.tokens.iter().find_map(|token| match token {
+                SgfToken::Size(width, height) => Some((*width, *height)),
+                _ => None,
+            })
+        });
+        size.or_else(|| self.game().default_size())
+    }
+
+    /// Cross-checks the handicap, setup stones and komi in the root node
+    ///
+    /// Handicap games are expected to have the handicap stones placed with `AB` in the root
+    /// node, and a komi of at most half a point. Even games are expected to use a komi common
+    /// for the rule set, or no komi at all. Games other than Go are not checked.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;HA[2]KM[0.5];B[pd])").unwrap();
+    /// assert_eq!(
+    ///     tree.check_game_info(),
+    ///     vec![GameInfoIssue::HandicapWithoutStones { handicap: 2 }]
+    /// );
+    ///
+    /// let tree: GameTree = parse("(;RU[Japanese]KM[6.5];B[pd])").unwrap();
+    /// assert!(tree.check_game_info().is_empty());
+    /// ```
+    pub fn check_game_info(&self) -> Vec<GameInfoIssue> {
+        let root = match self.nodes.first() {
+            Some(root) => &root.tokens,
+            None => return vec![],
+        };
+        if self.game() != Game::Go {
+            return vec![];
+        }
+
+        let mut issues = vec![];
+        let handicap = root.iter().find_map(|token| match token {
+            SgfToken::Handicap(handicap) => Some(*handicap),
+            _ => None,
+        });
+        let stones = root
+            .iter()
+            .filter(|token| {
+                matches!(
+                    token,
+                    SgfToken::Add {
+                        color: Color::Black,
+                        ..
+                    }
+                )
+            })
+            .count();
+        if let Some(handicap) = handicap {
+            if handicap < 2 {
+                issues.push(GameInfoIssue::InvalidHandicap(handicap));
+            } else if stones == 0 {
+                issues.push(GameInfoIssue::HandicapWithoutStones { handicap });
+            } else if stones != handicap as usize {
+                issues.push(GameInfoIssue::HandicapStoneMismatch { handicap, stones });
+            }
+        }
+
+        let komi = root.iter().find_map(|token| match token {
+            SgfToken::Komi(komi) => Some(komi.get()),
+            _ => None,
+        });
+        let rules = root.iter().find_map(|token| match token {
+            SgfToken::Rule(rules) => Some(rules.clone()),
+            _ => None,
+        });
+        let handicap = handicap.unwrap_or(0);
+        if let Some(komi) = komi {
+            if !is_usual_komi(komi, rules.as_ref(), handicap) {
+                issues.push(GameInfoIssue::UnusualKomi {
+                    komi,
+                    rules,
+                    handicap,
+                });
+            }
+        }
+        issues
+    }
+}
+
+fn is_usual_komi(komi: f32, rules: Option<&RuleSet>, handicap: u32) -> bool {
+    if handicap >= 2 {
+        return komi.abs() <= 0.5;
+    }
+    let usual: &[f32] = match rules {
+        Some(RuleSet::Japanese) => &[0.0, 4.5, 5.5, 6.5],
+        Some(RuleSet::Chinese) => &[0.0, 5.5, 7.5],
+        Some(RuleSet::AGA) => &[0.0, 5.5, 7.0, 7.5],
+        Some(RuleSet::NZ) => &[0.0, 7.0],
+        Some(RuleSet::GOE) => &[0.0, 8.0],
+        Some(RuleSet::Unknown(_)) | None => &[0.0, 0.5, 4.5, 5.5, 6.5, 7.0, 7.5, 8.0],
+    };
+    usual.contains(&komi)
+}
@@ -0,0 +1,239 @@
+use std::convert::TryFrom;
+
+use goban::pieces::stones::{Color as GobanColor, Stone};
+use goban::rules::game::Game;
+use goban::rules::{EndGame, Move, JAPANESE};
+
+use crate::board::is_valid_size;
+use crate::time::move_color;
+use crate::turn::{next_color, player_to_play};
+use crate::{
+    Action, Color, GameNode, GameTree, Outcome, Real, RuleSet, SgfError, SgfErrorKind, SgfToken,
+    TokenString,
+};
+
+/// Largest board `goban` can play on
+const GOBAN_MAX_SIZE: u32 = 19;
+
+/// Replays the main variation of a Go game into a `goban::rules::game::Game`
+///
+/// The game gets the size, komi and result from the root node, the `AB` and `AW` stones of the
+/// root node as setup, and the `PL` token of the root node or the color of the first move as
+/// the first player. Japanese rules are used when the `RU` token says so, and Chinese rules
+/// otherwise. Without a `KM` token the komi is zero.
+///
+/// `goban` can't place stones after the start of a game, play two moves of one color in a row,
+/// or use boards larger than 19 by 19, so games needing any of those fail with
+/// `SgfErrorKind::InvalidTokenValue`. Moves breaking the rules fail with
+/// `SgfErrorKind::IllegalMove`.
+///
+/// ```rust
+/// use std::convert::TryFrom;
+/// use sgf_parser::*;
+///
+/// let tree: GameTree = parse("(;SZ[9]KM[5.5]AB[cc][gg];W[ee];B[ec])").unwrap();
+/// let game = goban::rules::game::Game::try_from(&tree).unwrap();
+/// assert_eq!(game.size(), (9, 9));
+/// assert_eq!(game.komi(), 5.5);
+/// assert_eq!(game.turn(), goban::pieces::stones::Color::White);
+/// assert_eq!(game.goban().number_of_stones(), (3, 1));
+/// ```
+impl TryFrom<&GameTree> for Game {
+    type Error = SgfError;
+
+    fn try_from(tree: &GameTree) -> Result<Game, SgfError> {
+        if tree.game() != crate::Game::Go {
+            return Err(SgfErrorKind::InvalidTokenValue.into());
+        }
+        let (width, height) = match tree.board_size() {
+            Some((width, height))
+                if is_valid_size(width, height)
+                    && width <= GOBAN_MAX_SIZE
+                    && height <= GOBAN_MAX_SIZE =>
+            {
+                (width as u8, height as u8)
+            }
+            _ => return Err(SgfErrorKind::InvalidTokenValue.into()),
+        };
+        let to_point = |(x, y): (u8, u8)| {
+            if (1..=width).contains(&x) && (1..=height).contains(&y) {
+                Ok((y - 1, x - 1))
+            } else {
+                Err(SgfError::from(SgfErrorKind::InvalidTokenValue))
+            }
+        };
+
+        let mut builder = Game::builder();
+        builder.size((height, width)).komi(0.0);
+        let root = tree.nodes.first();
+        for token in root.iter().flat_map(|root| root.tokens.iter()) {
+            match token {
+                SgfToken::Komi(komi) => {
+                    builder.komi(komi.get());
+                }
+                SgfToken::Rule(RuleSet::Japanese) => {
+                    builder.rule(JAPANESE);
+                }
+                SgfToken::Result(outcome) => {
+                    builder.outcome(end_game(*outcome));
+                }
+                SgfToken::Handicap(stones) => {
+                    builder.handicap(*stones);
+                }
+                SgfToken::Add { color, coordinate } => {
+                    builder.add(Stone {
+                        coord: to_point(*coordinate)?,
+                        color: goban_color(*color),
+                    });
+                }
+                _ => {}
+            }
+        }
+        let first = root
+            .and_then(player_to_play)
+            .or_else(|| tree.iter().find_map(move_color))
+            .unwrap_or_else(|| next_color(root));
+        builder.turn(goban_color(first));
+        let mut game = builder
+            .build()
+            .map_err(|_| SgfErrorKind::InvalidTokenValue)?;
+
+        for (index, node) in tree.iter().enumerate() {
+            for token in &node.tokens {
+                match token {
+                    SgfToken::Add { .. } if index > 0 => {
+                        return Err(SgfErrorKind::InvalidTokenValue.into());
+                    }
+                    SgfToken::Move { color, action } => {
+                        if goban_color(*color) != game.turn() {
+                            return Err(SgfErrorKind::InvalidTokenValue.into());
+                        }
+                        let play = match action {
+                            Action::Move(x, y) => {
+                                let (row, column) = to_point((*x, *y))?;
+                                Move::Play(row, column)
+                            }
+                            Action::Pass => Move::Pass,
+                        };
+                        game.try_play(play)
+                            .map_err(|_| SgfError::from(SgfErrorKind::IllegalMove))?;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(game)
+    }
+}
+
+/// Replays the main variation of a Go game into a `goban::rules::game::Game`, see
+/// `TryFrom<&GameTree>`
+impl TryFrom<GameTree> for Game {
+    type Error = SgfError;
+
+    fn try_from(tree: GameTree) -> Result<Game, SgfError> {
+        Game::try_from(&tree)
+    }
+}
+
+/// Creates a single node game with the current position of a `goban::rules::game::Game`
+///
+/// `goban` keeps no record of the moves, so the position is set up with `AB` and `AW` tokens,
+/// like `GameTree::final_position_sgf` does, with a `PL` token for the color to play. The node
+/// also gets the board size, the komi when it is not zero, and the result of finished games.
+/// Captured stones are lost.
+///
+/// ```rust
+/// use std::convert::TryFrom;
+/// use sgf_parser::*;
+///
+/// let tree: GameTree = parse("(;SZ[9]KM[5.5];B[ee];W[ce];B[dc])").unwrap();
+/// let game = goban::rules::game::Game::try_from(&tree).unwrap();
+/// assert_eq!(
+///     GameTree::from(&game).to_string(),
+///     "(;AB[dc][ee]AW[ce]KM[5.5]PL[W]SZ[9])"
+/// );
+/// ```
+impl From<&Game> for GameTree {
+    fn from(game: &Game) -> GameTree {
+        let (height, width) = game.size();
+        let mut tokens = vec![SgfToken::Size(u32::from(width), u32::from(height))];
+        if let Some(komi) = Real::new(game.komi()).filter(|komi| *komi != Real::ZERO) {
+            tokens.push(SgfToken::Komi(komi));
+        }
+        for color in [Color::Black, Color::White] {
+            for row in 0..height {
+                for column in 0..width {
+                    if game.goban().get_color((row, column)) == Some(goban_color(color)) {
+                        tokens.push(SgfToken::Add {
+                            color,
+                            coordinate: (column + 1, row + 1),
+                        });
+                    }
+                }
+            }
+        }
+        let player = match game.turn() {
+            GobanColor::Black => "B",
+            GobanColor::White => "W",
+        };
+        tokens.push(SgfToken::Unknown((
+            TokenString::from_static("PL"),
+            TokenString::from_static(player),
+        )));
+        if let Some(outcome) = game.outcome().and_then(outcome) {
+            tokens.push(SgfToken::Result(outcome));
+        }
+        GameTree {
+            nodes: vec![GameNode::from(tokens)],
+            variations: vec![],
+        }
+    }
+}
+
+/// Creates a single node game with the current position of a `goban::rules::game::Game`, see
+/// `From<&Game>`
+impl From<Game> for GameTree {
+    fn from(game: Game) -> GameTree {
+        GameTree::from(&game)
+    }
+}
+
+fn goban_color(color: Color) -> GobanColor {
+    match color {
+        Color::Black => GobanColor::Black,
+        Color::White => GobanColor::White,
+    }
+}
+
+fn color(color: GobanColor) -> Color {
+    match color {
+        GobanColor::Black => Color::Black,
+        GobanColor::White => Color::White,
+    }
+}
+
+fn end_game(outcome: Outcome) -> EndGame {
+    match outcome {
+        Outcome::WinnerByResign(winner) => EndGame::WinnerByResign(goban_color(winner)),
+        Outcome::WinnerByForfeit(winner) => EndGame::WinnerByForfeit(goban_color(winner)),
+        Outcome::WinnerByPoints(winner, points) => {
+            EndGame::WinnerByScore(goban_color(winner), points.get())
+        }
+        Outcome::WinnerByTime(winner) => EndGame::WinnerByTime(goban_color(winner)),
+        Outcome::Draw => EndGame::Draw,
+    }
+}
+
+/// Converts the result of a `goban` game, `None` for scores that can't be written
+fn outcome(end_game: EndGame) -> Option<Outcome> {
+    Some(match end_game {
+        EndGame::WinnerByResign(winner) => Outcome::WinnerByResign(color(winner)),
+        EndGame::WinnerByForfeit(winner) => Outcome::WinnerByForfeit(color(winner)),
+        EndGame::WinnerByScore(winner, points) => {
+            Outcome::WinnerByPoints(color(winner), Real::new(points)?)
+        }
+        EndGame::WinnerByTime(winner) => Outcome::WinnerByTime(color(winner)),
+        EndGame::Draw => Outcome::Draw,
+    })
+}
@@ -0,0 +1,380 @@
+use crate::{
+    Action, Color, DisplayNodes, Emphasis, Encoding, Game, Outcome, Real, RuleSet, SgfToken,
+    TokenCategory, TokenString,
+};
+
+/// `SgfToken` grouped by property category
+///
+/// Allows exhaustive matching on a single category, without listing every other token. Convert
+/// with `From` in both directions, or `SgfToken::grouped`.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let token = SgfToken::from_pair("KM", "6.5");
+/// match token.grouped() {
+///     GroupedToken::GameInfo(GameInfoProp::Komi(komi)) => assert_eq!(komi, 6.5),
+///     _ => unreachable!(),
+/// }
+///
+/// let token: SgfToken = MarkupProp::Triangle { coordinate: (1, 1) }.into();
+/// assert_eq!(token, SgfToken::from_pair("TR", "aa"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum GroupedToken {
+    Move(MoveProp),
+    Setup(SetupProp),
+    GameInfo(GameInfoProp),
+    Markup(MarkupProp),
+    Timing(TimingProp),
+    Annotation(AnnotationProp),
+    Root(RootProp),
+    Unknown((TokenString, TokenString)),
+    Invalid((TokenString, TokenString)),
+}
+
+impl GroupedToken {
+    /// Gets the property class of the token, which is the group it is in
+    pub fn category(&self) -> TokenCategory {
+        match self {
+            GroupedToken::Move(_) => TokenCategory::Move,
+            GroupedToken::Setup(_) => TokenCategory::Setup,
+            GroupedToken::GameInfo(_) => TokenCategory::GameInfo,
+            GroupedToken::Markup(_) => TokenCategory::Markup,
+            GroupedToken::Timing(_) => TokenCategory::Timing,
+            GroupedToken::Annotation(_) => TokenCategory::Annotation,
+            GroupedToken::Root(_) => TokenCategory::Root,
+            GroupedToken::Unknown(_) => TokenCategory::Unknown,
+            GroupedToken::Invalid(_) => TokenCategory::Invalid,
+        }
+    }
+}
+
+/// Generates the grouped form of an arbitrary `SgfToken`
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for GroupedToken {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(SgfToken::arbitrary(u)?.grouped())
+    }
+}
+
+/// Move properties, `B` and `W`
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveProp {
+    Move { color: Color, action: Action },
+}
+
+/// Setup properties, `AB` and `AW`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SetupProp {
+    Add { color: Color, coordinate: (u8, u8) },
+}
+
+/// Game info properties, usually found in the root node
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameInfoProp {
+    PlayerName { color: Color, name: TokenString },
+    PlayerRank { color: Color, rank: String },
+    Rule(RuleSet),
+    Result(Outcome),
+    Komi(Real),
+    Event(TokenString),
+    Copyright(TokenString),
+    GameName(TokenString),
+    Place(TokenString),
+    Date(TokenString),
+    Overtime(TokenString),
+    TimeLimit(u32),
+    Handicap(u32),
+}
+
+/// Markup properties, drawn on the board
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarkupProp {
+    Square {
+        coordinate: (u8, u8),
+    },
+    Circle {
+        coordinate: (u8, u8),
+    },
+    Triangle {
+        coordinate: (u8, u8),
+    },
+    Label {
+        label: TokenString,
+        coordinate: (u8, u8),
+    },
+    EmptyList(TokenString),
+}
+
+/// Timing properties, `BL`, `WL`, `OB` and `OW`
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimingProp {
+    Time { color: Color, time: u32 },
+    MovesRemaining { color: Color, moves: u32 },
+}
+
+/// Node and move annotation properties
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnnotationProp {
+    Comment(TokenString),
+    NodeName(TokenString),
+    Even(Emphasis),
+    GoodFor { color: Color, emphasis: Emphasis },
+    Hotspot(Emphasis),
+    Unclear(Emphasis),
+    BadMove(Emphasis),
+    Tesuji(Emphasis),
+}
+
+/// Root properties, only allowed in the root node
+#[derive(Debug, Clone, PartialEq)]
+pub enum RootProp {
+    Game(Game),
+    VariationDisplay {
+        nodes: DisplayNodes,
+        on_board_display: bool,
+    },
+    Size(u32, u32),
+    FileFormat(u8),
+    Charset(Encoding),
+    Application {
+        name: TokenString,
+        version: TokenString,
+    },
+}
+
+impl From<SgfToken> for GroupedToken {
+    fn from(token: SgfToken) -> Self {
+        match token {
+            SgfToken::Move { color, action } => {
+                GroupedToken::Move(MoveProp::Move { color, action })
+            }
+            SgfToken::Add { color, coordinate } => {
+                GroupedToken::Setup(SetupProp::Add { color, coordinate })
+            }
+            SgfToken::PlayerName { color, name } => {
+                GroupedToken::GameInfo(GameInfoProp::PlayerName { color, name })
+            }
+            SgfToken::PlayerRank { color, rank } => {
+                GroupedToken::GameInfo(GameInfoProp::PlayerRank { color, rank })
+            }
+            SgfToken::Rule(value) => GroupedToken::GameInfo(GameInfoProp::Rule(value)),
+            SgfToken::Result(value) => GroupedToken::GameInfo(GameInfoProp::Result(value)),
+            SgfToken::Komi(value) => GroupedToken::GameInfo(GameInfoProp::Komi(value)),
+            SgfToken::Event(value) => GroupedToken::GameInfo(GameInfoProp::Event(value)),
+            SgfToken::Copyright(value) => GroupedToken::GameInfo(GameInfoProp::Copyright(value)),
+            SgfToken::GameName(value) => GroupedToken::GameInfo(GameInfoProp::GameName(value)),
+            SgfToken::Place(value) => GroupedToken::GameInfo(GameInfoProp::Place(value)),
+            SgfToken::Date(value) => GroupedToken::GameInfo(GameInfoProp::Date(value)),
+            SgfToken::Overtime(value) => GroupedToken::GameInfo(GameInfoProp::Overtime(value)),
+            SgfToken::TimeLimit(value) => GroupedToken::GameInfo(GameInfoProp::TimeLimit(value)),
+            SgfToken::Handicap(value) => GroupedToken::GameInfo(GameInfoProp::Handicap(value)),
+            SgfToken::Square { coordinate } => {
+                GroupedToken::Markup(MarkupProp::Square { coordinate })
+            }
+            SgfToken::Circle { coordinate } => {
+                GroupedToken::Markup(MarkupProp::Circle { coordinate })
+            }
+            SgfToken::Triangle { coordinate } => {
+                GroupedToken::Markup(MarkupProp::Triangle { coordinate })
+            }
+            SgfToken::Label { label, coordinate } => {
+                GroupedToken::Markup(MarkupProp::Label { label, coordinate })
+            }
+            SgfToken::Time { color, time } => {
+                GroupedToken::Timing(TimingProp::Time { color, time })
+            }
+            SgfToken::MovesRemaining { color, moves } => {
+                GroupedToken::Timing(TimingProp::MovesRemaining { color, moves })
+            }
+            SgfToken::Comment(value) => GroupedToken::Annotation(AnnotationProp::Comment(value)),
+            SgfToken::NodeName(value) => GroupedToken::Annotation(AnnotationProp::NodeName(value)),
+            SgfToken::Even(value) => GroupedToken::Annotation(AnnotationProp::Even(value)),
+            SgfToken::GoodFor { color, emphasis } => {
+                GroupedToken::Annotation(AnnotationProp::GoodFor { color, emphasis })
+            }
+            SgfToken::Hotspot(value) => GroupedToken::Annotation(AnnotationProp::Hotspot(value)),
+            SgfToken::Unclear(value) => GroupedToken::Annotation(AnnotationProp::Unclear(value)),
+            SgfToken::BadMove(value) => GroupedToken::Annotation(AnnotationProp::BadMove(value)),
+            SgfToken::Tesuji(value) => GroupedToken::Annotation(AnnotationProp::Tesuji(value)),
+            SgfToken::Game(value) => GroupedToken::Root(RootProp::Game(value)),
+            SgfToken::VariationDisplay {
+                nodes,
+                on_board_display,
+            } => GroupedToken::Root(RootProp::VariationDisplay {
+                nodes,
+                on_board_display,
+            }),
+            SgfToken::Size(width, height) => GroupedToken::Root(RootProp::Size(width, height)),
+            SgfToken::FileFormat(value) => GroupedToken::Root(RootProp::FileFormat(value)),
+            SgfToken::Charset(value) => GroupedToken::Root(RootProp::Charset(value)),
+            SgfToken::Application { name, version } => {
+                GroupedToken::Root(RootProp::Application { name, version })
+            }
+            SgfToken::EmptyList(identifier) => {
+                GroupedToken::Markup(MarkupProp::EmptyList(identifier))
+            }
+            SgfToken::Unknown(pair) => GroupedToken::Unknown(pair),
+            SgfToken::Invalid(pair) => GroupedToken::Invalid(pair),
+        }
+    }
+}
+
+impl From<GroupedToken> for SgfToken {
+    fn from(token: GroupedToken) -> Self {
+        match token {
+            GroupedToken::Move(prop) => prop.into(),
+            GroupedToken::Setup(prop) => prop.into(),
+            GroupedToken::GameInfo(prop) => prop.into(),
+            GroupedToken::Markup(prop) => prop.into(),
+            GroupedToken::Timing(prop) => prop.into(),
+            GroupedToken::Annotation(prop) => prop.into(),
+            GroupedToken::Root(prop) => prop.into(),
+            GroupedToken::Unknown(pair) => SgfToken::Unknown(pair),
+            GroupedToken::Invalid(pair) => SgfToken::Invalid(pair),
+        }
+    }
+}
+
+impl From<MoveProp> for SgfToken {
+    fn from(prop: MoveProp) -> Self {
+        match prop {
+            MoveProp::Move { color, action } => SgfToken::Move { color, action },
+        }
+    }
+}
+
+impl From<MoveProp> for GroupedToken {
+    fn from(prop: MoveProp) -> Self {
+        GroupedToken::Move(prop)
+    }
+}
+
+impl From<SetupProp> for SgfToken {
+    fn from(prop: SetupProp) -> Self {
+        match prop {
+            SetupProp::Add { color, coordinate } => SgfToken::Add { color, coordinate },
+        }
+    }
+}
+
+impl From<SetupProp> for GroupedToken {
+    fn from(prop: SetupProp) -> Self {
+        GroupedToken::Setup(prop)
+    }
+}
+
+impl From<GameInfoProp> for SgfToken {
+    fn from(prop: GameInfoProp) -> Self {
+        match prop {
+            GameInfoProp::PlayerName { color, name } => SgfToken::PlayerName { color, name },
+            GameInfoProp::PlayerRank { color, rank } => SgfToken::PlayerRank { color, rank },
+            GameInfoProp::Rule(value) => SgfToken::Rule(value),
+            GameInfoProp::Result(value) => SgfToken::Result(value),
+            GameInfoProp::Komi(value) => SgfToken::Komi(value),
+            GameInfoProp::Event(value) => SgfToken::Event(value),
+            GameInfoProp::Copyright(value) => SgfToken::Copyright(value),
+            GameInfoProp::GameName(value) => SgfToken::GameName(value),
+            GameInfoProp::Place(value) => SgfToken::Place(value),
+            GameInfoProp::Date(value) => SgfToken::Date(value),
+            GameInfoProp::Overtime(value) => SgfToken::Overtime(value),
+            GameInfoProp::TimeLimit(value) => SgfToken::TimeLimit(value),
+            GameInfoProp::Handicap(value) => SgfToken::Handicap(value),
+        }
+    }
+}
+
+impl From<GameInfoProp> for GroupedToken {
+    fn from(prop: GameInfoProp) -> Self {
+        GroupedToken::GameInfo(prop)
+    }
+}
+
+impl From<MarkupProp> for SgfToken {
+    fn from(prop: MarkupProp) -> Self {
+        match prop {
+            MarkupProp::Square { coordinate } => SgfToken::Square { coordinate },
+            MarkupProp::Circle { coordinate } => SgfToken::Circle { coordinate },
+            MarkupProp::Triangle { coordinate } => SgfToken::Triangle { coordinate },
+            MarkupProp::Label { label, coordinate } => SgfToken::Label { label, coordinate },
+            MarkupProp::EmptyList(identifier) => SgfToken::EmptyList(identifier),
+        }
+    }
+}
+
+impl From<MarkupProp> for GroupedToken {
+    fn from(prop: MarkupProp) -> Self {
+        GroupedToken::Markup(prop)
+    }
+}
+
+impl From<TimingProp> for SgfToken {
+    fn from(prop: TimingProp) -> Self {
+        match prop {
+            TimingProp::Time { color, time } => SgfToken::Time { color, time },
+            TimingProp::MovesRemaining { color, moves } => {
+                SgfToken::MovesRemaining { color, moves }
+            }
+        }
+    }
+}
+
+impl From<TimingProp> for GroupedToken {
+    fn from(prop: TimingProp) -> Self {
+        GroupedToken::Timing(prop)
+    }
+}
+
+impl From<AnnotationProp> for SgfToken {
+    fn from(prop: AnnotationProp) -> Self {
+        match prop {
+            AnnotationProp::Comment(value) => SgfToken::Comment(value),
+            AnnotationProp::NodeName(value) => SgfToken::NodeName(value),
+            AnnotationProp::Even(value) => SgfToken::Even(value),
+            AnnotationProp::GoodFor { color, emphasis } => SgfToken::GoodFor { color, emphasis },
+            AnnotationProp::Hotspot(value) => SgfToken::Hotspot(value),
+            AnnotationProp::Unclear(value) => SgfToken::Unclear(value),
+            AnnotationProp::BadMove(value) => SgfToken::BadMove(value),
+            AnnotationProp::Tesuji(value) => SgfToken::Tesuji(value),
+        }
+    }
+}
+
+impl From<AnnotationProp> for GroupedToken {
+    fn from(prop: AnnotationProp) -> Self {
+        GroupedToken::Annotation(prop)
+    }
+}
+
+impl From<RootProp> for SgfToken {
+    fn from(prop: RootProp) -> Self {
+        match prop {
+            RootProp::Game(value) => SgfToken::Game(value),
+            RootProp::VariationDisplay {
+                nodes,
+                on_board_display,
+            } => SgfToken::VariationDisplay {
+                nodes,
+                on_board_display,
+            },
+            RootProp::Size(width, height) => SgfToken::Size(width, height),
+            RootProp::FileFormat(value) => SgfToken::FileFormat(value),
+            RootProp::Charset(value) => SgfToken::Charset(value),
+            RootProp::Application { name, version } => SgfToken::Application { name, version },
+        }
+    }
+}
+
+impl From<RootProp> for GroupedToken {
+    fn from(prop: RootProp) -> Self {
+        GroupedToken::Root(prop)
+    }
+}
+
+impl SgfToken {
+    /// Converts the token to its grouped form
+    pub fn grouped(self) -> GroupedToken {
+        self.into()
+    }
+}
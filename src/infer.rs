@@ -0,0 +1,194 @@
+use crate::coords::str_to_coordinates;
+use crate::{Action, Board, Color, GameNode, GameTree, Outcome, Real, SgfToken};
+
+/// Number of nodes at the end of the game searched for a result comment
+const COMMENT_NODES: usize = 3;
+
+/// How much an inferred result can be trusted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Confidence {
+    /// Estimated from the final position, counting all stones as alive
+    Low,
+    /// Counted from the territory marks of the final position
+    Medium,
+    /// Stated in a comment
+    High,
+}
+
+/// A result proposed by `GameTree::infer_result`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InferredResult {
+    pub outcome: Outcome,
+    pub confidence: Confidence,
+}
+
+impl GameTree {
+    /// Proposes a result for games without `RE`, from the end of the main variation
+    ///
+    /// Checks comments in the last nodes for results like `W+R` or `White wins by 2.5`, then
+    /// counts the `TB` and `TW` territory marks of the last node, and finally estimates the
+    /// position if the game ended with two passes. Komi is taken into account when counting.
+    ///
+    /// Returns `None` when none of these are available.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;KM[6.5];B[aa];W[bb]C[White wins by 2.5 points])").unwrap();
+    /// let result = tree.infer_result().unwrap();
+    /// assert_eq!(
+    ///     result.outcome,
+    ///     Outcome::WinnerByPoints(Color::White, Real::new(2.5).unwrap())
+    /// );
+    /// assert_eq!(result.confidence, Confidence::High);
+    /// ```
+    pub fn infer_result(&self) -> Option<InferredResult> {
+        let nodes: Vec<&GameNode> = self.iter().collect();
+        let from_comment = nodes.iter().rev().take(COMMENT_NODES).find_map(|node| {
+            node.tokens.iter().find_map(|token| match token {
+                SgfToken::Comment(comment) => parse_comment_result(comment),
+                _ => None,
+            })
+        });
+        if let Some(outcome) = from_comment {
+            return Some(InferredResult {
+                outcome,
+                confidence: Confidence::High,
+            });
+        }
+
+        let board = Board::replay(self).ok()?;
+        let komi = self
+            .nodes
+            .first()
+            .and_then(|node| {
+                node.tokens.iter().find_map(|token| match token {
+                    SgfToken::Komi(komi) => Some(komi.get()),
+                    _ => None,
+                })
+            })
+            .unwrap_or(0.0);
+
+        if let Some(last) = nodes.last() {
+            if let Some((black, white)) = count_territory(&board, last) {
+                return Some(InferredResult {
+                    outcome: outcome_from_score(black as f32 - white as f32 - komi),
+                    confidence: Confidence::Medium,
+                });
+            }
+        }
+
+        let mut moves = nodes.iter().rev().filter_map(|node| {
+            node.tokens.iter().find_map(|token| match token {
+                SgfToken::Move { action, .. } => Some(*action),
+                _ => None,
+            })
+        });
+        if moves.next() == Some(Action::Pass) && moves.next() == Some(Action::Pass) {
+            let (black, white) = board.estimate_area();
+            return Some(InferredResult {
+                outcome: outcome_from_score(black as f32 - white as f32 - komi),
+                confidence: Confidence::Low,
+            });
+        }
+        None
+    }
+}
+
+fn outcome_from_score(score: f32) -> Outcome {
+    if score > 0.0 {
+        Outcome::WinnerByPoints(Color::Black, Real::saturating(score))
+    } else if score < 0.0 {
+        Outcome::WinnerByPoints(Color::White, Real::saturating(-score))
+    } else {
+        Outcome::Draw
+    }
+}
+
+/// Counts the area of both colors, using the territory marks in `node`
+///
+/// Marked points count for the marking color, even when a dead stone is on them. Returns `None`
+/// if the node has no territory marks.
+fn count_territory(board: &Board, node: &GameNode) -> Option<(u32, u32)> {
+    let mut marks = vec![];
+    for token in &node.tokens {
+        if let SgfToken::Unknown((identifier, value)) = token {
+            let color = match identifier.as_str() {
+                "TB" => Color::Black,
+                "TW" => Color::White,
+                _ => continue,
+            };
+            if let Ok(coordinate) = str_to_coordinates(value) {
+                marks.push((coordinate, color));
+            }
+        }
+    }
+    if marks.is_empty() {
+        return None;
+    }
+
+    let (width, height) = board.size();
+    let mut black = 0;
+    let mut white = 0;
+    for y in 1..=height {
+        for x in 1..=width {
+            let mark = marks
+                .iter()
+                .find(|(coordinate, _)| *coordinate == (x, y))
+                .map(|(_, color)| *color);
+            match mark.or_else(|| board.get((x, y))) {
+                Some(Color::Black) => black += 1,
+                Some(Color::White) => white += 1,
+                None => {}
+            }
+        }
+    }
+    Some((black, white))
+}
+
+/// Finds a result stated in a comment, in either the `RE` format or in English
+fn parse_comment_result(comment: &str) -> Option<Outcome> {
+    let lowercase = comment.to_lowercase();
+    let words: Vec<&str> = lowercase
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '!')
+        .map(|word| word.trim_end_matches('.'))
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    for word in &words {
+        if word.contains('+') {
+            if let SgfToken::Result(outcome) = SgfToken::from_pair("RE", &word.to_uppercase()) {
+                return Some(outcome);
+            }
+        }
+    }
+
+    for (index, word) in words.iter().enumerate() {
+        if *word == "jigo" || *word == "draw" {
+            return Some(Outcome::Draw);
+        }
+        if *word != "wins" && *word != "won" {
+            continue;
+        }
+        let winner = match index.checked_sub(1).map(|previous| words[previous]) {
+            Some("black") | Some("b") => Color::Black,
+            Some("white") | Some("w") => Color::White,
+            _ => continue,
+        };
+        if words.get(index + 1) != Some(&"by") {
+            continue;
+        }
+        let outcome = match words.get(index + 2) {
+            Some(&"resignation") | Some(&"resign") => Outcome::WinnerByResign(winner),
+            Some(&"time") => Outcome::WinnerByTime(winner),
+            Some(&"forfeit") => Outcome::WinnerByForfeit(winner),
+            Some(points) => match points.parse::<f32>().ok().and_then(Real::new) {
+                Some(points) => Outcome::WinnerByPoints(winner, points),
+                None => continue,
+            },
+            None => continue,
+        };
+        return Some(outcome);
+    }
+    None
+}
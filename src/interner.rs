@@ -0,0 +1,90 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::{GameNode, SgfToken, TokenString};
+
+/// Shares the storage of repeated token text, see `ParserOptions::intern_strings`
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Interner {
+    strings: HashSet<Arc<str>>,
+    /// Tokens holding interned text, by identifier and value, so repeated properties are
+    /// created without allocating their text again
+    tokens: HashMap<Box<str>, HashMap<Box<str>, SgfToken>>,
+}
+
+impl Interner {
+    /// Gets the token for a property, calling `create` only the first time the identifier and
+    /// value are seen
+    ///
+    /// `create` must only depend on the identifier and value. Tokens without interned text,
+    /// like moves, are not remembered, since cloning them saves nothing.
+    pub(crate) fn token(
+        &mut self,
+        identifier: &str,
+        value: &str,
+        create: impl FnOnce() -> SgfToken,
+    ) -> SgfToken {
+        if let Some(token) = self
+            .tokens
+            .get(identifier)
+            .and_then(|values| values.get(value))
+        {
+            return token.clone();
+        }
+        let mut token = create();
+        let strings = interned_strings(&mut token);
+        if strings.is_empty() {
+            return token;
+        }
+        for text in strings {
+            self.intern(text);
+        }
+        self.tokens
+            .entry(identifier.into())
+            .or_default()
+            .insert(value.into(), token.clone());
+        token
+    }
+
+    pub(crate) fn intern_node(&mut self, node: &mut GameNode) {
+        for token in &mut node.tokens {
+            for text in interned_strings(token) {
+                self.intern(text);
+            }
+        }
+    }
+
+    /// Replaces `text` with the earlier string holding the same text, or remembers it
+    fn intern(&mut self, text: &mut TokenString) {
+        let shared = match text.as_shared() {
+            Some(shared) => shared,
+            None => return,
+        };
+        match self.strings.get(shared) {
+            Some(earlier) => *text = TokenString::from(Arc::clone(earlier)),
+            None => {
+                self.strings.insert(Arc::clone(shared));
+            }
+        }
+    }
+}
+
+/// Strings of a token that are likely to repeat, leaving out comments and node names
+fn interned_strings(token: &mut SgfToken) -> Vec<&mut TokenString> {
+    match token {
+        SgfToken::PlayerName { name: value, .. }
+        | SgfToken::Event(value)
+        | SgfToken::Copyright(value)
+        | SgfToken::GameName(value)
+        | SgfToken::Place(value)
+        | SgfToken::Date(value)
+        | SgfToken::Overtime(value)
+        | SgfToken::EmptyList(value)
+        | SgfToken::Label { label: value, .. } => vec![value],
+        SgfToken::Application { name, version } => vec![name, version],
+        SgfToken::Unknown((identifier, value)) | SgfToken::Invalid((identifier, value)) => {
+            vec![identifier, value]
+        }
+        _ => vec![],
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{GameNode, GameTree};
+
+impl GameTree {
+    /// Serializes the tree as JSON, keeping the nodes and variations of the SGF
+    ///
+    /// Each node is an object mapping property identifiers to their list of values, in the
+    /// order the identifiers first appear in the node. Values are written unescaped, as they
+    /// would appear between the brackets of the SGF.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9]AB[cc][gg];B[ee]C[Tengen \"star\"](;W[dd])(;W[]))").unwrap();
+    /// assert_eq!(
+    ///     tree.to_json(),
+    ///     r#"{"nodes":[{"SZ":["9"],"AB":["cc","gg"]},{"B":["ee"],"C":["Tengen \"star\""]}],"variations":[{"nodes":[{"W":["dd"]}],"variations":[]},{"nodes":[{"W":[""]}],"variations":[]}]}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let nodes: Vec<String> = self.nodes.iter().map(node_json).collect();
+        let variations: Vec<String> = self.variations.iter().map(GameTree::to_json).collect();
+        format!(
+            "{{\"nodes\":[{}],\"variations\":[{}]}}",
+            nodes.join(","),
+            variations.join(",")
+        )
+    }
+}
+
+fn node_json(node: &GameNode) -> String {
+    let mut properties: Vec<(&str, Vec<String>)> = vec![];
+    for token in &node.tokens {
+        let mut value = String::new();
+        // Writing to a `String` can't fail
+        let _ = token.write_value(&mut value);
+        let value = json_string(&value);
+        match properties
+            .iter_mut()
+            .find(|(identifier, _)| *identifier == token.identifier())
+        {
+            Some((_, values)) => values.push(value),
+            None => properties.push((token.identifier(), vec![value])),
+        }
+    }
+    let properties: Vec<String> = properties
+        .iter()
+        .map(|(identifier, values)| format!("{}:[{}]", json_string(identifier), values.join(",")))
+        .collect();
+    format!("{{{}}}", properties.join(","))
+}
+
+/// Quotes and escapes `text` as a JSON string
+pub(crate) fn json_string(text: &str) -> String {
+    let mut output = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
@@ -0,0 +1,152 @@
+use std::ops::RangeInclusive;
+
+use crate::{to_gtp_vertex, Action, Board, Color, GameTree, SgfError, SgfErrorKind, SgfToken};
+
+impl GameTree {
+    /// Renders the position after `move_number` moves of the main variation as an `igo` diagram
+    ///
+    /// Move 0 is the position after the setup stones of the root node.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9]AB[cc];W[gg];B[cg])").unwrap();
+    /// assert_eq!(
+    ///     tree.to_latex(1).unwrap(),
+    ///     "\\gobansize{9}\n\\cleargoban\n\\black{c7}\n\\white{g3}\n\\showfullgoban\n"
+    /// );
+    /// ```
+    pub fn to_latex(&self, move_number: usize) -> Result<String, SgfError> {
+        let board = self.board_after(move_number)?;
+        let mut output = latex_position(&board)?;
+        output.push_str("\\showfullgoban\n");
+        Ok(output)
+    }
+
+    /// Renders the moves in `moves` as a numbered `igo` diagram
+    ///
+    /// Stones show the position before the first move of the range, with the moves of the
+    /// range numbered on top. Moves played on a point that already has a stone in the
+    /// diagram, and passes, are listed below the diagram.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[cc];W[gg];B[cg];W[])").unwrap();
+    /// assert_eq!(
+    ///     tree.to_latex_moves(2..=4).unwrap(),
+    ///     "\\gobansize{9}\n\\cleargoban\n\\black{c7}\n\
+    ///      \\white[2]{g3}\n\\black[3]{c3}\n\\showfullgoban\n\n4: pass\n"
+    /// );
+    /// ```
+    pub fn to_latex_moves(&self, moves: RangeInclusive<usize>) -> Result<String, SgfError> {
+        let (first, last) = (*moves.start(), *moves.end());
+        let mut board = self.board_after(first.saturating_sub(1))?;
+        let mut output = latex_position(&board)?;
+        let mut diagram = board.clone();
+        let mut notes = vec![];
+        let mut move_number = 0;
+        for node in self.iter() {
+            for token in &node.tokens {
+                match token {
+                    SgfToken::Move { color, action } => {
+                        move_number += 1;
+                        if move_number < first || move_number > last {
+                            continue;
+                        }
+                        match action {
+                            Action::Pass => notes.push(format!("{}: pass", move_number)),
+                            Action::Move(x, y) => {
+                                let coordinate = (*x, *y);
+                                board.play(*color, coordinate)?;
+                                let vertex = igo_vertex(coordinate, board.size().1)?;
+                                if diagram.get(coordinate).is_some() {
+                                    notes.push(format!("{}: {}", move_number, vertex));
+                                } else {
+                                    diagram.place(*color, coordinate)?;
+                                    output.push_str(&format!(
+                                        "\\{}[{}]{{{}}}\n",
+                                        color_name(*color),
+                                        move_number,
+                                        vertex
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                    SgfToken::Add { color, coordinate } if move_number >= first => {
+                        board.place(*color, *coordinate)?;
+                    }
+                    _ => {}
+                }
+            }
+            if move_number >= last {
+                break;
+            }
+        }
+        output.push_str("\\showfullgoban\n");
+        if !notes.is_empty() {
+            output.push('\n');
+            output.push_str(&notes.join(", "));
+            output.push('\n');
+        }
+        Ok(output)
+    }
+
+    /// Replays the main variation until `move_number` moves have been played
+    fn board_after(&self, move_number: usize) -> Result<Board, SgfError> {
+        let mut board = Board::for_tree(self)?;
+        let mut played = 0;
+        for node in self.iter() {
+            let moves = node
+                .tokens
+                .iter()
+                .filter(|token| matches!(token, SgfToken::Move { .. }))
+                .count();
+            if played + moves > move_number {
+                break;
+            }
+            board.apply_node(node)?;
+            played += moves;
+        }
+        Ok(board)
+    }
+}
+
+/// Renders the stones of `board` as `igo` commands, starting with the board setup
+fn latex_position(board: &Board) -> Result<String, SgfError> {
+    let (width, height) = board.size();
+    let mut output = format!("\\gobansize{{{}}}\n\\cleargoban\n", width.max(height));
+    for color in [Color::Black, Color::White].iter() {
+        let mut vertices = vec![];
+        for y in 1..=height {
+            for x in 1..=width {
+                if board.get((x, y)) == Some(*color) {
+                    vertices.push(igo_vertex((x, y), height)?);
+                }
+            }
+        }
+        if !vertices.is_empty() {
+            output.push_str(&format!(
+                "\\{}{{{}}}\n",
+                color_name(*color),
+                vertices.join(",")
+            ));
+        }
+    }
+    Ok(output)
+}
+
+/// `igo` names points like GTP, with lowercase letters
+fn igo_vertex(coordinate: (u8, u8), height: u8) -> Result<String, SgfError> {
+    to_gtp_vertex(coordinate, height)
+        .map(|vertex| vertex.to_lowercase())
+        .ok_or_else(|| SgfErrorKind::InvalidTokenValue.into())
+}
+
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::Black => "black",
+        Color::White => "white",
+    }
+}
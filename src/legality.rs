@@ -0,0 +1,172 @@
+use crate::board::{ko_rule, KoRule};
+use crate::board_cache::places_stones;
+use crate::{Action, Board, Color, Game, GameTree, IllegalMove, SgfToken, TreePath};
+
+/// Problem found with a move by `GameTree::check_moves`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MoveIssue {
+    /// The move breaks the basic rules, see `Board::play_action`
+    Illegal(IllegalMove),
+    /// The move repeats the position after the node at `repeats`, which the ko rule forbids
+    Superko { repeats: TreePath },
+}
+
+/// A move problem at a single node
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MoveProblem {
+    pub path: TreePath,
+    pub color: Color,
+    pub issue: MoveIssue,
+}
+
+/// A position reached on the current line, with the player to move after it when the node
+/// had a move
+struct Position {
+    hash: u64,
+    to_play: Option<Color>,
+    path: TreePath,
+}
+
+impl GameTree {
+    /// Replays all variations and reports the moves breaking the rules, using the ko rule of
+    /// the `RU` token in the root node
+    ///
+    /// Games without a known rule set are checked with simple ko. Illegal moves are still
+    /// played, like `Board::play` does, so the rest of the game is checked from the recorded
+    /// position. Games other than Go, and games with an invalid board size, are not checked.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee];W[ee];B[aa])").unwrap();
+    /// let problems = tree.check_moves();
+    /// assert_eq!(problems.len(), 1);
+    /// assert_eq!(problems[0].path, TreePath::new(vec![], 2));
+    /// assert_eq!(problems[0].issue, MoveIssue::Illegal(IllegalMove::Occupied));
+    /// ```
+    pub fn check_moves(&self) -> Vec<MoveProblem> {
+        let rules = self.nodes.first().and_then(|root| {
+            root.tokens.iter().find_map(|token| match token {
+                SgfToken::Rule(rules) => Some(ko_rule(rules)),
+                _ => None,
+            })
+        });
+        self.check_moves_with(rules.unwrap_or(KoRule::Simple))
+    }
+
+    /// Replays all variations and reports the moves breaking the rules, using `rule` to find
+    /// repeated positions
+    ///
+    /// A repeated position is reported with the path of the node that first reached it.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    /// use sgf_parser::board::KoRule;
+    ///
+    /// // A triple ko: after six moves the first position is back
+    /// let sgf = "(;SZ[9]AB[ba][ab][bc][cb][ga][fb][gc][bf][ag][bh][cg]\
+    ///            AW[ca][db][cc][ha][ib][hc][gb][cf][dg][ch]\
+    ///            ;W[bb];B[hb];W[bg];B[cb];W[gb];B[cg])";
+    /// let tree: GameTree = parse(sgf).unwrap();
+    /// assert!(tree.check_moves_with(KoRule::Simple).is_empty());
+    /// assert_eq!(
+    ///     tree.check_moves_with(KoRule::PositionalSuperko),
+    ///     vec![MoveProblem {
+    ///         path: TreePath::new(vec![], 6),
+    ///         color: Color::Black,
+    ///         issue: MoveIssue::Superko { repeats: TreePath::new(vec![], 0) },
+    ///     }]
+    /// );
+    /// ```
+    pub fn check_moves_with(&self, rule: KoRule) -> Vec<MoveProblem> {
+        let mut problems = vec![];
+        if self.game() != Game::Go {
+            return problems;
+        }
+        if let Ok(board) = Board::for_tree(self) {
+            check_tree(self, board, rule, &mut vec![], &mut vec![], &mut problems);
+        }
+        problems
+    }
+}
+
+fn check_tree(
+    tree: &GameTree,
+    mut board: Board,
+    rule: KoRule,
+    variations: &mut Vec<usize>,
+    history: &mut Vec<Position>,
+    problems: &mut Vec<MoveProblem>,
+) {
+    let length = history.len();
+    for (index, node) in tree.nodes.iter().enumerate() {
+        let path = TreePath::new(variations.clone(), index);
+        let mut moved = None;
+        let mut illegal = false;
+        for token in &node.tokens {
+            match token {
+                SgfToken::Add { color, coordinate } => {
+                    let _ = board.place(*color, *coordinate);
+                }
+                SgfToken::Move { color, action } => match board.play_action(*color, *action) {
+                    Ok(_) => moved = action.coordinate().map(|_| *color),
+                    Err(err) => {
+                        illegal = true;
+                        problems.push(MoveProblem {
+                            path: path.clone(),
+                            color: *color,
+                            issue: MoveIssue::Illegal(err),
+                        });
+                        if let Action::Move(x, y) = action {
+                            if board.play(*color, (*x, *y)).is_ok() {
+                                moved = Some(*color);
+                            }
+                        }
+                    }
+                },
+                _ => {}
+            }
+        }
+        let is_root = variations.is_empty() && index == 0;
+        if rule == KoRule::Simple || !(is_root || places_stones(node)) {
+            continue;
+        }
+        let hash = board.position_hash();
+        let to_play = moved.map(|color| !color);
+        if let (Some(color), false) = (moved, illegal) {
+            let repeated = history.iter().find(|position| {
+                position.hash == hash
+                    && (rule == KoRule::PositionalSuperko
+                        || position.to_play.is_none()
+                        || position.to_play == to_play)
+            });
+            if let Some(position) = repeated {
+                problems.push(MoveProblem {
+                    path: path.clone(),
+                    color,
+                    issue: MoveIssue::Superko {
+                        repeats: position.path.clone(),
+                    },
+                });
+            }
+        }
+        history.push(Position {
+            hash,
+            to_play,
+            path,
+        });
+    }
+    for (index, variation) in tree.variations.iter().enumerate() {
+        variations.push(index);
+        check_tree(
+            variation,
+            board.clone(),
+            rule,
+            variations,
+            history,
+            problems,
+        );
+        variations.pop();
+    }
+    history.truncate(length);
+}
@@ -30,14 +30,145 @@
 //! ```
 #![deny(rust_2018_idioms)]
 
+#[macro_use]
+mod diagnostics;
+mod annotation;
+mod anonymize;
+#[cfg(feature = "tokio")]
+mod async_io;
+pub mod board;
+mod board_cache;
+mod book;
+mod captures;
+mod category;
+mod charset;
+mod clock;
+mod collection;
+mod coordinate;
+pub mod coords;
+mod crop;
+mod dialect;
+mod dot;
 mod error;
+mod estimate;
+mod export;
+mod extras;
+mod format;
+mod game_info;
+#[cfg(feature = "goban")]
+mod goban_game;
+mod grouped;
+mod infer;
+mod interner;
+mod json;
+mod latex;
+mod legality;
+mod map;
+mod memory;
+mod merge;
+mod moves;
 mod node;
+mod normalize;
+mod options;
 mod parser;
+mod path;
+mod players;
+mod position;
+mod preserve;
+mod pretty;
+mod problem;
+mod promote;
+mod property;
+pub mod props;
+mod query;
+mod quirks;
+pub mod render;
+mod repair;
+mod reparse;
+mod review;
+mod roundtrip;
+mod sandbox;
+mod score;
+mod setup;
+mod shared;
+mod snapshot;
+mod stream;
+mod syntax;
+pub mod testing;
+mod time;
 mod token;
+mod token_string;
+mod tracked;
 mod tree;
+mod turn;
+mod value;
+mod variation;
+mod verify;
+mod warning;
+mod writer;
 
+pub use crate::annotation::Judgement;
+pub use crate::anonymize::AnonymizeOptions;
+#[cfg(feature = "tokio")]
+pub use crate::async_io::{parse_file_async, parse_file_async_with, AsyncEventReader};
+pub use crate::board::{Board, IllegalMove};
+pub use crate::board_cache::BoardCache;
+pub use crate::book::{BookMove, BookPosition, OpeningBook};
+pub use crate::category::{TokenCategories, TokenCategory};
+pub use crate::charset::{decode_bytes, parse_bytes_with, CharsetPolicy};
+pub use crate::clock::{ClockIssue, ClockProblem};
+pub use crate::collection::Collection;
+pub use crate::coordinate::{from_gtp_vertex, to_gtp_vertex, CoordinateSystem, Origin, Point};
+pub use crate::dialect::Dialect;
 pub use crate::error::{SgfError, SgfErrorKind};
+pub use crate::export::{GameRecord, GAME_RECORD_COLUMNS};
+pub use crate::extras::NodeExtras;
+pub use crate::format::{format_sgf, FormatStyle};
+pub use crate::game_info::GameInfoIssue;
+pub use crate::grouped::{
+    AnnotationProp, GameInfoProp, GroupedToken, MarkupProp, MoveProp, RootProp, SetupProp,
+    TimingProp,
+};
+pub use crate::infer::{Confidence, InferredResult};
+pub use crate::legality::{MoveIssue, MoveProblem};
+pub use crate::memory::MemoryUsage;
+pub use crate::merge::merge_collection;
+pub use crate::moves::BranchPoint;
 pub use crate::node::GameNode;
-pub use crate::parser::parse;
-pub use crate::token::{Action, Color, DisplayNodes, Encoding, Game, Outcome, RuleSet, SgfToken};
-pub use crate::tree::GameTree;
+pub use crate::options::{ParserOptions, ProgressCallback};
+pub use crate::parser::{
+    parse, parse_collection, parse_collection_with_options, parse_node, parse_sequence,
+    parse_with_options, parse_with_warnings, serialize_sequence, ParseProgress,
+};
+pub use crate::path::TreePath;
+pub use crate::players::{normalize_player_name, PlayerAliases, PlayerGame, PlayerIndex};
+pub use crate::pretty::DebugTree;
+pub use crate::problem::{Problem, ProblemSetBuilder};
+pub use crate::property::{properties, property_info, Multiplicity, PropertyInfo, PropertyType};
+pub use crate::props::{FindToken, Property};
+pub use crate::query::Query;
+pub use crate::quirks::Quirk;
+pub use crate::repair::{Repair, RepairOptions, Repaired};
+pub use crate::reparse::ReparseHints;
+pub use crate::review::{Review, ReviewFrame};
+pub use crate::roundtrip::{roundtrip, roundtrip_tree, RoundtripDifference, RoundtripReport};
+pub use crate::sandbox::{Sandbox, SandboxLine};
+pub use crate::score::FinalScore;
+pub use crate::shared::ArcGameTree;
+pub use crate::snapshot::IteratorState;
+pub use crate::stream::{parse_bytes, Event, Parser, TreeBuilder};
+pub use crate::syntax::{parse_syntax, SyntaxNode, SyntaxProperty, SyntaxTree};
+pub use crate::time::{TimeEntry, TimeSeries};
+pub use crate::token::{
+    Action, Color, DisplayNodes, Emphasis, Encoding, Game, IdentifierCase, Outcome, Rank, RuleSet,
+    SgfToken,
+};
+pub use crate::token_string::TokenString;
+pub use crate::tracked::{Mutation, TrackedGameTree};
+pub use crate::tree::{GameTree, VariationPreview};
+pub use crate::value::{format_real, Real, ValueType};
+pub use crate::verify::{verify_results, ResultCheck, ResultIssue, ResultReport};
+pub use crate::warning::{
+    DuplicateMode, ParseOutcome, ParseWarning, ParseWarningKind, RootTokenPolicy,
+};
+pub use crate::writer::{PassFormat, TokenOrder, WriterOptions};
@@ -0,0 +1,109 @@
+use crate::{GameNode, GameTree, SgfToken};
+
+impl GameTree {
+    /// Creates a new tree with every token replaced by the result of `f`, leaving this tree
+    /// untouched
+    ///
+    /// Tokens are visited in document order, and nodes keep their extras.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee];W[cc](;B[gg])(;B[cg]))").unwrap();
+    /// let mirrored = tree.map_tokens(|token| match token {
+    ///     SgfToken::Move { color, action: Action::Move(x, y) } => SgfToken::Move {
+    ///         color: *color,
+    ///         action: Action::Move(10 - x, *y),
+    ///     },
+    ///     token => token.clone(),
+    /// });
+    /// assert_eq!(mirrored.to_string(), "(;SZ[9];B[ee];W[gc](;B[cg])(;B[gg]))");
+    /// assert_eq!(tree.to_string(), "(;SZ[9];B[ee];W[cc](;B[gg])(;B[cg]))");
+    /// ```
+    pub fn map_tokens<F>(&self, mut f: F) -> GameTree
+    where
+        F: FnMut(&SgfToken) -> SgfToken,
+    {
+        self.map_nodes(|node| GameNode {
+            tokens: node.tokens.iter().map(&mut f).collect(),
+            extras: node.extras.clone(),
+        })
+    }
+
+    /// Creates a new tree with every node replaced by the result of `f`, leaving this tree
+    /// untouched
+    ///
+    /// Nodes are visited in document order.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee];W[cc])").unwrap();
+    /// let mut number = 0;
+    /// let numbered = tree.map_nodes(|node| {
+    ///     let mut node = node.clone();
+    ///     node.tokens.push(SgfToken::NodeName(number.to_string().into()));
+    ///     number += 1;
+    ///     node
+    /// });
+    /// assert_eq!(numbered.to_string(), "(;N[0]SZ[9];B[ee]N[1];N[2]W[cc])");
+    /// ```
+    pub fn map_nodes<F>(&self, mut f: F) -> GameTree
+    where
+        F: FnMut(&GameNode) -> GameNode,
+    {
+        map_tree(self, &mut f)
+    }
+
+    /// Creates a new tree with only the nodes `f` returns true for, leaving this tree
+    /// untouched
+    ///
+    /// A variation left without nodes is replaced by its own variations, so the remaining
+    /// nodes of every line stay in the same line.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee];C[Pass?](;C[No];W[cc])(;W[gg]))").unwrap();
+    /// let moves = tree.filter_nodes(|node| {
+    ///     node.tokens
+    ///         .iter()
+    ///         .any(|token| matches!(token, SgfToken::Move { .. }))
+    /// });
+    /// assert_eq!(moves.to_string(), "(;B[ee](;W[cc])(;W[gg]))");
+    ///
+    /// let comments = tree.filter_nodes(|node| !node.tokens_for("C").is_empty());
+    /// assert_eq!(comments.to_string(), "(;C[Pass?](;C[No]))");
+    /// ```
+    pub fn filter_nodes<F>(&self, mut f: F) -> GameTree
+    where
+        F: FnMut(&GameNode) -> bool,
+    {
+        filter_tree(self, &mut f)
+    }
+}
+
+fn map_tree(tree: &GameTree, f: &mut dyn FnMut(&GameNode) -> GameNode) -> GameTree {
+    GameTree {
+        nodes: tree.nodes.iter().map(&mut *f).collect(),
+        variations: tree
+            .variations
+            .iter()
+            .map(|variation| map_tree(variation, f))
+            .collect(),
+    }
+}
+
+fn filter_tree(tree: &GameTree, f: &mut dyn FnMut(&GameNode) -> bool) -> GameTree {
+    let nodes = tree.nodes.iter().filter(|node| f(node)).cloned().collect();
+    let mut variations = vec![];
+    for variation in &tree.variations {
+        let variation = filter_tree(variation, f);
+        if variation.nodes.is_empty() {
+            variations.extend(variation.variations);
+        } else {
+            variations.push(variation);
+        }
+    }
+    GameTree { nodes, variations }
+}
@@ -0,0 +1,123 @@
+use std::mem::size_of;
+
+use crate::{Encoding, GameNode, GameTree, RuleSet, SgfToken, TokenString};
+
+/// Approximate heap memory used by a `GameTree`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryUsage {
+    pub trees: usize,
+    pub nodes: usize,
+    pub tokens: usize,
+    /// Bytes allocated for nodes, tokens, variations and strings
+    pub allocated_bytes: usize,
+    /// Allocated bytes not holding any data, which `shrink_to_fit` can release
+    pub unused_bytes: usize,
+}
+
+impl GameTree {
+    /// Reports the approximate heap memory used by the tree, including all variations
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree = parse("(;C[comment];B[aa](;W[bb])(;W[cc]))").unwrap();
+    /// let usage = tree.memory_usage();
+    /// assert_eq!(usage.trees, 3);
+    /// assert_eq!(usage.nodes, 4);
+    /// assert_eq!(usage.tokens, 4);
+    ///
+    /// tree.shrink_to_fit();
+    /// assert_eq!(tree.memory_usage().unused_bytes, 0);
+    /// ```
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let mut usage = MemoryUsage::default();
+        add_tree_usage(self, &mut usage);
+        usage
+    }
+
+    /// Releases unused capacity in all nodes, tokens and variations
+    pub fn shrink_to_fit(&mut self) {
+        self.nodes.shrink_to_fit();
+        self.variations.shrink_to_fit();
+        for node in &mut self.nodes {
+            node.tokens.shrink_to_fit();
+            for token in &mut node.tokens {
+                for string in token_strings_mut(token) {
+                    string.shrink_to_fit();
+                }
+            }
+        }
+        for variation in &mut self.variations {
+            variation.shrink_to_fit();
+        }
+    }
+}
+
+fn add_tree_usage(tree: &GameTree, usage: &mut MemoryUsage) {
+    usage.trees += 1;
+    add_vec_usage(&tree.nodes, usage);
+    add_vec_usage(&tree.variations, usage);
+    for node in &tree.nodes {
+        add_node_usage(node, usage);
+    }
+    for variation in &tree.variations {
+        add_tree_usage(variation, usage);
+    }
+}
+
+fn add_node_usage(node: &GameNode, usage: &mut MemoryUsage) {
+    usage.nodes += 1;
+    usage.tokens += node.tokens.len();
+    add_vec_usage(&node.tokens, usage);
+    for token in &node.tokens {
+        for (capacity, len) in token_strings(token) {
+            usage.allocated_bytes += capacity;
+            usage.unused_bytes += capacity - len;
+        }
+    }
+}
+
+fn add_vec_usage<T>(vec: &Vec<T>, usage: &mut MemoryUsage) {
+    usage.allocated_bytes += vec.capacity() * size_of::<T>();
+    usage.unused_bytes += (vec.capacity() - vec.len()) * size_of::<T>();
+}
+
+/// Allocated and used bytes of all heap allocated strings in a token
+///
+/// Token strings are immutable, so all their bytes are used. Shared token strings are counted
+/// for each token holding them.
+fn token_strings(token: &SgfToken) -> Vec<(usize, usize)> {
+    let string = |value: &String| (value.capacity(), value.len());
+    let token_string = |value: &TokenString| (value.heap_len(), value.heap_len());
+    match token {
+        SgfToken::PlayerRank { rank: value, .. }
+        | SgfToken::Rule(RuleSet::Unknown(value))
+        | SgfToken::Charset(Encoding::Other(value)) => vec![string(value)],
+        SgfToken::PlayerName { name: value, .. }
+        | SgfToken::Event(value)
+        | SgfToken::Copyright(value)
+        | SgfToken::GameName(value)
+        | SgfToken::Place(value)
+        | SgfToken::Date(value)
+        | SgfToken::Overtime(value)
+        | SgfToken::Comment(value)
+        | SgfToken::NodeName(value)
+        | SgfToken::EmptyList(value)
+        | SgfToken::Label { label: value, .. } => vec![token_string(value)],
+        SgfToken::Application { name, version } => vec![token_string(name), token_string(version)],
+        SgfToken::Unknown((identifier, value)) | SgfToken::Invalid((identifier, value)) => {
+            vec![token_string(identifier), token_string(value)]
+        }
+        _ => vec![],
+    }
+}
+
+/// All heap allocated strings in a token that can be shrunk
+fn token_strings_mut(token: &mut SgfToken) -> Vec<&mut String> {
+    match token {
+        SgfToken::PlayerRank { rank: value, .. }
+        | SgfToken::Rule(RuleSet::Unknown(value))
+        | SgfToken::Charset(Encoding::Other(value)) => vec![value],
+        _ => vec![],
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{Collection, GameNode, GameTree, SgfToken};
+
+/// Node of the prefix tree built while merging
+struct MergeNode {
+    node: GameNode,
+    children: Vec<MergeNode>,
+}
+
+/// Merges the games of `collection` into a single tree, with variations where games diverge
+///
+/// Nodes are matched by their moves and setup stones, and the other tokens of matching nodes
+/// are combined. The root node holds the root tokens shared by all games, like `SZ`, while
+/// game specific information like player names is dropped.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let collection = parse_collection(
+///     "(;SZ[19]PB[A];B[pd];W[dp];B[pp])(;SZ[19]PB[B];B[pd];W[dd])(;SZ[19];B[pd]C[Hi];W[dp])",
+/// )
+/// .unwrap();
+/// let tree = merge_collection(&collection);
+/// assert_eq!(
+///     tree.to_string(),
+///     "(;SZ[19];B[pd]C[Hi](;W[dp];B[pp])(;W[dd]))"
+/// );
+/// ```
+pub fn merge_collection(collection: &Collection) -> GameTree {
+    let mut root = MergeNode {
+        node: common_root(collection),
+        children: vec![],
+    };
+    for game in &collection.games {
+        if game.nodes.is_empty() {
+            insert_variations(&mut root.children, game);
+        } else {
+            insert(&mut root.children, game, 1);
+        }
+    }
+    into_tree(root)
+}
+
+/// Gets a node holding the tokens found in the root node of every game
+fn common_root(collection: &Collection) -> GameNode {
+    let mut roots = collection
+        .games
+        .iter()
+        .filter_map(|game| game.nodes.first());
+    let mut tokens = match roots.next() {
+        Some(root) => root.tokens.clone(),
+        None => vec![],
+    };
+    for root in roots {
+        tokens.retain(|token| root.tokens.contains(token));
+    }
+    tokens.retain(|token| !token.is_game_info_token());
+    GameNode::from(tokens)
+}
+
+/// Inserts the nodes of `tree` from index `start`, followed by its variations
+fn insert(children: &mut Vec<MergeNode>, tree: &GameTree, start: usize) {
+    let mut children = children;
+    for node in &tree.nodes[start.min(tree.nodes.len())..] {
+        let key = merge_key(node);
+        let index = match children
+            .iter()
+            .position(|child| merge_key(&child.node) == key)
+        {
+            Some(index) => {
+                let tokens = &mut children[index].node.tokens;
+                for token in &node.tokens {
+                    if !tokens.contains(token) {
+                        tokens.push(token.clone());
+                    }
+                }
+                index
+            }
+            None => {
+                children.push(MergeNode {
+                    node: node.clone(),
+                    children: vec![],
+                });
+                children.len() - 1
+            }
+        };
+        children = &mut children[index].children;
+    }
+    insert_variations(children, tree);
+}
+
+fn insert_variations(children: &mut Vec<MergeNode>, tree: &GameTree) {
+    for variation in &tree.variations {
+        insert(children, variation, 0);
+    }
+}
+
+/// Gets the moves and setup stones of a node, which decide if nodes are merged
+fn merge_key(node: &GameNode) -> Vec<&SgfToken> {
+    let mut key: Vec<&SgfToken> = node
+        .tokens
+        .iter()
+        .filter(|token| matches!(token, SgfToken::Move { .. } | SgfToken::Add { .. }))
+        .collect();
+    key.sort();
+    key
+}
+
+fn into_tree(first: MergeNode) -> GameTree {
+    let mut nodes = vec![first.node];
+    let mut children = first.children;
+    while children.len() == 1 {
+        let child = children.pop().expect("There is exactly one child");
+        nodes.push(child.node);
+        children = child.children;
+    }
+    GameTree {
+        nodes,
+        variations: children.into_iter().map(into_tree).collect(),
+    }
+}
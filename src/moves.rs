@@ -0,0 +1,127 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{Action, Color, GameTree, SgfError, SgfToken, TreePath};
+
+/// A node followed by more than one variation
+///
+/// Found by `GameTree::branch_points`, and used as the source of the
+/// `SgfErrorKind::UnexpectedVariation` error from `GameTree::try_into_moves`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BranchPoint {
+    /// Path of the last node before the variations
+    pub path: TreePath,
+    /// Number of moves played from the root up to and including the node
+    pub move_number: usize,
+    /// Number of variations following the node
+    pub variations: usize,
+}
+
+impl fmt::Display for BranchPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} variations after node {}", self.variations, self.path)
+    }
+}
+
+impl Error for BranchPoint {}
+
+impl GameTree {
+    /// Gets every node followed by more than one variation, in the order they appear in the SGF
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee](;W[cc](;B[gg])(;B[cg])(;B[gc]))(;W[gg]))").unwrap();
+    /// let branches = tree.branch_points();
+    /// assert_eq!(branches.len(), 2);
+    /// assert_eq!(branches[0].path, TreePath::new(vec![], 1));
+    /// assert_eq!(branches[0].variations, 2);
+    /// assert_eq!(branches[1].path, TreePath::new(vec![0], 0));
+    /// assert_eq!(branches[1].move_number, 2);
+    /// assert_eq!(branches[1].variations, 3);
+    /// ```
+    pub fn branch_points(&self) -> Vec<BranchPoint> {
+        let mut found = vec![];
+        collect_branch_points(self, &mut vec![], 0, &mut found);
+        found
+    }
+
+    /// Gets all moves of the game, failing if the game has variations
+    ///
+    /// Unlike iterating over the main variation, other variations are not silently skipped.
+    /// The error has `SgfErrorKind::UnexpectedVariation` as kind, and the first `BranchPoint`
+    /// as source. A sequence followed by a single variation is still a single line.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    /// use std::error::Error;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee];W[])").unwrap();
+    /// assert_eq!(
+    ///     tree.try_into_moves().unwrap(),
+    ///     vec![(Color::Black, Action::Move(5, 5)), (Color::White, Action::Pass)]
+    /// );
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee](;W[cc])(;W[gg]))").unwrap();
+    /// let err = tree.try_into_moves().unwrap_err();
+    /// assert_eq!(err.kind, SgfErrorKind::UnexpectedVariation);
+    /// let branch = err.source().unwrap().downcast_ref::<BranchPoint>().unwrap();
+    /// assert_eq!(branch.path, TreePath::new(vec![], 1));
+    /// ```
+    pub fn try_into_moves(&self) -> Result<Vec<(Color, Action)>, SgfError> {
+        let mut moves = vec![];
+        let mut variations = vec![];
+        let mut tree = self;
+        loop {
+            moves.extend(tree.nodes.iter().flat_map(|node| {
+                node.tokens.iter().filter_map(|token| match token {
+                    SgfToken::Move { color, action } => Some((*color, *action)),
+                    _ => None,
+                })
+            }));
+            match tree.variations.len() {
+                0 => return Ok(moves),
+                1 => {
+                    variations.push(0);
+                    tree = &tree.variations[0];
+                }
+                count => {
+                    return Err(SgfError::unexpected_variation(BranchPoint {
+                        path: TreePath::new(variations, tree.nodes.len().saturating_sub(1)),
+                        move_number: moves.len(),
+                        variations: count,
+                    }));
+                }
+            }
+        }
+    }
+}
+
+fn count_moves(tree: &GameTree) -> usize {
+    tree.nodes
+        .iter()
+        .flat_map(|node| node.tokens.iter())
+        .filter(|token| matches!(token, SgfToken::Move { .. }))
+        .count()
+}
+
+fn collect_branch_points(
+    tree: &GameTree,
+    variations: &mut Vec<usize>,
+    moves: usize,
+    found: &mut Vec<BranchPoint>,
+) {
+    let moves = moves + count_moves(tree);
+    if tree.variations.len() > 1 {
+        found.push(BranchPoint {
+            path: TreePath::new(variations.clone(), tree.nodes.len().saturating_sub(1)),
+            move_number: moves,
+            variations: tree.variations.len(),
+        });
+    }
+    for (index, variation) in tree.variations.iter().enumerate() {
+        variations.push(index);
+        collect_branch_points(variation, variations, moves, found);
+        variations.pop();
+    }
+}
@@ -1,12 +1,176 @@
-use crate::SgfToken;
+use std::fmt;
+
+use std::any::Any;
+
+use crate::{property_info, Multiplicity, NodeExtras, PropertyInfo, SgfToken, TokenOrder};
 
 /// A game node, containing a vector of tokens
-#[derive(Debug, PartialEq, Clone)]
+///
+/// Application data that is not part of the SGF is kept in `extras`, see `GameNode::extras`.
+/// It is ignored when comparing or hashing nodes.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
 pub struct GameNode {
     pub tokens: Vec<SgfToken>,
+    pub(crate) extras: NodeExtras,
 }
 
 impl GameNode {
+    /// Checks if the node has no tokens, like the node in `(;)`
+    ///
+    /// Empty nodes are kept when parsing and serializing, so they can be used as placeholders.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;;B[aa])").unwrap();
+    /// assert!(tree.nodes[0].is_empty());
+    /// assert!(!tree.nodes[1].is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Gets the application data attached to the node
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut node = parse_node(";B[ee]").unwrap();
+    /// node.extras_mut().insert("visits", 1200u32);
+    /// assert_eq!(node.extras().get::<u32>("visits"), Some(&1200));
+    /// assert_eq!(node, parse_node(";B[ee]").unwrap());
+    /// ```
+    pub fn extras(&self) -> &NodeExtras {
+        &self.extras
+    }
+
+    /// Gets the application data attached to the node, for changing it
+    pub fn extras_mut(&mut self) -> &mut NodeExtras {
+        &mut self.extras
+    }
+
+    /// Stores the extra value under `key` as the property `identifier`, converted by `to_value`
+    ///
+    /// Replaces any tokens the node already has for `identifier`. Returns `false`, leaving the
+    /// node unchanged, when there is no value of type `T` under `key`.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut node = parse_node(";B[ee]").unwrap();
+    /// node.extras_mut().insert("winrate", 0.48f64);
+    /// assert!(node.write_extra("winrate", "XW", |winrate: &f64| format!("{:.2}", winrate)));
+    /// assert_eq!(node.to_string(), ";B[ee]XW[0.48]");
+    /// ```
+    pub fn write_extra<T: Any>(
+        &mut self,
+        key: &str,
+        identifier: &str,
+        to_value: impl Fn(&T) -> String,
+    ) -> bool {
+        let value = match self.extras.get::<T>(key) {
+            Some(value) => to_value(value),
+            None => return false,
+        };
+        self.tokens.retain(|token| token.identifier() != identifier);
+        self.tokens.push(SgfToken::from_pair(identifier, &value));
+        true
+    }
+
+    /// Gets all tokens with the property identifier `identifier`, like the stones of `AB`
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let node = parse_node(";AB[aa][bb]AW[cc]").unwrap();
+    /// assert_eq!(node.tokens_for("AB").len(), 2);
+    /// assert!(node.tokens_for("B").is_empty());
+    /// ```
+    pub fn tokens_for(&self, identifier: &str) -> Vec<&SgfToken> {
+        self.tokens
+            .iter()
+            .filter(|token| token.identifier() == identifier)
+            .collect()
+    }
+
+    /// Merges tokens sharing an identifier, as if the node held each property once
+    ///
+    /// Properties taking a list of values, and unknown properties, keep all distinct values.
+    /// Other properties keep their first value. Returns the identifiers of the properties that
+    /// had values dropped.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut node = parse_node(";TR[aa]KM[6.5]TR[bb][aa]KM[0.5]").unwrap();
+    /// assert_eq!(node.merge_duplicates(), vec!["KM".to_string()]);
+    /// assert_eq!(String::from(&node), ";KM[6.5]TR[aa][bb]");
+    /// ```
+    pub fn merge_duplicates(&mut self) -> Vec<String> {
+        let mut dropped: Vec<String> = vec![];
+        let mut kept: Vec<SgfToken> = Vec::with_capacity(self.tokens.len());
+        for token in self.tokens.drain(..) {
+            let identifier = token.identifier();
+            let is_list = !matches!(
+                property_info(identifier).map(PropertyInfo::multiplicity),
+                Some(Multiplicity::Single)
+            );
+            let duplicate = if is_list {
+                kept.contains(&token)
+            } else {
+                kept.iter().any(|other| other.identifier() == identifier)
+            };
+            if !duplicate {
+                kept.push(token);
+            } else if !is_list && !dropped.iter().any(|d| d == identifier) {
+                dropped.push(identifier.to_string());
+            }
+        }
+        self.tokens = kept;
+        dropped
+    }
+
+    /// Removes tokens repeating an earlier token of the node, and returns the number removed
+    ///
+    /// Unlike `merge_duplicates`, different values of the same property are kept.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut node = parse_node(";TR[aa]C[hi]TR[aa]TR[bb]").unwrap();
+    /// node.tokens.push(SgfToken::Comment("hi".into()));
+    /// assert_eq!(node.dedup_tokens(), 2);
+    /// assert_eq!(node.to_string(), ";C[hi]TR[aa][bb]");
+    /// ```
+    pub fn dedup_tokens(&mut self) -> usize {
+        let count = self.tokens.len();
+        let mut kept: Vec<SgfToken> = Vec::with_capacity(count);
+        for token in self.tokens.drain(..) {
+            if !kept.contains(&token) {
+                kept.push(token);
+            }
+        }
+        self.tokens = kept;
+        count - self.tokens.len()
+    }
+
+    /// Sorts the tokens in the order they are written with `order`
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut node = parse_node(";TR[aa]C[Nice]B[aa]KM[6.5]").unwrap();
+    /// node.sort_tokens(TokenOrder::Canonical);
+    /// assert_eq!(node.tokens[0], SgfToken::Komi(Real::new(6.5).unwrap()));
+    /// assert_eq!(node.tokens[3], SgfToken::Triangle { coordinate: (1, 1) });
+    /// ```
+    pub fn sort_tokens(&mut self, order: TokenOrder) {
+        self.tokens.sort_by_cached_key(|token| {
+            let string: String = token.into();
+            (order.rank(token), string)
+        });
+    }
+
     /// Gets a vector of all `SgfToken::Unknown` tokens
     pub fn get_unknown_tokens(&self) -> Vec<&SgfToken> {
         self.tokens
@@ -24,31 +188,129 @@ impl GameNode {
     }
 }
 
-impl Into<String> for &GameNode {
-    fn into(self) -> String {
-        let mut token_strings: Vec<String> = self.tokens.iter().map(|t| t.into()).collect();
-        token_strings.sort();
-        let (_, out) = token_strings
-            .iter()
-            .fold((None, vec![";"]), |(prev, mut out), token| {
-                let offset = token.find('[').unwrap_or_else(|| token.len());
-                match prev {
-                    Some(ref prop) if token.starts_with(prop) => {
-                        out.push(&token[offset..]);
-                        (prev, out)
-                    }
-                    _ => {
-                        out.push(&token);
-                        (Some(&token[0..offset]), out)
-                    }
-                }
-            });
-        out.join("")
-    }
-}
-
-impl Into<String> for GameNode {
-    fn into(self) -> String {
-        (&self).into()
+impl From<&GameNode> for String {
+    fn from(node: &GameNode) -> String {
+        let mut out = String::with_capacity(node.estimated_len());
+        NodeWriter::default()
+            .write(&node.tokens, TokenOrder::Identifier, &mut out)
+            .expect("Writing to a String never fails");
+        out
+    }
+}
+
+/// Serializes nodes, reusing its buffers between nodes to avoid allocating for every node
+#[derive(Debug, Default)]
+pub(crate) struct NodeWriter {
+    /// The tokens of the node, written one after another
+    scratch: String,
+    /// The rank and the range in `scratch` of each token
+    tokens: Vec<(u8, usize, usize)>,
+}
+
+impl NodeWriter {
+    /// Writes `tokens` as a node in `order`, writing the values of repeated properties together
+    pub(crate) fn write<W: fmt::Write>(
+        &mut self,
+        tokens: &[SgfToken],
+        order: TokenOrder,
+        out: &mut W,
+    ) -> fmt::Result {
+        let NodeWriter {
+            scratch,
+            tokens: ranges,
+        } = self;
+        scratch.clear();
+        ranges.clear();
+        for token in tokens {
+            let start = scratch.len();
+            token.write_to(scratch)?;
+            ranges.push((order.rank(token), start, scratch.len()));
+        }
+        ranges.sort_by(|(a_rank, a_start, a_end), (b_rank, b_start, b_end)| {
+            (a_rank, &scratch[*a_start..*a_end]).cmp(&(b_rank, &scratch[*b_start..*b_end]))
+        });
+        out.write_char(';')?;
+        let mut prev = None;
+        for (_, start, end) in ranges.iter() {
+            let token = &scratch[*start..*end];
+            let offset = token.find('[').unwrap_or(token.len());
+            if prev == Some(&token[..offset]) {
+                out.write_str(&token[offset..])?;
+            } else {
+                out.write_str(token)?;
+                prev = Some(&token[..offset]);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<GameNode> for String {
+    fn from(node: GameNode) -> String {
+        (&node).into()
+    }
+}
+
+impl fmt::Display for GameNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        NodeWriter::default().write(&self.tokens, TokenOrder::Identifier, f)
+    }
+}
+
+impl From<Vec<SgfToken>> for GameNode {
+    fn from(tokens: Vec<SgfToken>) -> Self {
+        GameNode {
+            tokens,
+            extras: NodeExtras::default(),
+        }
+    }
+}
+
+/// Generates nodes with their tokens in the order they are written, so that a written node is
+/// read back as the same node
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for GameNode {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut node = GameNode::from(Vec::<SgfToken>::arbitrary(u)?);
+        node.sort_tokens(TokenOrder::Identifier);
+        Ok(node)
+    }
+}
+
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let node: GameNode = vec![SgfToken::from_pair("B", "aa"), SgfToken::from_pair("C", "hi")]
+///     .into_iter()
+///     .collect();
+/// assert_eq!(node.to_string(), ";B[aa]C[hi]");
+/// ```
+impl std::iter::FromIterator<SgfToken> for GameNode {
+    fn from_iter<I: IntoIterator<Item = SgfToken>>(iter: I) -> Self {
+        GameNode::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl Extend<SgfToken> for GameNode {
+    fn extend<I: IntoIterator<Item = SgfToken>>(&mut self, iter: I) {
+        self.tokens.extend(iter)
+    }
+}
+
+impl IntoIterator for GameNode {
+    type Item = SgfToken;
+    type IntoIter = std::vec::IntoIter<SgfToken>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tokens.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a GameNode {
+    type Item = &'a SgfToken;
+    type IntoIter = std::slice::Iter<'a, SgfToken>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tokens.iter()
     }
 }
@@ -0,0 +1,116 @@
+use crate::{Color, GameNode, GameTree, SgfToken};
+
+impl GameTree {
+    /// Rewrites all variations to hold at most one move per node, and returns the number of
+    /// nodes that were split or merged
+    ///
+    /// A node with several moves is split into one node per move. Other tokens stay in the
+    /// first node, except timing tokens like `BL` and `OW`, which move to the node of the last
+    /// move of their color. A node holding only timing tokens for the move in the node before
+    /// it is merged into that node.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree =
+    ///     parse("(;SZ[9];B[ee]W[cc]WL[50]C[Opening];B[gg];BL[90])").unwrap();
+    /// assert_eq!(tree.normalize_moves(), 2);
+    /// assert_eq!(
+    ///     tree.to_string(),
+    ///     "(;SZ[9];B[ee]C[Opening];WL[50]W[cc];BL[90]B[gg])"
+    /// );
+    /// ```
+    pub fn normalize_moves(&mut self) -> usize {
+        let mut changes = 0;
+        let mut nodes: Vec<GameNode> = Vec::with_capacity(self.nodes.len());
+        for node in self.nodes.drain(..) {
+            let moves = node.tokens.iter().filter(|token| is_move(token)).count();
+            if moves > 1 {
+                nodes.extend(split_moves(node));
+                changes += 1;
+            } else if moves == 0 && can_merge_timing(nodes.last(), &node) {
+                if let Some(previous) = nodes.last_mut() {
+                    previous.tokens.extend(node.tokens);
+                }
+                changes += 1;
+            } else {
+                nodes.push(node);
+            }
+        }
+        self.nodes = nodes;
+        changes
+            + self
+                .variations
+                .iter_mut()
+                .map(GameTree::normalize_moves)
+                .sum::<usize>()
+    }
+}
+
+fn is_move(token: &SgfToken) -> bool {
+    matches!(token, SgfToken::Move { .. })
+}
+
+/// Gets the color of `BL`, `WL`, `OB` and `OW` tokens
+fn timing_color(token: &SgfToken) -> Option<Color> {
+    match token {
+        SgfToken::Time { color, .. } | SgfToken::MovesRemaining { color, .. } => Some(*color),
+        _ => None,
+    }
+}
+
+/// Checks if `node` only holds timing tokens that belong to the move of `previous`
+fn can_merge_timing(previous: Option<&GameNode>, node: &GameNode) -> bool {
+    let previous = match previous {
+        Some(previous) => previous,
+        None => return false,
+    };
+    let color = previous.tokens.iter().find_map(|token| match token {
+        SgfToken::Move { color, .. } => Some(*color),
+        _ => None,
+    });
+    !node.tokens.is_empty()
+        && color.is_some()
+        && node.tokens.iter().all(|token| {
+            timing_color(token) == color
+                && !previous
+                    .tokens
+                    .iter()
+                    .any(|existing| existing.identifier() == token.identifier())
+        })
+}
+
+/// Splits a node into one node per move
+fn split_moves(node: GameNode) -> Vec<GameNode> {
+    let mut first = GameNode {
+        tokens: vec![],
+        extras: node.extras,
+    };
+    let mut nodes: Vec<GameNode> = vec![];
+    let mut timing = vec![];
+    for token in node.tokens {
+        if is_move(&token) {
+            nodes.push(GameNode::from(vec![token]));
+        } else if timing_color(&token).is_some() {
+            timing.push(token);
+        } else {
+            first.tokens.push(token);
+        }
+    }
+    for token in timing {
+        let color = timing_color(&token);
+        let target = nodes.iter_mut().rev().find(|node| {
+            node.tokens.iter().any(|token| match token {
+                SgfToken::Move { color: c, .. } => Some(*c) == color,
+                _ => false,
+            })
+        });
+        match target {
+            Some(target) => target.tokens.push(token),
+            None => first.tokens.push(token),
+        }
+    }
+    first.tokens.append(&mut nodes[0].tokens);
+    nodes[0] = first;
+    nodes
+}
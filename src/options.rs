@@ -0,0 +1,158 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{CharsetPolicy, DuplicateMode, IdentifierCase, ParseProgress, RootTokenPolicy};
+
+/// Callback called by `parse_with_options` after each node has been created
+pub type ProgressCallback = Arc<dyn Fn(ParseProgress) + Send + Sync>;
+
+/// Options for `parse_bytes_with` and `parse_with_options`
+///
+/// Options are created with `ParserOptions::new`, and set with the method named after each
+/// field. New options may be added in any release, with defaults keeping the current
+/// behavior, so struct expressions should end with `..ParserOptions::default()`.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let options = ParserOptions::new()
+///     .identifier_case(IdentifierCase::Fold)
+///     .preserve_format(true);
+/// assert!(options.preserve_format);
+/// assert_eq!(options.charset_policy, CharsetPolicy::Strict);
+/// ```
+#[derive(Clone, Default)]
+pub struct ParserOptions {
+    pub charset_policy: CharsetPolicy,
+    pub identifier_case: IdentifierCase,
+    /// Record the original text and whitespace of every node, so that
+    /// `WriterOptions::preserve_format` can write unchanged nodes exactly as they were read
+    pub preserve_format: bool,
+    /// How root tokens outside the root node are handled
+    ///
+    /// `parse_bytes_with` has no warnings to report, so `RootTokenPolicy::Warn` is the same as
+    /// `RootTokenPolicy::Ignore` there.
+    pub root_tokens: RootTokenPolicy,
+    /// Apply the fixes for the known quirks of the application named by the `AP` token, see
+    /// `GameTree::apply_quirks`
+    ///
+    /// `parse_with_options` reports each applied quirk with a
+    /// `ParseWarningKind::AppliedQuirk` warning on the root node.
+    pub apply_quirks: bool,
+    /// How repeated identifiers in a single node are handled by `parse_with_options`
+    pub duplicates: DuplicateMode,
+    /// Share the storage of repeated text, like the identifiers and values of unknown
+    /// properties, player names and other game information
+    ///
+    /// Comments and node names are left alone, since they rarely repeat. Repeated properties
+    /// are created from the first one while parsing, so their text is only allocated once.
+    /// `parse_collection_with_options` shares text between all games of the input, and a
+    /// `TreeBuilder` created with this option between all games it builds, which saves memory
+    /// when indexing large collections, at the cost of hashing each string.
+    pub intern_strings: bool,
+    /// Called by `parse_with_options` after each node has been created
+    ///
+    /// Nodes are reported in the order they appear in the input, so `ParseProgress::bytes`
+    /// only grows between calls.
+    pub progress: Option<ProgressCallback>,
+}
+
+impl ParserOptions {
+    /// Creates the default options, used by `parse` and `parse_bytes`
+    pub fn new() -> Self {
+        ParserOptions::default()
+    }
+
+    /// Sets `charset_policy`
+    pub fn charset_policy(mut self, charset_policy: CharsetPolicy) -> Self {
+        self.charset_policy = charset_policy;
+        self
+    }
+
+    /// Sets `identifier_case`
+    pub fn identifier_case(mut self, identifier_case: IdentifierCase) -> Self {
+        self.identifier_case = identifier_case;
+        self
+    }
+
+    /// Sets `preserve_format`
+    pub fn preserve_format(mut self, preserve_format: bool) -> Self {
+        self.preserve_format = preserve_format;
+        self
+    }
+
+    /// Sets `root_tokens`
+    pub fn root_tokens(mut self, root_tokens: RootTokenPolicy) -> Self {
+        self.root_tokens = root_tokens;
+        self
+    }
+
+    /// Sets `apply_quirks`
+    pub fn apply_quirks(mut self, apply_quirks: bool) -> Self {
+        self.apply_quirks = apply_quirks;
+        self
+    }
+
+    /// Sets `duplicates`
+    pub fn duplicates(mut self, duplicates: DuplicateMode) -> Self {
+        self.duplicates = duplicates;
+        self
+    }
+
+    /// Sets `intern_strings`
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let options = ParserOptions::new().intern_strings(true);
+    /// let tree = parse_with_options("(;XX[engine];XX[engine])", &options).unwrap().tree;
+    /// match (&tree.nodes[0].tokens[0], &tree.nodes[1].tokens[0]) {
+    ///     (SgfToken::Unknown((a, _)), SgfToken::Unknown((b, _))) => {
+    ///         assert!(TokenString::ptr_eq(a, b))
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn intern_strings(mut self, intern_strings: bool) -> Self {
+        self.intern_strings = intern_strings;
+        self
+    }
+
+    /// Sets `progress`
+    ///
+    /// ```rust
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use sgf_parser::*;
+    ///
+    /// let nodes = Arc::new(AtomicUsize::new(0));
+    /// let counter = Arc::clone(&nodes);
+    /// let options = ParserOptions::new().progress(move |progress: ParseProgress| {
+    ///     counter.store(progress.nodes, Ordering::Relaxed);
+    /// });
+    /// parse_with_options("(;SZ[19];B[aa];W[bb])", &options).unwrap();
+    /// assert_eq!(nodes.load(Ordering::Relaxed), 3);
+    /// ```
+    pub fn progress(mut self, progress: impl Fn(ParseProgress) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+}
+
+impl fmt::Debug for ParserOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParserOptions")
+            .field("charset_policy", &self.charset_policy)
+            .field("identifier_case", &self.identifier_case)
+            .field("preserve_format", &self.preserve_format)
+            .field("root_tokens", &self.root_tokens)
+            .field("apply_quirks", &self.apply_quirks)
+            .field("duplicates", &self.duplicates)
+            .field("intern_strings", &self.intern_strings)
+            .field(
+                "progress",
+                &self.progress.as_ref().map(|_| "Fn(ParseProgress)"),
+            )
+            .finish()
+    }
+}
@@ -3,11 +3,17 @@ use pest::Parser;
 use pest::iterators::Pair;
 use pest_derive::*;
 
+use crate::diagnostics;
+use crate::interner::Interner;
+use crate::preserve;
+use crate::repair::lift_root_tokens;
+use crate::token::{from_property_value, normalize_identifier};
+use crate::warning::property_warnings;
 use crate::*;
 
 #[derive(Parser)]
 #[grammar = "../sgf.pest"]
-struct SGFParser;
+pub(crate) struct SGFParser;
 
 ///
 /// Main entry point to the library. Parses an SGF string, and returns a `GameTree`.
@@ -24,64 +30,412 @@ struct SGFParser;
 /// ```
 ///
 pub fn parse(input: &str) -> Result<GameTree, SgfError> {
+    parse_with_warnings(input).map(|outcome| outcome.tree)
+}
+
+/// Parses an SGF string like `parse`, but also returns the warnings found while parsing
+///
+/// Warnings describe input that was accepted, but is questionable, like empty property values
+/// or identifiers from older versions of the SGF specification.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let outcome = parse_with_warnings("(;CA[ISO-8859-1]CopyRight[2017];B[aa])").unwrap();
+/// assert_eq!(outcome.tree.count_max_nodes(), 2);
+/// assert_eq!(outcome.warnings.len(), 2);
+/// assert_eq!(
+///     outcome.warnings[0].kind,
+///     ParseWarningKind::UnknownCharset("ISO-8859-1".to_string())
+/// );
+/// ```
+pub fn parse_with_warnings(input: &str) -> Result<ParseOutcome, SgfError> {
+    parse_with_options(input, &ParserOptions::default())
+}
+
+/// Parses an SGF string like `parse_with_warnings`, using `options`
+///
+/// The charset policy of `options` only applies to `parse_bytes_with`, since the input is
+/// already a string.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let options = ParserOptions::new().identifier_case(IdentifierCase::Fold);
+/// let outcome = parse_with_options("(;sz[9]Km[6.5]size[9];b[ee])", &options).unwrap();
+/// assert_eq!(outcome.tree.to_string(), "(;KM[6.5]SZ[9]size[9];B[ee])");
+/// ```
+///
+/// FF[4] does not allow a node to hold the same property twice, like `;AB[aa]AB[bb]`. These
+/// are handled according to `ParserOptions::duplicates`.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let input = "(;AB[aa]C[first]AB[bb][aa]C[second])";
+///
+/// let options = ParserOptions::new().duplicates(DuplicateMode::Merge);
+/// let outcome = parse_with_options(input, &options).unwrap();
+/// assert_eq!(outcome.tree.to_string(), "(;AB[aa][bb]C[first])");
+/// assert_eq!(
+///     outcome.warnings[0].kind,
+///     ParseWarningKind::DuplicateIdentifier("C".to_string())
+/// );
+///
+/// let options = ParserOptions::new().duplicates(DuplicateMode::Reject);
+/// let err = parse_with_options(input, &options).unwrap_err();
+/// assert_eq!(err.kind, SgfErrorKind::DuplicateIdentifier);
+/// ```
+pub fn parse_with_options(input: &str, options: &ParserOptions) -> Result<ParseOutcome, SgfError> {
+    #[cfg(feature = "log")]
+    let started = std::time::Instant::now();
     let mut parse_roots =
         SGFParser::parse(Rule::game_tree, input).map_err(SgfError::parse_error)?;
-    if let Some(game_tree) = parse_roots.next() {
-        let tree = parse_pair(game_tree);
-        let game = create_game_tree(tree, true)?;
-        Ok(game)
-    } else {
-        Ok(GameTree::default())
+    let mut progress = progress_callback(options);
+    let mut context = ParseContext::new(input, options, &mut progress);
+    let tree = match parse_roots.next() {
+        Some(game_tree) => create_game(game_tree, input, options, &mut context)?,
+        None => GameTree::default(),
+    };
+    debug_event!(
+        "parsed {} nodes from {} bytes in {:?}, with {} warnings",
+        context.nodes,
+        input.len(),
+        started.elapsed(),
+        context.warnings.len()
+    );
+    Ok(ParseOutcome {
+        tree,
+        warnings: context.warnings,
+    })
+}
+
+/// Parses all game trees in an SGF string
+///
+/// `parse` only returns the first game tree, while SGF files may hold a collection of games.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let collection = parse_collection("(;PB[Honinbo];B[aa])\n(;PB[Shusaku];B[bb])").unwrap();
+/// assert_eq!(collection.games.len(), 2);
+/// assert_eq!(collection.to_string(), "(;PB[Honinbo];B[aa])(;PB[Shusaku];B[bb])");
+/// ```
+pub fn parse_collection(input: &str) -> Result<Collection, SgfError> {
+    parse_collection_with_options(input, &ParserOptions::default()).map(|outcome| outcome.tree)
+}
+
+/// Parses all game trees in an SGF string like `parse_collection`, using `options`
+///
+/// The options apply to every game like they do for `parse_with_options`, and
+/// `ParseWarning::game` tells which game each warning was found in. Progress is reported
+/// across all games.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let options = ParserOptions::new()
+///     .identifier_case(IdentifierCase::Fold)
+///     .root_tokens(RootTokenPolicy::MoveToRoot);
+/// let outcome =
+///     parse_collection_with_options("(;PB[Honinbo];b[aa])(;PB[Shusaku];sz[9]B[bb])", &options)
+///         .unwrap();
+/// assert_eq!(
+///     outcome.tree.to_string(),
+///     "(;PB[Honinbo];B[aa])(;PB[Shusaku]SZ[9];B[bb])"
+/// );
+/// assert_eq!(outcome.warnings.len(), 2);
+/// assert_eq!(outcome.warnings[1].game, 1);
+/// assert_eq!(outcome.warnings[1].node, 1);
+/// ```
+pub fn parse_collection_with_options(
+    input: &str,
+    options: &ParserOptions,
+) -> Result<ParseOutcome<Collection>, SgfError> {
+    let collection = SGFParser::parse(Rule::collection, input)
+        .map_err(SgfError::parse_error)?
+        .next()
+        .ok_or_else(|| SgfError::from(SgfErrorKind::ParseError))?;
+    let game_trees = collection
+        .into_inner()
+        .filter(|pair| pair.as_rule() != Rule::EOI)
+        .collect::<Vec<_>>();
+    // Each game owns the text up to the start of the next game, for `preserve_format`
+    let ends = game_trees
+        .iter()
+        .skip(1)
+        .map(|pair| pair.as_span().start())
+        .chain(std::iter::once(input.len()))
+        .collect::<Vec<_>>();
+    let mut progress = progress_callback(options);
+    let mut context = ParseContext::new(input, options, &mut progress);
+    let mut games = vec![];
+    for (game_tree, end) in game_trees.into_iter().zip(ends) {
+        context.game = games.len();
+        let source = &input[game_tree.as_span().start()..end];
+        games.push(create_game(game_tree, source, options, &mut context)?);
+    }
+    Ok(ParseOutcome {
+        tree: Collection { games },
+        warnings: context.warnings,
+    })
+}
+
+/// Calls `ParserOptions::progress`, if set
+fn progress_callback(options: &ParserOptions) -> impl FnMut(ParseProgress) + '_ {
+    move |report| {
+        if let Some(progress) = &options.progress {
+            progress(report)
+        }
+    }
+}
+
+/// Creates a game from its Pest result, applying the options that work on whole games
+///
+/// `source` is the text of the game, used to record its format with
+/// `ParserOptions::preserve_format`.
+fn create_game(
+    game_tree: Pair<'_, Rule>,
+    source: &str,
+    options: &ParserOptions,
+    context: &mut ParseContext<'_>,
+) -> Result<GameTree, SgfError> {
+    let tree = parse_pair(game_tree, &mut context.tokens);
+    let mut tree = create_game_tree(tree, true, context)?;
+    if options.root_tokens == RootTokenPolicy::MoveToRoot {
+        lift_root_tokens(&mut tree, &mut vec![]);
+    }
+    if options.preserve_format {
+        preserve::record_source(source, &mut tree)?;
+    }
+    if options.apply_quirks {
+        for quirk in tree.apply_quirks() {
+            context.warnings.push(ParseWarning {
+                game: context.game,
+                variations: vec![],
+                node: 0,
+                kind: ParseWarningKind::AppliedQuirk(quirk),
+            });
+        }
+    }
+    Ok(tree)
+}
+
+/// Parses a single node without surrounding parentheses, like `;B[dd]C[hello]`
+///
+/// Root tokens are allowed, since the fragment might be the root of a game.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let node = parse_node(";B[dd]C[hello]").unwrap();
+/// assert_eq!(node.tokens.len(), 2);
+///
+/// let sgf: String = node.into();
+/// assert_eq!(sgf, ";B[dd]C[hello]");
+/// ```
+pub fn parse_node(input: &str) -> Result<GameNode, SgfError> {
+    let mut nodes = parse_fragment(Rule::node_fragment, input)?;
+    nodes.pop().ok_or_else(|| SgfErrorKind::ParseError.into())
+}
+
+/// Parses a sequence of nodes without surrounding parentheses, like `;B[dd];W[pp]`
+///
+/// Root tokens are allowed in all nodes. Use `serialize_sequence` to convert the nodes back.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let nodes = parse_sequence(";B[dd]\n;W[pp]").unwrap();
+/// assert_eq!(nodes.len(), 2);
+/// assert_eq!(serialize_sequence(&nodes), ";B[dd];W[pp]");
+/// ```
+pub fn parse_sequence(input: &str) -> Result<Vec<GameNode>, SgfError> {
+    parse_fragment(Rule::sequence_fragment, input)
+}
+
+/// Serializes nodes as a sequence without surrounding parentheses, the inverse of
+/// `parse_sequence`
+pub fn serialize_sequence(nodes: &[GameNode]) -> String {
+    nodes.iter().map(String::from).collect()
+}
+
+fn parse_fragment(rule: Rule, input: &str) -> Result<Vec<GameNode>, SgfError> {
+    let fragment = SGFParser::parse(rule, input)
+        .map_err(SgfError::parse_error)?
+        .next()
+        .ok_or_else(|| SgfError::from(SgfErrorKind::ParseError))?;
+    let nodes = fragment
+        .into_inner()
+        .filter(|pair| pair.as_rule() != Rule::EOI)
+        .flat_map(
+            |pair| match parse_pair(pair, &mut TokenFactory::default()) {
+                ParserNode::Sequence(nodes) => nodes,
+                node => vec![node],
+            },
+        )
+        .collect();
+    Ok(create_sequence(nodes, DuplicateMode::Warn)?
+        .into_iter()
+        .map(|(node, _, _)| node)
+        .collect())
+}
+
+/// Progress reported to `ParserOptions::progress`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseProgress {
+    /// Number of nodes created so far
+    pub nodes: usize,
+    /// Offset in the input of the end of the last created node
+    pub bytes: usize,
+    /// Length of the input
+    pub total_bytes: usize,
+}
+
+/// State shared while creating the `GameTree` from the Pest result
+struct ParseContext<'a> {
+    /// Index of the game being created in the collection
+    game: usize,
+    /// Creates the tokens of all games, sharing their text with `ParserOptions::intern_strings`
+    tokens: TokenFactory,
+    variations_path: Vec<usize>,
+    warnings: Vec<ParseWarning>,
+    nodes: usize,
+    total_bytes: usize,
+    duplicates: DuplicateMode,
+    root_tokens: RootTokenPolicy,
+    progress: &'a mut dyn FnMut(ParseProgress),
+}
+
+impl<'a> ParseContext<'a> {
+    fn new(
+        input: &str,
+        options: &ParserOptions,
+        progress: &'a mut dyn FnMut(ParseProgress),
+    ) -> Self {
+        ParseContext {
+            game: 0,
+            tokens: TokenFactory {
+                case: options.identifier_case,
+                interner: options.intern_strings.then(Interner::default),
+            },
+            variations_path: vec![],
+            warnings: vec![],
+            nodes: 0,
+            total_bytes: input.len(),
+            duplicates: options.duplicates,
+            root_tokens: options.root_tokens,
+            progress,
+        }
     }
 }
 
 /// Creates a `GameTree` from the Pest result
-fn create_game_tree(parser_node: ParserNode<'_>, is_root: bool) -> Result<GameTree, SgfError> {
+fn create_game_tree(
+    parser_node: ParserNode<'_>,
+    is_root: bool,
+    context: &mut ParseContext<'_>,
+) -> Result<GameTree, SgfError> {
     if let ParserNode::GameTree(tree_nodes) = parser_node {
         let mut nodes: Vec<GameNode> = vec![];
         let mut variations: Vec<GameTree> = vec![];
         for node in tree_nodes {
             match node {
                 ParserNode::Sequence(sequence_nodes) => {
-                    nodes.extend(parse_sequence(sequence_nodes)?)
+                    for (node, kinds, end) in create_sequence(sequence_nodes, context.duplicates)? {
+                        let (game, path) = (context.game, &context.variations_path);
+                        context
+                            .warnings
+                            .extend(kinds.into_iter().map(|kind| ParseWarning {
+                                game,
+                                variations: path.clone(),
+                                node: nodes.len(),
+                                kind,
+                            }));
+                        nodes.push(node);
+                        context.nodes += 1;
+                        (context.progress)(ParseProgress {
+                            nodes: context.nodes,
+                            bytes: end,
+                            total_bytes: context.total_bytes,
+                        });
+                    }
                 }
                 ParserNode::GameTree(_) => {
-                    variations.push(create_game_tree(node, false)?);
+                    context.variations_path.push(variations.len());
+                    let variation = create_game_tree(node, false, context);
+                    context.variations_path.pop();
+                    variations.push(variation?);
                 }
                 _ => {
                     return Err(SgfErrorKind::ParseError.into());
                 }
             }
         }
-        let mut iter = nodes.iter();
-        if is_root {
-            iter.next();
-        }
-        let in_valid = iter.any(|node| node.tokens.iter().any(|token| token.is_root_token()));
-        if in_valid {
-            Err(SgfErrorKind::InvalidRootTokenPlacement.into())
-        } else {
-            Ok(GameTree { nodes, variations })
+        let skip = if is_root { 1 } else { 0 };
+        for (index, node) in nodes.iter().enumerate().skip(skip) {
+            for token in node.tokens.iter().filter(|token| token.is_root_token()) {
+                match context.root_tokens {
+                    RootTokenPolicy::Error => {
+                        return Err(SgfErrorKind::InvalidRootTokenPlacement.into())
+                    }
+                    RootTokenPolicy::Warn => context.warnings.push(ParseWarning {
+                        game: context.game,
+                        variations: context.variations_path.clone(),
+                        node: index,
+                        kind: ParseWarningKind::MisplacedRootToken(token.identifier().to_string()),
+                    }),
+                    RootTokenPolicy::Ignore | RootTokenPolicy::MoveToRoot => {}
+                }
+            }
         }
+        Ok(GameTree { nodes, variations })
     } else {
         Err(SgfErrorKind::ParseError.into())
     }
 }
 
-/// Parses a sequence of nodes to be added to a `GameTree`
-fn parse_sequence(sequence_nodes: Vec<ParserNode<'_>>) -> Result<Vec<GameNode>, SgfError> {
+/// Creates a sequence of nodes to be added to a `GameTree`, along with the warnings and the end
+/// offset of each node
+fn create_sequence(
+    sequence_nodes: Vec<ParserNode<'_>>,
+    duplicates: DuplicateMode,
+) -> Result<Vec<(GameNode, Vec<ParseWarningKind>, usize)>, SgfError> {
     let mut nodes = vec![];
-    for sequence_node in &sequence_nodes {
-        if let ParserNode::Node(node_tokens) = sequence_node {
+    for sequence_node in sequence_nodes {
+        if let ParserNode::Node(node_tokens, end) = sequence_node {
             let mut tokens: Vec<SgfToken> = vec![];
+            let mut warnings = vec![];
+            let mut repeated: Vec<String> = vec![];
             for t in node_tokens {
-                if let ParserNode::Token(new_tokens) = t {
-                    tokens.extend(new_tokens.clone());
+                if let ParserNode::Token(new_tokens, new_warnings) = t {
+                    if let Some(identifier) = new_tokens.first().map(SgfToken::identifier) {
+                        let seen = tokens.iter().any(|token| token.identifier() == identifier);
+                        if seen && !repeated.iter().any(|r| r == identifier) {
+                            repeated.push(identifier.to_string());
+                        }
+                    }
+                    tokens.extend(new_tokens);
+                    warnings.extend(new_warnings);
                 } else {
                     return Err(SgfErrorKind::ParseError.into());
                 }
             }
-            nodes.push(GameNode { tokens });
+            let mut node = GameNode::from(tokens);
+            match duplicates {
+                DuplicateMode::Warn => {}
+                DuplicateMode::Merge => repeated = node.merge_duplicates(),
+                DuplicateMode::Reject if !repeated.is_empty() => {
+                    return Err(SgfErrorKind::DuplicateIdentifier.into());
+                }
+                DuplicateMode::Reject => {}
+            }
+            warnings.extend(
+                repeated
+                    .into_iter()
+                    .map(ParseWarningKind::DuplicateIdentifier),
+            );
+            nodes.push((node, warnings, end));
         } else {
             return Err(SgfErrorKind::ParseError.into());
         }
@@ -90,22 +444,65 @@ fn parse_sequence(sequence_nodes: Vec<ParserNode<'_>>) -> Result<Vec<GameNode>,
 }
 
 /// Intermediate nodes from parsing the SGF file
-#[derive(Debug, PartialEq, Clone)]
+///
+/// Not `Clone`, so nodes and tokens are always moved into the resulting `GameTree`
+#[derive(Debug, PartialEq)]
 enum ParserNode<'a> {
-    Token(Vec<SgfToken>),
+    Token(Vec<SgfToken>, Vec<ParseWarningKind>),
     Text(&'a str),
-    Node(Vec<ParserNode<'a>>),
+    Node(Vec<ParserNode<'a>>, usize),
     Sequence(Vec<ParserNode<'a>>),
     GameTree(Vec<ParserNode<'a>>),
 }
 
-fn parse_pair(pair: Pair<'_, Rule>) -> ParserNode<'_> {
+/// Creates tokens from property identifiers and values
+#[derive(Default)]
+struct TokenFactory {
+    case: IdentifierCase,
+    interner: Option<Interner>,
+}
+
+impl TokenFactory {
+    fn create(&mut self, identifier: &str, value: &str, index: usize) -> SgfToken {
+        let case = self.case;
+        let create = || from_property_value(identifier, value, index, case);
+        match &mut self.interner {
+            // Obsolete `L` labels depend on the position of the value, so they are not shared
+            Some(interner) if normalize_identifier(identifier) != "L" => {
+                interner.token(identifier, value, create)
+            }
+            _ => create(),
+        }
+    }
+}
+
+fn parse_pair<'a>(pair: Pair<'a, Rule>, factory: &mut TokenFactory) -> ParserNode<'a> {
     match pair.as_rule() {
-        Rule::game_tree => ParserNode::GameTree(pair.into_inner().map(parse_pair).collect()),
-        Rule::sequence => ParserNode::Sequence(pair.into_inner().map(parse_pair).collect()),
-        Rule::node => ParserNode::Node(pair.into_inner().map(parse_pair).collect()),
+        Rule::game_tree => ParserNode::GameTree(
+            pair.into_inner()
+                .map(|pair| parse_pair(pair, factory))
+                .collect(),
+        ),
+        Rule::sequence => ParserNode::Sequence(
+            pair.into_inner()
+                .map(|pair| parse_pair(pair, factory))
+                .collect(),
+        ),
+        Rule::node => {
+            let end = pair.as_span().end();
+            ParserNode::Node(
+                pair.into_inner()
+                    .map(|pair| parse_pair(pair, factory))
+                    .collect(),
+                end,
+            )
+        }
         Rule::property => {
-            let text_nodes = pair.into_inner().map(parse_pair).collect::<Vec<_>>();
+            let text_nodes = pair
+                .into_inner()
+                .map(|pair| parse_pair(pair, factory))
+                .collect::<Vec<_>>();
+            let mut warnings = vec![];
             let (_, ts) = text_nodes
                 .iter()
                 .try_fold((None, vec![]), |(ident, mut tokens), value| {
@@ -113,7 +510,10 @@ fn parse_pair(pair: Pair<'_, Rule>) -> ParserNode<'_> {
                         match ident {
                             None => Some((Some(*value), tokens)),
                             Some(id) => {
-                                tokens.push(SgfToken::from_pair(id, value));
+                                let token = factory.create(id, value, tokens.len());
+                                diagnostics::token_created(id, value, &token);
+                                warnings.extend(property_warnings(id, value, &token));
+                                tokens.push(token);
                                 Some((ident, tokens))
                             }
                         }
@@ -124,7 +524,7 @@ fn parse_pair(pair: Pair<'_, Rule>) -> ParserNode<'_> {
                 .expect(
                     "Pest parsing guarantee that all properties have an identifier and a value",
                 );
-            ParserNode::Token(ts)
+            ParserNode::Token(ts, warnings)
         }
         Rule::property_identifier => ParserNode::Text(pair.as_str()),
         Rule::property_value => {
@@ -132,6 +532,9 @@ fn parse_pair(pair: Pair<'_, Rule>) -> ParserNode<'_> {
             let end = value.len() - 1;
             ParserNode::Text(&value[1..end])
         }
+        Rule::node_fragment | Rule::sequence_fragment | Rule::collection | Rule::EOI => {
+            unreachable!();
+        }
         Rule::inner => {
             unreachable!();
         }
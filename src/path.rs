@@ -0,0 +1,131 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::{GameTree, SgfError};
+
+/// Location of a node in a `GameTree`
+///
+/// `variations` holds the variation index picked at each branch point, starting from the root
+/// sequence, and `node` is the index of the node in the sequence reached that way.
+///
+/// Paths are ordered like the nodes appear in the SGF text, which is a pre-order walk of the
+/// tree: a node comes after its ancestors, and before the nodes of later variations.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let mut paths = vec![
+///     TreePath::new(vec![1], 0),
+///     TreePath::new(vec![0, 1], 0),
+///     TreePath::new(vec![], 2),
+///     TreePath::new(vec![0], 1),
+/// ];
+/// paths.sort();
+/// assert_eq!(
+///     paths,
+///     vec![
+///         TreePath::new(vec![], 2),
+///         TreePath::new(vec![0], 1),
+///         TreePath::new(vec![0, 1], 0),
+///         TreePath::new(vec![1], 0),
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TreePath {
+    pub variations: Vec<usize>,
+    pub node: usize,
+}
+
+impl TreePath {
+    /// Creates a new path
+    pub fn new(variations: Vec<usize>, node: usize) -> Self {
+        TreePath { variations, node }
+    }
+
+    /// Checks if the node at this path comes before the node at `other` on the line to it
+    ///
+    /// A path is not its own ancestor.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let path = TreePath::new(vec![0], 1);
+    /// assert!(path.is_ancestor_of(&TreePath::new(vec![0], 2)));
+    /// assert!(path.is_ancestor_of(&TreePath::new(vec![0, 1], 0)));
+    /// assert!(!path.is_ancestor_of(&TreePath::new(vec![1], 2)));
+    /// assert!(!path.is_ancestor_of(&path));
+    /// ```
+    pub fn is_ancestor_of(&self, other: &TreePath) -> bool {
+        if self.variations == other.variations {
+            self.node < other.node
+        } else {
+            other.variations.starts_with(&self.variations)
+        }
+    }
+}
+
+impl Ord for TreePath {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // The nodes of a sequence come before all of its variations, so comparing the
+        // variations with shorter prefixes first gives the pre-order
+        (&self.variations, self.node).cmp(&(&other.variations, other.node))
+    }
+}
+
+impl PartialOrd for TreePath {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl GameTree {
+    /// Gets the path of the last node shared by the lines to `a` and `b`
+    ///
+    /// Moving from `a` to `b` takes the steps back from `a` to this node, and then the steps
+    /// forward to `b`. Fails with `SgfErrorKind::NodeNotFound` if either node does not exist.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee](;W[cc];B[gg])(;W[gc];B[cg];W[gg]))").unwrap();
+    /// let a = TreePath::new(vec![0], 1);
+    /// let b = TreePath::new(vec![1], 2);
+    /// let common = tree.common_ancestor(&a, &b).unwrap();
+    /// assert_eq!(common, TreePath::new(vec![], 1));
+    ///
+    /// let depth = |path| tree.get_line_to(path).unwrap().len();
+    /// assert_eq!(depth(&a) - depth(&common), 2);
+    /// assert_eq!(depth(&b) - depth(&common), 3);
+    /// ```
+    pub fn common_ancestor(&self, a: &TreePath, b: &TreePath) -> Result<TreePath, SgfError> {
+        self.get_node(a)?;
+        self.get_node(b)?;
+        let shared = a
+            .variations
+            .iter()
+            .zip(b.variations.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let variations = a.variations[..shared].to_vec();
+        let node = match (a.variations.len() == shared, b.variations.len() == shared) {
+            (true, true) => a.node.min(b.node),
+            (true, false) => a.node,
+            (false, true) => b.node,
+            (false, false) => {
+                let sequence = self.get_sequence(&variations)?;
+                sequence.nodes.len().saturating_sub(1)
+            }
+        };
+        Ok(TreePath::new(variations, node))
+    }
+}
+
+impl fmt::Display for TreePath {
+    /// Formats the path as the variation indexes separated by `.`, followed by `:` and the node
+    /// index, like `1.0:2`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let variations: Vec<String> = self.variations.iter().map(|v| v.to_string()).collect();
+        write!(f, "{}:{}", variations.join("."), self.node)
+    }
+}
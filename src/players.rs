@@ -0,0 +1,154 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{Collection, Color, SgfToken};
+
+/// Normalizes a player name for comparisons
+///
+/// Full-width characters are folded to their ASCII forms, the name is lowercased, and runs of
+/// whitespace are collapsed to a single space.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// assert_eq!(normalize_player_name("  Lee   Sedol "), "lee sedol");
+/// assert_eq!(normalize_player_name("ＬＥＥ　Ｓｅｄｏｌ"), "lee sedol");
+/// ```
+pub fn normalize_player_name(name: &str) -> String {
+    let folded: String = name
+        .chars()
+        .map(|c| match c {
+            '\u{3000}' => ' ',
+            '\u{ff01}'..='\u{ff5e}' => {
+                std::char::from_u32(c as u32 - 0xfee0).expect("Folded character is ASCII")
+            }
+            c => c,
+        })
+        .flat_map(char::to_lowercase)
+        .collect();
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Alternative spellings of player names, mapped to a single canonical name
+///
+/// Names are normalized with `normalize_player_name` before they are looked up.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PlayerAliases {
+    aliases: HashMap<String, String>,
+}
+
+impl PlayerAliases {
+    /// Creates an empty alias configuration
+    pub fn new() -> Self {
+        PlayerAliases::default()
+    }
+
+    /// Registers `alias` as another name of `canonical`
+    pub fn add(&mut self, alias: &str, canonical: &str) -> &mut Self {
+        self.aliases.insert(
+            normalize_player_name(alias),
+            normalize_player_name(canonical),
+        );
+        self
+    }
+
+    /// Gets the normalized canonical form of `name`
+    pub fn resolve(&self, name: &str) -> String {
+        let name = normalize_player_name(name);
+        match self.aliases.get(&name) {
+            Some(canonical) => canonical.clone(),
+            None => name,
+        }
+    }
+}
+
+/// A game a player appears in, as an index into the `Collection`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlayerGame {
+    pub game: usize,
+    pub color: Color,
+}
+
+/// Games of a `Collection`, grouped by the normalized names of their players
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PlayerIndex {
+    pub players: BTreeMap<String, Vec<PlayerGame>>,
+    aliases: PlayerAliases,
+}
+
+impl PlayerIndex {
+    /// Gets all games of the player called `name`, in collection order
+    pub fn get(&self, name: &str) -> &[PlayerGame] {
+        self.players
+            .get(&self.aliases.resolve(name))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Gets an iterator over the normalized player names
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.players.keys().map(String::as_str)
+    }
+}
+
+impl Collection {
+    /// Builds an index from player names to the games they appear in
+    ///
+    /// Names are taken from the `PB` and `PW` tokens of each game, and compared without regard
+    /// to case, character width or extra whitespace.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let collection = parse_collection("(;PB[Lee Sedol]PW[Gu Li])(;PB[Gu  Li]PW[LEE SEDOL])").unwrap();
+    /// let index = collection.index_by_player();
+    ///
+    /// let games = index.get("lee sedol");
+    /// assert_eq!(games.len(), 2);
+    /// assert_eq!(games[1], PlayerGame { game: 1, color: Color::White });
+    /// ```
+    pub fn index_by_player(&self) -> PlayerIndex {
+        self.index_by_player_with(&PlayerAliases::new())
+    }
+
+    /// Builds an index from player names to games, merging names according to `aliases`
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut aliases = PlayerAliases::new();
+    /// aliases.add("Yi Se-tol", "Lee Sedol");
+    ///
+    /// let collection = parse_collection("(;PB[Lee Sedol])(;PW[Yi Se-tol])").unwrap();
+    /// let index = collection.index_by_player_with(&aliases);
+    /// assert_eq!(index.get("Yi Se-tol").len(), 2);
+    /// assert_eq!(index.names().collect::<Vec<_>>(), vec!["lee sedol"]);
+    /// ```
+    pub fn index_by_player_with(&self, aliases: &PlayerAliases) -> PlayerIndex {
+        let mut players: BTreeMap<String, Vec<PlayerGame>> = BTreeMap::new();
+        for (game, tree) in self.games.iter().enumerate() {
+            let root = match tree.nodes.first() {
+                Some(root) => root,
+                None => continue,
+            };
+            for token in &root.tokens {
+                if let SgfToken::PlayerName { color, name } = token {
+                    let name = aliases.resolve(name);
+                    if name.is_empty() {
+                        continue;
+                    }
+                    let entry = PlayerGame {
+                        game,
+                        color: *color,
+                    };
+                    let games = players.entry(name).or_default();
+                    if !games.contains(&entry) {
+                        games.push(entry);
+                    }
+                }
+            }
+        }
+        PlayerIndex {
+            players,
+            aliases: aliases.clone(),
+        }
+    }
+}
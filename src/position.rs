@@ -0,0 +1,61 @@
+use crate::turn::next_color;
+use crate::{Board, Color, GameNode, GameTree, SgfError, SgfToken, TokenString};
+
+impl GameTree {
+    /// Creates a single node game with the position at the end of the main variation, set up
+    /// with `AB` and `AW` tokens
+    ///
+    /// The node keeps the root and game information tokens of the root node, and gets a `PL`
+    /// token for the color to play next. Fails with `SgfErrorKind::IllegalMove` if the main
+    /// variation can't be replayed.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree =
+    ///     parse("(;SZ[9]PB[Lee]AB[ee];W[cc]C[Hi];B[ba];W[aa];B[ab](;W[gg])(;W[cg]))").unwrap();
+    /// assert_eq!(
+    ///     tree.final_position_sgf().unwrap().to_string(),
+    ///     "(;AB[ab][ba][ee]AW[cc][gg]PB[Lee]PL[B]SZ[9])"
+    /// );
+    /// ```
+    pub fn final_position_sgf(&self) -> Result<GameTree, SgfError> {
+        let board = Board::replay(self)?;
+        let mut tokens: Vec<SgfToken> = self
+            .nodes
+            .first()
+            .map(|root| {
+                root.tokens
+                    .iter()
+                    .filter(|token| token.is_root_token() || token.is_game_info_token())
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let (width, height) = board.size();
+        for color in [Color::Black, Color::White] {
+            for y in 1..=height {
+                for x in 1..=width {
+                    if board.get((x, y)) == Some(color) {
+                        tokens.push(SgfToken::Add {
+                            color,
+                            coordinate: (x, y),
+                        });
+                    }
+                }
+            }
+        }
+        let player = match next_color(self.iter()) {
+            Color::Black => "B",
+            Color::White => "W",
+        };
+        tokens.push(SgfToken::Unknown((
+            TokenString::from_static("PL"),
+            TokenString::from_static(player),
+        )));
+        Ok(GameTree {
+            nodes: vec![GameNode::from(tokens)],
+            variations: vec![],
+        })
+    }
+}
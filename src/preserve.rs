@@ -0,0 +1,134 @@
+use pest::iterators::Pair;
+use pest::Parser;
+
+use crate::parser::{Rule, SGFParser};
+use crate::{GameNode, GameTree, SgfError, SgfToken};
+
+/// Extras key of the `NodeSource` of a node parsed with `ParserOptions::preserve_format`
+const NODE_SOURCE: &str = "sgf_parser::node_source";
+/// Extras key of the `TreeSource`, stored on the first node of each tree
+const TREE_SOURCE: &str = "sgf_parser::tree_source";
+
+/// The original text of a node, and the tokens it was parsed into
+#[derive(Debug)]
+struct NodeSource {
+    tokens: Vec<SgfToken>,
+    /// Whitespace between the previous node or `(`, and the `;` of this node
+    leading: String,
+    text: String,
+}
+
+/// Whitespace around the parentheses of a tree
+#[derive(Debug)]
+struct TreeSource {
+    /// Whitespace between the previous variation or node, and the `(` of this tree
+    leading: String,
+    /// Whitespace before the `)` of this tree
+    closing: String,
+    /// Whitespace after the `)` of the root tree
+    trailing: String,
+}
+
+/// Stores the original text and whitespace of every node of `tree`, which was parsed from
+/// `input`, in the extras of the nodes
+pub(crate) fn record_source(input: &str, tree: &mut GameTree) -> Result<(), SgfError> {
+    let root = SGFParser::parse(Rule::game_tree, input)
+        .map_err(SgfError::parse_error)?
+        .next();
+    if let Some(root) = root {
+        let trailing = input[root.as_span().end()..].to_string();
+        record_tree(input, root, tree, String::new(), trailing);
+    }
+    Ok(())
+}
+
+fn record_tree(
+    input: &str,
+    pair: Pair<'_, Rule>,
+    tree: &mut GameTree,
+    leading: String,
+    trailing: String,
+) {
+    let span = pair.as_span();
+    let mut cursor = span.start() + 1;
+    let mut nodes = tree.nodes.iter_mut();
+    let mut variations = tree.variations.iter_mut();
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::sequence => {
+                for node_pair in inner.into_inner() {
+                    // Whitespace after the last property is part of the node span, but is
+                    // kept as leading whitespace of whatever follows the node
+                    let node_span = node_pair.as_span();
+                    let text = node_span.as_str().trim_end();
+                    if let Some(node) = nodes.next() {
+                        let source = NodeSource {
+                            tokens: node.tokens.clone(),
+                            leading: input[cursor..node_span.start()].to_string(),
+                            text: text.to_string(),
+                        };
+                        node.extras.insert(NODE_SOURCE, source);
+                    }
+                    cursor = node_span.start() + text.len();
+                }
+            }
+            Rule::game_tree => {
+                let (start, end) = (inner.as_span().start(), inner.as_span().end());
+                if let Some(variation) = variations.next() {
+                    let leading = input[cursor..start].to_string();
+                    record_tree(input, inner, variation, leading, String::new());
+                }
+                cursor = end;
+            }
+            _ => {}
+        }
+    }
+    let source = TreeSource {
+        leading,
+        closing: input[cursor..span.end() - 1].to_string(),
+        trailing,
+    };
+    if let Some(first) = tree.nodes.first_mut() {
+        first.extras.insert(TREE_SOURCE, source);
+    }
+}
+
+/// Writes `tree` with the recorded whitespace, and the original text of every node whose
+/// tokens are unchanged, using `write_node` for all other nodes
+pub(crate) fn write_tree(
+    tree: &GameTree,
+    write_node: &mut dyn FnMut(&GameNode, &mut String),
+    out: &mut String,
+) {
+    let source = tree
+        .nodes
+        .first()
+        .and_then(|node| node.extras.get::<TreeSource>(TREE_SOURCE));
+    if let Some(source) = source {
+        out.push_str(&source.leading);
+    }
+    out.push('(');
+    for node in &tree.nodes {
+        match node.extras.get::<NodeSource>(NODE_SOURCE) {
+            Some(source) if source.tokens == node.tokens => {
+                out.push_str(&source.leading);
+                out.push_str(&source.text);
+            }
+            Some(source) => {
+                out.push_str(&source.leading);
+                write_node(node, out);
+            }
+            None => write_node(node, out),
+        }
+    }
+    for variation in &tree.variations {
+        write_tree(variation, write_node, out);
+    }
+    if let Some(source) = source {
+        out.push_str(&source.closing);
+    }
+    out.push(')');
+    if let Some(source) = source {
+        out.push_str(&source.trailing);
+    }
+}
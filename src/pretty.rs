@@ -0,0 +1,96 @@
+use std::fmt;
+
+use crate::{GameNode, GameTree, SgfToken, TokenCategory};
+
+/// Longest comment snippet shown for a node, in characters
+const SNIPPET_LENGTH: usize = 24;
+
+/// Displays an outline of a `GameTree`, one node per line, with variations indented below the
+/// node they follow
+///
+/// Each node shows its moves, setup stones and annotations, like `N` or `BM`, followed by the
+/// start of its comment on the same line. Other tokens are left out.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let tree: GameTree =
+///     parse("(;SZ[9];B[dc]C[Opening];W[ef](;B[aa]BM[1])(;B[cc]N[Joseki];W[ee]))").unwrap();
+/// assert_eq!(
+///     tree.debug_tree().to_string(),
+///     ";\n;B[dc] \"Opening\"\n;W[ef]\n├─ ;BM[1]B[aa]\n└─ ;B[cc]N[Joseki]\n   ;W[ee]\n"
+/// );
+/// ```
+pub struct DebugTree<'a> {
+    tree: &'a GameTree,
+}
+
+impl GameTree {
+    /// Gets an outline of the nodes and variations of the tree, for debugging
+    pub fn debug_tree(&self) -> DebugTree<'_> {
+        DebugTree { tree: self }
+    }
+}
+
+impl<'a> fmt::Display for DebugTree<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_tree(f, self.tree, "", "")
+    }
+}
+
+/// Writes the nodes of `tree`, using `first` as prefix for the first node, and `rest` for the
+/// remaining lines
+fn write_tree(f: &mut fmt::Formatter<'_>, tree: &GameTree, first: &str, rest: &str) -> fmt::Result {
+    for (index, node) in tree.nodes.iter().enumerate() {
+        let prefix = if index == 0 { first } else { rest };
+        writeln!(f, "{}{}", prefix, outline(node))?;
+    }
+    let count = tree.variations.len();
+    for (index, variation) in tree.variations.iter().enumerate() {
+        let (branch, indent) = if index + 1 == count {
+            ("└─ ", "   ")
+        } else {
+            ("├─ ", "│  ")
+        };
+        let first = format!("{}{}", rest, branch);
+        let rest = format!("{}{}", rest, indent);
+        write_tree(f, variation, &first, &rest)?;
+    }
+    Ok(())
+}
+
+/// Gets the key tokens of `node` as SGF, followed by the start of its comment
+fn outline(node: &GameNode) -> String {
+    let key_tokens: GameNode = node
+        .tokens
+        .iter()
+        .filter(|token| match token {
+            SgfToken::Comment(_) => false,
+            token => matches!(
+                token.category(),
+                TokenCategory::Move | TokenCategory::Setup | TokenCategory::Annotation
+            ),
+        })
+        .cloned()
+        .collect();
+    let mut line = key_tokens.to_string();
+    let comment = node.tokens.iter().find_map(|token| match token {
+        SgfToken::Comment(comment) => Some(comment),
+        _ => None,
+    });
+    if let Some(comment) = comment {
+        line.push_str(&format!(" \"{}\"", snippet(comment)));
+    }
+    line
+}
+
+/// Joins the lines of `comment`, and cuts it at `SNIPPET_LENGTH` characters
+fn snippet(comment: &str) -> String {
+    let text = comment.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= SNIPPET_LENGTH {
+        return text;
+    }
+    let mut cut: String = text.chars().take(SNIPPET_LENGTH - 1).collect();
+    cut.push('…');
+    cut
+}
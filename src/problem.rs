@@ -0,0 +1,157 @@
+use crate::{Collection, Game, GameTree, SgfError, SgfErrorKind, SgfToken};
+
+/// A single problem of a problem set, with the name and comment written as `GN` and `GC`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Problem {
+    pub tree: GameTree,
+    pub name: Option<String>,
+    pub comment: Option<String>,
+}
+
+impl From<GameTree> for Problem {
+    fn from(tree: GameTree) -> Self {
+        Problem {
+            tree,
+            name: None,
+            comment: None,
+        }
+    }
+}
+
+/// Combines problems into a single collection, like the problem set files read by tsumego
+/// applications
+///
+/// Every problem gets its own game, with `GM[1]`, `FF[4]` and a board size in the root node.
+/// Problems without a name, and without a `GN` token, are named after the set and their
+/// 1-based number. Problems without a comment, and without a `GC` token, get the comment of the
+/// set, if any.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let collection = ProblemSetBuilder::new("Life and death")
+///     .comment("Black to live")
+///     .problem(parse("(;SZ[9]AB[aa]AW[bb];B[cc])").unwrap())
+///     .problem(Problem {
+///         name: Some("Bent four".to_string()),
+///         ..Problem::from(parse("(;AB[dd])").unwrap())
+///     })
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     collection.to_string(),
+///     "(;AB[aa]AW[bb]FF[4]GC[Black to live]GM[1]GN[Life and death 1]SZ[9];B[cc])\
+///      (;AB[dd]FF[4]GC[Black to live]GM[1]GN[Bent four]SZ[19])"
+/// );
+///
+/// let problems = collection.split_problems();
+/// assert_eq!(problems[1].name.as_deref(), Some("Bent four"));
+/// assert_eq!(problems[1].tree.to_string(), "(;AB[dd]FF[4]GM[1]SZ[19])");
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProblemSetBuilder {
+    name: String,
+    comment: Option<String>,
+    problems: Vec<Problem>,
+}
+
+impl ProblemSetBuilder {
+    /// Creates an empty problem set named `name`
+    pub fn new(name: impl Into<String>) -> Self {
+        ProblemSetBuilder {
+            name: name.into(),
+            ..ProblemSetBuilder::default()
+        }
+    }
+
+    /// Sets the comment of problems without one
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Adds a problem, from a tree or a `Problem`
+    pub fn problem(mut self, problem: impl Into<Problem>) -> Self {
+        self.problems.push(problem.into());
+        self
+    }
+
+    /// Adds all problems of `problems`
+    pub fn problems<P: Into<Problem>>(mut self, problems: impl IntoIterator<Item = P>) -> Self {
+        self.problems.extend(problems.into_iter().map(Into::into));
+        self
+    }
+
+    /// Creates the collection, failing with `SgfErrorKind::NodeNotFound` if a problem has no
+    /// root node
+    pub fn build(self) -> Result<Collection, SgfError> {
+        let ProblemSetBuilder {
+            name: set_name,
+            comment: set_comment,
+            problems,
+        } = self;
+        let mut games = Vec::with_capacity(problems.len());
+        for (index, problem) in problems.into_iter().enumerate() {
+            let found = take_metadata(problem.tree);
+            let mut tree = found.tree;
+            let name = problem.name.or(found.name);
+            let comment = problem.comment.or(found.comment);
+            let root = tree
+                .nodes
+                .first_mut()
+                .ok_or_else(|| SgfError::from(SgfErrorKind::NodeNotFound))?;
+            if !root.tokens.iter().any(|token| token.identifier() == "GM") {
+                root.tokens.push(SgfToken::Game(Game::Go));
+            }
+            if !root.tokens.iter().any(|token| token.identifier() == "FF") {
+                root.tokens.push(SgfToken::from_pair("FF", "4"));
+            }
+            if !root.tokens.iter().any(|token| token.identifier() == "SZ") {
+                root.tokens.push(SgfToken::Size(19, 19));
+            }
+            let name = name.unwrap_or_else(|| format!("{} {}", set_name, index + 1));
+            root.tokens.push(SgfToken::GameName(name.into()));
+            if let Some(comment) = comment.or_else(|| set_comment.clone()) {
+                root.tokens.push(SgfToken::from_pair("GC", &comment));
+            }
+            games.push(tree);
+        }
+        Ok(Collection::new(games))
+    }
+}
+
+impl Collection {
+    /// Splits a problem set into its problems, taking the `GN` and `GC` tokens out of the root
+    /// nodes
+    ///
+    /// This is the reverse of `ProblemSetBuilder::build`, see there for an example.
+    pub fn split_problems(&self) -> Vec<Problem> {
+        self.games
+            .iter()
+            .map(|game| take_metadata(game.clone()))
+            .collect()
+    }
+}
+
+/// Moves the values of the `GN` and `GC` tokens of the root node to a `Problem`
+fn take_metadata(mut tree: GameTree) -> Problem {
+    let (mut name, mut comment) = (None, None);
+    if let Some(root) = tree.nodes.first_mut() {
+        root.tokens.retain(|token| match token {
+            SgfToken::GameName(value) => {
+                name = Some(value.to_string());
+                false
+            }
+            SgfToken::Unknown((identifier, value)) if identifier == "GC" => {
+                comment = Some(value.to_string());
+                false
+            }
+            _ => true,
+        });
+    }
+    Problem {
+        tree,
+        name,
+        comment,
+    }
+}
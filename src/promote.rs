@@ -0,0 +1,53 @@
+use crate::{GameTree, SgfToken};
+
+impl GameTree {
+    /// Rewrites `SgfToken::Unknown` tokens in all variations using `promote`, and returns the
+    /// number of tokens that were replaced
+    ///
+    /// `promote` gets the identifier and value of each unknown token, and returns the token to
+    /// use instead, or `None` to keep the unknown token.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree = parse("(;SZ[9]XH[2];B[ee]XC[Nice];W[cc]XQ[1])").unwrap();
+    /// let promoted = tree.promote_unknown(|identifier, value| match identifier {
+    ///     "XH" => value.parse().ok().map(SgfToken::Handicap),
+    ///     "XC" => Some(SgfToken::Comment(value.into())),
+    ///     _ => None,
+    /// });
+    /// assert_eq!(promoted, 2);
+    /// assert_eq!(tree.to_string(), "(;HA[2]SZ[9];B[ee]C[Nice];W[cc]XQ[1])");
+    /// ```
+    pub fn promote_unknown<F>(&mut self, mut promote: F) -> usize
+    where
+        F: FnMut(&str, &str) -> Option<SgfToken>,
+    {
+        promote_tree(self, &mut promote)
+    }
+}
+
+fn promote_tree(
+    tree: &mut GameTree,
+    promote: &mut dyn FnMut(&str, &str) -> Option<SgfToken>,
+) -> usize {
+    let mut promoted = 0;
+    for token in tree
+        .nodes
+        .iter_mut()
+        .flat_map(|node| node.tokens.iter_mut())
+    {
+        if let SgfToken::Unknown((identifier, value)) = token {
+            if let Some(replacement) = promote(identifier, value) {
+                *token = replacement;
+                promoted += 1;
+            }
+        }
+    }
+    promoted
+        + tree
+            .variations
+            .iter_mut()
+            .map(|variation| promote_tree(variation, promote))
+            .sum::<usize>()
+}
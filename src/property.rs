@@ -0,0 +1,481 @@
+use crate::{SgfToken, ValueType};
+
+/// Property types defined by the FF[4] specification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertyType {
+    /// Only allowed in nodes with a move
+    Move,
+    /// Only allowed in nodes without a move
+    Setup,
+    /// Only allowed in the root node
+    Root,
+    /// Only allowed once on each path through the tree, usually in the root node
+    GameInfo,
+    /// Allowed anywhere
+    None,
+}
+
+/// How many values a property takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Multiplicity {
+    Single,
+    /// One or more values
+    List,
+    /// Zero or more values
+    EList,
+}
+
+/// Everything known about an SGF property
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PropertyInfo {
+    pub identifier: &'static str,
+    /// Descriptive name, as used in the specification
+    pub name: &'static str,
+    pub property_type: PropertyType,
+    /// Whether the value applies to all following nodes, until it is changed
+    pub inherit: bool,
+}
+
+impl PropertyInfo {
+    /// Gets the value type of the property
+    pub fn value_type(&self) -> ValueType {
+        SgfToken::value_type_of(self.identifier).expect("All properties have a value type")
+    }
+
+    /// Gets how many values the property takes
+    pub fn multiplicity(&self) -> Multiplicity {
+        match self.value_type() {
+            ValueType::List(_) => Multiplicity::List,
+            ValueType::EList(_) => Multiplicity::EList,
+            _ => Multiplicity::Single,
+        }
+    }
+}
+
+/// All FF[4] properties, including the Go specific ones
+const PROPERTIES: &[PropertyInfo] = &[
+    PropertyInfo {
+        identifier: "B",
+        name: "Black move",
+        property_type: PropertyType::Move,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "KO",
+        name: "Ko",
+        property_type: PropertyType::Move,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "MN",
+        name: "Move number",
+        property_type: PropertyType::Move,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "W",
+        name: "White move",
+        property_type: PropertyType::Move,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "BM",
+        name: "Bad move",
+        property_type: PropertyType::Move,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "DO",
+        name: "Doubtful",
+        property_type: PropertyType::Move,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "IT",
+        name: "Interesting",
+        property_type: PropertyType::Move,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "TE",
+        name: "Tesuji",
+        property_type: PropertyType::Move,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "BL",
+        name: "Black time left",
+        property_type: PropertyType::Move,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "WL",
+        name: "White time left",
+        property_type: PropertyType::Move,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "OB",
+        name: "Black overtime stones",
+        property_type: PropertyType::Move,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "OW",
+        name: "White overtime stones",
+        property_type: PropertyType::Move,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "AB",
+        name: "Add black",
+        property_type: PropertyType::Setup,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "AE",
+        name: "Add empty",
+        property_type: PropertyType::Setup,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "AW",
+        name: "Add white",
+        property_type: PropertyType::Setup,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "PL",
+        name: "Player to play",
+        property_type: PropertyType::Setup,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "AP",
+        name: "Application",
+        property_type: PropertyType::Root,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "CA",
+        name: "Charset",
+        property_type: PropertyType::Root,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "FF",
+        name: "File format",
+        property_type: PropertyType::Root,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "GM",
+        name: "Game",
+        property_type: PropertyType::Root,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "ST",
+        name: "Style",
+        property_type: PropertyType::Root,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "SZ",
+        name: "Size",
+        property_type: PropertyType::Root,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "AN",
+        name: "Annotator",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "BR",
+        name: "Black rank",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "BT",
+        name: "Black team",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "CP",
+        name: "Copyright",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "DT",
+        name: "Date",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "EV",
+        name: "Event",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "GN",
+        name: "Game name",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "GC",
+        name: "Game comment",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "HA",
+        name: "Handicap",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "KM",
+        name: "Komi",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "ON",
+        name: "Opening",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "OT",
+        name: "Overtime",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "PB",
+        name: "Black player",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "PC",
+        name: "Place",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "PW",
+        name: "White player",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "RE",
+        name: "Result",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "RO",
+        name: "Round",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "RU",
+        name: "Rules",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "SO",
+        name: "Source",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "TM",
+        name: "Time limit",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "US",
+        name: "User",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "WR",
+        name: "White rank",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "WT",
+        name: "White team",
+        property_type: PropertyType::GameInfo,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "C",
+        name: "Comment",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "DM",
+        name: "Even position",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "GB",
+        name: "Good for black",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "GW",
+        name: "Good for white",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "HO",
+        name: "Hotspot",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "N",
+        name: "Node name",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "UC",
+        name: "Unclear position",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "V",
+        name: "Value",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "AR",
+        name: "Arrow",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "CR",
+        name: "Circle",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "DD",
+        name: "Dim points",
+        property_type: PropertyType::None,
+        inherit: true,
+    },
+    PropertyInfo {
+        identifier: "LB",
+        name: "Label",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "LN",
+        name: "Line",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "MA",
+        name: "Mark",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "SL",
+        name: "Selected",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "SQ",
+        name: "Square",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "TR",
+        name: "Triangle",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "FG",
+        name: "Figure",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "PM",
+        name: "Print move mode",
+        property_type: PropertyType::None,
+        inherit: true,
+    },
+    PropertyInfo {
+        identifier: "VW",
+        name: "View",
+        property_type: PropertyType::None,
+        inherit: true,
+    },
+    PropertyInfo {
+        identifier: "TB",
+        name: "Black territory",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+    PropertyInfo {
+        identifier: "TW",
+        name: "White territory",
+        property_type: PropertyType::None,
+        inherit: false,
+    },
+];
+
+/// Gets all properties known by this crate
+pub fn properties() -> &'static [PropertyInfo] {
+    PROPERTIES
+}
+
+/// Looks up a property by identifier
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let info = property_info("AB").unwrap();
+/// assert_eq!(info.name, "Add black");
+/// assert_eq!(info.property_type, PropertyType::Setup);
+/// assert_eq!(info.multiplicity(), Multiplicity::List);
+/// assert_eq!(info.value_type(), ValueType::List(Box::new(ValueType::Stone)));
+///
+/// assert_eq!(property_info("XX"), None);
+/// ```
+pub fn property_info(identifier: &str) -> Option<&'static PropertyInfo> {
+    PROPERTIES.iter().find(|info| info.identifier == identifier)
+}
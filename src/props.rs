@@ -0,0 +1,313 @@
+//! Marker types for each SGF property, used with `FindToken` and the `token!` macro
+//!
+//! ```rust
+//! use sgf_parser::*;
+//!
+//! let tree: GameTree = parse("(;KM[6.5]PB[Shusaku]AB[dd][pp];B[qd])").unwrap();
+//! assert_eq!(tree.first::<props::Komi>(), Some(6.5));
+//! assert_eq!(token!(tree, BlackName).unwrap(), "Shusaku");
+//! assert_eq!(tree.nodes[0].all::<props::AddBlack>(), vec![(4, 4), (16, 16)]);
+//! assert!(tree.nodes[1].has::<props::BlackMove>());
+//! ```
+use crate::{
+    Action, Color, DisplayNodes, Emphasis, Encoding, Game as GameType, GameNode, GameTree, Outcome,
+    Rank, RuleSet, SgfToken, TokenString,
+};
+
+/// A property that can be extracted from an `SgfToken`
+pub trait Property {
+    /// Value held by the property
+    type Value;
+
+    /// Gets the value of `token`, if it is this property
+    fn extract(token: &SgfToken) -> Option<Self::Value>;
+}
+
+/// Typed lookups of properties, using the marker types of this module
+pub trait FindToken {
+    /// Gets the tokens searched by the lookups
+    fn tokens(&self) -> &[SgfToken];
+
+    /// Gets the value of the first token of property `P`
+    fn first<P: Property>(&self) -> Option<P::Value> {
+        self.tokens().iter().find_map(P::extract)
+    }
+
+    /// Gets the values of all tokens of property `P`
+    fn all<P: Property>(&self) -> Vec<P::Value> {
+        self.tokens().iter().filter_map(P::extract).collect()
+    }
+
+    /// Checks if there is a token of property `P`
+    fn has<P: Property>(&self) -> bool {
+        self.tokens()
+            .iter()
+            .any(|token| P::extract(token).is_some())
+    }
+}
+
+impl FindToken for GameNode {
+    fn tokens(&self) -> &[SgfToken] {
+        &self.tokens
+    }
+}
+
+/// Looks in the root node, where the game info properties are stored
+impl FindToken for GameTree {
+    fn tokens(&self) -> &[SgfToken] {
+        self.nodes
+            .first()
+            .map_or(&[], |node| node.tokens.as_slice())
+    }
+}
+
+/// Gets the first value of a property from a node or tree
+///
+/// `token!(source, Komi)` is short for `source.first::<props::Komi>()`.
+#[macro_export]
+macro_rules! token {
+    ($source:expr, $property:ident) => {{
+        use $crate::FindToken as _;
+        ($source).first::<$crate::props::$property>()
+    }};
+}
+
+macro_rules! property {
+    ($(#[$doc:meta])* $name:ident: $value:ty, $pattern:pat => $extract:expr) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $name;
+
+        impl Property for $name {
+            type Value = $value;
+
+            fn extract(token: &SgfToken) -> Option<$value> {
+                match token {
+                    $pattern => Some($extract),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+property!(
+    /// `B` or `W`
+    Move: (Color, Action),
+    SgfToken::Move { color, action } => (*color, *action)
+);
+property!(
+    /// `B`
+    BlackMove: Action,
+    SgfToken::Move { color: Color::Black, action } => *action
+);
+property!(
+    /// `W`
+    WhiteMove: Action,
+    SgfToken::Move { color: Color::White, action } => *action
+);
+property!(
+    /// `AB` or `AW`
+    Add: (Color, (u8, u8)),
+    SgfToken::Add { color, coordinate } => (*color, *coordinate)
+);
+property!(
+    /// `AB`
+    AddBlack: (u8, u8),
+    SgfToken::Add { color: Color::Black, coordinate } => *coordinate
+);
+property!(
+    /// `AW`
+    AddWhite: (u8, u8),
+    SgfToken::Add { color: Color::White, coordinate } => *coordinate
+);
+property!(
+    /// `BL`
+    BlackTime: u32,
+    SgfToken::Time { color: Color::Black, time } => *time
+);
+property!(
+    /// `WL`
+    WhiteTime: u32,
+    SgfToken::Time { color: Color::White, time } => *time
+);
+property!(
+    /// `OB`
+    BlackMovesRemaining: u32,
+    SgfToken::MovesRemaining { color: Color::Black, moves } => *moves
+);
+property!(
+    /// `OW`
+    WhiteMovesRemaining: u32,
+    SgfToken::MovesRemaining { color: Color::White, moves } => *moves
+);
+property!(
+    /// `PB`
+    BlackName: TokenString,
+    SgfToken::PlayerName { color: Color::Black, name } => name.clone()
+);
+property!(
+    /// `PW`
+    WhiteName: TokenString,
+    SgfToken::PlayerName { color: Color::White, name } => name.clone()
+);
+property!(
+    /// `BR`
+    BlackRank: Rank,
+    SgfToken::PlayerRank { color: Color::Black, rank } => Rank::from(rank.as_str())
+);
+property!(
+    /// `WR`
+    WhiteRank: Rank,
+    SgfToken::PlayerRank { color: Color::White, rank } => Rank::from(rank.as_str())
+);
+property!(
+    /// `GM`
+    Game: GameType,
+    SgfToken::Game(game) => *game
+);
+property!(
+    /// `RU`
+    Rules: RuleSet,
+    SgfToken::Rule(rules) => rules.clone()
+);
+property!(
+    /// `RE`
+    Result: Outcome,
+    SgfToken::Result(outcome) => *outcome
+);
+property!(
+    /// `KM`
+    Komi: f32,
+    SgfToken::Komi(komi) => komi.get()
+);
+property!(
+    /// `EV`
+    Event: TokenString,
+    SgfToken::Event(value) => value.clone()
+);
+property!(
+    /// `CP`
+    Copyright: TokenString,
+    SgfToken::Copyright(value) => value.clone()
+);
+property!(
+    /// `GN`
+    GameName: TokenString,
+    SgfToken::GameName(value) => value.clone()
+);
+property!(
+    /// `ST`
+    VariationDisplay: (DisplayNodes, bool),
+    SgfToken::VariationDisplay { nodes, on_board_display } => (*nodes, *on_board_display)
+);
+property!(
+    /// `PC`
+    Place: TokenString,
+    SgfToken::Place(value) => value.clone()
+);
+property!(
+    /// `DT`
+    Date: TokenString,
+    SgfToken::Date(value) => value.clone()
+);
+property!(
+    /// `SZ`, as `(width, height)`
+    Size: (u32, u32),
+    SgfToken::Size(width, height) => (*width, *height)
+);
+property!(
+    /// `FF`
+    FileFormat: u8,
+    SgfToken::FileFormat(format) => *format
+);
+property!(
+    /// `OT`
+    Overtime: TokenString,
+    SgfToken::Overtime(value) => value.clone()
+);
+property!(
+    /// `TM`
+    TimeLimit: u32,
+    SgfToken::TimeLimit(time) => *time
+);
+property!(
+    /// `HA`
+    Handicap: u32,
+    SgfToken::Handicap(handicap) => *handicap
+);
+property!(
+    /// `C`
+    Comment: TokenString,
+    SgfToken::Comment(value) => value.clone()
+);
+property!(
+    /// `N`
+    NodeName: TokenString,
+    SgfToken::NodeName(value) => value.clone()
+);
+property!(
+    /// `CA`
+    Charset: Encoding,
+    SgfToken::Charset(encoding) => encoding.clone()
+);
+property!(
+    /// `AP`, as `(name, version)`
+    Application: (TokenString, TokenString),
+    SgfToken::Application { name, version } => (name.clone(), version.clone())
+);
+property!(
+    /// `SQ`
+    Square: (u8, u8),
+    SgfToken::Square { coordinate } => *coordinate
+);
+property!(
+    /// `CR`
+    Circle: (u8, u8),
+    SgfToken::Circle { coordinate } => *coordinate
+);
+property!(
+    /// `TR`
+    Triangle: (u8, u8),
+    SgfToken::Triangle { coordinate } => *coordinate
+);
+property!(
+    /// `LB`, as `(coordinate, label)`
+    Label: ((u8, u8), TokenString),
+    SgfToken::Label { coordinate, label } => (*coordinate, label.clone())
+);
+property!(
+    /// `DM`
+    Even: Emphasis,
+    SgfToken::Even(emphasis) => *emphasis
+);
+property!(
+    /// `GB`
+    GoodForBlack: Emphasis,
+    SgfToken::GoodFor { color: Color::Black, emphasis } => *emphasis
+);
+property!(
+    /// `GW`
+    GoodForWhite: Emphasis,
+    SgfToken::GoodFor { color: Color::White, emphasis } => *emphasis
+);
+property!(
+    /// `HO`
+    Hotspot: Emphasis,
+    SgfToken::Hotspot(emphasis) => *emphasis
+);
+property!(
+    /// `UC`
+    Unclear: Emphasis,
+    SgfToken::Unclear(emphasis) => *emphasis
+);
+property!(
+    /// `BM`
+    BadMove: Emphasis,
+    SgfToken::BadMove(emphasis) => *emphasis
+);
+property!(
+    /// `TE`
+    Tesuji: Emphasis,
+    SgfToken::Tesuji(emphasis) => *emphasis
+);
@@ -0,0 +1,134 @@
+use std::ops::{Bound, RangeBounds};
+
+use crate::{normalize_player_name, Collection, Color, GameTree, SgfToken};
+
+/// Filter over the game information of the games in a `Collection`
+///
+/// All conditions added to the query have to match. Player names are compared with
+/// `normalize_player_name`, and dates are compared as `YYYY-MM-DD` strings using the first date
+/// of the `DT` token.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let collection = parse_collection(
+///     "(;SZ[19]DT[2016-03-09]PB[Lee Sedol]RE[W+R])\
+///      (;SZ[19]DT[2016-03-13]PW[Lee Sedol]RE[B+R])\
+///      (;SZ[9]DT[2016-03-14]PB[Lee Sedol]RE[B+R])",
+/// )
+/// .unwrap();
+///
+/// let games = Query::new()
+///     .player("lee sedol")
+///     .result_winner(Color::Black)
+///     .board_size(19)
+///     .date_range("2016-03-10"..)
+///     .run(&collection);
+/// assert_eq!(games, vec![&collection.games[1]]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Query {
+    players: Vec<(Option<Color>, String)>,
+    winner: Option<Color>,
+    size: Option<(u32, u32)>,
+    dates: Option<(Bound<String>, Bound<String>)>,
+}
+
+impl Query {
+    /// Creates a query matching all games
+    pub fn new() -> Self {
+        Query::default()
+    }
+
+    /// Only matches games where `name` played either color
+    pub fn player(mut self, name: &str) -> Self {
+        self.players.push((None, normalize_player_name(name)));
+        self
+    }
+
+    /// Only matches games where `name` played `color`
+    pub fn player_with_color(mut self, name: &str, color: Color) -> Self {
+        self.players
+            .push((Some(color), normalize_player_name(name)));
+        self
+    }
+
+    /// Only matches games won by `color`
+    pub fn result_winner(mut self, color: Color) -> Self {
+        self.winner = Some(color);
+        self
+    }
+
+    /// Only matches games on a square board of the given size
+    pub fn board_size(self, size: u32) -> Self {
+        self.board_dimensions(size, size)
+    }
+
+    /// Only matches games on a board with the given dimensions
+    pub fn board_dimensions(mut self, width: u32, height: u32) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Only matches games played within `range`, given as `YYYY-MM-DD` strings
+    pub fn date_range<S: AsRef<str>, R: RangeBounds<S>>(mut self, range: R) -> Self {
+        let owned = |bound: Bound<&S>| match bound {
+            Bound::Included(date) => Bound::Included(date.as_ref().to_string()),
+            Bound::Excluded(date) => Bound::Excluded(date.as_ref().to_string()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        self.dates = Some((owned(range.start_bound()), owned(range.end_bound())));
+        self
+    }
+
+    /// Checks if `tree` matches all conditions of the query
+    pub fn matches(&self, tree: &GameTree) -> bool {
+        let tokens = match tree.nodes.first() {
+            Some(root) => &root.tokens[..],
+            None => &[],
+        };
+        let players_match = self.players.iter().all(|(wanted_color, wanted_name)| {
+            tokens.iter().any(|token| match token {
+                SgfToken::PlayerName { color, name } => {
+                    (wanted_color.is_none() || *wanted_color == Some(*color))
+                        && normalize_player_name(name) == *wanted_name
+                }
+                _ => false,
+            })
+        });
+        if !players_match {
+            return false;
+        }
+        if let Some(winner) = self.winner {
+            let won = tokens.iter().any(|token| match token {
+                SgfToken::Result(outcome) => outcome.get_winner() == Some(winner),
+                _ => false,
+            });
+            if !won {
+                return false;
+            }
+        }
+        if self.size.is_some() && self.size != tree.board_size() {
+            return false;
+        }
+        if let Some(range) = &self.dates {
+            let date = tokens.iter().find_map(|token| match token {
+                SgfToken::Date(date) => date.split(',').next().map(str::trim),
+                _ => None,
+            });
+            match date {
+                Some(date) if range.contains(&date.to_string()) => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// Gets all games of `collection` matching the query, in collection order
+    pub fn run<'a>(&self, collection: &'a Collection) -> Vec<&'a GameTree> {
+        collection
+            .iter()
+            .filter(|tree| self.matches(tree))
+            .collect()
+    }
+}
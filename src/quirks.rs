@@ -0,0 +1,101 @@
+use crate::{Dialect, GameTree, RepairOptions, SgfToken};
+
+/// A known deviation from the SGF specification in files written by an application
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Quirk {
+    /// Passes are written as `tt`, as in FF[3], fixed by converting them to passes on boards up
+    /// to 19x19
+    TtPasses,
+    /// The `CA` token names the charset of the system, rather than the encoding of the file,
+    /// fixed by setting it to UTF-8, which is the encoding of all parsed text
+    MislabeledCharset,
+    /// Game information is written like Fox Weiqi does, fixed with `Dialect::Fox`
+    FoxDialect,
+    /// Game information is written like Tygem does, fixed with `Dialect::Tygem`
+    TygemDialect,
+}
+
+/// Applications with known quirks, by lowercase `AP` name
+const APPLICATIONS: [(&str, &[Quirk]); 7] = [
+    ("cgoban", &[Quirk::TtPasses]),
+    ("cgoban2", &[Quirk::TtPasses]),
+    ("multigo", &[Quirk::MislabeledCharset]),
+    ("foxwq", &[Quirk::FoxDialect]),
+    ("fox", &[Quirk::FoxDialect]),
+    ("野狐围棋", &[Quirk::FoxDialect]),
+    ("tygem", &[Quirk::TygemDialect]),
+];
+
+impl Quirk {
+    /// Gets the known quirks of the application named `name` in an `AP` token
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// assert_eq!(Quirk::for_application("CGoban"), vec![Quirk::TtPasses]);
+    /// assert!(Quirk::for_application("Sabaki").is_empty());
+    /// ```
+    pub fn for_application(name: &str) -> Vec<Quirk> {
+        let name = name.trim().to_lowercase();
+        APPLICATIONS
+            .iter()
+            .find(|(application, _)| *application == name)
+            .map(|(_, quirks)| quirks.to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Applies the fix for the quirk, and returns true if the tree was changed
+    pub fn apply(self, tree: &mut GameTree) -> bool {
+        match self {
+            Quirk::TtPasses | Quirk::MislabeledCharset => {
+                let options = RepairOptions {
+                    reparse_invalid: false,
+                    lift_root_tokens: false,
+                    merge_duplicates: false,
+                    normalize_charset: self == Quirk::MislabeledCharset,
+                    convert_tt_passes: self == Quirk::TtPasses,
+                    split_setup_moves: false,
+                };
+                let repaired = tree.repair(&options);
+                let changed = !repaired.is_unchanged();
+                *tree = repaired.tree;
+                changed
+            }
+            Quirk::FoxDialect => tree.normalize_dialect(Dialect::Fox) > 0,
+            Quirk::TygemDialect => tree.normalize_dialect(Dialect::Tygem) > 0,
+        }
+    }
+}
+
+impl GameTree {
+    /// Gets the known quirks of the application named by the `AP` token of the root node
+    pub fn application_quirks(&self) -> Vec<Quirk> {
+        self.nodes
+            .first()
+            .and_then(|root| {
+                root.tokens.iter().find_map(|token| match token {
+                    SgfToken::Application { name, .. } => Some(Quirk::for_application(name)),
+                    _ => None,
+                })
+            })
+            .unwrap_or_default()
+    }
+
+    /// Applies the fixes for `application_quirks`, and returns the quirks that changed the tree
+    ///
+    /// This is done while parsing with `ParserOptions::apply_quirks`.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree = parse("(;AP[CGoban:1.6.2]SZ[19];B[pd];W[tt])").unwrap();
+    /// assert_eq!(tree.apply_quirks(), vec![Quirk::TtPasses]);
+    /// assert_eq!(tree.to_string(), "(;AP[CGoban:1.6.2]SZ[19];B[pd];W[])");
+    /// ```
+    pub fn apply_quirks(&mut self) -> Vec<Quirk> {
+        self.application_quirks()
+            .into_iter()
+            .filter(|quirk| quirk.apply(self))
+            .collect()
+    }
+}
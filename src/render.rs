@@ -0,0 +1,6 @@
+//! Image renderers for board positions
+//!
+//! Each renderer is behind a feature flag of the same name.
+
+#[cfg(feature = "svg")]
+pub mod svg;
@@ -0,0 +1,273 @@
+//! SVG rendering of board positions
+//!
+//! ```rust
+//! use sgf_parser::*;
+//!
+//! let tree: GameTree = parse("(;SZ[9];B[cc];W[gg]TR[cc])").unwrap();
+//! let svg = render::svg::render(&tree, &TreePath::new(vec![], 2)).unwrap();
+//! assert!(svg.starts_with("<svg"));
+//! assert!(svg.contains("<polygon"));
+//! ```
+
+use std::fmt::Write;
+
+use crate::board::star_points;
+use crate::{Action, Board, Color, GameTree, SgfError, SgfToken, TreePath};
+
+/// Options for `render_with`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SvgOptions {
+    /// Distance between two lines of the board, in pixels
+    pub cell_size: u32,
+    /// Draw the `TR`, `SQ`, `CR` and `LB` marks of the rendered node
+    pub marks: bool,
+    /// Mark the stone placed by the last move
+    pub highlight_last_move: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            cell_size: 24,
+            marks: true,
+            highlight_last_move: true,
+        }
+    }
+}
+
+/// A mark drawn on top of a point
+#[derive(Debug, Clone, PartialEq)]
+enum Mark {
+    Triangle,
+    Square,
+    Circle,
+    Label(String),
+}
+
+const BOARD_COLOR: &str = "#dcb35c";
+
+/// Renders the position at the node at `path`, with the default options
+pub fn render(tree: &GameTree, path: &TreePath) -> Result<String, SgfError> {
+    render_with(tree, path, &SvgOptions::default())
+}
+
+/// Renders the position at the node at `path`
+///
+/// The position is found by replaying all nodes from the root.
+///
+/// ```rust
+/// use sgf_parser::*;
+/// use sgf_parser::render::svg::{render_with, SvgOptions};
+///
+/// let tree: GameTree = parse("(;SZ[5];B[aa]LB[bb:A])").unwrap();
+/// let options = SvgOptions { cell_size: 10, ..SvgOptions::default() };
+/// let svg = render_with(&tree, &TreePath::new(vec![], 1), &options).unwrap();
+/// assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"60\" height=\"60\""));
+/// assert!(svg.contains(">A</text>"));
+/// ```
+pub fn render_with(
+    tree: &GameTree,
+    path: &TreePath,
+    options: &SvgOptions,
+) -> Result<String, SgfError> {
+    let line = tree.get_line_to(path)?;
+    let mut board = Board::for_tree(tree)?;
+    let mut last_move = None;
+    for node in &line {
+        board.apply_node(node)?;
+        for token in &node.tokens {
+            if let SgfToken::Move { action, .. } = token {
+                last_move = match action {
+                    Action::Move(x, y) => Some((*x, *y)),
+                    Action::Pass => None,
+                };
+            }
+        }
+    }
+    let mut marks = vec![];
+    if options.marks {
+        if let Some(node) = line.last() {
+            for token in &node.tokens {
+                match token {
+                    SgfToken::Triangle { coordinate } => marks.push((*coordinate, Mark::Triangle)),
+                    SgfToken::Square { coordinate } => marks.push((*coordinate, Mark::Square)),
+                    SgfToken::Label { label, coordinate } => {
+                        marks.push((*coordinate, Mark::Label(label.to_string())))
+                    }
+                    SgfToken::Circle { coordinate } => marks.push((*coordinate, Mark::Circle)),
+                    _ => {}
+                }
+            }
+        }
+    }
+    if !options.highlight_last_move {
+        last_move = None;
+    }
+    Ok(render_board(&board, &marks, last_move, options.cell_size))
+}
+
+fn render_board(
+    board: &Board,
+    marks: &[((u8, u8), Mark)],
+    last_move: Option<(u8, u8)>,
+    cell_size: u32,
+) -> String {
+    let (width, height) = board.size();
+    let cell = cell_size as f32;
+    let position = |(x, y): (u8, u8)| (cell * f32::from(x), cell * f32::from(y));
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">",
+        w = cell * (f32::from(width) + 1.0),
+        h = cell * (f32::from(height) + 1.0),
+    );
+    let _ = write!(
+        svg,
+        "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+        BOARD_COLOR
+    );
+
+    for x in 1..=width {
+        let (start, end) = (position((x, 1)), position((x, height)));
+        let _ = write!(
+            svg,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"black\"/>",
+            start.0, start.1, end.0, end.1
+        );
+    }
+    for y in 1..=height {
+        let (start, end) = (position((1, y)), position((width, y)));
+        let _ = write!(
+            svg,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"black\"/>",
+            start.0, start.1, end.0, end.1
+        );
+    }
+    for point in star_points(width, height) {
+        let (cx, cy) = position(point);
+        let _ = write!(
+            svg,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"black\"/>",
+            cx,
+            cy,
+            cell * 0.1
+        );
+    }
+
+    for y in 1..=height {
+        for x in 1..=width {
+            if let Some(color) = board.get((x, y)) {
+                let (cx, cy) = position((x, y));
+                let _ = write!(
+                    svg,
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\" stroke=\"black\"/>",
+                    cx,
+                    cy,
+                    cell * 0.48,
+                    color_name(color)
+                );
+            }
+        }
+    }
+
+    for (point, mark) in marks {
+        let (cx, cy) = position(*point);
+        let stroke = contrast(board.get(*point));
+        let size = cell * 0.3;
+        match mark {
+            Mark::Triangle => {
+                let _ = write!(
+                    svg,
+                    "<polygon points=\"{},{} {},{} {},{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+                    cx,
+                    cy - size,
+                    cx - size * 0.866,
+                    cy + size * 0.5,
+                    cx + size * 0.866,
+                    cy + size * 0.5,
+                    stroke
+                );
+            }
+            Mark::Square => {
+                let _ = write!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+                    cx - size * 0.75,
+                    cy - size * 0.75,
+                    size * 1.5,
+                    size * 1.5,
+                    stroke
+                );
+            }
+            Mark::Circle => {
+                let _ = write!(
+                    svg,
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+                    cx, cy, size, stroke
+                );
+            }
+            Mark::Label(label) => {
+                if board.get(*point).is_none() {
+                    let _ = write!(
+                        svg,
+                        "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>",
+                        cx,
+                        cy,
+                        cell * 0.4,
+                        BOARD_COLOR
+                    );
+                }
+                let _ = write!(
+                    svg,
+                    "<text x=\"{}\" y=\"{}\" font-size=\"{}\" font-family=\"sans-serif\" \
+                     text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"{}\">{}</text>",
+                    cx,
+                    cy,
+                    cell * 0.6,
+                    stroke,
+                    escape(label)
+                );
+            }
+        }
+    }
+
+    if let Some(point) = last_move {
+        if board.get(point).is_some() && !marks.iter().any(|(marked, _)| *marked == point) {
+            let (cx, cy) = position(point);
+            let _ = write!(
+                svg,
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+                cx,
+                cy,
+                cell * 0.25,
+                contrast(board.get(point))
+            );
+        }
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::Black => "black",
+        Color::White => "white",
+    }
+}
+
+/// Color that stands out on top of the point
+fn contrast(stone: Option<Color>) -> &'static str {
+    match stone {
+        Some(Color::Black) => "white",
+        _ => "black",
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,228 @@
+use crate::{Encoding, GameNode, GameTree, ReparseHints, SgfToken, TreePath};
+
+/// Selects which fixes `GameTree::repair` applies
+///
+/// All fixes are enabled by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RepairOptions {
+    /// Read invalid tokens again with relaxed rules, using `SgfToken::try_reparse`
+    pub reparse_invalid: bool,
+    /// Move root tokens like `SZ` and `CA` found outside the root node to the root node
+    pub lift_root_tokens: bool,
+    /// Merge tokens sharing an identifier, using `GameNode::merge_duplicates`
+    pub merge_duplicates: bool,
+    /// Replace the `CA` token with UTF-8, which is the encoding of all parsed text
+    pub normalize_charset: bool,
+    /// Convert moves to `tt` into passes on boards up to 19x19
+    pub convert_tt_passes: bool,
+    /// Split nodes holding both setup tokens and moves, using `GameTree::split_setup_moves`
+    pub split_setup_moves: bool,
+}
+
+impl Default for RepairOptions {
+    fn default() -> Self {
+        RepairOptions {
+            reparse_invalid: true,
+            lift_root_tokens: true,
+            merge_duplicates: true,
+            normalize_charset: true,
+            convert_tt_passes: true,
+            split_setup_moves: true,
+        }
+    }
+}
+
+/// A single change made by `GameTree::repair`
+///
+/// Paths point to nodes of the tree passed to `repair`, since only splitting nodes changes the
+/// shape of the tree, and it is done last.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Repair {
+    /// An invalid token was replaced by the token read from its fixed value
+    ReparsedToken { path: TreePath, identifier: String },
+    /// A root token was moved from the node to the root node
+    LiftedRootToken { path: TreePath, identifier: String },
+    /// A root token was removed from the node, since the root node already had it
+    DroppedRootToken { path: TreePath, identifier: String },
+    /// Tokens sharing an identifier were merged, dropping the extra values of the properties
+    /// in `dropped`
+    MergedDuplicates {
+        path: TreePath,
+        dropped: Vec<String>,
+    },
+    /// The `CA` token naming this charset was replaced with UTF-8
+    NormalizedCharset(String),
+    /// A move to `tt` was converted into a pass
+    ConvertedPass(TreePath),
+    /// The node was split into a setup node and a move node
+    SplitSetup(TreePath),
+}
+
+/// The result of `GameTree::repair`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repaired {
+    pub tree: GameTree,
+    /// Changes made, in the order they were applied
+    pub changes: Vec<Repair>,
+}
+
+impl Repaired {
+    /// Checks if the tree needed no repairs
+    pub fn is_unchanged(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl GameTree {
+    /// Applies the fixes selected in `options` to a copy of the tree, and returns it with a log
+    /// of the changes
+    ///
+    /// The fixes are applied in the order of the fields of `RepairOptions`. Repairing a
+    /// repaired tree again makes no further changes. Root tokens outside the root node are
+    /// rejected by the parser, but can be found in trees built or edited in code.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree = parse("(;CA[Latin1];AB[cc]B[ee]B[ee];W[tt])").unwrap();
+    /// tree.nodes[1].tokens.push(SgfToken::Size(9, 9));
+    ///
+    /// let repaired = tree.repair(&RepairOptions::default());
+    /// assert_eq!(
+    ///     repaired.tree.to_string(),
+    ///     "(;CA[UTF-8]SZ[9];AB[cc];B[ee];W[])"
+    /// );
+    /// assert_eq!(
+    ///     repaired.changes[0],
+    ///     Repair::LiftedRootToken {
+    ///         path: TreePath::new(vec![], 1),
+    ///         identifier: "SZ".to_string(),
+    ///     }
+    /// );
+    /// assert_eq!(repaired.changes.len(), 5);
+    ///
+    /// assert!(repaired.tree.repair(&RepairOptions::default()).is_unchanged());
+    /// ```
+    pub fn repair(&self, options: &RepairOptions) -> Repaired {
+        let mut tree = self.clone();
+        let mut changes = vec![];
+        if options.reparse_invalid {
+            let hints = ReparseHints::default();
+            for_each_node(&mut tree, &mut vec![], &mut |path, node| {
+                for token in node.tokens.iter_mut() {
+                    if let Some(reparsed) = token.try_reparse(&hints) {
+                        changes.push(Repair::ReparsedToken {
+                            path: path.clone(),
+                            identifier: reparsed.identifier().to_string(),
+                        });
+                        *token = reparsed;
+                    }
+                }
+            });
+        }
+        if options.lift_root_tokens {
+            lift_root_tokens(&mut tree, &mut changes);
+        }
+        if options.merge_duplicates {
+            for_each_node(&mut tree, &mut vec![], &mut |path, node| {
+                let count = node.tokens.len();
+                let dropped = node.merge_duplicates();
+                if node.tokens.len() != count {
+                    changes.push(Repair::MergedDuplicates { path, dropped });
+                }
+            });
+        }
+        if options.normalize_charset {
+            if let Some(root) = tree.nodes.first_mut() {
+                for token in root.tokens.iter_mut() {
+                    if let SgfToken::Charset(Encoding::Other(charset)) = token {
+                        changes.push(Repair::NormalizedCharset(charset.clone()));
+                        *token = SgfToken::Charset(Encoding::UTF8);
+                    }
+                }
+            }
+        }
+        if options.convert_tt_passes {
+            let board_size = tree.board_size().unwrap_or((19, 19));
+            for_each_node(&mut tree, &mut vec![], &mut |path, node| {
+                let mut converted = false;
+                for token in node.tokens.iter_mut() {
+                    if let SgfToken::Move { action, .. } = token {
+                        let normalized = action.normalize_pass(board_size);
+                        if normalized != *action {
+                            *action = normalized;
+                            converted = true;
+                        }
+                    }
+                }
+                if converted {
+                    changes.push(Repair::ConvertedPass(path));
+                }
+            });
+        }
+        if options.split_setup_moves {
+            changes.extend(tree.mixed_setup_nodes().into_iter().map(Repair::SplitSetup));
+            tree.split_setup_moves();
+        }
+        Repaired { tree, changes }
+    }
+}
+
+/// Moves root tokens out of all nodes but the root, keeping the first value of each property
+pub(crate) fn lift_root_tokens(tree: &mut GameTree, changes: &mut Vec<Repair>) {
+    let mut present: Vec<String> = tree
+        .nodes
+        .first()
+        .map(|root| {
+            root.tokens
+                .iter()
+                .map(|token| token.identifier().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut lifted: Vec<SgfToken> = vec![];
+    for_each_node(tree, &mut vec![], &mut |path, node| {
+        if path.variations.is_empty() && path.node == 0 {
+            return;
+        }
+        let (root_tokens, tokens) = node
+            .tokens
+            .drain(..)
+            .partition(|token: &SgfToken| token.is_root_token());
+        node.tokens = tokens;
+        for token in root_tokens {
+            let identifier = token.identifier().to_string();
+            if present.contains(&identifier) {
+                changes.push(Repair::DroppedRootToken {
+                    path: path.clone(),
+                    identifier,
+                });
+            } else {
+                present.push(identifier.clone());
+                changes.push(Repair::LiftedRootToken {
+                    path: path.clone(),
+                    identifier,
+                });
+                lifted.push(token);
+            }
+        }
+    });
+    if let Some(root) = tree.nodes.first_mut() {
+        root.tokens.extend(lifted);
+    }
+}
+
+fn for_each_node(
+    tree: &mut GameTree,
+    variations: &mut Vec<usize>,
+    f: &mut dyn FnMut(TreePath, &mut GameNode),
+) {
+    for (index, node) in tree.nodes.iter_mut().enumerate() {
+        f(TreePath::new(variations.clone(), index), node);
+    }
+    for (index, variation) in tree.variations.iter_mut().enumerate() {
+        variations.push(index);
+        for_each_node(variation, variations, f);
+        variations.pop();
+    }
+}
@@ -0,0 +1,67 @@
+use crate::token::normalize_identifier;
+use crate::{SgfToken, ValueType};
+
+/// Selects which relaxed rules `SgfToken::try_reparse` uses
+///
+/// All rules are enabled by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReparseHints {
+    /// Remove whitespace around the value, like in `B[ dd ]`
+    pub trim_whitespace: bool,
+    /// Read a comma as the decimal point of `Real` values, like in `KM[6,5]`
+    pub decimal_comma: bool,
+}
+
+impl Default for ReparseHints {
+    fn default() -> Self {
+        ReparseHints {
+            trim_whitespace: true,
+            decimal_comma: true,
+        }
+    }
+}
+
+impl SgfToken {
+    /// Tries to read an `SgfToken::Invalid` token again, after fixing its value with the rules
+    /// selected in `hints`
+    ///
+    /// Returns `None` for other tokens, and for values the rules can't fix.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let hints = ReparseHints::default();
+    /// let token = SgfToken::from_pair("B", " dd ");
+    /// assert_eq!(
+    ///     token.try_reparse(&hints),
+    ///     Some(SgfToken::Move { color: Color::Black, action: Action::Move(4, 4) })
+    /// );
+    ///
+    /// let token = SgfToken::from_pair("KM", "6,5");
+    /// assert_eq!(token.try_reparse(&hints), Some(SgfToken::Komi(Real::new(6.5).unwrap())));
+    ///
+    /// let token = SgfToken::from_pair("KM", "six");
+    /// assert_eq!(token.try_reparse(&hints), None);
+    /// ```
+    pub fn try_reparse(&self, hints: &ReparseHints) -> Option<SgfToken> {
+        let (identifier, value) = match self {
+            SgfToken::Invalid((identifier, value)) => (identifier, value),
+            _ => return None,
+        };
+        let value_type = SgfToken::value_type_of(&normalize_identifier(identifier))?;
+        let mut fixed = value.to_string();
+        if hints.trim_whitespace {
+            fixed = fixed.trim().to_string();
+        }
+        if hints.decimal_comma && value_type == ValueType::Real {
+            fixed = fixed.replace(',', ".");
+        }
+        if fixed == *value {
+            return None;
+        }
+        match SgfToken::from_pair(identifier, &fixed) {
+            SgfToken::Invalid(_) => None,
+            token => Some(token),
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use crate::coords::point_value;
+use crate::json::json_string;
+use crate::{Action, Board, Color, GameNode, GameTree, SgfError, SgfToken, TreePath};
+
+/// A single step of a `Review`, showing the position after a node
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewFrame {
+    /// Number of moves played to reach the position
+    pub move_number: usize,
+    /// The move played in the node, if any
+    pub last_move: Option<(Color, Action)>,
+    pub board: Board,
+    pub comment: Option<String>,
+    /// `SQ`, `CR`, `TR` and `LB` tokens of the node
+    pub marks: Vec<SgfToken>,
+}
+
+/// The positions along a line of a game, for stepping through it in a viewer
+#[derive(Debug, Clone, PartialEq)]
+pub struct Review {
+    pub frames: Vec<ReviewFrame>,
+}
+
+impl GameTree {
+    /// Builds a review of the main variation, with one frame per node
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9]C[Start];B[ee]TR[ee];W[])").unwrap();
+    /// let review = tree.review().unwrap();
+    ///
+    /// assert_eq!(review.frames.len(), 3);
+    /// assert_eq!(review.frames[0].comment, Some("Start".to_string()));
+    /// assert_eq!(review.frames[1].board.get((5, 5)), Some(Color::Black));
+    /// assert_eq!(review.frames[2].last_move, Some((Color::White, Action::Pass)));
+    /// ```
+    pub fn review(&self) -> Result<Review, SgfError> {
+        let nodes: Vec<&GameNode> = self.iter().collect();
+        review_line(self, &nodes)
+    }
+
+    /// Builds a review of all nodes from the root to the node at `path`
+    pub fn review_to(&self, path: &TreePath) -> Result<Review, SgfError> {
+        let nodes = self.get_line_to(path)?;
+        review_line(self, &nodes)
+    }
+}
+
+fn review_line(tree: &GameTree, nodes: &[&GameNode]) -> Result<Review, SgfError> {
+    let mut board = Board::for_tree(tree)?;
+    let mut move_number = 0;
+    let mut frames = vec![];
+    for node in nodes {
+        board.apply_node(node)?;
+        let mut frame = ReviewFrame {
+            move_number,
+            last_move: None,
+            board: board.clone(),
+            comment: None,
+            marks: vec![],
+        };
+        for token in &node.tokens {
+            match token {
+                SgfToken::Move { color, action } => {
+                    move_number += 1;
+                    frame.move_number = move_number;
+                    frame.last_move = Some((*color, *action));
+                }
+                SgfToken::Comment(comment) => frame.comment = Some(comment.to_string()),
+                SgfToken::Square { .. }
+                | SgfToken::Circle { .. }
+                | SgfToken::Triangle { .. }
+                | SgfToken::Label { .. } => frame.marks.push(token.clone()),
+                _ => {}
+            }
+        }
+        frames.push(frame);
+    }
+    Ok(Review { frames })
+}
+
+impl Review {
+    /// Serializes the review as JSON for slideshow players
+    ///
+    /// Points are written as SGF coordinates like `dd`, and moves as SGF properties like
+    /// `B[dd]`. Each frame lists all stones on the board, so frames can be shown in any order.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee]C[Center]LB[ee:A])").unwrap();
+    /// let json = tree.review().unwrap().to_json();
+    /// assert!(json.starts_with(r#"{"size":[9,9],"frames":[{"move_number":0,"move":null"#));
+    /// assert!(json.ends_with(
+    ///     r#"{"move_number":1,"move":"B[ee]","black":["ee"],"white":[],"comment":"Center","marks":[{"type":"label","point":"ee","label":"A"}]}]}"#
+    /// ));
+    /// ```
+    pub fn to_json(&self) -> String {
+        let size = self
+            .frames
+            .first()
+            .map_or((0, 0), |frame| frame.board.size());
+        let frames: Vec<String> = self.frames.iter().map(frame_json).collect();
+        format!(
+            "{{\"size\":[{},{}],\"frames\":[{}]}}",
+            size.0,
+            size.1,
+            frames.join(",")
+        )
+    }
+}
+
+fn frame_json(frame: &ReviewFrame) -> String {
+    let (width, height) = frame.board.size();
+    let mut black = vec![];
+    let mut white = vec![];
+    for y in 1..=height {
+        for x in 1..=width {
+            let point = format!("\"{}\"", point_value((x, y)));
+            match frame.board.get((x, y)) {
+                Some(Color::Black) => black.push(point),
+                Some(Color::White) => white.push(point),
+                None => {}
+            }
+        }
+    }
+    let last_move = match frame.last_move {
+        Some((color, action)) => json_string(&SgfToken::Move { color, action }.to_string()),
+        None => "null".to_string(),
+    };
+    let comment = match &frame.comment {
+        Some(comment) => json_string(comment),
+        None => "null".to_string(),
+    };
+    let marks: Vec<String> = frame
+        .marks
+        .iter()
+        .filter_map(|token| match token {
+            SgfToken::Square { coordinate } => Some(mark_json("square", *coordinate)),
+            SgfToken::Circle { coordinate } => Some(mark_json("circle", *coordinate)),
+            SgfToken::Triangle { coordinate } => Some(mark_json("triangle", *coordinate)),
+            SgfToken::Label { label, coordinate } => Some(format!(
+                "{{\"type\":\"label\",\"point\":\"{}\",\"label\":{}}}",
+                point_value(*coordinate),
+                json_string(label)
+            )),
+            _ => None,
+        })
+        .collect();
+    format!(
+        "{{\"move_number\":{},\"move\":{},\"black\":[{}],\"white\":[{}],\"comment\":{},\"marks\":[{}]}}",
+        frame.move_number,
+        last_move,
+        black.join(","),
+        white.join(","),
+        comment,
+        marks.join(",")
+    )
+}
+
+fn mark_json(kind: &str, coordinate: (u8, u8)) -> String {
+    format!(
+        "{{\"type\":\"{}\",\"point\":\"{}\"}}",
+        kind,
+        point_value(coordinate)
+    )
+}
@@ -0,0 +1,148 @@
+use std::borrow::Cow;
+
+use crate::{parse, Encoding, GameNode, GameTree, SgfError, SgfErrorKind, SgfToken};
+
+/// A semantic difference found between a tree and its re-parsed serialization
+#[derive(Debug, Clone, PartialEq)]
+pub enum RoundtripDifference {
+    /// The serialized tree could not be parsed again
+    ReparseFailed(SgfErrorKind),
+    /// A sequence has a different number of nodes after the round trip
+    NodeCount {
+        variations: Vec<usize>,
+        original: usize,
+        reparsed: usize,
+    },
+    /// A sequence has a different number of variations after the round trip
+    VariationCount {
+        variations: Vec<usize>,
+        original: usize,
+        reparsed: usize,
+    },
+    /// A node has different tokens after the round trip
+    Tokens {
+        variations: Vec<usize>,
+        node: usize,
+        original: Vec<SgfToken>,
+        reparsed: Vec<SgfToken>,
+    },
+}
+
+/// Result of a parse, serialize and re-parse cycle
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundtripReport {
+    /// The serialized form of the parsed tree
+    pub serialized: String,
+    pub differences: Vec<RoundtripDifference>,
+}
+
+impl RoundtripReport {
+    /// Checks if the round trip kept all information
+    pub fn is_lossless(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+/// Parses `input`, serializes the result, parses it again, and reports any differences
+///
+/// Token order within a node, and the conversion of `CA` to UTF-8, are not reported as
+/// differences. Variations are identified by the variation indexes leading to them.
+///
+/// Returns an error if `input` itself can not be parsed.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let report = roundtrip("(;SZ[19]KM[6.50];B[aa](;W[bb])(;W[cc]))").unwrap();
+/// assert!(report.is_lossless());
+/// assert_eq!(report.serialized, "(;KM[6.5]SZ[19];B[aa](;W[bb])(;W[cc]))");
+/// ```
+pub fn roundtrip(input: &str) -> Result<RoundtripReport, SgfError> {
+    let tree = parse(input)?;
+    Ok(roundtrip_tree(&tree))
+}
+
+/// Serializes `tree`, parses the output, and reports any differences
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let tree: GameTree = parse("(;B[aa];W[bb])").unwrap();
+/// assert!(roundtrip_tree(&tree).is_lossless());
+/// ```
+pub fn roundtrip_tree(tree: &GameTree) -> RoundtripReport {
+    let serialized: String = tree.into();
+    let differences = match parse(&serialized) {
+        Ok(reparsed) => {
+            let mut differences = vec![];
+            compare_trees(tree, &reparsed, &mut vec![], &mut differences);
+            differences
+        }
+        Err(err) => vec![RoundtripDifference::ReparseFailed(err.kind)],
+    };
+    RoundtripReport {
+        serialized,
+        differences,
+    }
+}
+
+fn compare_trees(
+    original: &GameTree,
+    reparsed: &GameTree,
+    variations: &mut Vec<usize>,
+    differences: &mut Vec<RoundtripDifference>,
+) {
+    if original.nodes.len() != reparsed.nodes.len() {
+        differences.push(RoundtripDifference::NodeCount {
+            variations: variations.clone(),
+            original: original.nodes.len(),
+            reparsed: reparsed.nodes.len(),
+        });
+    }
+    for (node, (a, b)) in original.nodes.iter().zip(&reparsed.nodes).enumerate() {
+        let original = normalized_tokens(a);
+        let reparsed = normalized_tokens(b);
+        if original != reparsed {
+            differences.push(RoundtripDifference::Tokens {
+                variations: variations.clone(),
+                node,
+                original: original.into_iter().map(Cow::into_owned).collect(),
+                reparsed: reparsed.into_iter().map(Cow::into_owned).collect(),
+            });
+        }
+    }
+    if original.variations.len() != reparsed.variations.len() {
+        differences.push(RoundtripDifference::VariationCount {
+            variations: variations.clone(),
+            original: original.variations.len(),
+            reparsed: reparsed.variations.len(),
+        });
+    }
+    for (index, (a, b)) in original
+        .variations
+        .iter()
+        .zip(&reparsed.variations)
+        .enumerate()
+    {
+        variations.push(index);
+        compare_trees(a, b, variations, differences);
+        variations.pop();
+    }
+}
+
+/// Tokens of `node`, in serialization order and with the charset converted to UTF-8
+///
+/// Tokens are only cloned when they are changed by the normalization
+fn normalized_tokens(node: &GameNode) -> Vec<Cow<'_, SgfToken>> {
+    let mut tokens = node
+        .tokens
+        .iter()
+        .map(|token| match token {
+            SgfToken::Charset(_) => Cow::Owned(SgfToken::Charset(Encoding::UTF8)),
+            token => Cow::Borrowed(token),
+        })
+        .map(|token| (String::from(token.as_ref()), token))
+        .collect::<Vec<_>>();
+    tokens.sort_by(|(a, _), (b, _)| a.cmp(b));
+    tokens.into_iter().map(|(_, token)| token).collect()
+}
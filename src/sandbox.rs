@@ -0,0 +1,197 @@
+use crate::turn::next_color;
+use crate::{Action, Board, Color, GameNode, GameTree, SgfError, SgfToken, TreePath};
+
+/// A scratch line of nodes continuing the tree from a node, made by `GameTree::sandbox_at`
+///
+/// The sandbox borrows the tree instead of copying it, and only owns the nodes added to it, so
+/// many trial lines can be tried cheaply from the same position. Dropping the sandbox discards
+/// the trial, and `finish` turns it into a `SandboxLine` to commit with
+/// `GameTree::commit_sandbox`.
+#[derive(Debug, Clone)]
+pub struct Sandbox<'a> {
+    tree: &'a GameTree,
+    path: TreePath,
+    nodes: Vec<GameNode>,
+}
+
+/// The nodes of a finished `Sandbox`, not tied to the lifetime of the tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct SandboxLine {
+    /// Path of the node the line continues from
+    pub path: TreePath,
+    pub nodes: Vec<GameNode>,
+}
+
+impl GameTree {
+    /// Starts a sandbox continuing the tree from the node at `path`
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree = parse("(;SZ[9];B[ee];W[cc])").unwrap();
+    /// let mut sandbox = tree.sandbox_at(&TreePath::new(vec![], 1)).unwrap();
+    /// sandbox
+    ///     .push_move(Color::White, Action::Move(3, 7))
+    ///     .push_move(Color::Black, Action::Move(7, 3));
+    /// let line = sandbox.finish();
+    ///
+    /// let path = tree.commit_sandbox(line).unwrap();
+    /// assert_eq!(path, TreePath::new(vec![1], 1));
+    /// assert_eq!(tree.to_string(), "(;SZ[9];B[ee](;W[cc])(;W[cg];B[gc]))");
+    /// ```
+    pub fn sandbox_at(&self, path: &TreePath) -> Result<Sandbox<'_>, SgfError> {
+        self.get_node(path)?;
+        Ok(Sandbox {
+            tree: self,
+            path: path.clone(),
+            nodes: vec![],
+        })
+    }
+
+    /// Inserts the nodes of a finished sandbox, and returns the path of the last one
+    ///
+    /// Nodes already in the tree are followed rather than duplicated, and the rest of the line
+    /// is added as a new variation where it leaves the tree. A line continuing the end of a
+    /// sequence without variations is appended to it.
+    pub fn commit_sandbox(&mut self, line: SandboxLine) -> Result<TreePath, SgfError> {
+        let SandboxLine { path, nodes } = line;
+        self.get_node(&path)?;
+        let sequence = self.get_sequence_mut(&path.variations)?;
+        Ok(insert_line(sequence, path.variations, path.node, nodes))
+    }
+}
+
+impl<'a> Sandbox<'a> {
+    /// Path of the node the sandbox continues from
+    pub fn path(&self) -> &TreePath {
+        &self.path
+    }
+
+    /// Nodes added to the sandbox
+    pub fn nodes(&self) -> &[GameNode] {
+        &self.nodes
+    }
+
+    /// Adds a node to the end of the trial line
+    pub fn push_node(&mut self, node: GameNode) -> &mut Self {
+        self.nodes.push(node);
+        self
+    }
+
+    /// Adds a node holding a single move to the end of the trial line
+    pub fn push_move(&mut self, color: Color, action: Action) -> &mut Self {
+        self.push_node(GameNode::from(vec![SgfToken::Move { color, action }]))
+    }
+
+    /// Adds a node holding a single move, with the color inferred from the line so far
+    ///
+    /// The color is the one given by `GameTree::color_to_play` for the end of the trial line.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee])").unwrap();
+    /// let mut sandbox = tree.sandbox_at(&TreePath::new(vec![], 1)).unwrap();
+    /// sandbox.play_auto((3, 3)).play_auto((7, 7));
+    /// assert_eq!(sandbox.finish().nodes, parse_sequence(";W[cc];B[gg]").unwrap());
+    /// ```
+    pub fn play_auto(&mut self, coordinate: (u8, u8)) -> &mut Self {
+        let color = next_color(self.line());
+        self.push_move(color, Action::Move(coordinate.0, coordinate.1))
+    }
+
+    /// Removes the last node of the trial line
+    pub fn pop(&mut self) -> Option<GameNode> {
+        self.nodes.pop()
+    }
+
+    /// Gets all nodes from the root of the tree to the end of the trial line
+    pub fn line(&self) -> Vec<&GameNode> {
+        let mut line = self.tree.get_line_to(&self.path).unwrap_or_default();
+        line.extend(self.nodes.iter());
+        line
+    }
+
+    /// Replays the tree and the trial line to get the position at the end of the trial
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee])").unwrap();
+    /// let mut sandbox = tree.sandbox_at(&TreePath::new(vec![], 1)).unwrap();
+    /// sandbox.push_move(Color::White, Action::Move(3, 3));
+    /// let board = sandbox.board().unwrap();
+    /// assert_eq!(board.get((3, 3)), Some(Color::White));
+    /// assert_eq!(tree.get_node(&TreePath::new(vec![], 1)).unwrap().tokens.len(), 1);
+    /// ```
+    pub fn board(&self) -> Result<Board, SgfError> {
+        let mut board = Board::for_tree(self.tree)?;
+        for node in self.line() {
+            board.apply_node(node)?;
+        }
+        Ok(board)
+    }
+
+    /// Drops the trial line, leaving the tree as it was
+    pub fn discard(self) {}
+
+    /// Finishes the trial, releasing the borrow of the tree
+    pub fn finish(self) -> SandboxLine {
+        SandboxLine {
+            path: self.path,
+            nodes: self.nodes,
+        }
+    }
+}
+
+/// Inserts `nodes` after the node at `index` of `sequence`, which is at `variations`
+fn insert_line(
+    sequence: &mut GameTree,
+    variations: Vec<usize>,
+    mut index: usize,
+    nodes: Vec<GameNode>,
+) -> TreePath {
+    let mut nodes = nodes.into_iter();
+    while let Some(node) = nodes.next() {
+        let next = index + 1;
+        if next < sequence.nodes.len() {
+            if sequence.nodes[next] == node {
+                index = next;
+                continue;
+            }
+            let tail = GameTree {
+                nodes: sequence.nodes.split_off(next),
+                variations: std::mem::take(&mut sequence.variations),
+            };
+            sequence.variations = vec![tail];
+        } else if sequence.variations.is_empty() {
+            sequence.nodes.push(node);
+            index = next;
+            continue;
+        } else if let Some(variation) = sequence
+            .variations
+            .iter()
+            .position(|variation| variation.nodes.first() == Some(&node))
+        {
+            let mut variations = variations;
+            variations.push(variation);
+            return insert_line(
+                &mut sequence.variations[variation],
+                variations,
+                0,
+                nodes.collect(),
+            );
+        }
+        let mut new_nodes = vec![node];
+        new_nodes.extend(nodes);
+        let node = new_nodes.len() - 1;
+        sequence.variations.push(GameTree {
+            nodes: new_nodes,
+            variations: vec![],
+        });
+        let mut variations = variations;
+        variations.push(sequence.variations.len() - 1);
+        return TreePath::new(variations, node);
+    }
+    TreePath::new(variations, index)
+}
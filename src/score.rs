@@ -0,0 +1,185 @@
+use crate::board::{scoring_method, ScoringMethod};
+use crate::coords::point_value;
+use crate::{
+    Board, Color, GameNode, GameTree, Outcome, Real, SgfError, SgfErrorKind, SgfToken, TokenString,
+};
+
+/// The count of the final position, made by `GameTree::score`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FinalScore {
+    /// All dead stones, taken off the board before counting
+    pub dead: Vec<(u8, u8)>,
+    pub black_territory: Vec<(u8, u8)>,
+    pub white_territory: Vec<(u8, u8)>,
+    /// Points of black, as counted by the scoring method
+    pub black: f32,
+    /// Points of white, including komi
+    pub white: f32,
+    pub outcome: Outcome,
+}
+
+impl GameTree {
+    /// Counts the final position of the main variation, after removing the groups containing
+    /// the points in `dead`
+    ///
+    /// A single stone of each dead group is enough. Points under dead stones count as territory
+    /// when the region around them is surrounded by the other color. The `RU` and `KM` tokens
+    /// of the root node select the scoring method, see `board::scoring_method`, and the komi.
+    ///
+    /// Fails if a point in `dead` has no stone, or if the main variation can't be replayed.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse(
+    ///     "(;SZ[5]KM[0.5]RU[Chinese]AB[ca][cb][cc][cd][ce]AW[da][db][dc][dd][de][bc])",
+    /// )
+    /// .unwrap();
+    /// let score = tree.score(&[(2, 3)]).unwrap();
+    /// assert_eq!(score.dead, vec![(2, 3)]);
+    /// assert_eq!((score.black, score.white), (15.0, 10.5));
+    /// assert_eq!(score.outcome.get_points(), Some(4.5));
+    /// ```
+    pub fn score(&self, dead: &[(u8, u8)]) -> Result<FinalScore, SgfError> {
+        let mut board = Board::replay(self)?;
+        let mut removed = vec![];
+        for point in dead {
+            if removed.contains(point) {
+                continue;
+            }
+            let group = board.remove_group(*point);
+            if group.is_empty() {
+                return Err(SgfErrorKind::InvalidTokenValue.into());
+            }
+            removed.extend(group);
+        }
+        removed.sort_unstable_by_key(|&(x, y)| (y, x));
+
+        let root = self
+            .nodes
+            .first()
+            .map(|node| &node.tokens[..])
+            .unwrap_or(&[]);
+        let komi = root
+            .iter()
+            .find_map(|token| match token {
+                SgfToken::Komi(komi) => Some(komi.get()),
+                _ => None,
+            })
+            .unwrap_or(0.0);
+        let method = root
+            .iter()
+            .find_map(|token| match token {
+                SgfToken::Rule(rules) => Some(scoring_method(rules)),
+                _ => None,
+            })
+            .unwrap_or(ScoringMethod::Area);
+
+        let black_territory = board.territory(Color::Black);
+        let white_territory = board.territory(Color::White);
+        let points = |color: Color, territory: &[(u8, u8)]| {
+            let extra = match method {
+                ScoringMethod::Area => stone_count(&board, color),
+                ScoringMethod::Territory => board.captures(color),
+            };
+            (territory.len() as u32 + extra) as f32
+        };
+        let black = points(Color::Black, &black_territory);
+        let white = points(Color::White, &white_territory) + komi;
+        Ok(FinalScore {
+            dead: removed,
+            black_territory,
+            white_territory,
+            black,
+            white,
+            outcome: outcome_from_points(black, white),
+        })
+    }
+
+    /// Scores the game with `GameTree::score`, and writes the result to the tree
+    ///
+    /// The last node of the main variation gets `TB` and `TW` tokens for the territory and `MA`
+    /// marks on the dead stones, replacing any it had, and the `RE` token of the root node is
+    /// replaced with the outcome.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree =
+    ///     parse("(;SZ[5]KM[0.5]AB[ca][cb][cc][cd][ce]AW[da][db][dc][dd][de][bc];B[];W[])")
+    ///         .unwrap();
+    /// tree.write_score(&[(2, 3)]).unwrap();
+    /// let last = &tree.nodes[2];
+    /// assert_eq!(last.tokens_for("TB").len(), 10);
+    /// assert_eq!(last.tokens_for("MA").len(), 1);
+    /// let outcome = Outcome::WinnerByPoints(Color::Black, Real::new(4.5).unwrap());
+    /// assert_eq!(tree.infer_result().unwrap().outcome, outcome);
+    /// assert_eq!(tree.nodes[0].tokens_for("RE"), vec![&SgfToken::Result(outcome)]);
+    /// ```
+    pub fn write_score(&mut self, dead: &[(u8, u8)]) -> Result<FinalScore, SgfError> {
+        let score = self.score(dead)?;
+        if let Some(root) = self.nodes.first_mut() {
+            root.tokens
+                .retain(|token| !matches!(token, SgfToken::Result(_)));
+            root.tokens.push(SgfToken::Result(score.outcome));
+        }
+        if let Some(last) = last_node_mut(self) {
+            last.tokens.retain(|token| !is_score_mark(token));
+            let marks = [
+                ("TB", &score.black_territory),
+                ("TW", &score.white_territory),
+                ("MA", &score.dead),
+            ];
+            for (identifier, points) in marks.iter() {
+                last.tokens.extend(points.iter().map(|point| {
+                    SgfToken::Unknown((
+                        TokenString::from_static(identifier),
+                        point_value(*point).into(),
+                    ))
+                }));
+            }
+        }
+        Ok(score)
+    }
+}
+
+fn stone_count(board: &Board, color: Color) -> u32 {
+    let (width, height) = board.size();
+    let mut count = 0;
+    for y in 1..=height {
+        for x in 1..=width {
+            if board.get((x, y)) == Some(color) {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+fn outcome_from_points(black: f32, white: f32) -> Outcome {
+    if black > white {
+        Outcome::WinnerByPoints(Color::Black, Real::saturating(black - white))
+    } else if white > black {
+        Outcome::WinnerByPoints(Color::White, Real::saturating(white - black))
+    } else {
+        Outcome::Draw
+    }
+}
+
+fn is_score_mark(token: &SgfToken) -> bool {
+    match token {
+        SgfToken::Unknown((identifier, _)) | SgfToken::EmptyList(identifier) => {
+            matches!(identifier.as_str(), "TB" | "TW" | "MA")
+        }
+        _ => false,
+    }
+}
+
+/// Gets the last node of the main variation
+fn last_node_mut(tree: &mut GameTree) -> Option<&mut GameNode> {
+    let mut sequence = tree;
+    while !sequence.variations.is_empty() {
+        sequence = &mut sequence.variations[0];
+    }
+    sequence.nodes.last_mut()
+}
@@ -0,0 +1,103 @@
+use crate::{GameNode, GameTree, SgfToken, TreePath};
+
+impl GameTree {
+    /// Finds the nodes holding both setup tokens, like `AB`, `AW` and `AE`, and moves, which
+    /// FF[4] does not allow
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];AB[cc]B[ee](;W[gg])(;AW[gc]W[cg]))").unwrap();
+    /// assert_eq!(
+    ///     tree.mixed_setup_nodes(),
+    ///     vec![TreePath::new(vec![], 1), TreePath::new(vec![1], 0)]
+    /// );
+    /// ```
+    pub fn mixed_setup_nodes(&self) -> Vec<TreePath> {
+        let mut found = vec![];
+        collect_mixed(self, &mut vec![], &mut found);
+        found
+    }
+
+    /// Splits every node found by `mixed_setup_nodes` into a setup node followed by a move
+    /// node, and returns the number of nodes that were split
+    ///
+    /// The setup node keeps the setup, root and game info tokens, and the extras of the node.
+    /// All other tokens, like comments, markup and timing, describe the move and stay with it.
+    /// Tokens keep their order within each node.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree = parse("(;SZ[9];AB[cc]AE[dd]B[ee]C[Good shape]TR[ee])").unwrap();
+    /// assert_eq!(tree.split_setup_moves(), 1);
+    /// assert_eq!(
+    ///     tree.to_string(),
+    ///     "(;SZ[9];AB[cc]AE[dd];B[ee]C[Good shape]TR[ee])"
+    /// );
+    /// assert!(tree.mixed_setup_nodes().is_empty());
+    /// ```
+    pub fn split_setup_moves(&mut self) -> usize {
+        let mut changes = 0;
+        let mut nodes: Vec<GameNode> = Vec::with_capacity(self.nodes.len());
+        for node in self.nodes.drain(..) {
+            if is_mixed(&node) {
+                let (setup, play) = split_setup(node);
+                nodes.push(setup);
+                nodes.push(play);
+                changes += 1;
+            } else {
+                nodes.push(node);
+            }
+        }
+        self.nodes = nodes;
+        changes
+            + self
+                .variations
+                .iter_mut()
+                .map(GameTree::split_setup_moves)
+                .sum::<usize>()
+    }
+}
+
+/// Checks for `AB`, `AW`, and the `AE` and `PL` tokens kept as unknown tokens
+fn is_setup(token: &SgfToken) -> bool {
+    token.is_setup_token() || matches!(token.identifier(), "AE" | "PL")
+}
+
+fn is_mixed(node: &GameNode) -> bool {
+    node.tokens.iter().any(is_setup)
+        && node
+            .tokens
+            .iter()
+            .any(|token| matches!(token, SgfToken::Move { .. }))
+}
+
+fn split_setup(node: GameNode) -> (GameNode, GameNode) {
+    let mut setup = GameNode {
+        tokens: vec![],
+        extras: node.extras,
+    };
+    let mut play = GameNode::default();
+    for token in node.tokens {
+        if is_setup(&token) || token.is_root_token() || token.is_game_info_token() {
+            setup.tokens.push(token);
+        } else {
+            play.tokens.push(token);
+        }
+    }
+    (setup, play)
+}
+
+fn collect_mixed(tree: &GameTree, variations: &mut Vec<usize>, found: &mut Vec<TreePath>) {
+    for (index, node) in tree.nodes.iter().enumerate() {
+        if is_mixed(node) {
+            found.push(TreePath::new(variations.clone(), index));
+        }
+    }
+    for (index, variation) in tree.variations.iter().enumerate() {
+        variations.push(index);
+        collect_mixed(variation, variations, found);
+        variations.pop();
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{Action, Color, GameNode, GameTree, SgfError, SgfErrorKind, SgfToken, TreePath};
+
+/// A game tree with reference counted nodes, for sharing between threads
+///
+/// Cloning an `ArcGameTree` only copies pointers, so a writer can hand out snapshots to
+/// reader threads and keep modifying its own copy. Modifications copy the nodes and
+/// sequences they touch when those are shared, leaving existing snapshots unchanged.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let mut tree = ArcGameTree::from(parse("(;SZ[19];B[pd])").unwrap());
+/// let snapshot = tree.snapshot();
+/// let reader = std::thread::spawn(move || snapshot.to_string());
+///
+/// tree.push_move(Color::White, Action::Move(4, 4)).unwrap();
+/// assert_eq!(reader.join().unwrap(), "(;SZ[19];B[pd])");
+/// assert_eq!(tree.to_string(), "(;SZ[19];B[pd];W[dd])");
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ArcGameTree {
+    pub nodes: Vec<Arc<GameNode>>,
+    pub variations: Vec<Arc<ArcGameTree>>,
+}
+
+impl ArcGameTree {
+    /// Gets a copy of the tree sharing all nodes with `self`
+    pub fn snapshot(&self) -> ArcGameTree {
+        self.clone()
+    }
+
+    /// Gets the node at `path`
+    pub fn get_node(&self, path: &TreePath) -> Result<&GameNode, SgfError> {
+        let sequence = path.variations.iter().try_fold(self, |tree, variation| {
+            tree.variations
+                .get(*variation)
+                .map(|tree| &**tree)
+                .ok_or_else(|| SgfError::from(SgfErrorKind::VariationNotFound))
+        })?;
+        sequence
+            .nodes
+            .get(path.node)
+            .map(|node| &**node)
+            .ok_or_else(|| SgfErrorKind::NodeNotFound.into())
+    }
+
+    /// Gets the node at `path` for modification, copying it first if it is shared
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree = ArcGameTree::from(parse("(;SZ[9];B[ee])").unwrap());
+    /// let snapshot = tree.snapshot();
+    /// let path = TreePath::new(vec![], 1);
+    /// tree.node_mut(&path).unwrap().tokens.push(SgfToken::Comment("Good".into()));
+    ///
+    /// assert_eq!(tree.to_string(), "(;SZ[9];B[ee]C[Good])");
+    /// assert_eq!(snapshot.to_string(), "(;SZ[9];B[ee])");
+    /// ```
+    pub fn node_mut(&mut self, path: &TreePath) -> Result<&mut GameNode, SgfError> {
+        self.get_node(path)?;
+        let sequence = self.sequence_mut(&path.variations);
+        Ok(Arc::make_mut(&mut sequence.nodes[path.node]))
+    }
+
+    /// Appends a node to the end of the main variation, like `GameTree::push_node`
+    pub fn push_node(&mut self, node: GameNode) -> Result<String, SgfError> {
+        if !self.nodes.is_empty() && node.tokens.iter().any(|token| token.is_root_token()) {
+            return Err(SgfErrorKind::InvalidRootTokenPlacement.into());
+        }
+        let text = String::from(&node);
+        let mut tree = self;
+        while !tree.variations.is_empty() {
+            tree = Arc::make_mut(&mut tree.variations[0]);
+        }
+        tree.nodes.push(Arc::new(node));
+        Ok(text)
+    }
+
+    /// Appends a node with a single move to the end of the main variation
+    pub fn push_move(&mut self, color: Color, action: Action) -> Result<String, SgfError> {
+        self.push_node(GameNode::from(vec![SgfToken::Move { color, action }]))
+    }
+
+    /// Adds `nodes` as a new variation following the node at `path`, and returns the path of
+    /// the first added node
+    ///
+    /// When the node at `path` is not the last of its sequence, the sequence is split there and
+    /// the existing nodes become the first variation. Fails with `SgfErrorKind::NodeNotFound`
+    /// if `nodes` is empty, and like `push_node` if they contain root tokens.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree = ArcGameTree::from(parse("(;SZ[9];B[ee];W[cc])").unwrap());
+    /// let nodes = vec![parse_node(";W[gg]").unwrap(), parse_node(";B[cc]").unwrap()];
+    /// let path = tree.add_variation(&TreePath::new(vec![], 1), nodes).unwrap();
+    ///
+    /// assert_eq!(path, TreePath::new(vec![1], 0));
+    /// assert_eq!(tree.to_string(), "(;SZ[9];B[ee](;W[cc])(;W[gg];B[cc]))");
+    /// ```
+    pub fn add_variation(
+        &mut self,
+        path: &TreePath,
+        nodes: Vec<GameNode>,
+    ) -> Result<TreePath, SgfError> {
+        self.get_node(path)?;
+        if nodes.is_empty() {
+            return Err(SgfErrorKind::NodeNotFound.into());
+        }
+        if nodes
+            .iter()
+            .any(|node| node.tokens.iter().any(|token| token.is_root_token()))
+        {
+            return Err(SgfErrorKind::InvalidRootTokenPlacement.into());
+        }
+        let sequence = self.sequence_mut(&path.variations);
+        let next = path.node + 1;
+        if next < sequence.nodes.len() {
+            let tail = ArcGameTree {
+                nodes: sequence.nodes.split_off(next),
+                variations: std::mem::take(&mut sequence.variations),
+            };
+            sequence.variations.push(Arc::new(tail));
+        }
+        sequence.variations.push(Arc::new(ArcGameTree {
+            nodes: nodes.into_iter().map(Arc::new).collect(),
+            variations: vec![],
+        }));
+        let mut variations = path.variations.clone();
+        variations.push(sequence.variations.len() - 1);
+        Ok(TreePath::new(variations, 0))
+    }
+
+    /// Gets the sequence at `variations`, copying every shared sequence on the way
+    ///
+    /// The variations are expected to exist.
+    fn sequence_mut(&mut self, variations: &[usize]) -> &mut ArcGameTree {
+        variations.iter().fold(self, |tree, variation| {
+            Arc::make_mut(&mut tree.variations[*variation])
+        })
+    }
+}
+
+impl From<GameTree> for ArcGameTree {
+    fn from(tree: GameTree) -> Self {
+        ArcGameTree {
+            nodes: tree.nodes.into_iter().map(Arc::new).collect(),
+            variations: tree
+                .variations
+                .into_iter()
+                .map(|variation| Arc::new(variation.into()))
+                .collect(),
+        }
+    }
+}
+
+impl From<&ArcGameTree> for GameTree {
+    fn from(tree: &ArcGameTree) -> Self {
+        GameTree {
+            nodes: tree.nodes.iter().map(|node| (**node).clone()).collect(),
+            variations: tree
+                .variations
+                .iter()
+                .map(|variation| GameTree::from(&**variation))
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for ArcGameTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nodes: String = self
+            .nodes
+            .iter()
+            .map(|node| String::from(&**node))
+            .collect();
+        let variations: String = self
+            .variations
+            .iter()
+            .map(|variation| variation.to_string())
+            .collect();
+        write!(f, "({}{})", nodes, variations)
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::tree::GameTreeIterator;
+use crate::{GameTree, SgfError, SgfErrorKind, TreePath};
+
+/// The position of a `GameTreeIterator`, made by `GameTreeIterator::snapshot`
+///
+/// The state only holds indexes, so it can be restored on another copy of the tree, like one
+/// parsed again from the same SGF. It is written as text like `1.0:2/0`, which is the path of
+/// the next node followed by the variation picked for the end of its sequence.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let sgf = "(;B[aa](;W[bb];B[cc])(;W[dd];B[ee]))";
+/// let tree: GameTree = parse(sgf).unwrap();
+/// let mut iter = tree.iter();
+/// iter.pick_variation(1).unwrap();
+/// iter.next();
+/// iter.next();
+/// let saved = iter.snapshot().to_string();
+/// assert_eq!(saved, "1:1/0");
+///
+/// let reparsed: GameTree = parse(sgf).unwrap();
+/// let state: IteratorState = saved.parse().unwrap();
+/// let mut iter = reparsed.restore_iter(&state).unwrap();
+/// assert_eq!(iter.next().unwrap().tokens, vec![SgfToken::from_pair("B", "ee")]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IteratorState {
+    /// Path of the next node, where `node` may be the length of the sequence when all its nodes
+    /// have been visited
+    pub path: TreePath,
+    /// Variation picked to continue with after the sequence
+    pub variation: usize,
+}
+
+impl<'a> GameTreeIterator<'a> {
+    /// Captures the position of the iterator, to continue from later with
+    /// `GameTree::restore_iter`
+    pub fn snapshot(&self) -> IteratorState {
+        IteratorState {
+            path: TreePath::new(self.variations.clone(), self.index),
+            variation: self.variation,
+        }
+    }
+}
+
+impl GameTree {
+    /// Creates an iterator at the position captured in `state`
+    ///
+    /// Fails when the tree has no node or variation at the captured position, like when the
+    /// tree was edited since the snapshot.
+    pub fn restore_iter(&self, state: &IteratorState) -> Result<GameTreeIterator<'_>, SgfError> {
+        let tree = self.get_sequence(&state.path.variations)?;
+        if state.path.node > tree.nodes.len() {
+            return Err(SgfErrorKind::NodeNotFound.into());
+        }
+        if state.variation > 0 && state.variation >= tree.variations.len() {
+            return Err(SgfErrorKind::VariationNotFound.into());
+        }
+        let mut iter = GameTreeIterator::new(tree, state.path.variations.clone(), state.path.node);
+        iter.variation = state.variation;
+        iter.picked = true;
+        Ok(iter)
+    }
+}
+
+impl fmt::Display for IteratorState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.path, self.variation)
+    }
+}
+
+impl FromStr for IteratorState {
+    type Err = SgfError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let parse_index = |value: &str| -> Result<usize, SgfError> {
+            value.parse().map_err(SgfError::parse_error)
+        };
+        let malformed = || SgfError::from(SgfErrorKind::ParseError);
+        let (path, variation) = input.split_once('/').ok_or_else(malformed)?;
+        let (variations, node) = path.split_once(':').ok_or_else(malformed)?;
+        let variations = if variations.is_empty() {
+            vec![]
+        } else {
+            variations
+                .split('.')
+                .map(parse_index)
+                .collect::<Result<_, _>>()?
+        };
+        Ok(IteratorState {
+            path: TreePath::new(variations, parse_index(node)?),
+            variation: parse_index(variation)?,
+        })
+    }
+}
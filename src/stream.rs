@@ -0,0 +1,290 @@
+use crate::interner::Interner;
+use crate::repair::lift_root_tokens;
+use crate::token::from_property_value;
+use crate::{
+    parse_bytes_with, CharsetPolicy, GameNode, GameTree, IdentifierCase, ParserOptions,
+    RootTokenPolicy, SgfError, SgfErrorKind, SgfToken,
+};
+
+/// Events emitted by the incremental `Parser`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// Start of a game tree, or a variation, `(`
+    StartTree,
+    /// A node with all of its properties
+    Node(GameNode),
+    /// End of a game tree, or a variation, `)`
+    EndTree,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Start,
+    Node,
+    Identifier,
+    AwaitingValue,
+    Value,
+}
+
+/// Push based SGF parser, for input that arrives in chunks
+///
+/// Nodes are emitted as soon as they are complete, which is when the next node, variation or
+/// the end of the current tree starts. Chunks may be split anywhere, including in the middle
+/// of a multi-byte character.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let mut parser = Parser::new();
+/// let mut events = parser.feed(b"(;SZ[19];B[a").unwrap();
+/// assert_eq!(events.len(), 2);
+///
+/// events.extend(parser.feed(b"a];W[bb])").unwrap());
+/// events.extend(parser.finish().unwrap());
+///
+/// assert_eq!(events.len(), 5);
+/// assert_eq!(events[4], Event::EndTree);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Parser {
+    state: State,
+    depth: usize,
+    tokens: Option<Vec<SgfToken>>,
+    identifier: String,
+    value: Vec<u8>,
+    /// Number of values read for the current identifier
+    values: usize,
+    escaped: bool,
+    identifier_case: IdentifierCase,
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Parser {
+            state: State::Start,
+            depth: 0,
+            tokens: None,
+            identifier: String::new(),
+            value: vec![],
+            values: 0,
+            escaped: false,
+            identifier_case: IdentifierCase::default(),
+        }
+    }
+}
+
+impl Parser {
+    /// Creates a new parser, waiting for the start of a game tree
+    pub fn new() -> Self {
+        Parser::default()
+    }
+
+    /// Creates a new parser matching identifiers according to `options`
+    pub fn with_options(options: &ParserOptions) -> Self {
+        Parser {
+            identifier_case: options.identifier_case,
+            ..Parser::default()
+        }
+    }
+
+    /// Feeds a chunk of input to the parser, and returns all events completed by it
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Vec<Event>, SgfError> {
+        let mut events = vec![];
+        for &byte in chunk {
+            self.consume(byte, &mut events)?;
+        }
+        Ok(events)
+    }
+
+    /// Signals the end of the input
+    ///
+    /// Returns an error if the input ended in the middle of a game tree
+    pub fn finish(&mut self) -> Result<Vec<Event>, SgfError> {
+        if self.depth != 0 || self.state != State::Start {
+            return Err(SgfErrorKind::ParseError.into());
+        }
+        Ok(vec![])
+    }
+
+    fn consume(&mut self, byte: u8, events: &mut Vec<Event>) -> Result<(), SgfError> {
+        match self.state {
+            State::Value => {
+                if self.escaped {
+                    self.value.push(byte);
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.value.push(byte);
+                    self.escaped = true;
+                } else if byte == b']' {
+                    let value = String::from_utf8_lossy(&self.value);
+                    let token = from_property_value(
+                        &self.identifier,
+                        &value,
+                        self.values,
+                        self.identifier_case,
+                    );
+                    crate::diagnostics::token_created(&self.identifier, &value, &token);
+                    self.tokens
+                        .as_mut()
+                        .expect("Values are only parsed inside a node")
+                        .push(token);
+                    self.value.clear();
+                    self.values += 1;
+                    self.state = State::Node;
+                } else {
+                    self.value.push(byte);
+                }
+            }
+            State::Identifier if byte.is_ascii_alphabetic() => {
+                self.identifier.push(byte as char);
+            }
+            State::Identifier | State::AwaitingValue if byte == b'[' => {
+                self.state = State::Value;
+            }
+            State::Identifier | State::AwaitingValue if byte.is_ascii_whitespace() => {
+                self.state = State::AwaitingValue;
+            }
+            State::Identifier | State::AwaitingValue => {
+                return Err(SgfErrorKind::ParseError.into());
+            }
+            State::Start | State::Node => match byte {
+                b'(' => {
+                    self.flush_node(events);
+                    self.depth += 1;
+                    self.state = State::Start;
+                    events.push(Event::StartTree);
+                }
+                b')' if self.depth > 0 => {
+                    self.flush_node(events);
+                    self.depth -= 1;
+                    self.state = State::Start;
+                    events.push(Event::EndTree);
+                }
+                b';' if self.depth > 0 => {
+                    self.flush_node(events);
+                    self.tokens = Some(vec![]);
+                    self.state = State::Node;
+                }
+                b'[' if self.state == State::Node && !self.identifier.is_empty() => {
+                    self.state = State::Value;
+                }
+                byte if self.state == State::Node && byte.is_ascii_alphabetic() => {
+                    self.identifier.clear();
+                    self.identifier.push(byte as char);
+                    self.values = 0;
+                    self.state = State::Identifier;
+                }
+                byte if byte.is_ascii_whitespace() => {}
+                _ => return Err(SgfErrorKind::ParseError.into()),
+            },
+        }
+        Ok(())
+    }
+
+    fn flush_node(&mut self, events: &mut Vec<Event>) {
+        if let Some(tokens) = self.tokens.take() {
+            events.push(Event::Node(GameNode::from(tokens)));
+        }
+        self.identifier.clear();
+    }
+}
+
+/// Builds `GameTree`s from the events of the incremental `Parser`
+///
+/// Applies the same validation as `parse`, rejecting root tokens outside the first node unless
+/// created with another `RootTokenPolicy`.
+#[derive(Debug, Clone, Default)]
+pub struct TreeBuilder {
+    stack: Vec<GameTree>,
+    trees: Vec<GameTree>,
+    root_tokens: RootTokenPolicy,
+    interner: Option<Interner>,
+}
+
+impl TreeBuilder {
+    /// Creates an empty builder
+    pub fn new() -> Self {
+        TreeBuilder::default()
+    }
+
+    /// Creates an empty builder handling root tokens outside the first node with
+    /// `options.root_tokens`, and sharing repeated text between all built games when
+    /// `options.intern_strings` is set
+    pub fn with_options(options: &ParserOptions) -> Self {
+        TreeBuilder {
+            root_tokens: options.root_tokens,
+            interner: options.intern_strings.then(Interner::default),
+            ..TreeBuilder::default()
+        }
+    }
+
+    /// Adds an event to the tree being built
+    pub fn push(&mut self, event: Event) -> Result<(), SgfError> {
+        match event {
+            Event::StartTree => self.stack.push(GameTree::default()),
+            Event::Node(mut node) => {
+                let is_root = self.stack.len() == 1;
+                let tree = self
+                    .stack
+                    .last_mut()
+                    .ok_or_else(|| SgfError::from(SgfErrorKind::ParseError))?;
+                if tree.has_variations() {
+                    return Err(SgfErrorKind::ParseError.into());
+                }
+                let root_allowed = is_root && tree.nodes.is_empty();
+                if !root_allowed
+                    && self.root_tokens == RootTokenPolicy::Error
+                    && node.tokens.iter().any(|token| token.is_root_token())
+                {
+                    return Err(SgfErrorKind::InvalidRootTokenPlacement.into());
+                }
+                if let Some(interner) = &mut self.interner {
+                    interner.intern_node(&mut node);
+                }
+                tree.nodes.push(node);
+            }
+            Event::EndTree => {
+                let tree = self
+                    .stack
+                    .pop()
+                    .ok_or_else(|| SgfError::from(SgfErrorKind::ParseError))?;
+                match self.stack.last_mut() {
+                    Some(parent) => parent.variations.push(tree),
+                    None => self.trees.push(tree),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets all completed game trees, failing if a tree is still open
+    pub fn finish(self) -> Result<Vec<GameTree>, SgfError> {
+        if self.stack.is_empty() {
+            let mut trees = self.trees;
+            if self.root_tokens == RootTokenPolicy::MoveToRoot {
+                for tree in trees.iter_mut() {
+                    lift_root_tokens(tree, &mut vec![]);
+                }
+            }
+            Ok(trees)
+        } else {
+            Err(SgfErrorKind::ParseError.into())
+        }
+    }
+}
+
+/// Parses SGF bytes with the hand-written incremental `Parser`, instead of the pest grammar
+///
+/// Returns the first game tree, like `parse`. The input does not have to be valid UTF-8 as a
+/// whole, invalid sequences in property values are replaced with `U+FFFD`.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let input = "(;SZ[19];B[aa](;W[bb])(;W[cc]))";
+/// assert_eq!(parse_bytes(input.as_bytes()).unwrap(), parse(input).unwrap());
+/// ```
+pub fn parse_bytes(input: &[u8]) -> Result<GameTree, SgfError> {
+    let options = ParserOptions::new().charset_policy(CharsetPolicy::AssumeUtf8Lossy);
+    parse_bytes_with(input, &options)
+}
@@ -0,0 +1,114 @@
+use std::ops::Range;
+
+use pest::iterators::Pair;
+use pest::Parser;
+
+use crate::parser::{Rule, SGFParser};
+use crate::{SgfError, SgfErrorKind};
+
+/// A game tree as written in the input, from `(` to `)`
+///
+/// All spans are byte ranges of the input passed to `parse_syntax`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SyntaxTree {
+    pub span: Range<usize>,
+    pub nodes: Vec<SyntaxNode>,
+    pub variations: Vec<SyntaxTree>,
+}
+
+/// A node as written in the input, from the `;` to the end of its last property
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SyntaxNode {
+    pub span: Range<usize>,
+    pub properties: Vec<SyntaxProperty>,
+}
+
+/// A property as written in the input, from the identifier to the `]` of its last value
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SyntaxProperty {
+    pub span: Range<usize>,
+    pub identifier: Range<usize>,
+    /// Spans of the values, without the brackets, and with escapes left in place
+    pub values: Vec<Range<usize>>,
+}
+
+/// Parses the game trees of an SGF collection into their syntactic structure, without
+/// interpreting any of the properties
+///
+/// Uses the same grammar as `parse`, so input accepted here is accepted there, except for
+/// errors found while creating tokens, like root tokens outside the root node. Meant for
+/// tools working on the text itself, like syntax highlighters and formatters.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let input = "(;SZ[9] ;B[ee]C[Hi \\] there](;W[cc]))";
+/// let trees = parse_syntax(input).unwrap();
+/// let node = &trees[0].nodes[1];
+/// assert_eq!(&input[node.span.clone()], ";B[ee]C[Hi \\] there]");
+///
+/// let comment = &node.properties[1];
+/// assert_eq!(&input[comment.identifier.clone()], "C");
+/// assert_eq!(&input[comment.values[0].clone()], "Hi \\] there");
+/// assert_eq!(trees[0].variations[0].span, 28..36);
+/// ```
+pub fn parse_syntax(input: &str) -> Result<Vec<SyntaxTree>, SgfError> {
+    let collection = SGFParser::parse(Rule::collection, input)
+        .map_err(SgfError::parse_error)?
+        .next()
+        .ok_or_else(|| SgfError::from(SgfErrorKind::ParseError))?;
+    Ok(collection
+        .into_inner()
+        .filter(|pair| pair.as_rule() == Rule::game_tree)
+        .map(syntax_tree)
+        .collect())
+}
+
+fn syntax_tree(pair: Pair<'_, Rule>) -> SyntaxTree {
+    let mut tree = SyntaxTree {
+        span: span_of(&pair),
+        nodes: vec![],
+        variations: vec![],
+    };
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::sequence => tree.nodes.extend(inner.into_inner().map(syntax_node)),
+            Rule::game_tree => tree.variations.push(syntax_tree(inner)),
+            _ => {}
+        }
+    }
+    tree
+}
+
+fn syntax_node(pair: Pair<'_, Rule>) -> SyntaxNode {
+    SyntaxNode {
+        span: span_of(&pair),
+        properties: pair.into_inner().map(syntax_property).collect(),
+    }
+}
+
+fn syntax_property(pair: Pair<'_, Rule>) -> SyntaxProperty {
+    let span = span_of(&pair);
+    let mut inner = pair.into_inner();
+    let identifier = inner
+        .next()
+        .map(|pair| span_of(&pair))
+        .expect("Pest parsing guarantee that all properties have an identifier");
+    let values = inner
+        .map(|value| {
+            let span = span_of(&value);
+            span.start + 1..span.end - 1
+        })
+        .collect();
+    SyntaxProperty {
+        span,
+        identifier,
+        values,
+    }
+}
+
+/// Gets the span of a pair, without the whitespace after it that Pest may include
+fn span_of(pair: &Pair<'_, Rule>) -> Range<usize> {
+    let start = pair.as_span().start();
+    start..start + pair.as_str().trim_end().len()
+}
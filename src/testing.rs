@@ -0,0 +1,103 @@
+//! Helpers for checking collections of real world SGF files
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{parse, roundtrip_tree, RoundtripDifference, SgfError, SgfErrorKind};
+
+/// Why a corpus file failed the check
+#[derive(Debug, Clone, PartialEq)]
+pub enum CorpusFailure {
+    /// The file could not be parsed
+    Parse(SgfErrorKind),
+    /// The parsed tree changed when serialized and parsed again
+    Roundtrip(Vec<RoundtripDifference>),
+}
+
+/// Result of checking a single corpus file
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorpusFile {
+    pub path: PathBuf,
+    pub failure: Option<CorpusFailure>,
+}
+
+/// Result of checking all files in a corpus
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CorpusReport {
+    pub files: Vec<CorpusFile>,
+}
+
+impl CorpusReport {
+    /// Gets the files that failed the check
+    pub fn failures(&self) -> Vec<&CorpusFile> {
+        self.files
+            .iter()
+            .filter(|file| file.failure.is_some())
+            .collect()
+    }
+
+    /// Checks if all files passed
+    pub fn is_clean(&self) -> bool {
+        self.files.iter().all(|file| file.failure.is_none())
+    }
+}
+
+/// Parses every `.sgf` file below `path`, and checks that it round trips without changes
+///
+/// Directories are searched recursively, and files are reported in path order. Files that are
+/// not valid UTF-8 are read as ISO-8859-1, which is the default charset of the specification.
+///
+/// Returns an error if the directory can not be read.
+///
+/// ```rust
+/// use sgf_parser::testing::check_corpus;
+///
+/// let report = check_corpus("tests/sgf").unwrap();
+/// assert!(report.is_clean(), "{:#?}", report.failures());
+/// ```
+pub fn check_corpus(path: impl AsRef<Path>) -> Result<CorpusReport, SgfError> {
+    let mut paths = vec![];
+    collect_files(path.as_ref(), &mut paths)?;
+    paths.sort();
+
+    let mut report = CorpusReport::default();
+    for path in paths {
+        let content = fs::read(&path).map_err(SgfError::io_error)?;
+        let failure = check_file(&decode(content));
+        report.files.push(CorpusFile { path, failure });
+    }
+    Ok(report)
+}
+
+fn collect_files(path: &Path, paths: &mut Vec<PathBuf>) -> Result<(), SgfError> {
+    for entry in fs::read_dir(path).map_err(SgfError::io_error)? {
+        let path = entry.map_err(SgfError::io_error)?.path();
+        if path.is_dir() {
+            collect_files(&path, paths)?;
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("sgf"))
+        {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn decode(content: Vec<u8>) -> String {
+    String::from_utf8(content)
+        .unwrap_or_else(|err| err.into_bytes().iter().map(|&c| c as char).collect())
+}
+
+fn check_file(source: &str) -> Option<CorpusFailure> {
+    match parse(source) {
+        Ok(tree) => {
+            let report = roundtrip_tree(&tree);
+            if report.is_lossless() {
+                None
+            } else {
+                Some(CorpusFailure::Roundtrip(report.differences))
+            }
+        }
+        Err(err) => Some(CorpusFailure::Parse(err.kind)),
+    }
+}
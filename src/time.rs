@@ -0,0 +1,104 @@
+use crate::{Color, GameNode, GameTree, SgfToken};
+
+/// Clock information recorded with a single move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeEntry {
+    /// 1-based number of the move in the main variation, counting moves of both colors
+    pub move_number: usize,
+    /// Seconds left after the move, from `BL` or `WL`
+    pub remaining: Option<u32>,
+    /// Seconds spent on the move
+    ///
+    /// Computed from the previous remaining time of the same color, or the `TM` time limit for
+    /// the first move. `None` when either value is missing, or when the clock went up, like
+    /// when a byo-yomi period starts.
+    pub used: Option<u32>,
+    /// Moves left in the current overtime period, from `OB` or `OW`
+    pub moves_remaining: Option<u32>,
+}
+
+/// Per-move clock information for both colors
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TimeSeries {
+    pub black: Vec<TimeEntry>,
+    pub white: Vec<TimeEntry>,
+}
+
+impl TimeSeries {
+    /// Gets the entries for `color`
+    pub fn get(&self, color: Color) -> &[TimeEntry] {
+        match color {
+            Color::Black => &self.black,
+            Color::White => &self.white,
+        }
+    }
+}
+
+impl GameTree {
+    /// Collects the clock information of every move in the main variation
+    ///
+    /// Each color gets one entry per move it played, in move order.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;TM[600];B[aa]BL[590];W[bb]WL[580];B[cc]BL[570])").unwrap();
+    /// let series = tree.time_series();
+    ///
+    /// let used: Vec<_> = series.black.iter().map(|entry| entry.used).collect();
+    /// assert_eq!(used, vec![Some(10), Some(20)]);
+    /// assert_eq!(series.white[0].move_number, 2);
+    /// ```
+    pub fn time_series(&self) -> TimeSeries {
+        let time_limit = self.nodes.first().and_then(|node| {
+            node.tokens.iter().find_map(|token| match token {
+                SgfToken::TimeLimit(time) => Some(*time),
+                _ => None,
+            })
+        });
+        let mut series = TimeSeries::default();
+        let mut previous = (time_limit, time_limit);
+        let mut move_number = 0;
+        for node in self.iter() {
+            let color = match move_color(node) {
+                Some(color) => color,
+                None => continue,
+            };
+            move_number += 1;
+            let remaining = node.tokens.iter().find_map(|token| match token {
+                SgfToken::Time { color: c, time } if *c == color => Some(*time),
+                _ => None,
+            });
+            let moves_remaining = node.tokens.iter().find_map(|token| match token {
+                SgfToken::MovesRemaining { color: c, moves } if *c == color => Some(*moves),
+                _ => None,
+            });
+            let (last, entries) = match color {
+                Color::Black => (&mut previous.0, &mut series.black),
+                Color::White => (&mut previous.1, &mut series.white),
+            };
+            let used = match (*last, remaining) {
+                (Some(last), Some(remaining)) => last.checked_sub(remaining),
+                _ => None,
+            };
+            if remaining.is_some() {
+                *last = remaining;
+            }
+            entries.push(TimeEntry {
+                move_number,
+                remaining,
+                used,
+                moves_remaining,
+            });
+        }
+        series
+    }
+}
+
+/// Gets the color of the move played in `node`, if any
+pub(crate) fn move_color(node: &GameNode) -> Option<Color> {
+    node.tokens.iter().find_map(|token| match token {
+        SgfToken::Move { color, .. } => Some(*color),
+        _ => None,
+    })
+}
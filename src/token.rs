@@ -1,11 +1,17 @@
-use crate::token::Action::{Move, Pass};
+use crate::board::{is_valid_size, MAX_SIZE};
+use crate::coords::{str_to_action, str_to_coordinates, write_coordinate};
+use crate::token::Action::Move;
 use crate::token::Color::{Black, White};
 use crate::token::Outcome::{Draw, WinnerByForfeit, WinnerByPoints, WinnerByResign, WinnerByTime};
-use crate::{SgfError, SgfErrorKind};
+use crate::{property_info, Real, SgfError, SgfErrorKind, TokenString, ValueType};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt;
 use std::ops::Not;
 
 /// Indicates what color the token is related to
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Color {
     Black,
     White,
@@ -21,16 +27,31 @@ impl Not for Color {
     }
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub enum Outcome {
     WinnerByResign(Color),
     WinnerByForfeit(Color),
-    WinnerByPoints(Color, f32),
+    WinnerByPoints(Color, Real),
     WinnerByTime(Color),
     Draw,
 }
 
 impl Outcome {
+    /// Gets the winning margin, for wins by points
+    pub fn get_points(self) -> Option<f32> {
+        match self {
+            WinnerByPoints(_, points) => Some(points.get()),
+            _ => None,
+        }
+    }
+
+    /// Checks if the game ended without a winner
+    pub fn is_draw(self) -> bool {
+        self == Draw
+    }
+
     pub fn get_winner(self) -> Option<Color> {
         match self {
             WinnerByTime(color)
@@ -42,6 +63,115 @@ impl Outcome {
     }
 }
 
+/// Player rank, from the `BR` and `WR` properties
+///
+/// `SgfToken::PlayerRank` keeps the rank as written, and `Rank::from` reads it. Parses the
+/// common forms like `5k`, `5 kyu`, `2d`, `3p` and `9段`. Ranks are ordered from
+/// 30 kyu to 9 dan, followed by the professional ranks. `Other` holds anything else, including
+/// uncertain ranks like `3k?`, and is ordered after all other ranks by its text.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// assert_eq!(Rank::from("5 kyu"), Rank::Kyu(5));
+/// assert_eq!(Rank::from("9段"), Rank::Dan(9));
+/// assert!(Rank::from("2d") >= Rank::Dan(2));
+/// assert!(Rank::from("1p") > Rank::Dan(9));
+/// assert!(Rank::from("1k") < Rank::Dan(1));
+/// assert_eq!(Rank::from("5 kyu").to_string(), "5k");
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Rank {
+    Kyu(u8),
+    Dan(u8),
+    Pro(u8),
+    Other(String),
+}
+
+impl Rank {
+    /// Converts the rank to a number, where 1 kyu is `0`, 2 kyu is `-1`, 1 dan is `1` and
+    /// professional ranks come after 9 dan
+    pub fn to_numeric(&self) -> Option<i32> {
+        match self {
+            Rank::Kyu(kyu) => Some(1 - i32::from(*kyu)),
+            Rank::Dan(dan) => Some(i32::from(*dan)),
+            Rank::Pro(pro) => Some(9 + i32::from(*pro)),
+            Rank::Other(_) => None,
+        }
+    }
+
+    /// Gets an approximate EGF style rating, where 1 kyu is 2000 and 1 dan is 2100
+    ///
+    /// Each amateur rank adds 100 points, and each professional rank 30 points on top of 7 dan.
+    pub fn to_rating(&self) -> Option<i32> {
+        match self {
+            Rank::Kyu(kyu) => Some(2100 - 100 * i32::from(*kyu)),
+            Rank::Dan(dan) => Some(2000 + 100 * i32::from(*dan)),
+            Rank::Pro(pro) => Some(2700 + 30 * i32::from(*pro)),
+            Rank::Other(_) => None,
+        }
+    }
+}
+
+impl From<&str> for Rank {
+    fn from(s: &str) -> Self {
+        let trimmed = s.trim();
+        let digits = trimmed
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(trimmed.len());
+        let number = trimmed[..digits].parse::<u8>().ok();
+        let unit = trimmed[digits..].trim().to_lowercase();
+        match (number, unit.as_str()) {
+            (Some(n @ 1..=30), "k" | "kyu" | "級" | "级" | "급") => Rank::Kyu(n),
+            (Some(n @ 1..=9), "d" | "dan" | "段" | "단") => Rank::Dan(n),
+            (Some(n @ 1..=9), "p" | "pro") => Rank::Pro(n),
+            _ => Rank::Other(s.to_owned()),
+        }
+    }
+}
+
+/// Generates ranks that are written and read back as the same rank, so `Kyu` is 1 to 30, `Dan`
+/// and `Pro` 1 to 9, and `Other` never holds a rank `Rank::from` reads
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Rank {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=3)? {
+            0 => Rank::Kyu(u.int_in_range(1..=30)?),
+            1 => Rank::Dan(u.int_in_range(1..=9)?),
+            2 => Rank::Pro(u.int_in_range(1..=9)?),
+            _ => Rank::from(arbitrary_text(u)?.as_str()),
+        })
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rank::Kyu(kyu) => write!(f, "{}k", kyu),
+            Rank::Dan(dan) => write!(f, "{}d", dan),
+            Rank::Pro(pro) => write!(f, "{}p", pro),
+            Rank::Other(rank) => f.write_str(rank),
+        }
+    }
+}
+
+impl Ord for Rank {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Rank::Other(a), Rank::Other(b)) => a.cmp(b),
+            (Rank::Other(_), _) => Ordering::Greater,
+            (_, Rank::Other(_)) => Ordering::Less,
+            _ => self.to_numeric().cmp(&other.to_numeric()),
+        }
+    }
+}
+
+impl PartialOrd for Rank {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 ///Provides the used rules for this game.
 ///Because there are many different rules, SGF requires
 ///mandatory names only for a small set of well known rule sets.
@@ -52,7 +182,8 @@ impl Outcome {
 /// "GOE" (the Ing rules of Goe)
 /// "Japanese" (the Nihon-Kiin rule set)
 /// "NZ" (New Zealand rules)
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
 pub enum RuleSet {
     Japanese,
     NZ,
@@ -75,49 +206,267 @@ impl From<&str> for RuleSet {
     }
 }
 
-impl ToString for RuleSet {
-    fn to_string(&self) -> String {
-        match self {
+/// Generates rule sets like `RuleSet::from` reads them, so `Unknown` never holds a known name
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for RuleSet {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=5)? {
+            0 => RuleSet::Japanese,
+            1 => RuleSet::NZ,
+            2 => RuleSet::GOE,
+            3 => RuleSet::AGA,
+            4 => RuleSet::Chinese,
+            _ => RuleSet::from(arbitrary_text(u)?.as_str()),
+        })
+    }
+}
+
+impl RuleSet {
+    /// Checks if the rule set is one of the rule sets named by the specification, or Chinese
+    pub fn is_known(&self) -> bool {
+        !matches!(self, RuleSet::Unknown(_))
+    }
+}
+
+impl fmt::Display for RuleSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
             RuleSet::Japanese => "Japanese",
             RuleSet::NZ => "NZ",
             RuleSet::GOE => "GOE",
             RuleSet::AGA => "AGA",
             RuleSet::Chinese => "Chinese",
             RuleSet::Unknown(v) => v,
-        }
-        .to_owned()
+        };
+        write!(f, "{}", value)
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Action {
-    Move(u8, u8),
+    Move(
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_coordinate))] u8,
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_coordinate))] u8,
+    ),
     Pass,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+/// Games identified by the `GM` property
+///
+/// Codes without a name in the specification are kept as `Other`.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Debug)]
 pub enum Game {
     Go,
+    Othello,
+    Chess,
+    GomokuRenju,
+    NineMensMorris,
+    Backgammon,
+    ChineseChess,
+    Shogi,
+    LinesOfAction,
+    Ataxx,
+    Hex,
+    Jungle,
+    Neutron,
+    PhilosophersFootball,
+    Quadrature,
+    Trax,
+    Tantrix,
+    Amazons,
+    Octi,
+    Gess,
+    Twixt,
+    Zertz,
+    Plateau,
+    Yinsh,
+    Punct,
+    Gobblet,
+    Hive,
+    Exxit,
+    Hnefatafl,
+    Kuba,
+    Tripples,
+    Chase,
+    TumblingDown,
+    Sahara,
+    Byte,
+    Focus,
+    Dvonn,
+    Tamsk,
+    Gipf,
+    Kropki,
     Other(u8),
 }
 
-#[derive(Clone, Eq, PartialEq, Debug)]
+/// `GM` codes and names of the games listed in the FF[4] specification
+const GAMES: &[(Game, u8, &str)] = &[
+    (Game::Go, 1, "Go"),
+    (Game::Othello, 2, "Othello"),
+    (Game::Chess, 3, "Chess"),
+    (Game::GomokuRenju, 4, "Gomoku and Renju"),
+    (Game::NineMensMorris, 5, "Nine Men's Morris"),
+    (Game::Backgammon, 6, "Backgammon"),
+    (Game::ChineseChess, 7, "Chinese chess"),
+    (Game::Shogi, 8, "Shogi"),
+    (Game::LinesOfAction, 9, "Lines of Action"),
+    (Game::Ataxx, 10, "Ataxx"),
+    (Game::Hex, 11, "Hex"),
+    (Game::Jungle, 12, "Jungle"),
+    (Game::Neutron, 13, "Neutron"),
+    (Game::PhilosophersFootball, 14, "Philosopher's Football"),
+    (Game::Quadrature, 15, "Quadrature"),
+    (Game::Trax, 16, "Trax"),
+    (Game::Tantrix, 17, "Tantrix"),
+    (Game::Amazons, 18, "Amazons"),
+    (Game::Octi, 19, "Octi"),
+    (Game::Gess, 20, "Gess"),
+    (Game::Twixt, 21, "Twixt"),
+    (Game::Zertz, 22, "Zertz"),
+    (Game::Plateau, 23, "Plateau"),
+    (Game::Yinsh, 24, "Yinsh"),
+    (Game::Punct, 25, "Punct"),
+    (Game::Gobblet, 26, "Gobblet"),
+    (Game::Hive, 27, "Hive"),
+    (Game::Exxit, 28, "Exxit"),
+    (Game::Hnefatafl, 29, "Hnefatafl"),
+    (Game::Kuba, 30, "Kuba"),
+    (Game::Tripples, 31, "Tripples"),
+    (Game::Chase, 32, "Chase"),
+    (Game::TumblingDown, 33, "Tumbling Down"),
+    (Game::Sahara, 34, "Sahara"),
+    (Game::Byte, 35, "Byte"),
+    (Game::Focus, 36, "Focus"),
+    (Game::Dvonn, 37, "Dvonn"),
+    (Game::Tamsk, 38, "Tamsk"),
+    (Game::Gipf, 39, "Gipf"),
+    (Game::Kropki, 40, "Kropki"),
+];
+
+impl Game {
+    /// Gets the game for a `GM` code
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// assert_eq!(Game::from_code(11), Game::Hex);
+    /// assert_eq!(Game::from_code(99), Game::Other(99));
+    /// assert_eq!(Game::Hex.code(), 11);
+    /// assert_eq!(Game::Hex.name(), Some("Hex"));
+    /// ```
+    pub fn from_code(code: u8) -> Game {
+        GAMES
+            .iter()
+            .find(|(_, c, _)| *c == code)
+            .map_or(Game::Other(code), |(game, _, _)| *game)
+    }
+
+    /// Gets the `GM` code of the game
+    pub fn code(self) -> u8 {
+        match self {
+            Game::Other(code) => code,
+            game => GAMES
+                .iter()
+                .find(|(g, _, _)| *g == game)
+                .map(|(_, code, _)| *code)
+                .expect("All named games are in the table"),
+        }
+    }
+
+    /// Gets the name of the game, or `None` for unknown codes
+    pub fn name(self) -> Option<&'static str> {
+        GAMES
+            .iter()
+            .find(|(g, _, _)| *g == self)
+            .map(|(_, _, name)| *name)
+    }
+
+    /// Gets the board size used when `SZ` is missing, for games where the specification
+    /// defines one
+    pub fn default_size(self) -> Option<(u32, u32)> {
+        match self {
+            Game::Go => Some((19, 19)),
+            Game::Othello | Game::Chess | Game::LinesOfAction => Some((8, 8)),
+            Game::GomokuRenju => Some((15, 15)),
+            Game::Hex => Some((11, 11)),
+            Game::Amazons => Some((10, 10)),
+            _ => None,
+        }
+    }
+}
+
+/// Generates games like `Game::from_code` reads them, so `Other` never holds a named code
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Game {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Game::from_code(u8::arbitrary(u)?))
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Encoding {
     UTF8,
     Other(String),
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum DisplayNodes {
     Children,
     Siblings,
 }
 
+/// Value of the SGF `Double` type, used by annotation properties
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Emphasis {
+    Normal,
+    Emphasized,
+}
+
+impl Emphasis {
+    fn from_str(value: &str) -> Option<Emphasis> {
+        match value {
+            "1" => Some(Emphasis::Normal),
+            "2" => Some(Emphasis::Emphasized),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Emphasis::Normal => "1",
+            Emphasis::Emphasized => "2",
+        }
+    }
+}
+
 /// Enum describing all possible SGF Properties
-#[derive(Debug, PartialEq, Clone)]
+///
+/// Tokens are ordered by variant, in the order they are declared here, and then by value.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let mut tokens = vec![
+///     SgfToken::from_pair("PW", "white"),
+///     SgfToken::from_pair("AB", "bb"),
+///     SgfToken::from_pair("AB", "aa"),
+/// ];
+/// tokens.sort();
+///
+/// assert_eq!(tokens[0], SgfToken::from_pair("AB", "aa"));
+/// assert_eq!(tokens[2], SgfToken::from_pair("PW", "white"));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub enum SgfToken {
     Add {
         color: Color,
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_point))]
         coordinate: (u8, u8),
     },
     Move {
@@ -130,52 +479,85 @@ pub enum SgfToken {
     },
     PlayerName {
         color: Color,
-        name: String,
+        name: TokenString,
     },
     PlayerRank {
         color: Color,
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_text))]
         rank: String,
     },
     Game(Game),
     Rule(RuleSet),
     Result(Outcome),
-    Komi(f32),
-    Event(String),
-    Copyright(String),
-    GameName(String),
+    Komi(Real),
+    Event(TokenString),
+    Copyright(TokenString),
+    GameName(TokenString),
     VariationDisplay {
         nodes: DisplayNodes,
         on_board_display: bool,
     },
-    Place(String),
-    Date(String),
+    Place(TokenString),
+    Date(TokenString),
     Size(u32, u32),
-    FileFormat(u8),
-    Overtime(String),
+    FileFormat(#[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_file_format))] u8),
+    Overtime(TokenString),
     TimeLimit(u32),
     MovesRemaining {
         color: Color,
         moves: u32,
     },
     Handicap(u32),
-    Comment(String),
-    Charset(Encoding),
+    Comment(TokenString),
+    /// Name of the node, `N`, also used as the name of a variation by its first node
+    NodeName(TokenString),
+    Charset(#[cfg_attr(feature = "arbitrary", arbitrary(value = Encoding::UTF8))] Encoding),
     Application {
-        name: String,
-        version: String,
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_application_name))]
+        name: TokenString,
+        version: TokenString,
     },
-    Unknown((String, String)),
-    Invalid((String, String)),
+    Unknown(
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_unknown))]
+        (TokenString, TokenString),
+    ),
+    Invalid(
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_invalid))]
+        (TokenString, TokenString),
+    ),
+    /// A property taking an empty list of points, like `VW[]`, `DD[]`, `TB[]` or `TW[]`
+    ///
+    /// For `VW` and `DD` the empty list resets the value inherited from earlier nodes.
+    EmptyList(
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_empty_list))] TokenString,
+    ),
     Square {
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_point))]
+        coordinate: (u8, u8),
+    },
+    Circle {
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_point))]
         coordinate: (u8, u8),
     },
     Triangle {
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_point))]
         coordinate: (u8, u8),
     },
     Label {
-        label: String,
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_label))]
+        label: TokenString,
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_point))]
         coordinate: (u8, u8),
     },
+    Even(Emphasis),
+    GoodFor {
+        color: Color,
+        emphasis: Emphasis,
+    },
+    Hotspot(Emphasis),
+    Unclear(Emphasis),
+    BadMove(Emphasis),
+    Tesuji(Emphasis),
 }
 
 impl SgfToken {
@@ -183,7 +565,8 @@ impl SgfToken {
     ///
     /// Returns `SgfToken::Unknown((identifier, value))` for tokens without a matching identifier
     ///
-    /// Returns `SgfToken::Invalid((identifier, value))` for tokens with a matching identifier, but invalid value
+    /// Returns `SgfToken::Invalid((identifier, value))` for tokens with a matching identifier, but invalid value,
+    /// and for identifiers without any uppercase letters, like `ab`
     ///
     /// ```rust
     /// use sgf_parser::*;
@@ -195,22 +578,25 @@ impl SgfToken {
     /// assert_eq!(token, SgfToken::Move { color: Color::Black, action: Action::Pass });
     ///
     /// let token = SgfToken::from_pair("B", "not_coord");
-    /// assert_eq!(token, SgfToken::Invalid(("B".to_string(), "not_coord".to_string())));
+    /// assert_eq!(token, SgfToken::Invalid(("B".into(), "not_coord".into())));
     ///
     /// let token = SgfToken::from_pair("FOO", "aa");
-    /// assert_eq!(token, SgfToken::Unknown(("FOO".to_string(), "aa".to_string())));
+    /// assert_eq!(token, SgfToken::Unknown(("FOO".into(), "aa".into())));
     /// ```
     pub fn from_pair(base_ident: &str, value: &str) -> SgfToken {
-        let ident = base_ident
-            .chars()
-            .filter(|c| c.is_uppercase())
-            .collect::<String>();
+        let ident = normalize_identifier(base_ident);
+        if ident.is_empty() {
+            return SgfToken::Invalid((base_ident.into(), value.into()));
+        }
+        if value.is_empty() && matches!(Self::value_type_of(&ident), Some(ValueType::EList(_))) {
+            return SgfToken::EmptyList(ident.into());
+        }
         let token: Option<SgfToken> = match ident.as_ref() {
             "LB" => split_label_text(value).and_then(|(coord, label)| {
                 str_to_coordinates(coord)
                     .ok()
                     .map(|coordinate| SgfToken::Label {
-                        label: label[1..].to_string(),
+                        label: label[1..].into(),
                         coordinate,
                     })
             }),
@@ -222,6 +608,9 @@ impl SgfToken {
             "SQ" => str_to_coordinates(value)
                 .ok()
                 .map(|coordinate| SgfToken::Square { coordinate }),
+            "CR" => str_to_coordinates(value)
+                .ok()
+                .map(|coordinate| SgfToken::Circle { coordinate }),
             "TR" => str_to_coordinates(value)
                 .ok()
                 .map(|coordinate| SgfToken::Triangle { coordinate }),
@@ -231,23 +620,21 @@ impl SgfToken {
                     color: Color::Black,
                     coordinate,
                 }),
-            "B" => move_str_to_coord(value)
-                .ok()
-                .map(|coordinate| SgfToken::Move {
-                    color: Color::Black,
-                    action: coordinate,
-                }),
+            "B" => str_to_action(value).ok().map(|coordinate| SgfToken::Move {
+                color: Color::Black,
+                action: coordinate,
+            }),
             "BL" => value.parse().ok().map(|time| SgfToken::Time {
                 color: Color::Black,
                 time,
             }),
             "PB" => Some(SgfToken::PlayerName {
                 color: Color::Black,
-                name: value.to_string(),
+                name: value.into(),
             }),
             "BR" => Some(SgfToken::PlayerRank {
                 color: Color::Black,
-                rank: value.to_string(),
+                rank: value.to_owned(),
             }),
             "AW" => str_to_coordinates(value)
                 .ok()
@@ -255,26 +642,28 @@ impl SgfToken {
                     color: Color::White,
                     coordinate,
                 }),
-            "W" => move_str_to_coord(value)
-                .ok()
-                .map(|coordinate| SgfToken::Move {
-                    color: Color::White,
-                    action: coordinate,
-                }),
+            "W" => str_to_action(value).ok().map(|coordinate| SgfToken::Move {
+                color: Color::White,
+                action: coordinate,
+            }),
             "WL" => value.parse().ok().map(|time| SgfToken::Time {
                 color: Color::White,
                 time,
             }),
             "PW" => Some(SgfToken::PlayerName {
                 color: Color::White,
-                name: value.to_string(),
+                name: value.into(),
             }),
             "WR" => Some(SgfToken::PlayerRank {
                 color: Color::White,
-                rank: value.to_string(),
+                rank: value.to_owned(),
             }),
-            "RE" => parse_outcome_str(value).ok().map(SgfToken::Result),
-            "KM" => value.parse().ok().map(SgfToken::Komi),
+            "RE" => parse_result_value(value).ok().map(SgfToken::Result),
+            "KM" => value
+                .parse::<f32>()
+                .ok()
+                .and_then(Real::new)
+                .map(SgfToken::Komi),
             "SZ" => {
                 if let Some((width, height)) = split_size_text(value) {
                     Some(SgfToken::Size(width, height))
@@ -284,23 +673,20 @@ impl SgfToken {
             }
             "FF" => value.parse().ok().map(|v| match v {
                 0..=4 => SgfToken::FileFormat(v),
-                _ => SgfToken::Invalid((ident.to_string(), value.to_string())),
+                _ => SgfToken::Invalid((ident.into(), value.into())),
             }),
             "TM" => value.parse().ok().map(SgfToken::TimeLimit),
-            "EV" => Some(SgfToken::Event(value.to_string())),
-            "OT" => Some(SgfToken::Overtime(value.to_string())),
-            "C" => Some(SgfToken::Comment(value.to_string())),
-            "GN" => Some(SgfToken::GameName(value.to_string())),
-            "CR" => Some(SgfToken::Copyright(value.to_string())),
-            "DT" => Some(SgfToken::Date(value.to_string())),
-            "PC" => Some(SgfToken::Place(value.to_string())),
+            "EV" => Some(SgfToken::Event(value.into())),
+            "OT" => Some(SgfToken::Overtime(value.into())),
+            "C" => Some(SgfToken::Comment(value.into())),
+            "N" => Some(SgfToken::NodeName(value.into())),
+            "GN" => Some(SgfToken::GameName(value.into())),
+            "CP" => Some(SgfToken::Copyright(value.into())),
+            "DT" => Some(SgfToken::Date(value.into())),
+            "PC" => Some(SgfToken::Place(value.into())),
             "GM" => match value.parse::<u8>() {
-                Ok(1) => Some(SgfToken::Game(Game::Go)),
-                Ok(n) => Some(SgfToken::Game(Game::Other(n))),
-                Err(_) => Some(SgfToken::Invalid((
-                    base_ident.to_string(),
-                    value.to_string(),
-                ))),
+                Ok(n) => Some(SgfToken::Game(Game::from_code(n))),
+                Err(_) => Some(SgfToken::Invalid((base_ident.into(), value.into()))),
             },
             "CA" => match value.to_string().to_lowercase().as_str() {
                 "utf-8" => Some(SgfToken::Charset(Encoding::UTF8)),
@@ -311,38 +697,252 @@ impl SgfToken {
                     color: Color::Black,
                     moves: n,
                 }),
-                Err(_) => Some(SgfToken::Invalid((
-                    base_ident.to_string(),
-                    value.to_string(),
-                ))),
+                Err(_) => Some(SgfToken::Invalid((base_ident.into(), value.into()))),
             },
             "OW" => match value.parse::<u32>() {
                 Ok(n) => Some(SgfToken::MovesRemaining {
                     color: Color::White,
                     moves: n,
                 }),
-                Err(_) => Some(SgfToken::Invalid((
-                    base_ident.to_string(),
-                    value.to_string(),
-                ))),
+                Err(_) => Some(SgfToken::Invalid((base_ident.into(), value.into()))),
             },
-            "AP" => parse_application_str(value)
-                .ok()
-                .map(|(name, version)| SgfToken::Application { name, version }),
+            "AP" => {
+                parse_application_str(value)
+                    .ok()
+                    .map(|(name, version)| SgfToken::Application {
+                        name: name.into(),
+                        version: version.into(),
+                    })
+            }
             "ST" => parse_variation_display_str(value)
                 .ok()
                 .map(|(nodes, on_board_display)| SgfToken::VariationDisplay {
                     nodes,
                     on_board_display,
                 }),
-            _ => Some(SgfToken::Unknown((
-                base_ident.to_string(),
-                value.to_string(),
-            ))),
+            "DM" => Emphasis::from_str(value).map(SgfToken::Even),
+            "GB" => Emphasis::from_str(value).map(|emphasis| SgfToken::GoodFor {
+                color: Color::Black,
+                emphasis,
+            }),
+            "GW" => Emphasis::from_str(value).map(|emphasis| SgfToken::GoodFor {
+                color: Color::White,
+                emphasis,
+            }),
+            "HO" => Emphasis::from_str(value).map(SgfToken::Hotspot),
+            "UC" => Emphasis::from_str(value).map(SgfToken::Unclear),
+            "BM" => Emphasis::from_str(value).map(SgfToken::BadMove),
+            "TE" => Emphasis::from_str(value).map(SgfToken::Tesuji),
+            _ => Some(SgfToken::Unknown((base_ident.into(), value.into()))),
         };
         match token {
             Some(token) => token,
-            _ => SgfToken::Invalid((base_ident.to_string(), value.to_string())),
+            _ => SgfToken::Invalid((base_ident.into(), value.into())),
+        }
+    }
+
+    /// Converts a `identifier` and `value` pair to a SGF token like `from_pair`, matching the
+    /// identifier according to `case`
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let token = SgfToken::from_pair_with("sz", "9", IdentifierCase::Fold);
+    /// assert_eq!(token, SgfToken::Size(9, 9));
+    ///
+    /// let token = SgfToken::from_pair_with("sz", "9", IdentifierCase::StripLowercase);
+    /// assert_eq!(token, SgfToken::Invalid(("sz".into(), "9".into())));
+    /// ```
+    pub fn from_pair_with(base_ident: &str, value: &str, case: IdentifierCase) -> SgfToken {
+        match case {
+            IdentifierCase::StripLowercase => SgfToken::from_pair(base_ident, value),
+            IdentifierCase::Fold => SgfToken::from_pair(&fold_identifier(base_ident), value),
+        }
+    }
+
+    /// Creates a move token, checking that `coordinate` is on a board of `board_size`
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let token = SgfToken::move_to(Color::Black, (4, 4), (19, 19)).unwrap();
+    /// assert_eq!(token, SgfToken::Move { color: Color::Black, action: Action::Move(4, 4) });
+    ///
+    /// let err = SgfToken::move_to(Color::Black, (20, 4), (19, 19)).unwrap_err();
+    /// assert_eq!(err.kind, SgfErrorKind::InvalidTokenValue);
+    /// ```
+    pub fn move_to(
+        color: Color,
+        coordinate: (u8, u8),
+        board_size: (u8, u8),
+    ) -> Result<SgfToken, SgfError> {
+        check_on_board(coordinate, board_size)?;
+        Ok(SgfToken::Move {
+            color,
+            action: Move(coordinate.0, coordinate.1),
+        })
+    }
+
+    /// Creates a setup token adding a stone, checking that `coordinate` is on a board of
+    /// `board_size`
+    pub fn add(
+        color: Color,
+        coordinate: (u8, u8),
+        board_size: (u8, u8),
+    ) -> Result<SgfToken, SgfError> {
+        check_on_board(coordinate, board_size)?;
+        Ok(SgfToken::Add { color, coordinate })
+    }
+
+    /// Creates a komi token, checking that the value is a finite number
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// assert_eq!(
+    ///     SgfToken::komi(6.5).unwrap(),
+    ///     SgfToken::Komi(Real::new(6.5).unwrap())
+    /// );
+    /// assert!(SgfToken::komi(f32::NAN).is_err());
+    /// ```
+    pub fn komi(komi: f32) -> Result<SgfToken, SgfError> {
+        Real::new(komi)
+            .map(SgfToken::Komi)
+            .ok_or_else(|| SgfErrorKind::InvalidTokenValue.into())
+    }
+
+    /// Creates a board size token, checking that both dimensions can be written as SGF
+    /// coordinates
+    pub fn size(width: u32, height: u32) -> Result<SgfToken, SgfError> {
+        if is_valid_size(width, height) {
+            Ok(SgfToken::Size(width, height))
+        } else {
+            Err(SgfErrorKind::InvalidTokenValue.into())
+        }
+    }
+
+    /// Creates a label token, checking that the label is not empty and that `coordinate` can be
+    /// written as an SGF coordinate
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let token = SgfToken::label((1, 1), "A").unwrap();
+    /// let string_token: String = token.into();
+    /// assert_eq!(string_token, "LB[aa:A]");
+    ///
+    /// assert!(SgfToken::label((1, 1), "").is_err());
+    /// ```
+    pub fn label(coordinate: (u8, u8), label: &str) -> Result<SgfToken, SgfError> {
+        check_on_board(coordinate, (MAX_SIZE, MAX_SIZE))?;
+        if label.is_empty() {
+            return Err(SgfErrorKind::InvalidTokenValue.into());
+        }
+        Ok(SgfToken::Label {
+            label: label.into(),
+            coordinate,
+        })
+    }
+
+    /// Gets the property identifier of the token, like `KM` for `SgfToken::Komi`
+    ///
+    /// Use this, or `SgfToken::grouped`, rather than matching on every variant, since new
+    /// variants are added as more properties are supported.
+    ///
+    /// ```
+    /// use sgf_parser::*;
+    ///
+    /// assert_eq!(SgfToken::from_pair("KM", "6.5").identifier(), "KM");
+    /// assert_eq!(SgfToken::from_pair("XX", "foo").identifier(), "XX");
+    /// ```
+    pub fn identifier(&self) -> &str {
+        match self {
+            SgfToken::Add {
+                color: Color::Black,
+                ..
+            } => "AB",
+            SgfToken::Add {
+                color: Color::White,
+                ..
+            } => "AW",
+            SgfToken::Move {
+                color: Color::Black,
+                ..
+            } => "B",
+            SgfToken::Move {
+                color: Color::White,
+                ..
+            } => "W",
+            SgfToken::Time {
+                color: Color::Black,
+                ..
+            } => "BL",
+            SgfToken::Time {
+                color: Color::White,
+                ..
+            } => "WL",
+            SgfToken::PlayerName {
+                color: Color::Black,
+                ..
+            } => "PB",
+            SgfToken::PlayerName {
+                color: Color::White,
+                ..
+            } => "PW",
+            SgfToken::PlayerRank {
+                color: Color::Black,
+                ..
+            } => "BR",
+            SgfToken::PlayerRank {
+                color: Color::White,
+                ..
+            } => "WR",
+            SgfToken::MovesRemaining {
+                color: Color::Black,
+                ..
+            } => "OB",
+            SgfToken::MovesRemaining {
+                color: Color::White,
+                ..
+            } => "OW",
+            SgfToken::GoodFor {
+                color: Color::Black,
+                ..
+            } => "GB",
+            SgfToken::GoodFor {
+                color: Color::White,
+                ..
+            } => "GW",
+            SgfToken::Game(_) => "GM",
+            SgfToken::Rule(_) => "RU",
+            SgfToken::Result(_) => "RE",
+            SgfToken::Komi(_) => "KM",
+            SgfToken::Event(_) => "EV",
+            SgfToken::Copyright(_) => "CP",
+            SgfToken::GameName(_) => "GN",
+            SgfToken::VariationDisplay { .. } => "ST",
+            SgfToken::Place(_) => "PC",
+            SgfToken::Date(_) => "DT",
+            SgfToken::Size(..) => "SZ",
+            SgfToken::FileFormat(_) => "FF",
+            SgfToken::Overtime(_) => "OT",
+            SgfToken::TimeLimit(_) => "TM",
+            SgfToken::Handicap(_) => "HA",
+            SgfToken::Comment(_) => "C",
+            SgfToken::NodeName(_) => "N",
+            SgfToken::Charset(_) => "CA",
+            SgfToken::Application { .. } => "AP",
+            SgfToken::Unknown((identifier, _)) | SgfToken::Invalid((identifier, _)) => identifier,
+            SgfToken::EmptyList(identifier) => identifier,
+            SgfToken::Square { .. } => "SQ",
+            SgfToken::Circle { .. } => "CR",
+            SgfToken::Triangle { .. } => "TR",
+            SgfToken::Label { .. } => "LB",
+            SgfToken::Even(_) => "DM",
+            SgfToken::Hotspot(_) => "HO",
+            SgfToken::Unclear(_) => "UC",
+            SgfToken::BadMove(_) => "BM",
+            SgfToken::Tesuji(_) => "TE",
         }
     }
 
@@ -426,124 +1026,85 @@ impl SgfToken {
     }
 }
 
-impl Into<String> for &SgfToken {
-    fn into(self) -> String {
+impl SgfToken {
+    /// Writes the token as SGF into `out`, escaping the value
+    ///
+    /// This is the same text as `String::from`, without allocating for each token.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut out = String::new();
+    /// SgfToken::from_pair("C", "a]b").write_to(&mut out).unwrap();
+    /// assert_eq!(out, "C[a\\]b]");
+    /// ```
+    pub fn write_to<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        out.write_str(self.identifier())?;
+        out.write_char('[')?;
+        let mut escaper = Escaper {
+            out: &mut *out,
+            pending_escape: false,
+        };
+        self.write_value(&mut escaper)?;
+        escaper.finish()?;
+        out.write_char(']')
+    }
+
+    /// Writes the unescaped value of the token
+    pub(crate) fn write_value<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        let color = |color: &Color| match color {
+            Black => "B",
+            White => "W",
+        };
         match self {
             SgfToken::Label { label, coordinate } => {
-                let value = coordinate_to_str(*coordinate);
-                format!("LB[{}:{}]", value, label)
+                write_coordinate(*coordinate, out)?;
+                write!(out, ":{}", label)
             }
-            SgfToken::Handicap(nb_stones) => format!("HA[{}]", nb_stones),
-            SgfToken::Rule(rule) => format!("RU[{}]", rule.to_string()),
+            SgfToken::Handicap(nb_stones) => write!(out, "{}", nb_stones),
+            SgfToken::Rule(rule) => write!(out, "{}", rule),
             SgfToken::Result(outcome) => match outcome {
-                WinnerByPoints(color, points) => format!(
-                    "RE[{}+{}]",
-                    match color {
-                        Black => "B",
-                        White => "W",
-                    },
-                    points
-                ),
-                WinnerByResign(color) => format!(
-                    "RE[{}+R]",
-                    match color {
-                        Black => "B",
-                        White => "W",
-                    }
-                ),
-
-                WinnerByTime(color) => format!(
-                    "RE[{}+T]",
-                    match color {
-                        Black => "B",
-                        White => "W",
-                    }
-                ),
-                WinnerByForfeit(color) => format!(
-                    "RE[{}+F]",
-                    match color {
-                        Black => "B",
-                        White => "W",
-                    }
-                ),
-                Draw => "RE[Draw]".to_string(),
-            },
-            SgfToken::Square { coordinate } => {
-                let value = coordinate_to_str(*coordinate);
-                format!("SQ[{}]", value)
-            }
-            SgfToken::Triangle { coordinate } => {
-                let value = coordinate_to_str(*coordinate);
-                format!("TR[{}]", value)
-            }
-            SgfToken::Add { color, coordinate } => {
-                let token = match color {
-                    Color::Black => "AB",
-                    Color::White => "AW",
-                };
-                let value = coordinate_to_str(*coordinate);
-                format!("{}[{}]", token, value)
-            }
-            SgfToken::Move { color, action } => {
-                let token = match color {
-                    Color::Black => "B",
-                    Color::White => "W",
-                };
-                let value = match *action {
-                    Move(x, y) => coordinate_to_str((x, y)),
-                    Pass => String::new(),
-                };
-                format!("{}[{}]", token, value)
-            }
-            SgfToken::Time { color, time } => {
-                let token = match color {
-                    Color::Black => "BL",
-                    Color::White => "WL",
-                };
-                format!("{}[{}]", token, time)
-            }
-            SgfToken::PlayerName { color, name } => {
-                let token = match color {
-                    Color::Black => "PB",
-                    Color::White => "PW",
-                };
-                format!("{}[{}]", token, name)
-            }
-            SgfToken::PlayerRank { color, rank } => {
-                let token = match color {
-                    Color::Black => "BR",
-                    Color::White => "WR",
-                };
-                format!("{}[{}]", token, rank)
-            }
-            SgfToken::Komi(komi) => format!("KM[{}]", komi),
-            SgfToken::FileFormat(v) => format!("FF[{}]", v),
-            SgfToken::Size(width, height) if width == height => format!("SZ[{}]", width),
-            SgfToken::Size(width, height) => format!("SZ[{}:{}]", width, height),
-            SgfToken::TimeLimit(time) => format!("TM[{}]", time),
-            SgfToken::Event(value) => format!("EV[{}]", value),
-            SgfToken::Comment(value) => format!("C[{}]", value),
-            SgfToken::Overtime(value) => format!("OT[{}]", value),
-            SgfToken::GameName(value) => format!("GN[{}]", value),
-            SgfToken::Copyright(value) => format!("CR[{}]", value),
-            SgfToken::Date(value) => format!("DT[{}]", value),
-            SgfToken::Place(value) => format!("PC[{}]", value),
-            SgfToken::Game(game) => format!(
-                "GM[{}]",
-                match game {
-                    Game::Go => &1u8,
-                    Game::Other(n) => n,
+                WinnerByPoints(winner, points) => {
+                    write!(out, "{}+{}", color(winner), points)
                 }
-            ),
-            SgfToken::Charset(_) => "CA[UTF-8]".to_string(),
-            SgfToken::MovesRemaining { color, moves } => format!(
-                "O{}[{}]",
-                match color {
-                    Color::Black => 'B',
-                    Color::White => 'W',
-                },
-                moves
-            ),
+                WinnerByResign(winner) => write!(out, "{}+R", color(winner)),
+                WinnerByTime(winner) => write!(out, "{}+T", color(winner)),
+                WinnerByForfeit(winner) => write!(out, "{}+F", color(winner)),
+                Draw => out.write_str("Draw"),
+            },
+            SgfToken::Square { coordinate }
+            | SgfToken::Circle { coordinate }
+            | SgfToken::Triangle { coordinate }
+            | SgfToken::Add { coordinate, .. } => write_coordinate(*coordinate, out),
+            SgfToken::Move {
+                action: Action::Move(x, y),
+                ..
+            } => write_coordinate((*x, *y), out),
+            SgfToken::Move {
+                action: Action::Pass,
+                ..
+            } => Ok(()),
+            SgfToken::Time { time, .. } => write!(out, "{}", time),
+            SgfToken::Komi(komi) => write!(out, "{}", komi),
+            SgfToken::FileFormat(v) => write!(out, "{}", v),
+            SgfToken::Size(width, height) if width == height => write!(out, "{}", width),
+            SgfToken::Size(width, height) => write!(out, "{}:{}", width, height),
+            SgfToken::TimeLimit(time) => write!(out, "{}", time),
+            SgfToken::PlayerRank { rank, .. } => out.write_str(rank),
+            SgfToken::PlayerName { name: value, .. }
+            | SgfToken::Event(value)
+            | SgfToken::Comment(value)
+            | SgfToken::NodeName(value)
+            | SgfToken::Overtime(value)
+            | SgfToken::GameName(value)
+            | SgfToken::Copyright(value)
+            | SgfToken::Date(value)
+            | SgfToken::Place(value)
+            | SgfToken::Unknown((_, value))
+            | SgfToken::Invalid((_, value)) => out.write_str(value),
+            SgfToken::Game(game) => write!(out, "{}", game.code()),
+            SgfToken::Charset(_) => out.write_str("UTF-8"),
+            SgfToken::MovesRemaining { moves, .. } => write!(out, "{}", moves),
             SgfToken::VariationDisplay {
                 nodes,
                 on_board_display,
@@ -554,18 +1115,182 @@ impl Into<String> for &SgfToken {
                     (DisplayNodes::Children, false) => 2,
                     (DisplayNodes::Siblings, false) => 3,
                 };
-                format!("ST[{}]", num)
+                write!(out, "{}", num)
             }
-            SgfToken::Application { name, version } => format!("AP[{}:{}]", name, version),
-            SgfToken::Unknown((ident, prop)) => format!("{}[{}]", ident, prop),
-            SgfToken::Invalid((ident, prop)) => format!("{}[{}]", ident, prop),
+            SgfToken::Application { name, version } => write!(out, "{}:{}", name, version),
+            SgfToken::Even(emphasis)
+            | SgfToken::GoodFor { emphasis, .. }
+            | SgfToken::Hotspot(emphasis)
+            | SgfToken::Unclear(emphasis)
+            | SgfToken::BadMove(emphasis)
+            | SgfToken::Tesuji(emphasis) => out.write_str(emphasis.as_str()),
+            SgfToken::EmptyList(_) => Ok(()),
         }
     }
 }
 
-impl Into<String> for SgfToken {
-    fn into(self) -> String {
-        (&self).into()
+impl From<&SgfToken> for String {
+    fn from(token: &SgfToken) -> String {
+        let mut out = String::with_capacity(token.estimated_len() + 2);
+        token
+            .write_to(&mut out)
+            .expect("Writing to a String never fails");
+        out
+    }
+}
+
+impl From<SgfToken> for String {
+    fn from(token: SgfToken) -> String {
+        (&token).into()
+    }
+}
+
+impl fmt::Display for SgfToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_to(f)
+    }
+}
+
+/// How the parser matches identifiers that contain lowercase letters, like `sz` or `CoPyright`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum IdentifierCase {
+    /// Remove lowercase letters, as FF[3] used them to spell out long names like `CoPyright`
+    ///
+    /// Common long names where this gives the wrong identifier, like `Komi` and `Size`, are
+    /// read as their FF[4] identifier instead.
+    #[default]
+    StripLowercase,
+    /// Uppercase the identifier when that gives a known property, like `sz` or `Km`
+    ///
+    /// Other identifiers have their lowercase letters removed as with `StripLowercase`.
+    Fold,
+}
+
+/// Uppercases an identifier if that gives a known property, and otherwise returns it unchanged
+fn fold_identifier(identifier: &str) -> Cow<'_, str> {
+    let folded = identifier.to_uppercase();
+    if folded != identifier && property_info(&folded).is_some() {
+        Cow::Owned(folded)
+    } else {
+        Cow::Borrowed(identifier)
+    }
+}
+
+/// FF[3] long identifiers, and the FF[4] identifiers they are read as
+///
+/// Removing the lowercase letters works for most long names, like `AddBlack`, but not for
+/// names like `Komi` or `Size`, so these are looked up first. `CopyRight`, as some older
+/// applications spell it, would otherwise be read as `CR`, a circle mark.
+const LONG_IDENTIFIERS: [(&str, &str); 47] = [
+    ("AddBlack", "AB"),
+    ("AddEmpty", "AE"),
+    ("AddWhite", "AW"),
+    ("Annotator", "AN"),
+    ("Application", "AP"),
+    ("Black", "B"),
+    ("BlackRank", "BR"),
+    ("BlackTeam", "BT"),
+    ("BlackTerritory", "TB"),
+    ("BlackTime", "BL"),
+    ("Charset", "CA"),
+    ("Circle", "CR"),
+    ("Comment", "C"),
+    ("CopyRight", "CP"),
+    ("Copyright", "CP"),
+    ("Date", "DT"),
+    ("Event", "EV"),
+    ("Figure", "FG"),
+    ("FileFormat", "FF"),
+    ("Game", "GM"),
+    ("GameComment", "GC"),
+    ("GameName", "GN"),
+    ("Handicap", "HA"),
+    ("Komi", "KM"),
+    ("Label", "LB"),
+    ("Mark", "MA"),
+    ("MoveNumber", "MN"),
+    ("Name", "N"),
+    ("Opening", "ON"),
+    ("Overtime", "OT"),
+    ("Place", "PC"),
+    ("Player", "PL"),
+    ("PlayerBlack", "PB"),
+    ("PlayerWhite", "PW"),
+    ("Result", "RE"),
+    ("Round", "RO"),
+    ("Rules", "RU"),
+    ("Size", "SZ"),
+    ("Source", "SO"),
+    ("Square", "SQ"),
+    ("TimeLimit", "TM"),
+    ("Triangle", "TR"),
+    ("User", "US"),
+    ("White", "W"),
+    ("WhiteRank", "WR"),
+    ("WhiteTeam", "WT"),
+    ("WhiteTerritory", "TW"),
+];
+
+/// Gets the FF[4] identifier of an FF[3] long identifier, like `KM` for `Komi`
+pub(crate) fn long_identifier(identifier: &str) -> Option<&'static str> {
+    LONG_IDENTIFIERS
+        .iter()
+        .find(|(long, _)| *long == identifier)
+        .map(|(_, short)| *short)
+}
+
+/// Strips all non uppercase characters from an identifier, as FF[3] allowed lowercase letters
+///
+/// Long identifiers in `LONG_IDENTIFIERS` are replaced by their FF[4] identifier instead. Only
+/// allocates when the identifier contains characters that have to be removed, which is rare
+/// for modern files.
+pub(crate) fn normalize_identifier(identifier: &str) -> Cow<'_, str> {
+    if identifier.chars().all(|c| c.is_uppercase()) {
+        Cow::Borrowed(identifier)
+    } else if let Some(short) = long_identifier(identifier) {
+        Cow::Borrowed(short)
+    } else {
+        Cow::Owned(identifier.chars().filter(|c| c.is_uppercase()).collect())
+    }
+}
+
+/// Converts a value of the obsolete FF[3] `L` property to a label, lettered by the position
+/// of the value in the property, as `LB[aa:a]`
+///
+/// Returns `SgfToken::Invalid` for values that aren't points, and past the 26th value.
+pub(crate) fn obsolete_label(identifier: &str, value: &str, index: usize) -> SgfToken {
+    match str_to_coordinates(value) {
+        Ok(coordinate) if index < 26 => SgfToken::Label {
+            label: char::from(b'a' + index as u8).to_string().into(),
+            coordinate,
+        },
+        _ => SgfToken::Invalid((identifier.into(), value.into())),
+    }
+}
+
+/// Converts the value at `index` of a property to a token, like `SgfToken::from_pair_with`,
+/// but also reading the obsolete `L` property
+pub(crate) fn from_property_value(
+    identifier: &str,
+    value: &str,
+    index: usize,
+    case: IdentifierCase,
+) -> SgfToken {
+    if normalize_identifier(identifier) == "L" {
+        obsolete_label(identifier, value, index)
+    } else {
+        SgfToken::from_pair_with(identifier, value, case)
+    }
+}
+
+/// Checks that a 1-based coordinate is inside a board of `board_size`
+fn check_on_board(coordinate: (u8, u8), board_size: (u8, u8)) -> Result<(), SgfError> {
+    let (x, y) = coordinate;
+    let (width, height) = board_size;
+    if x == 0 || y == 0 || x > width || y > height {
+        Err(SgfErrorKind::InvalidTokenValue.into())
+    } else {
+        Ok(())
     }
 }
 
@@ -578,21 +1303,133 @@ fn split_size_text(input: &str) -> Option<(u32, u32)> {
     Some((width, height))
 }
 
-/// Converts goban coordinates to string representation
-fn coordinate_to_str(coordinate: (u8, u8)) -> String {
-    fn to_char(c: u8) -> char {
-        (c + if c < 27 { 96 } else { 38 }) as char
+/// Escapes property values written through it, so that they parse back to the same value
+///
+/// Values are stored as they appear in the SGF text, so escape sequences are kept as they are.
+/// Unescaped `]` characters, and a trailing unpaired `\`, are escaped.
+struct Escaper<'a, W: fmt::Write> {
+    out: &'a mut W,
+    /// The last character written was a `\` starting an escape sequence
+    pending_escape: bool,
+}
+
+impl<'a, W: fmt::Write> Escaper<'a, W> {
+    /// Completes an unpaired `\` at the end of the value
+    fn finish(self) -> fmt::Result {
+        if self.pending_escape {
+            self.out.write_char('\\')?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, W: fmt::Write> fmt::Write for Escaper<'a, W> {
+    fn write_str(&mut self, value: &str) -> fmt::Result {
+        let mut written = 0;
+        for (index, c) in value.char_indices() {
+            if self.pending_escape {
+                self.pending_escape = false;
+            } else if c == '\\' {
+                self.pending_escape = true;
+            } else if c == ']' {
+                self.out.write_str(&value[written..index])?;
+                self.out.write_char('\\')?;
+                written = index;
+            }
+        }
+        self.out.write_str(&value[written..])
+    }
+}
+
+/// Generates text as it appears in SGF, with `]` and a trailing `\` escaped like the writer does
+#[cfg(feature = "arbitrary")]
+pub(crate) fn arbitrary_text(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<String> {
+    let value = <&str as arbitrary::Arbitrary>::arbitrary(u)?;
+    let mut out = String::with_capacity(value.len());
+    let mut escaper = Escaper {
+        out: &mut out,
+        pending_escape: false,
+    };
+    fmt::Write::write_str(&mut escaper, value)
+        .and_then(|_| escaper.finish())
+        .expect("Writing to a String never fails");
+    Ok(out)
+}
+
+/// Generates a point on the largest board
+#[cfg(feature = "arbitrary")]
+fn arbitrary_point(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<(u8, u8)> {
+    Ok((arbitrary_coordinate(u)?, arbitrary_coordinate(u)?))
+}
+
+#[cfg(feature = "arbitrary")]
+fn arbitrary_coordinate(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<u8> {
+    u.int_in_range(1..=MAX_SIZE)
+}
+
+/// Generates the `FF` versions the parser accepts
+#[cfg(feature = "arbitrary")]
+fn arbitrary_file_format(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<u8> {
+    u.int_in_range(0..=4)
+}
+
+/// Generates a non-empty label, since `LB[aa:]` has no label to read
+#[cfg(feature = "arbitrary")]
+fn arbitrary_label(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<TokenString> {
+    let mut label = arbitrary_text(u)?;
+    if label.is_empty() {
+        label.push(char::from(u.int_in_range(b'A'..=b'Z')?));
+    }
+    Ok(label.into())
+}
+
+/// Generates an application name without `:`, which separates the name from the version
+#[cfg(feature = "arbitrary")]
+fn arbitrary_application_name(
+    u: &mut arbitrary::Unstructured<'_>,
+) -> arbitrary::Result<TokenString> {
+    Ok(arbitrary_text(u)?.replace(':', "").into())
+}
+
+/// Generates an unknown property, with an identifier starting with `X` that no property uses
+#[cfg(feature = "arbitrary")]
+fn arbitrary_unknown(
+    u: &mut arbitrary::Unstructured<'_>,
+) -> arbitrary::Result<(TokenString, TokenString)> {
+    let mut identifier = String::from("X");
+    for _ in 0..u.int_in_range(0..=2)? {
+        identifier.push(char::from(u.int_in_range(b'A'..=b'Z')?));
     }
+    Ok((identifier.into(), arbitrary_text(u)?.into()))
+}
 
-    let x = to_char(coordinate.0);
-    let y = to_char(coordinate.1);
+/// Generates a known property with a value that can not be read, using `?` when the generated
+/// value happens to be valid
+#[cfg(feature = "arbitrary")]
+fn arbitrary_invalid(
+    u: &mut arbitrary::Unstructured<'_>,
+) -> arbitrary::Result<(TokenString, TokenString)> {
+    const IDENTIFIERS: &[&str] = &[
+        "AB", "AP", "AW", "B", "BL", "BM", "CR", "DM", "FF", "GB", "GM", "GW", "HA", "HO", "KM",
+        "LB", "OB", "OW", "SQ", "ST", "SZ", "TE", "TM", "TR", "UC", "W", "WL",
+    ];
+    let identifier = *u.choose(IDENTIFIERS)?;
+    let value = arbitrary_text(u)?;
+    match SgfToken::from_pair(identifier, &value) {
+        SgfToken::Invalid(pair) => Ok(pair),
+        _ => Ok((identifier.into(), "?".into())),
+    }
+}
 
-    format!("{}{}", x, y)
+/// Generates the identifier of a property taking a list of points that may be empty
+#[cfg(feature = "arbitrary")]
+fn arbitrary_empty_list(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<TokenString> {
+    Ok(TokenString::from(*u.choose(&["DD", "TB", "TW", "VW"])?))
 }
 
 /// If possible, splits a label text into coordinate and label pair
 fn split_label_text(input: &str) -> Option<(&str, &str)> {
-    if input.len() >= 4 {
+    if input.len() >= 4 && input.is_char_boundary(2) {
         Some(input.split_at(2))
     } else {
         None
@@ -609,12 +1446,12 @@ fn parse_variation_display_str(input: &str) -> Result<(DisplayNodes, bool), SgfE
     }
 }
 
-fn parse_application_str(input: &str) -> Result<(String, String), SgfError> {
+fn parse_application_str(input: &str) -> Result<(&str, &str), SgfError> {
     let index = input
         .find(':')
         .ok_or_else(|| SgfError::from(SgfErrorKind::ParseError))?;
     let (name, version) = input.split_at(index);
-    Ok((name.to_string(), version[1..].to_string()))
+    Ok((name, &version[1..]))
 }
 
 /// Provides the result of the game. It is MANDATORY to use the
@@ -644,19 +1481,21 @@ fn parse_outcome_str(s: &str) -> Result<Outcome, SgfError> {
         return Err(SgfError::from(SgfErrorKind::ParseError));
     }
 
-    let winner: Color = match &winner_option[0] as &str {
+    let winner: Color = match winner_option[0] {
         "B" => Black,
         "W" => White,
         _ => return Err(SgfError::from(SgfErrorKind::ParseError)),
     };
 
-    match &winner_option[1] as &str {
+    match winner_option[1] {
         "F" | "Forfeit" => Ok(WinnerByForfeit(winner)),
         "R" | "Resign" => Ok(WinnerByResign(winner)),
         "T" | "Time" => Ok(WinnerByTime(winner)),
         points => {
-            if let Ok(outcome) = points
+            if let Some(outcome) = points
                 .parse::<f32>()
+                .ok()
+                .and_then(Real::new)
                 .map(|score| WinnerByPoints(winner, score))
             {
                 Ok(outcome)
@@ -667,38 +1506,11 @@ fn parse_outcome_str(s: &str) -> Result<Outcome, SgfError> {
     }
 }
 
-fn move_str_to_coord(input: &str) -> Result<Action, SgfError> {
-    if input.is_empty() {
-        Ok(Pass)
-    } else {
-        match str_to_coordinates(input) {
-            Ok(coordinates) => Ok(Move(coordinates.0, coordinates.1)),
-            Err(e) => Err(e),
-        }
-    }
-}
-
-/// Converts a string describing goban coordinates to numeric coordinates
-fn str_to_coordinates(input: &str) -> Result<(u8, u8), SgfError> {
-    if input.len() != 2 {
-        Err(SgfErrorKind::ParseError.into())
-    } else {
-        let coords = input
-            .as_bytes()
-            .iter()
-            .map(|c| convert_u8_to_coordinate(*c))
-            .collect::<Vec<_>>();
-        Ok((coords[0], coords[1]))
-    }
-}
-
-/// Converts a u8 char to numeric coordinates
-///
-#[inline]
-fn convert_u8_to_coordinate(c: u8) -> u8 {
-    if c > 96 {
-        c - 96
-    } else {
-        c - 38
-    }
+/// Reads the result with `parse_outcome_str`, falling back to Japanese, Chinese and Korean
+/// labels like `黒中押し勝ち` or `흑 3집반승` when the `localized-results` feature is enabled
+fn parse_result_value(s: &str) -> Result<Outcome, SgfError> {
+    let outcome = parse_outcome_str(s);
+    #[cfg(feature = "localized-results")]
+    let outcome = outcome.or_else(|error| crate::dialect::parse_result_label(s).ok_or(error));
+    outcome
 }
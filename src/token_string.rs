@@ -0,0 +1,224 @@
+use std::borrow::{Borrow, Cow};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Text held by a `SgfToken`, like a comment, a player name or the value of an unknown property
+///
+/// The text can't be changed, so cloning only copies a pointer: constant text is borrowed
+/// with `TokenString::from_static`, and other text is reference counted. Cloning a large tree
+/// to edit a variation shares all token text with the original.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// const PASS_COMMENT: TokenString = TokenString::from_static("Pass");
+///
+/// let token = SgfToken::Comment(PASS_COMMENT);
+/// assert_eq!(token.to_string(), "C[Pass]");
+///
+/// let name = TokenString::from("Honinbo Shusaku");
+/// let copy = name.clone();
+/// assert!(TokenString::ptr_eq(&name, &copy));
+/// assert_eq!(copy, "Honinbo Shusaku");
+/// ```
+#[derive(Clone)]
+pub struct TokenString(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    Static(&'static str),
+    Shared(Arc<str>),
+}
+
+impl TokenString {
+    /// Creates a token string borrowing `value`, without allocating
+    pub const fn from_static(value: &'static str) -> TokenString {
+        TokenString(Repr::Static(value))
+    }
+
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            Repr::Static(value) => value,
+            Repr::Shared(value) => value,
+        }
+    }
+
+    /// Number of bytes of the text stored on the heap, zero for static text
+    pub(crate) fn heap_len(&self) -> usize {
+        match &self.0 {
+            Repr::Static(_) => 0,
+            Repr::Shared(value) => value.len(),
+        }
+    }
+
+    /// Gets the reference counted storage of the text, `None` for static text
+    pub(crate) fn as_shared(&self) -> Option<&Arc<str>> {
+        match &self.0 {
+            Repr::Static(_) => None,
+            Repr::Shared(value) => Some(value),
+        }
+    }
+
+    /// Checks if both strings use the same storage, so cloning one of them didn't copy the text
+    pub fn ptr_eq(a: &TokenString, b: &TokenString) -> bool {
+        std::ptr::eq(a.as_str(), b.as_str())
+    }
+}
+
+impl Default for TokenString {
+    fn default() -> Self {
+        TokenString::from_static("")
+    }
+}
+
+impl Deref for TokenString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for TokenString {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for TokenString {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<&str> for TokenString {
+    fn from(value: &str) -> Self {
+        if value.is_empty() {
+            TokenString::default()
+        } else {
+            TokenString(Repr::Shared(Arc::from(value)))
+        }
+    }
+}
+
+impl From<String> for TokenString {
+    fn from(value: String) -> Self {
+        if value.is_empty() {
+            TokenString::default()
+        } else {
+            TokenString(Repr::Shared(Arc::from(value)))
+        }
+    }
+}
+
+impl From<&String> for TokenString {
+    fn from(value: &String) -> Self {
+        TokenString::from(value.as_str())
+    }
+}
+
+impl From<Cow<'_, str>> for TokenString {
+    fn from(value: Cow<'_, str>) -> Self {
+        match value {
+            Cow::Borrowed(value) => TokenString::from(value),
+            Cow::Owned(value) => TokenString::from(value),
+        }
+    }
+}
+
+impl From<Arc<str>> for TokenString {
+    fn from(value: Arc<str>) -> Self {
+        TokenString(Repr::Shared(value))
+    }
+}
+
+impl From<TokenString> for String {
+    fn from(value: TokenString) -> Self {
+        value.as_str().to_owned()
+    }
+}
+
+impl fmt::Debug for TokenString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for TokenString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq for TokenString {
+    fn eq(&self, other: &TokenString) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for TokenString {}
+
+impl PartialEq<str> for TokenString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for TokenString {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for TokenString {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<TokenString> for str {
+    fn eq(&self, other: &TokenString) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<TokenString> for &str {
+    fn eq(&self, other: &TokenString) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl PartialEq<TokenString> for String {
+    fn eq(&self, other: &TokenString) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl Hash for TokenString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl Ord for TokenString {
+    fn cmp(&self, other: &TokenString) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl PartialOrd for TokenString {
+    fn partial_cmp(&self, other: &TokenString) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Generates text as it appears in SGF, since token strings hold values as they are written
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for TokenString {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(TokenString::from(crate::token::arbitrary_text(u)?))
+    }
+}
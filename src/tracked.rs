@@ -0,0 +1,526 @@
+use std::fmt;
+
+use crate::{Action, Color, GameNode, GameTree, SgfError, SgfErrorKind, SgfToken, TreePath};
+
+/// A change made to a `TrackedGameTree`
+///
+/// Mutations hold the removed or replaced values, so every mutation can be reverted with its
+/// `inverse`. The `Display` implementation gives a single line text form, suitable for logs or
+/// for sending to collaborators.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mutation {
+    /// A node was inserted at `path`
+    NodeAdded { path: TreePath, node: GameNode },
+    /// The node at `path` was removed
+    NodeRemoved { path: TreePath, node: GameNode },
+    /// A token was inserted at `index` in the node at `path`
+    TokenAdded {
+        path: TreePath,
+        index: usize,
+        token: SgfToken,
+    },
+    /// The token at `index` in the node at `path` was replaced
+    TokenChanged {
+        path: TreePath,
+        index: usize,
+        old: SgfToken,
+        new: SgfToken,
+    },
+    /// The token at `index` in the node at `path` was removed
+    TokenRemoved {
+        path: TreePath,
+        index: usize,
+        token: SgfToken,
+    },
+    /// A variation was inserted, reached by picking `variations` at each branch point
+    VariationAdded {
+        variations: Vec<usize>,
+        tree: GameTree,
+    },
+    /// The variation reached by picking `variations` at each branch point was removed
+    VariationRemoved {
+        variations: Vec<usize>,
+        tree: GameTree,
+    },
+}
+
+impl Mutation {
+    /// Gets the mutation reverting this one
+    pub fn inverse(&self) -> Mutation {
+        match self.clone() {
+            Mutation::NodeAdded { path, node } => Mutation::NodeRemoved { path, node },
+            Mutation::NodeRemoved { path, node } => Mutation::NodeAdded { path, node },
+            Mutation::TokenAdded { path, index, token } => {
+                Mutation::TokenRemoved { path, index, token }
+            }
+            Mutation::TokenChanged {
+                path,
+                index,
+                old,
+                new,
+            } => Mutation::TokenChanged {
+                path,
+                index,
+                old: new,
+                new: old,
+            },
+            Mutation::TokenRemoved { path, index, token } => {
+                Mutation::TokenAdded { path, index, token }
+            }
+            Mutation::VariationAdded { variations, tree } => {
+                Mutation::VariationRemoved { variations, tree }
+            }
+            Mutation::VariationRemoved { variations, tree } => {
+                Mutation::VariationAdded { variations, tree }
+            }
+        }
+    }
+
+    /// Applies the mutation to `tree`
+    ///
+    /// Fails without changing `tree` if the mutation does not fit it, like when a path does not
+    /// exist, or a removed value differs from the one recorded.
+    pub fn apply(&self, tree: &mut GameTree) -> Result<(), SgfError> {
+        match self {
+            Mutation::NodeAdded { path, node } => {
+                let sequence = tree.get_sequence_mut(&path.variations)?;
+                if path.node > sequence.nodes.len() {
+                    return Err(SgfErrorKind::NodeNotFound.into());
+                }
+                sequence.nodes.insert(path.node, node.clone());
+            }
+            Mutation::NodeRemoved { path, node } => {
+                let sequence = tree.get_sequence_mut(&path.variations)?;
+                if sequence.nodes.get(path.node) != Some(node) {
+                    return Err(SgfErrorKind::NodeNotFound.into());
+                }
+                sequence.nodes.remove(path.node);
+            }
+            Mutation::TokenAdded { path, index, token } => {
+                let tokens = get_tokens_mut(tree, path)?;
+                if *index > tokens.len() {
+                    return Err(SgfErrorKind::InvalidTokenValue.into());
+                }
+                tokens.insert(*index, token.clone());
+            }
+            Mutation::TokenChanged {
+                path,
+                index,
+                old,
+                new,
+            } => {
+                let tokens = get_tokens_mut(tree, path)?;
+                match tokens.get_mut(*index) {
+                    Some(token) if token == old => *token = new.clone(),
+                    _ => return Err(SgfErrorKind::InvalidTokenValue.into()),
+                }
+            }
+            Mutation::TokenRemoved { path, index, token } => {
+                let tokens = get_tokens_mut(tree, path)?;
+                if tokens.get(*index) != Some(token) {
+                    return Err(SgfErrorKind::InvalidTokenValue.into());
+                }
+                tokens.remove(*index);
+            }
+            Mutation::VariationAdded {
+                variations,
+                tree: variation,
+            } => {
+                let (index, parent) = split_variations(variations)?;
+                let parent = tree.get_sequence_mut(parent)?;
+                if index > parent.variations.len() {
+                    return Err(SgfErrorKind::VariationNotFound.into());
+                }
+                parent.variations.insert(index, variation.clone());
+            }
+            Mutation::VariationRemoved {
+                variations,
+                tree: variation,
+            } => {
+                let (index, parent) = split_variations(variations)?;
+                let parent = tree.get_sequence_mut(parent)?;
+                if parent.variations.get(index) != Some(variation) {
+                    return Err(SgfErrorKind::VariationNotFound.into());
+                }
+                parent.variations.remove(index);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Mutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mutation::NodeAdded { path, node } => {
+                write!(f, "node-added {} {}", path, String::from(node))
+            }
+            Mutation::NodeRemoved { path, node } => {
+                write!(f, "node-removed {} {}", path, String::from(node))
+            }
+            Mutation::TokenAdded { path, index, token } => {
+                write!(f, "token-added {} {} {}", path, index, token)
+            }
+            Mutation::TokenChanged {
+                path,
+                index,
+                old,
+                new,
+            } => write!(f, "token-changed {} {} {} {}", path, index, old, new),
+            Mutation::TokenRemoved { path, index, token } => {
+                write!(f, "token-removed {} {} {}", path, index, token)
+            }
+            Mutation::VariationAdded { variations, tree } => {
+                write!(f, "variation-added {} {}", fmt_variations(variations), tree)
+            }
+            Mutation::VariationRemoved { variations, tree } => {
+                write!(
+                    f,
+                    "variation-removed {} {}",
+                    fmt_variations(variations),
+                    tree
+                )
+            }
+        }
+    }
+}
+
+/// A `GameTree` that records every change made through it as a `Mutation`
+///
+/// Changes are also kept in an undo history. Each change is a separate step, unless it is made
+/// between `begin_transaction` and `commit`, which groups the changes into a single step.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let tree: GameTree = parse("(;SZ[9];B[aa])").unwrap();
+/// let mut tracked = TrackedGameTree::new(tree);
+///
+/// tracked.add_token(&TreePath::new(vec![], 1), SgfToken::from_pair("C", "hello")).unwrap();
+/// tracked.remove_node(&TreePath::new(vec![], 0)).unwrap_err();
+///
+/// let events: Vec<String> = tracked.take_events().iter().map(|e| e.to_string()).collect();
+/// assert_eq!(events, vec!["token-added :1 1 C[hello]"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TrackedGameTree {
+    tree: GameTree,
+    events: Vec<Mutation>,
+    undo: Vec<Vec<Mutation>>,
+    redo: Vec<Vec<Mutation>>,
+    transaction: Option<Vec<Mutation>>,
+}
+
+impl TrackedGameTree {
+    /// Starts tracking changes to `tree`
+    pub fn new(tree: GameTree) -> Self {
+        TrackedGameTree {
+            tree,
+            ..TrackedGameTree::default()
+        }
+    }
+
+    /// Gets the current tree
+    pub fn tree(&self) -> &GameTree {
+        &self.tree
+    }
+
+    /// Stops tracking, and returns the current tree
+    pub fn into_inner(self) -> GameTree {
+        self.tree
+    }
+
+    /// Gets all mutations recorded since the last `take_events`
+    pub fn events(&self) -> &[Mutation] {
+        &self.events
+    }
+
+    /// Removes and returns all recorded mutations
+    pub fn take_events(&mut self) -> Vec<Mutation> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Applies `mutation` and records it
+    ///
+    /// Clears the redo history.
+    pub fn apply(&mut self, mutation: Mutation) -> Result<(), SgfError> {
+        mutation.apply(&mut self.tree)?;
+        self.events.push(mutation.clone());
+        self.redo.clear();
+        match &mut self.transaction {
+            Some(transaction) => transaction.push(mutation),
+            None => self.undo.push(vec![mutation]),
+        }
+        Ok(())
+    }
+
+    /// Starts grouping changes into a single undo step
+    ///
+    /// Has no effect if a transaction is already open.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree = TrackedGameTree::new(parse("(;SZ[9];B[aa])").unwrap());
+    /// tree.begin_transaction();
+    /// tree.push_move(Color::White, Action::Move(2, 2)).unwrap();
+    /// tree.push_move(Color::Black, Action::Move(3, 3)).unwrap();
+    /// tree.commit();
+    ///
+    /// assert!(tree.undo().unwrap());
+    /// assert_eq!(tree.tree().count_max_nodes(), 2);
+    ///
+    /// assert!(tree.redo().unwrap());
+    /// assert_eq!(tree.tree().count_max_nodes(), 4);
+    /// ```
+    pub fn begin_transaction(&mut self) {
+        if self.transaction.is_none() {
+            self.transaction = Some(vec![]);
+        }
+    }
+
+    /// Ends the open transaction, adding its changes to the history as a single step
+    pub fn commit(&mut self) {
+        if let Some(transaction) = self.transaction.take() {
+            if !transaction.is_empty() {
+                self.undo.push(transaction);
+            }
+        }
+    }
+
+    /// Checks if there is a step to undo
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty() || self.transaction.as_ref().is_some_and(|t| !t.is_empty())
+    }
+
+    /// Checks if there is a step to redo
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Reverts the last step, committing any open transaction first
+    ///
+    /// The reverting mutations are recorded as events. Returns `false` if there was nothing to
+    /// undo.
+    pub fn undo(&mut self) -> Result<bool, SgfError> {
+        self.commit();
+        let step = match self.undo.pop() {
+            Some(step) => step,
+            None => return Ok(false),
+        };
+        let inverse: Vec<Mutation> = step.iter().rev().map(Mutation::inverse).collect();
+        if let Err(err) = self.replay(&inverse) {
+            self.undo.push(step);
+            return Err(err);
+        }
+        self.redo.push(step);
+        Ok(true)
+    }
+
+    /// Applies the last undone step again
+    ///
+    /// Returns `false` if there was nothing to redo.
+    pub fn redo(&mut self) -> Result<bool, SgfError> {
+        self.commit();
+        let step = match self.redo.pop() {
+            Some(step) => step,
+            None => return Ok(false),
+        };
+        if let Err(err) = self.replay(&step) {
+            self.redo.push(step);
+            return Err(err);
+        }
+        self.undo.push(step);
+        Ok(true)
+    }
+
+    /// Appends a node to the end of the main variation, see `GameTree::push_node`
+    pub fn push_node(&mut self, node: GameNode) -> Result<(), SgfError> {
+        let (variations, index) = self.tree.main_tip();
+        self.insert_node(&TreePath::new(variations, index), node)
+    }
+
+    /// Appends a move to the end of the main variation, see `GameTree::push_move`
+    pub fn push_move(&mut self, color: Color, action: Action) -> Result<(), SgfError> {
+        self.push_node(GameNode::from(vec![SgfToken::Move { color, action }]))
+    }
+
+    /// Applies `mutations` in order, reverting them all if one fails
+    fn replay(&mut self, mutations: &[Mutation]) -> Result<(), SgfError> {
+        let mut tree = self.tree.clone();
+        for mutation in mutations {
+            mutation.apply(&mut tree)?;
+        }
+        self.tree = tree;
+        self.events.extend(mutations.iter().cloned());
+        Ok(())
+    }
+
+    /// Inserts `node` at `path`, moving later nodes of the sequence back
+    ///
+    /// The root node can not be replaced, and other nodes can not hold root tokens.
+    pub fn insert_node(&mut self, path: &TreePath, node: GameNode) -> Result<(), SgfError> {
+        if is_root(path) && !self.tree.nodes.is_empty() {
+            return Err(SgfErrorKind::InvalidRootTokenPlacement.into());
+        }
+        check_root_tokens(path, std::slice::from_ref(&node))?;
+        self.apply(Mutation::NodeAdded {
+            path: path.clone(),
+            node,
+        })
+    }
+
+    /// Removes the node at `path`, and returns it
+    pub fn remove_node(&mut self, path: &TreePath) -> Result<GameNode, SgfError> {
+        if is_root(path) {
+            return Err(SgfErrorKind::InvalidRootTokenPlacement.into());
+        }
+        let node = self.tree.get_node(path)?.clone();
+        self.apply(Mutation::NodeRemoved {
+            path: path.clone(),
+            node: node.clone(),
+        })?;
+        Ok(node)
+    }
+
+    /// Adds `token` to the end of the node at `path`
+    pub fn add_token(&mut self, path: &TreePath, token: SgfToken) -> Result<(), SgfError> {
+        check_root_token(path, &token)?;
+        let index = self.tree.get_node(path)?.tokens.len();
+        self.apply(Mutation::TokenAdded {
+            path: path.clone(),
+            index,
+            token,
+        })
+    }
+
+    /// Replaces the token at `index` in the node at `path`, and returns the old token
+    pub fn set_token(
+        &mut self,
+        path: &TreePath,
+        index: usize,
+        token: SgfToken,
+    ) -> Result<SgfToken, SgfError> {
+        check_root_token(path, &token)?;
+        let old = get_token(&self.tree, path, index)?;
+        self.apply(Mutation::TokenChanged {
+            path: path.clone(),
+            index,
+            old: old.clone(),
+            new: token,
+        })?;
+        Ok(old)
+    }
+
+    /// Removes the token at `index` in the node at `path`, and returns it
+    pub fn remove_token(&mut self, path: &TreePath, index: usize) -> Result<SgfToken, SgfError> {
+        let token = get_token(&self.tree, path, index)?;
+        self.apply(Mutation::TokenRemoved {
+            path: path.clone(),
+            index,
+            token: token.clone(),
+        })?;
+        Ok(token)
+    }
+
+    /// Adds `variation` after the existing variations of the sequence reached by `variations`
+    pub fn add_variation(
+        &mut self,
+        variations: &[usize],
+        variation: GameTree,
+    ) -> Result<(), SgfError> {
+        if !variation.is_valid_variation() {
+            return Err(SgfErrorKind::InvalidRootTokenPlacement.into());
+        }
+        let index = self.tree.get_sequence(variations)?.variations.len();
+        let mut path = variations.to_vec();
+        path.push(index);
+        self.apply(Mutation::VariationAdded {
+            variations: path,
+            tree: variation,
+        })
+    }
+
+    /// Removes the variation reached by picking `variations` at each branch point, and returns
+    /// it
+    pub fn remove_variation(&mut self, variations: &[usize]) -> Result<GameTree, SgfError> {
+        let variation = self.tree.get_sequence(variations)?.clone();
+        self.apply(Mutation::VariationRemoved {
+            variations: variations.to_vec(),
+            tree: variation.clone(),
+        })?;
+        Ok(variation)
+    }
+}
+
+impl GameTree {
+    /// Gets the variation path to the last sequence of the main variation, and its length
+    fn main_tip(&self) -> (Vec<usize>, usize) {
+        let mut variations = vec![];
+        let mut tree = self;
+        while let Some(variation) = tree.variations.first() {
+            variations.push(0);
+            tree = variation;
+        }
+        (variations, tree.nodes.len())
+    }
+
+    /// Checks that no node in the tree holds root tokens
+    fn is_valid_variation(&self) -> bool {
+        self.nodes
+            .iter()
+            .all(|node| node.tokens.iter().all(|token| !token.is_root_token()))
+            && self.variations.iter().all(GameTree::is_valid_variation)
+    }
+}
+
+fn is_root(path: &TreePath) -> bool {
+    path.variations.is_empty() && path.node == 0
+}
+
+fn check_root_token(path: &TreePath, token: &SgfToken) -> Result<(), SgfError> {
+    if token.is_root_token() && !is_root(path) {
+        Err(SgfErrorKind::InvalidRootTokenPlacement.into())
+    } else {
+        Ok(())
+    }
+}
+
+fn check_root_tokens(path: &TreePath, nodes: &[GameNode]) -> Result<(), SgfError> {
+    nodes
+        .iter()
+        .flat_map(|node| node.tokens.iter())
+        .try_for_each(|token| check_root_token(path, token))
+}
+
+fn get_token(tree: &GameTree, path: &TreePath, index: usize) -> Result<SgfToken, SgfError> {
+    tree.get_node(path)?
+        .tokens
+        .get(index)
+        .cloned()
+        .ok_or_else(|| SgfErrorKind::InvalidTokenValue.into())
+}
+
+fn get_tokens_mut<'a>(
+    tree: &'a mut GameTree,
+    path: &TreePath,
+) -> Result<&'a mut Vec<SgfToken>, SgfError> {
+    tree.get_sequence_mut(&path.variations)?
+        .nodes
+        .get_mut(path.node)
+        .map(|node| &mut node.tokens)
+        .ok_or_else(|| SgfErrorKind::NodeNotFound.into())
+}
+
+/// Splits a variation path into the index of the variation, and the path to its parent
+fn split_variations(variations: &[usize]) -> Result<(usize, &[usize]), SgfError> {
+    variations
+        .split_last()
+        .map(|(index, parent)| (*index, parent))
+        .ok_or_else(|| SgfErrorKind::VariationNotFound.into())
+}
+
+fn fmt_variations(variations: &[usize]) -> String {
+    let variations: Vec<String> = variations.iter().map(|v| v.to_string()).collect();
+    variations.join(".")
+}
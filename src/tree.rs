@@ -1,7 +1,13 @@
-use crate::{GameNode, SgfError, SgfErrorKind, SgfToken};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::node::NodeWriter;
+use crate::{
+    parse, Action, Color, GameNode, SgfError, SgfErrorKind, SgfToken, TokenOrder, TreePath,
+};
 
 /// A game tree, containing it's nodes and possible variations following the last node
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GameTree {
     pub nodes: Vec<GameNode>,
     pub variations: Vec<GameTree>,
@@ -31,6 +37,76 @@ impl GameTree {
         count + variation_count
     }
 
+    /// Counts all nodes in the tree, including every variation
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;B[dc];W[ef](;B[aa])(;B[cc];W[ee]))").unwrap();
+    ///
+    /// assert_eq!(tree.total_node_count(), 5);
+    /// assert_eq!(tree.count_max_nodes(), 4);
+    /// ```
+    pub fn total_node_count(&self) -> usize {
+        self.nodes.len()
+            + self
+                .variations
+                .iter()
+                .map(|v| v.total_node_count())
+                .sum::<usize>()
+    }
+
+    /// Counts the number of distinct lines in the tree, which is the number of leaf sequences
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;B[dc](;W[ef](;B[aa])(;B[bb]))(;W[cc]))").unwrap();
+    /// assert_eq!(tree.leaf_count(), 3);
+    /// ```
+    pub fn leaf_count(&self) -> usize {
+        if self.variations.is_empty() {
+            1
+        } else {
+            self.variations.iter().map(|v| v.leaf_count()).sum()
+        }
+    }
+
+    /// Gets the deepest nesting of variations, 0 for a tree without variations
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;B[dc](;W[ef](;B[aa])(;B[bb]))(;W[cc]))").unwrap();
+    /// assert_eq!(tree.max_depth(), 2);
+    /// assert_eq!(tree.variations[1].max_depth(), 0);
+    /// ```
+    pub fn max_depth(&self) -> usize {
+        self.variations
+            .iter()
+            .map(|v| v.max_depth() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Counts all variations in the tree, including variations nested in other variations
+    ///
+    /// Unlike `count_variations`, which only counts the variations following the last node.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;B[dc](;W[ef](;B[aa])(;B[bb]))(;W[cc]))").unwrap();
+    /// assert_eq!(tree.variation_count_recursive(), 4);
+    /// assert_eq!(tree.count_variations(), 2);
+    /// ```
+    pub fn variation_count_recursive(&self) -> usize {
+        self.variations
+            .iter()
+            .map(|v| v.variation_count_recursive() + 1)
+            .sum()
+    }
+
     /// Gets a vector of all nodes that contain a `SgfToken::Unknown` token
     ///
     /// ```rust
@@ -149,7 +225,136 @@ impl GameTree {
     /// assert_eq!(count, tree.count_max_nodes());
     /// ```
     pub fn iter(&self) -> GameTreeIterator<'_> {
-        GameTreeIterator::new(self)
+        GameTreeIterator::new(self, vec![], 0)
+    }
+
+    /// Gets an iterator starting at the node at `path`, and following the first variation at
+    /// each branch point after it
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;B[dc];W[ef](;B[aa])(;B[cc];W[ee]))").unwrap();
+    ///
+    /// let iter = tree.iter_from(&TreePath::new(vec![1], 0)).unwrap();
+    /// assert_eq!(iter.count(), 2);
+    /// ```
+    pub fn iter_from(&self, path: &TreePath) -> Result<GameTreeIterator<'_>, SgfError> {
+        let tree = self.get_sequence(&path.variations)?;
+        if path.node >= tree.nodes.len() {
+            return Err(SgfErrorKind::NodeNotFound.into());
+        }
+        Ok(GameTreeIterator::new(
+            tree,
+            path.variations.clone(),
+            path.node,
+        ))
+    }
+
+    /// Gets the node at `path`
+    pub fn get_node(&self, path: &TreePath) -> Result<&GameNode, SgfError> {
+        self.get_sequence(&path.variations)?
+            .nodes
+            .get(path.node)
+            .ok_or_else(|| SgfErrorKind::NodeNotFound.into())
+    }
+
+    /// Gets all nodes from the root up to, and including, the node at `path`
+    pub fn get_line_to(&self, path: &TreePath) -> Result<Vec<&GameNode>, SgfError> {
+        self.get_node(path)?;
+        let mut line = vec![];
+        let mut tree = self;
+        for variation in &path.variations {
+            line.extend(tree.nodes.iter());
+            tree = &tree.variations[*variation];
+        }
+        line.extend(tree.nodes[..=path.node].iter());
+        Ok(line)
+    }
+
+    /// Iterates backwards from the node at `path` to the root
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;B[dc];W[ef](;B[aa])(;B[cc];W[ee]))").unwrap();
+    ///
+    /// let mut iter = tree.iter_rev_from(&TreePath::new(vec![1], 1)).unwrap();
+    /// assert_eq!(iter.next(), Some(&GameNode::from(vec![SgfToken::from_pair("W", "ee")])));
+    /// assert_eq!(iter.count(), 3);
+    /// ```
+    pub fn iter_rev_from(
+        &self,
+        path: &TreePath,
+    ) -> Result<impl Iterator<Item = &GameNode>, SgfError> {
+        Ok(self.get_line_to(path)?.into_iter().rev())
+    }
+
+    /// Iterates backwards over the main variation, from the last node to the root
+    pub fn iter_rev(&self) -> impl Iterator<Item = &GameNode> {
+        self.iter().collect::<Vec<_>>().into_iter().rev()
+    }
+
+    /// Gets the sequence reached by picking `variations` at each branch point
+    pub(crate) fn get_sequence(&self, variations: &[usize]) -> Result<&GameTree, SgfError> {
+        variations.iter().try_fold(self, |tree, variation| {
+            tree.variations
+                .get(*variation)
+                .ok_or_else(|| SgfErrorKind::VariationNotFound.into())
+        })
+    }
+
+    /// Gets the sequence reached by picking `variations` at each branch point, for modification
+    pub(crate) fn get_sequence_mut(
+        &mut self,
+        variations: &[usize],
+    ) -> Result<&mut GameTree, SgfError> {
+        variations.iter().try_fold(self, |tree, variation| {
+            tree.variations
+                .get_mut(*variation)
+                .ok_or_else(|| SgfErrorKind::VariationNotFound.into())
+        })
+    }
+
+    /// Appends a node to the end of the main variation, and returns the SGF text of the node
+    ///
+    /// Only the branch points of the main variation are walked, so appending to a game without
+    /// variations takes amortized constant time. The returned text, like `;W[dd]`, can be
+    /// broadcast to clients holding a copy of the tree.
+    ///
+    /// Fails if `node` contains root tokens, and the tree already has nodes.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree = parse("(;SZ[19];B[pd])").unwrap();
+    /// let text = tree.push_move(Color::White, Action::Move(4, 4)).unwrap();
+    /// assert_eq!(text, ";W[dd]");
+    ///
+    /// let sgf: String = tree.into();
+    /// assert_eq!(sgf, "(;SZ[19];B[pd];W[dd])");
+    /// ```
+    pub fn push_node(&mut self, node: GameNode) -> Result<String, SgfError> {
+        if !self.nodes.is_empty() && node.tokens.iter().any(|token| token.is_root_token()) {
+            return Err(SgfErrorKind::InvalidRootTokenPlacement.into());
+        }
+        let text = String::from(&node);
+        self.main_tip_mut().nodes.push(node);
+        Ok(text)
+    }
+
+    /// Appends a node with a single move to the end of the main variation, see `push_node`
+    pub fn push_move(&mut self, color: Color, action: Action) -> Result<String, SgfError> {
+        self.push_node(GameNode::from(vec![SgfToken::Move { color, action }]))
+    }
+
+    /// Gets the last sequence of the main variation
+    fn main_tip_mut(&mut self) -> &mut GameTree {
+        let mut tree = self;
+        while !tree.variations.is_empty() {
+            tree = &mut tree.variations[0];
+        }
+        tree
     }
 
     /// Checks if the tree is valid. `self` is assumed to be a root tree, so it can contain
@@ -194,40 +399,283 @@ impl GameTree {
     }
 }
 
-impl Into<String> for &GameTree {
-    fn into(self) -> String {
-        let nodes = self
-            .nodes
-            .iter()
-            .map(|n| -> String { n.into() })
-            .collect::<String>();
-        let variations = self
-            .variations
-            .iter()
-            .map(|n| -> String { n.into() })
-            .collect::<String>();
-        format!("({}{})", nodes, variations)
+impl GameTree {
+    /// Writes the tree as SGF into `out`, the same text as `String::from`
+    ///
+    /// All nodes are written straight into `out`, so writing into a `String` sized with
+    /// `estimated_sgf_len` allocates only a few buffers for the whole tree.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee](;W[cc])(;W[gg]))").unwrap();
+    /// let mut out = String::with_capacity(tree.estimated_sgf_len());
+    /// tree.write_to(&mut out).unwrap();
+    /// assert_eq!(out, "(;SZ[9];B[ee](;W[cc])(;W[gg]))");
+    /// ```
+    pub fn write_to<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        write_tree(self, &mut NodeWriter::default(), out)
+    }
+}
+
+fn write_tree<W: fmt::Write>(tree: &GameTree, writer: &mut NodeWriter, out: &mut W) -> fmt::Result {
+    out.write_char('(')?;
+    for node in &tree.nodes {
+        writer.write(&node.tokens, TokenOrder::Identifier, out)?;
+    }
+    for variation in &tree.variations {
+        write_tree(variation, writer, out)?;
+    }
+    out.write_char(')')
+}
+
+impl From<&GameTree> for String {
+    fn from(tree: &GameTree) -> String {
+        let mut out = String::with_capacity(tree.estimated_sgf_len());
+        tree.write_to(&mut out)
+            .expect("Writing to a String never fails");
+        out
+    }
+}
+
+impl From<GameTree> for String {
+    fn from(tree: GameTree) -> String {
+        (&tree).into()
+    }
+}
+
+impl fmt::Display for GameTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_to(f)
+    }
+}
+
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let tree: GameTree = "(;B[aa];W[bb])".parse().unwrap();
+/// assert_eq!(tree.to_string(), "(;B[aa];W[bb])");
+/// ```
+impl FromStr for GameTree {
+    type Err = SgfError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse(input)
+    }
+}
+
+/// Iterates over the main variation, like `GameTree::iter`
+impl<'a> IntoIterator for &'a GameTree {
+    type Item = &'a GameNode;
+    type IntoIter = GameTreeIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Generates trees that `parse` reads back as the same tree
+///
+/// Every sequence has at least one node, and root tokens like `SZ` are only in the first node.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for GameTree {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        arbitrary_tree(u, true)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+fn arbitrary_tree(
+    u: &mut arbitrary::Unstructured<'_>,
+    is_root: bool,
+) -> arbitrary::Result<GameTree> {
+    use arbitrary::Arbitrary;
+
+    let mut nodes = vec![GameNode::arbitrary(u)?];
+    nodes.extend(Vec::<GameNode>::arbitrary(u)?);
+    let skip = if is_root { 1 } else { 0 };
+    for node in nodes.iter_mut().skip(skip) {
+        node.tokens.retain(|token| !token.is_root_token());
+    }
+    let mut variations = vec![];
+    while bool::arbitrary(u)? {
+        variations.push(arbitrary_tree(u, false)?);
+    }
+    Ok(GameTree { nodes, variations })
+}
+
+/// Builds a tree with a single sequence of nodes and no variations
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let tree: GameTree = parse("(;SZ[9];B[ee](;W[cc];B[gg])(;W[gg]))").unwrap();
+/// let main_line: GameTree = tree.iter().cloned().collect();
+/// assert_eq!(main_line.to_string(), "(;SZ[9];B[ee];W[cc];B[gg])");
+/// ```
+impl std::iter::FromIterator<GameNode> for GameTree {
+    fn from_iter<I: IntoIterator<Item = GameNode>>(iter: I) -> Self {
+        GameTree {
+            nodes: iter.into_iter().collect(),
+            variations: vec![],
+        }
     }
 }
 
-impl Into<String> for GameTree {
-    fn into(self) -> String {
-        (&self).into()
+/// Appends nodes to the end of the main variation, like `GameTree::push_node`
+///
+/// Unlike `push_node`, root tokens in the appended nodes are not checked.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let mut tree: GameTree = parse("(;SZ[9];B[ee](;W[cc])(;W[gg]))").unwrap();
+/// tree.extend(parse("(;B[gc];W[cg])").unwrap().nodes);
+/// assert_eq!(tree.to_string(), "(;SZ[9];B[ee](;W[cc];B[gc];W[cg])(;W[gg]))");
+/// ```
+impl Extend<GameNode> for GameTree {
+    fn extend<I: IntoIterator<Item = GameNode>>(&mut self, iter: I) {
+        self.main_tip_mut().nodes.extend(iter)
     }
 }
 
+/// Callback choosing the variation to continue with at a branch point, see
+/// `GameTreeIterator::with_chooser`
+type Chooser<'a> = Box<dyn FnMut(&[VariationPreview<'a>]) -> usize + 'a>;
+
+/// Iterates over the nodes of a single line through a `GameTree`
+///
+/// The line follows the first variation at each branch point, unless another one is picked
+/// with `pick_variation`, `with_path` or `with_chooser`.
 pub struct GameTreeIterator<'a> {
-    tree: &'a GameTree,
-    index: usize,
-    variation: usize,
+    pub(crate) tree: &'a GameTree,
+    /// Variations picked to reach `tree` from the root
+    pub(crate) variations: Vec<usize>,
+    pub(crate) index: usize,
+    pub(crate) variation: usize,
+    /// Whether `variation` was picked by `pick_variation` or `with_path`, so `chooser` is
+    /// not asked for it
+    pub(crate) picked: bool,
+    /// Variations to pick at the next branch points, in reverse order
+    pub(crate) planned: Vec<usize>,
+    pub(crate) chooser: Option<Chooser<'a>>,
 }
 
 impl<'a> GameTreeIterator<'a> {
-    fn new(game_tree: &'a GameTree) -> Self {
+    pub(crate) fn new(tree: &'a GameTree, variations: Vec<usize>, index: usize) -> Self {
         GameTreeIterator {
-            tree: game_tree,
-            index: 0,
+            tree,
+            variations,
+            index,
             variation: 0,
+            picked: false,
+            planned: vec![],
+            chooser: None,
+        }
+    }
+
+    /// Follows `path` at the next branch points, starting with the one at the end of the
+    /// current sequence
+    ///
+    /// Branch points after the end of `path` are decided by `with_chooser`, or take the first
+    /// variation. Fails if an index in `path` is out of range for its branch point.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;B[aa](;W[bb](;B[cc])(;B[dd]))(;W[ee]))").unwrap();
+    /// let iter = tree.iter().with_path(&[0, 1]).unwrap();
+    /// let moves: Vec<String> = iter.map(|node| node.to_string()).collect();
+    /// assert_eq!(moves, vec![";B[aa]", ";W[bb]", ";B[dd]"]);
+    ///
+    /// let err = tree.iter().with_path(&[1, 0]).err().unwrap();
+    /// assert_eq!(
+    ///     err.kind,
+    ///     SgfErrorKind::VariationOutOfRange { requested: 0, available: 0 }
+    /// );
+    /// ```
+    pub fn with_path(mut self, path: &[usize]) -> Result<Self, SgfError> {
+        let mut tree = self.tree;
+        for variation in path {
+            let available = tree.variations.len();
+            tree = tree.variations.get(*variation).ok_or_else(|| {
+                SgfError::from(SgfErrorKind::VariationOutOfRange {
+                    requested: *variation,
+                    available,
+                })
+            })?;
+        }
+        self.planned = path.iter().rev().copied().collect();
+        self.variation = self.planned_variation();
+        Ok(self)
+    }
+
+    /// Calls `chooser` at each branch point to pick the variation to continue with
+    ///
+    /// `chooser` gets a preview of each variation, like `variations` returns, and is called when
+    /// the iterator enters a sequence ending in a branch point that is not decided by
+    /// `with_path`. A choice made this way can still be changed with `pick_variation`.
+    /// Returning an index out of range ends the iteration at the branch point.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree =
+    ///     parse("(;B[aa](;W[bb](;B[cc])(;N[Main]B[dd]))(;N[Main]W[ee];B[ff]))").unwrap();
+    /// let mut iter = tree.iter().with_chooser(|previews| {
+    ///     previews
+    ///         .iter()
+    ///         .position(|preview| preview.name == Some("Main"))
+    ///         .unwrap_or(0)
+    /// });
+    /// assert_eq!(iter.by_ref().count(), 3);
+    /// assert_eq!(iter.variations_taken(), &[1]);
+    /// ```
+    pub fn with_chooser(
+        mut self,
+        chooser: impl FnMut(&[VariationPreview<'a>]) -> usize + 'a,
+    ) -> Self {
+        self.chooser = Some(Box::new(chooser));
+        if !self.picked {
+            self.variation = self.planned_variation();
+        }
+        self
+    }
+
+    /// Gets the variation picked at each branch point passed so far, from the root of the tree
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;B[aa](;W[bb](;B[cc])(;B[dd]))(;W[ee]))").unwrap();
+    /// let mut iter = tree.iter().with_path(&[0, 1]).unwrap();
+    /// iter.next();
+    /// assert!(iter.variations_taken().is_empty());
+    /// iter.next();
+    /// assert_eq!(iter.variations_taken(), &[0]);
+    /// iter.next();
+    /// assert_eq!(iter.variations_taken(), &[0, 1]);
+    /// ```
+    pub fn variations_taken(&self) -> &[usize] {
+        &self.variations
+    }
+
+    /// Decides the variation to continue with after the current sequence
+    fn planned_variation(&mut self) -> usize {
+        self.picked = false;
+        if self.tree.variations.is_empty() {
+            return 0;
+        }
+        if let Some(variation) = self.planned.pop() {
+            self.picked = true;
+            return variation;
+        }
+        match self.chooser.as_mut() {
+            Some(chooser) => {
+                let previews = preview_variations(self.tree);
+                chooser(&previews)
+            }
+            None => 0,
         }
     }
 
@@ -242,14 +690,96 @@ impl<'a> GameTreeIterator<'a> {
     }
 
     /// Picks a varation in the current `GameTree` to continue with, once the nodes haves been exhausted
+    ///
+    /// Once the iterator has moved into the picked variation, `count_variations` and
+    /// `pick_variation` refer to the sub-branches of that variation, which start out with the
+    /// first sub-branch picked.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;B[aa](;W[bb](;B[cc])(;B[dd]))(;W[ee]))").unwrap();
+    /// let mut iter = tree.iter();
+    ///
+    /// iter.next();
+    /// iter.pick_variation(0).unwrap();
+    /// iter.next();
+    /// assert_eq!(iter.count_variations(), 2);
+    /// iter.pick_variation(1).unwrap();
+    /// assert_eq!(iter.next(), Some(&GameNode::from(vec![SgfToken::from_pair("B", "dd")])));
+    /// ```
     pub fn pick_variation(&mut self, variation: usize) -> Result<usize, SgfError> {
-        if variation < self.tree.variations.len() {
+        let available = self.tree.variations.len();
+        if variation < available {
             self.variation = variation;
+            self.picked = true;
             Ok(self.variation)
         } else {
-            Err(SgfErrorKind::VariationNotFound.into())
+            Err(SgfErrorKind::VariationOutOfRange {
+                requested: variation,
+                available,
+            }
+            .into())
         }
     }
+
+    /// Gets a preview of each variation in the current `GameTree`, to choose one for
+    /// `pick_variation`
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;B[aa](;N[Attach]W[ab])(;C[Quiet];W[dd]))").unwrap();
+    /// let mut iter = tree.iter();
+    /// let previews = iter.variations();
+    /// assert_eq!(previews[0].name, Some("Attach"));
+    /// assert_eq!(previews[1].first_move, Some((Color::White, Action::Move(4, 4))));
+    ///
+    /// let err = iter.pick_variation(2).unwrap_err();
+    /// assert_eq!(
+    ///     err.kind,
+    ///     SgfErrorKind::VariationOutOfRange { requested: 2, available: 2 }
+    /// );
+    /// ```
+    pub fn variations(&self) -> Vec<VariationPreview<'a>> {
+        preview_variations(self.tree)
+    }
+}
+
+fn preview_variations(tree: &GameTree) -> Vec<VariationPreview<'_>> {
+    tree.variations
+        .iter()
+        .enumerate()
+        .map(|(index, variation)| VariationPreview {
+            index,
+            first_move: variation.nodes.iter().find_map(|node| {
+                node.tokens.iter().find_map(|token| match token {
+                    SgfToken::Move { color, action } => Some((*color, *action)),
+                    _ => None,
+                })
+            }),
+            name: variation.nodes.first().and_then(|node| {
+                node.tokens.iter().find_map(|token| match token {
+                    SgfToken::NodeName(name) => Some(name.as_str()),
+                    _ => None,
+                })
+            }),
+            nodes: variation.nodes.len(),
+        })
+        .collect()
+}
+
+/// The start of a variation, returned by `GameTreeIterator::variations`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VariationPreview<'a> {
+    /// Index to pass to `GameTreeIterator::pick_variation`
+    pub index: usize,
+    /// The first move in the nodes of the variation, before any sub-branches
+    pub first_move: Option<(Color, Action)>,
+    /// Name of the variation, from the `N` token of its first node
+    pub name: Option<&'a str>,
+    /// Number of nodes before the variation branches or ends
+    pub nodes: usize,
 }
 
 impl<'a> Iterator for GameTreeIterator<'a> {
@@ -259,17 +789,15 @@ impl<'a> Iterator for GameTreeIterator<'a> {
         match self.tree.nodes.get(self.index) {
             Some(node) => {
                 self.index += 1;
-                Some(&node)
+                Some(node)
             }
             None => {
-                if !self.tree.variations.is_empty() {
-                    self.tree = &self.tree.variations[self.variation];
-                    self.index = 0;
-                    self.variation = 0;
-                    self.next()
-                } else {
-                    None
-                }
+                let variation = self.tree.variations.get(self.variation)?;
+                self.tree = variation;
+                self.variations.push(self.variation);
+                self.index = 0;
+                self.variation = self.planned_variation();
+                self.next()
             }
         }
     }
@@ -0,0 +1,97 @@
+use crate::time::move_color;
+use crate::{Color, GameNode, GameTree, SgfError, SgfToken, TreePath};
+
+impl GameTree {
+    /// Gets the color to play after the node at `path`
+    ///
+    /// This is the opposite color of the last move on the line to the node, unless a `PL`
+    /// token after that move says otherwise. Without moves, White plays first in handicap
+    /// games and Black in all others.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;HA[2]AB[cc][gg];W[ee];B[ce];PL[B])").unwrap();
+    /// assert_eq!(tree.color_to_play(&TreePath::new(vec![], 0)).unwrap(), Color::White);
+    /// assert_eq!(tree.color_to_play(&TreePath::new(vec![], 2)).unwrap(), Color::White);
+    /// assert_eq!(tree.color_to_play(&TreePath::new(vec![], 3)).unwrap(), Color::Black);
+    /// ```
+    pub fn color_to_play(&self, path: &TreePath) -> Result<Color, SgfError> {
+        Ok(next_color(self.get_line_to(path)?))
+    }
+
+    /// Finds moves of the same color as the move before them, and returns their paths
+    ///
+    /// Such moves are legal, but usually a mistake in the record. Moves announced by a `PL`
+    /// token after the previous move are not reported.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee];B[cc](;W[gg])(;PL[B];B[gc]))").unwrap();
+    /// assert_eq!(tree.same_color_moves(), vec![TreePath::new(vec![], 2)]);
+    /// ```
+    pub fn same_color_moves(&self) -> Vec<TreePath> {
+        let mut found = vec![];
+        find_same_color_moves(self, &mut vec![], (None, None), &mut found);
+        found
+    }
+}
+
+/// Gets the color to play after `line`, see `GameTree::color_to_play`
+pub(crate) fn next_color<'a>(line: impl IntoIterator<Item = &'a GameNode>) -> Color {
+    let mut line = line.into_iter().peekable();
+    let handicap = line.peek().is_some_and(|root| {
+        root.tokens
+            .iter()
+            .any(|token| matches!(token, SgfToken::Handicap(stones) if *stones >= 2))
+    });
+    let mut next = if handicap { Color::White } else { Color::Black };
+    for node in line {
+        if let Some(color) = player_to_play(node) {
+            next = color;
+        }
+        if let Some(color) = move_color(node) {
+            next = !color;
+        }
+    }
+    next
+}
+
+/// Walks the tree with the color of the last move and of a `PL` token since then
+fn find_same_color_moves(
+    tree: &GameTree,
+    variations: &mut Vec<usize>,
+    mut state: (Option<Color>, Option<Color>),
+    found: &mut Vec<TreePath>,
+) {
+    for (index, node) in tree.nodes.iter().enumerate() {
+        if let Some(color) = player_to_play(node) {
+            state.1 = Some(color);
+        }
+        if let Some(color) = move_color(node) {
+            let (last, player) = state;
+            if last == Some(color) && player != Some(color) {
+                found.push(TreePath::new(variations.clone(), index));
+            }
+            state = (Some(color), None);
+        }
+    }
+    for (index, variation) in tree.variations.iter().enumerate() {
+        variations.push(index);
+        find_same_color_moves(variation, variations, state, found);
+        variations.pop();
+    }
+}
+
+/// Reads the `PL` token of the node, kept as an unknown token
+pub(crate) fn player_to_play(node: &GameNode) -> Option<Color> {
+    node.tokens.iter().find_map(|token| match token {
+        SgfToken::Unknown((identifier, value)) if identifier == "PL" => match value.trim() {
+            "B" | "b" => Some(Color::Black),
+            "W" | "w" => Some(Color::White),
+            _ => None,
+        },
+        _ => None,
+    })
+}
@@ -0,0 +1,271 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::SgfToken;
+
+/// Value types defined by the FF[4] specification
+///
+/// `List` and `EList` describe properties that take one or more, or zero or more, values. The
+/// element type describes each of the values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueType {
+    None,
+    Number,
+    Real,
+    Double,
+    Color,
+    SimpleText,
+    Text,
+    Point,
+    Move,
+    Stone,
+    Compose(Box<ValueType>, Box<ValueType>),
+    List(Box<ValueType>),
+    EList(Box<ValueType>),
+    Either(Box<ValueType>, Box<ValueType>),
+}
+
+impl ValueType {
+    /// Checks if a single property value is valid for this type
+    ///
+    /// For `List` and `EList` the value is checked against the element type, with `EList`
+    /// also accepting an empty value.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let value_type = SgfToken::value_type_of("KM").unwrap();
+    /// assert!(value_type.is_valid("6.5"));
+    /// assert!(!value_type.is_valid("six"));
+    ///
+    /// let value_type = SgfToken::value_type_of("LB").unwrap();
+    /// assert!(value_type.is_valid("aa:label"));
+    /// assert!(!value_type.is_valid("aa"));
+    /// ```
+    pub fn is_valid(&self, value: &str) -> bool {
+        match self {
+            ValueType::None => value.is_empty(),
+            ValueType::Number => is_number(value),
+            ValueType::Real => is_real(value),
+            ValueType::Double => value == "1" || value == "2",
+            ValueType::Color => value == "B" || value == "W",
+            ValueType::SimpleText | ValueType::Text => true,
+            ValueType::Point | ValueType::Stone => is_point(value),
+            ValueType::Move => value.is_empty() || is_point(value),
+            ValueType::Compose(first, second) => match split_compose(value) {
+                Some((a, b)) => first.is_valid(a) && second.is_valid(b),
+                None => false,
+            },
+            ValueType::List(element) => element.is_valid(value),
+            ValueType::EList(element) => value.is_empty() || element.is_valid(value),
+            ValueType::Either(a, b) => a.is_valid(value) || b.is_valid(value),
+        }
+    }
+}
+
+impl SgfToken {
+    /// Gets the FF[4] value type for a property identifier
+    ///
+    /// Works for all identifiers in the specification, including the ones without a dedicated
+    /// `SgfToken` variant. Returns `None` for identifiers outside the specification.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// assert_eq!(SgfToken::value_type_of("KM"), Some(ValueType::Real));
+    /// assert_eq!(
+    ///     SgfToken::value_type_of("AB"),
+    ///     Some(ValueType::List(Box::new(ValueType::Stone)))
+    /// );
+    /// assert_eq!(SgfToken::value_type_of("FOO"), None);
+    /// ```
+    pub fn value_type_of(identifier: &str) -> Option<ValueType> {
+        use ValueType::*;
+        let list = |value_type| List(Box::new(value_type));
+        let elist = |value_type| EList(Box::new(value_type));
+        let compose = |a, b| Compose(Box::new(a), Box::new(b));
+        let value_type = match identifier {
+            "B" | "W" => Move,
+            "KO" | "DO" | "IT" => None,
+            "MN" | "FF" | "GM" | "ST" | "OB" | "OW" | "PM" | "HA" => Number,
+            "AB" | "AW" => list(Stone),
+            "AE" | "CR" | "MA" | "SL" | "SQ" | "TR" => list(Point),
+            "PL" => Color,
+            "C" | "GC" => Text,
+            "DM" | "GB" | "GW" | "HO" | "UC" | "BM" | "TE" => Double,
+            "N" | "CA" | "AN" | "BR" | "BT" | "CP" | "DT" | "EV" | "GN" | "ON" | "OT" | "PB"
+            | "PC" | "PW" | "RE" | "RO" | "RU" | "SO" | "US" | "WR" | "WT" => SimpleText,
+            "V" | "TM" | "BL" | "WL" | "KM" => Real,
+            "AR" | "LN" => list(compose(Point, Point)),
+            "DD" | "VW" | "TB" | "TW" => elist(Point),
+            "LB" => list(compose(Point, SimpleText)),
+            "AP" => compose(SimpleText, SimpleText),
+            "SZ" => Either(Box::new(Number), Box::new(compose(Number, Number))),
+            "FG" => Either(Box::new(None), Box::new(compose(Number, SimpleText))),
+            _ => return Option::None,
+        };
+        Some(value_type)
+    }
+}
+
+fn is_number(value: &str) -> bool {
+    let digits = value
+        .strip_prefix(|c| c == '+' || c == '-')
+        .unwrap_or(value);
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Formats a number as a SGF `Real` value, like the `6.5` of `KM[6.5]`
+///
+/// Whole numbers are written without decimals, and other numbers with a single decimal when
+/// that reads back as the same `f32`, or otherwise with the fewest decimals that do. Numbers are
+/// never written in scientific notation. Negative zero, and values that are not finite and can
+/// not be represented, are written as `0`.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// assert_eq!(format_real(7.0), "7");
+/// assert_eq!(format_real(375.5), "375.5");
+/// assert_eq!(format_real(-0.0), "0");
+/// assert_eq!(format_real(1e20), "100000000000000000000");
+/// ```
+pub fn format_real(value: f32) -> String {
+    if !value.is_finite() || value == 0.0 {
+        return "0".to_string();
+    }
+    let tenths = format!("{:.1}", value);
+    if value.fract() != 0.0 && tenths.parse::<f32>() == Ok(value) {
+        tenths
+    } else {
+        value.to_string()
+    }
+}
+
+/// A value of the SGF `Real` type, like komi or the margin of a win by points
+///
+/// Reals are always finite, and negative zero is stored as zero, so they can be compared,
+/// hashed and ordered like integers.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let komi = Real::new(6.5).unwrap();
+/// assert_eq!(komi.get(), 6.5);
+/// assert_eq!(komi.to_string(), "6.5");
+/// assert_eq!(Real::new(-0.0), Some(Real::ZERO));
+/// assert_eq!(Real::new(f32::NAN), None);
+/// assert!(Real::new(-1.0).unwrap() < Real::ZERO);
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Real(f32);
+
+impl Real {
+    pub const ZERO: Real = Real(0.0);
+
+    /// Creates a real from `value`, returns `None` for NaN and infinite values
+    pub fn new(value: f32) -> Option<Real> {
+        if value.is_finite() {
+            Some(Real(value + 0.0))
+        } else {
+            None
+        }
+    }
+
+    /// Creates a real from the result of a calculation, which can only overflow in theory
+    ///
+    /// NaN becomes zero and infinite values become the largest finite value of their sign.
+    pub(crate) fn saturating(value: f32) -> Real {
+        if value.is_nan() {
+            Real::ZERO
+        } else {
+            Real(value.clamp(f32::MIN, f32::MAX) + 0.0)
+        }
+    }
+
+    pub fn get(self) -> f32 {
+        self.0
+    }
+}
+
+impl From<Real> for f32 {
+    fn from(real: Real) -> f32 {
+        real.0
+    }
+}
+
+impl PartialEq for Real {
+    fn eq(&self, other: &Real) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl PartialEq<f32> for Real {
+    fn eq(&self, other: &f32) -> bool {
+        self.0 == *other
+    }
+}
+
+impl Eq for Real {}
+
+impl Hash for Real {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+impl Ord for Real {
+    fn cmp(&self, other: &Real) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl PartialOrd for Real {
+    fn partial_cmp(&self, other: &Real) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Real {
+    /// Writes the real like `format_real`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_real(self.0))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Real {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Real::saturating(f32::arbitrary(u)?))
+    }
+}
+
+fn is_real(value: &str) -> bool {
+    match value.find('.') {
+        Some(index) => {
+            let fraction = &value[index + 1..];
+            is_number(&value[..index])
+                && !fraction.is_empty()
+                && fraction.chars().all(|c| c.is_ascii_digit())
+        }
+        None => is_number(value),
+    }
+}
+
+fn is_point(value: &str) -> bool {
+    value.len() == 2 && value.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// Splits a compose value on the first `:` not escaped by a backslash
+fn split_compose(value: &str) -> Option<(&str, &str)> {
+    let mut escaped = false;
+    for (index, c) in value.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            ':' if !escaped => return Some((&value[..index], &value[index + 1..])),
+            _ => escaped = false,
+        }
+    }
+    None
+}
@@ -0,0 +1,184 @@
+use std::cmp::Ordering;
+
+use crate::{Action, Color, GameNode, GameTree, SgfError, SgfErrorKind, SgfToken, TreePath};
+
+impl GameTree {
+    /// Gets the names of the variations following this sequence
+    ///
+    /// The name of a variation is the `N` token of its first node. Use the same method on
+    /// `variations` to get the names of nested variations.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee](;N[Joseki]W[cc])(;W[gg]))").unwrap();
+    /// assert_eq!(tree.variation_names(), vec![Some("Joseki"), None]);
+    /// ```
+    pub fn variation_names(&self) -> Vec<Option<&str>> {
+        self.variations
+            .iter()
+            .map(|variation| {
+                variation.nodes.first().and_then(|node| {
+                    node.tokens.iter().find_map(|token| match token {
+                        SgfToken::NodeName(name) => Some(name.as_str()),
+                        _ => None,
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// Sets or, with `None`, removes the name of the variation at index `variation`
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree = parse("(;SZ[9];B[ee](;W[cc])(;W[gg]))").unwrap();
+    /// tree.set_variation_name(0, Some("Correct")).unwrap();
+    /// tree.set_variation_name(1, Some("Mistake")).unwrap();
+    /// assert_eq!(tree.to_string(), "(;SZ[9];B[ee](;N[Correct]W[cc])(;N[Mistake]W[gg]))");
+    ///
+    /// tree.set_variation_name(1, None).unwrap();
+    /// assert_eq!(tree.variation_names(), vec![Some("Correct"), None]);
+    /// ```
+    pub fn set_variation_name(
+        &mut self,
+        variation: usize,
+        name: Option<&str>,
+    ) -> Result<(), SgfError> {
+        let node = self
+            .variations
+            .get_mut(variation)
+            .ok_or_else(|| SgfError::from(SgfErrorKind::VariationNotFound))?
+            .nodes
+            .first_mut()
+            .ok_or_else(|| SgfError::from(SgfErrorKind::NodeNotFound))?;
+        node.tokens
+            .retain(|token| !matches!(token, SgfToken::NodeName(_)));
+        if let Some(name) = name {
+            node.tokens.push(SgfToken::NodeName(name.into()));
+        }
+        Ok(())
+    }
+
+    /// Adds a variation following the node at `path`, with one node per move, and returns the
+    /// path of its first node
+    ///
+    /// This is meant for adding lines from other sources, like the principal variation of an
+    /// engine, to a game. `comment`, if any, is set on the first node. As with
+    /// `ArcGameTree::add_variation`, the sequence is split when the node at `path` is not the
+    /// last of its sequence. Fails with `SgfErrorKind::NodeNotFound` if `moves` is empty.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let mut tree: GameTree = parse("(;SZ[9];B[ee];W[cc];B[gg])").unwrap();
+    /// let pv = [
+    ///     (Color::White, Action::Move(7, 3)),
+    ///     (Color::Black, Action::Move(3, 7)),
+    /// ];
+    /// let path = tree
+    ///     .attach_variation_at(&TreePath::new(vec![], 1), &pv, Some("Engine: 55% win"))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(path, TreePath::new(vec![1], 0));
+    /// assert_eq!(
+    ///     tree.to_string(),
+    ///     "(;SZ[9];B[ee](;W[cc];B[gg])(;C[Engine: 55% win]W[gc];B[cg]))"
+    /// );
+    /// ```
+    pub fn attach_variation_at(
+        &mut self,
+        path: &TreePath,
+        moves: &[(Color, Action)],
+        comment: Option<&str>,
+    ) -> Result<TreePath, SgfError> {
+        self.get_node(path)?;
+        if moves.is_empty() {
+            return Err(SgfErrorKind::NodeNotFound.into());
+        }
+        let mut nodes: Vec<GameNode> = moves
+            .iter()
+            .map(|(color, action)| {
+                GameNode::from(vec![SgfToken::Move {
+                    color: *color,
+                    action: *action,
+                }])
+            })
+            .collect();
+        if let Some(comment) = comment {
+            nodes[0].tokens.push(SgfToken::Comment(comment.into()));
+        }
+        let sequence = self.get_sequence_mut(&path.variations)?;
+        let next = path.node + 1;
+        if next < sequence.nodes.len() {
+            let tail = GameTree {
+                nodes: sequence.nodes.split_off(next),
+                variations: std::mem::take(&mut sequence.variations),
+            };
+            sequence.variations.push(tail);
+        }
+        sequence.variations.push(GameTree {
+            nodes,
+            variations: vec![],
+        });
+        let mut variations = path.variations.clone();
+        variations.push(sequence.variations.len() - 1);
+        Ok(TreePath::new(variations, 0))
+    }
+
+    /// Gets the line with the most nodes, as the path of its last node and all nodes from the
+    /// root, preferring the first variation in the SGF when lines are equally long
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[ee](;W[cc])(;W[gg];B[cg])(;W[gc]))").unwrap();
+    /// let (path, nodes) = tree.longest_line();
+    /// assert_eq!(path, TreePath::new(vec![1], 1));
+    /// assert_eq!(nodes.len(), tree.count_max_nodes());
+    ///
+    /// let (path, nodes) = tree.shortest_line();
+    /// assert_eq!(path, TreePath::new(vec![0], 0));
+    /// assert_eq!(nodes.len(), 3);
+    /// ```
+    pub fn longest_line(&self) -> (TreePath, Vec<&GameNode>) {
+        self.line_by(Ordering::Greater)
+    }
+
+    /// Gets the line with the fewest nodes, like `longest_line`
+    pub fn shortest_line(&self) -> (TreePath, Vec<&GameNode>) {
+        self.line_by(Ordering::Less)
+    }
+
+    /// Gets the first line whose length compares as `wanted` to all lines before it
+    fn line_by(&self, wanted: Ordering) -> (TreePath, Vec<&GameNode>) {
+        let (_, variations) = pick_line(self, wanted);
+        let mut nodes = vec![];
+        let mut tree = self;
+        nodes.extend(tree.nodes.iter());
+        for variation in &variations {
+            tree = &tree.variations[*variation];
+            nodes.extend(tree.nodes.iter());
+        }
+        let path = TreePath::new(variations, tree.nodes.len().saturating_sub(1));
+        (path, nodes)
+    }
+}
+
+/// Gets the length of the picked line below `tree`, and the variations leading to its end
+fn pick_line(tree: &GameTree, wanted: Ordering) -> (usize, Vec<usize>) {
+    let mut best: Option<(usize, Vec<usize>)> = None;
+    for (index, variation) in tree.variations.iter().enumerate() {
+        let (length, mut variations) = pick_line(variation, wanted);
+        if best
+            .as_ref()
+            .is_none_or(|(best, _)| length.cmp(best) == wanted)
+        {
+            variations.insert(0, index);
+            best = Some((length, variations));
+        }
+    }
+    let (length, variations) = best.unwrap_or_default();
+    (tree.nodes.len() + length, variations)
+}
@@ -0,0 +1,152 @@
+use crate::{Board, Color, GameTree, Outcome, SgfErrorKind, SgfToken};
+
+/// Problems found when comparing a recorded `RE` token with the replayed game
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResultIssue {
+    /// The game has no `RE` token
+    MissingResult,
+    /// The recorded margin can not be reached on a board of this size
+    ImpossibleScore { margin: f32, max: f32 },
+    /// The recorded winner differs from the winner of the area estimate
+    WinnerMismatch {
+        recorded: Option<Color>,
+        estimated: Option<Color>,
+    },
+    /// The main variation could not be replayed
+    ReplayFailed(SgfErrorKind),
+}
+
+/// Verification result for a single game in a collection
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultCheck {
+    /// Index of the game in the collection
+    pub game: usize,
+    pub recorded: Option<Outcome>,
+    /// Stones captured by black and white, as `(black, white)`
+    pub captures: (u32, u32),
+    /// Area estimate of the final position including komi, positive when black is ahead
+    pub estimated_score: Option<f32>,
+    pub issues: Vec<ResultIssue>,
+}
+
+/// Report produced by `verify_results`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ResultReport {
+    pub games: Vec<ResultCheck>,
+}
+
+impl ResultReport {
+    /// Gets all games with at least one issue
+    pub fn flagged(&self) -> Vec<&ResultCheck> {
+        self.games
+            .iter()
+            .filter(|check| !check.issues.is_empty())
+            .collect()
+    }
+
+    /// Checks if no game in the collection had any issues
+    pub fn is_clean(&self) -> bool {
+        self.games.iter().all(|check| check.issues.is_empty())
+    }
+}
+
+/// Replays every game in `trees`, and compares the recorded result with the final position
+///
+/// Only the main variation of each game is replayed. Score estimates count all stones as alive,
+/// so winner mismatches are only reported for results given by points, or draws.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let games = vec![
+///     parse("(;SZ[9]KM[6.5]RE[B+375];B[ee])").unwrap(),
+///     parse("(;SZ[9]KM[6.5]RE[W+R];B[ee];W[cc])").unwrap(),
+/// ];
+/// let report = verify_results(&games);
+///
+/// let flagged = report.flagged();
+/// assert_eq!(flagged.len(), 1);
+/// assert_eq!(flagged[0].game, 0);
+/// assert_eq!(
+///     flagged[0].issues[0],
+///     ResultIssue::ImpossibleScore { margin: 375.0, max: 87.5 }
+/// );
+/// ```
+pub fn verify_results(trees: &[GameTree]) -> ResultReport {
+    let games = trees
+        .iter()
+        .enumerate()
+        .map(|(game, tree)| verify_result(game, tree))
+        .collect();
+    ResultReport { games }
+}
+
+fn verify_result(game: usize, tree: &GameTree) -> ResultCheck {
+    let root = tree.nodes.first().map(|node| node.tokens.as_slice());
+    let root = root.unwrap_or(&[]);
+    let recorded = root.iter().find_map(|token| match token {
+        SgfToken::Result(outcome) => Some(*outcome),
+        _ => None,
+    });
+    let komi = root
+        .iter()
+        .find_map(|token| match token {
+            SgfToken::Komi(komi) => Some(komi.get()),
+            _ => None,
+        })
+        .unwrap_or(0.0);
+
+    let mut issues = vec![];
+    if recorded.is_none() {
+        issues.push(ResultIssue::MissingResult);
+    }
+
+    if let (Ok(board), Some(Outcome::WinnerByPoints(_, margin))) = (Board::for_tree(tree), recorded)
+    {
+        let margin = margin.get();
+        let (width, height) = board.size();
+        let max = f32::from(width) * f32::from(height) + komi.abs();
+        if !(0.0..=max).contains(&margin) {
+            issues.push(ResultIssue::ImpossibleScore { margin, max });
+        }
+    }
+
+    let (captures, estimated_score) = match Board::replay(tree) {
+        Ok(board) => {
+            let (black, white) = board.estimate_area();
+            let score = black as f32 - white as f32 - komi;
+            let captures = (board.captures(Color::Black), board.captures(Color::White));
+            (captures, Some(score))
+        }
+        Err(err) => {
+            issues.push(ResultIssue::ReplayFailed(err.kind));
+            ((0, 0), None)
+        }
+    };
+
+    if let (Some(outcome), Some(score)) = (recorded, estimated_score) {
+        if matches!(outcome, Outcome::WinnerByPoints(..) | Outcome::Draw) {
+            let estimated = if score > 0.0 {
+                Some(Color::Black)
+            } else if score < 0.0 {
+                Some(Color::White)
+            } else {
+                None
+            };
+            if estimated != outcome.get_winner() {
+                issues.push(ResultIssue::WinnerMismatch {
+                    recorded: outcome.get_winner(),
+                    estimated,
+                });
+            }
+        }
+    }
+
+    ResultCheck {
+        game,
+        recorded,
+        captures,
+        estimated_score,
+        issues,
+    }
+}
@@ -0,0 +1,140 @@
+use crate::token::{long_identifier, normalize_identifier};
+use crate::{Encoding, GameTree, Quirk, SgfToken};
+
+/// Identifiers defined in FF[3], but removed from FF[4]
+const DEPRECATED_IDENTIFIERS: [&str; 16] = [
+    "BS", "CH", "EL", "EX", "ID", "L", "LT", "M", "OM", "OP", "OV", "RG", "SC", "SE", "SI", "TC",
+];
+
+/// Result of `parse_with_warnings` and `parse_with_options`, or of
+/// `parse_collection_with_options` holding a `Collection`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOutcome<T = GameTree> {
+    pub tree: T,
+    pub warnings: Vec<ParseWarning>,
+}
+
+/// A warning about questionable input, and where in the tree it was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseWarning {
+    /// Index of the game in the collection, always 0 when parsing a single game
+    pub game: usize,
+    /// Variation indexes leading to the sequence containing the node
+    pub variations: Vec<usize>,
+    /// Index of the node in its sequence
+    pub node: usize,
+    pub kind: ParseWarningKind,
+}
+
+/// Describes what kind of warning we're dealing with
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseWarningKind {
+    /// A property, other than a move, had an empty value
+    EmptyValue { identifier: String },
+    /// An identifier with lowercase letters was converted to its FF[4] form
+    NormalizedIdentifier {
+        original: String,
+        normalized: String,
+    },
+    /// An FF[3] long identifier, like `BlackRank` or `Komi`, was converted to its FF[4] form
+    DeprecatedLongIdentifier {
+        original: String,
+        normalized: String,
+    },
+    /// The `CA` token names a charset other than UTF-8
+    UnknownCharset(String),
+    /// The identifier is from FF[3], and is no longer part of the specification
+    DeprecatedIdentifier(String),
+    /// The identifier occurs more than once in the node
+    ///
+    /// With `DuplicateMode::Merge`, only reported for properties taking a single value, where
+    /// the values after the first were dropped.
+    DuplicateIdentifier(String),
+    /// The identifier has no uppercase letters, like `ab`, and was kept as an invalid token
+    MalformedIdentifier(String),
+    /// A root token, like `SZ`, was found outside the root node, and kept with
+    /// `RootTokenPolicy::Warn`
+    MisplacedRootToken(String),
+    /// The fix for a quirk of the application that wrote the file was applied, with
+    /// `ParserOptions::apply_quirks`
+    AppliedQuirk(Quirk),
+}
+
+/// How repeated identifiers in a single node are handled, set with `ParserOptions::duplicates`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DuplicateMode {
+    /// Keep all tokens, and report a `ParseWarningKind::DuplicateIdentifier` warning
+    #[default]
+    Warn,
+    /// Merge the values of list properties, using `GameNode::merge_duplicates`
+    Merge,
+    /// Fail with `SgfErrorKind::DuplicateIdentifier`
+    Reject,
+}
+
+/// How root tokens like `SZ` or `CA` outside the root node are handled while parsing
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let input = "(;GM[1];B[ee]SZ[9];W[cc])";
+/// let err = parse(input).unwrap_err();
+/// assert_eq!(err.kind, SgfErrorKind::InvalidRootTokenPlacement);
+///
+/// let options = ParserOptions::new().root_tokens(RootTokenPolicy::MoveToRoot);
+/// let outcome = parse_with_options(input, &options).unwrap();
+/// assert_eq!(outcome.tree.to_string(), "(;GM[1]SZ[9];B[ee];W[cc])");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RootTokenPolicy {
+    /// Keep the tokens where they are
+    Ignore,
+    /// Keep the tokens where they are, and report a `ParseWarningKind::MisplacedRootToken`
+    /// warning for each
+    Warn,
+    /// Fail with `SgfErrorKind::InvalidRootTokenPlacement`
+    #[default]
+    Error,
+    /// Move the tokens to the root node, dropping those the root node already has, like
+    /// `RepairOptions::lift_root_tokens`
+    MoveToRoot,
+}
+
+/// Finds the warnings for a single property value
+pub(crate) fn property_warnings(
+    identifier: &str,
+    value: &str,
+    token: &SgfToken,
+) -> Vec<ParseWarningKind> {
+    let mut warnings = vec![];
+    let normalized = normalize_identifier(identifier);
+    if value.is_empty() && !matches!(token, SgfToken::Move { .. }) {
+        warnings.push(ParseWarningKind::EmptyValue {
+            identifier: identifier.to_string(),
+        });
+    }
+    if normalized.is_empty() {
+        warnings.push(ParseWarningKind::MalformedIdentifier(
+            identifier.to_string(),
+        ));
+    } else if long_identifier(identifier).is_some() {
+        warnings.push(ParseWarningKind::DeprecatedLongIdentifier {
+            original: identifier.to_string(),
+            normalized: normalized.to_string(),
+        });
+    } else if normalized != identifier && !matches!(token, SgfToken::Unknown(_)) {
+        warnings.push(ParseWarningKind::NormalizedIdentifier {
+            original: identifier.to_string(),
+            normalized: normalized.to_string(),
+        });
+    }
+    if let SgfToken::Charset(Encoding::Other(charset)) = token {
+        warnings.push(ParseWarningKind::UnknownCharset(charset.clone()));
+    }
+    if DEPRECATED_IDENTIFIERS.contains(&normalized.as_ref()) {
+        warnings.push(ParseWarningKind::DeprecatedIdentifier(
+            normalized.into_owned(),
+        ));
+    }
+    warnings
+}
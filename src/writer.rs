@@ -0,0 +1,244 @@
+use crate::node::NodeWriter;
+use crate::preserve;
+use crate::{Action, GameNode, GameTree, SgfToken};
+
+/// How passes are written by `GameTree::serialize_with`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PassFormat {
+    /// An empty value, `B[]`, as defined by FF[4]
+    #[default]
+    Empty,
+    /// The `tt` point used by FF[3], on boards up to 19x19
+    ///
+    /// Larger boards use an empty value, since `tt` is a point on the board there.
+    Tt,
+}
+
+/// How the tokens of a node are ordered when written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TokenOrder {
+    /// Sorted by identifier, the order used by `String::from`
+    #[default]
+    Identifier,
+    /// Root and game info tokens first, then setup, move, timing, annotation and markup tokens,
+    /// and unknown tokens last, each group sorted by identifier
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9]KM[6.5];TR[ee]C[Nice]BL[30]B[ee])").unwrap();
+    /// let options = WriterOptions::new().token_order(TokenOrder::Canonical);
+    /// assert_eq!(
+    ///     tree.serialize_with(&options),
+    ///     "(;SZ[9]KM[6.5];B[ee]BL[30]C[Nice]TR[ee])"
+    /// );
+    /// ```
+    Canonical,
+}
+
+impl TokenOrder {
+    /// Gets the group of `token`, written in increasing order
+    pub(crate) fn rank(self, token: &SgfToken) -> u8 {
+        match self {
+            TokenOrder::Identifier => 0,
+            TokenOrder::Canonical => token.category() as u8,
+        }
+    }
+}
+
+/// Options for `GameTree::serialize_with`
+///
+/// Like `ParserOptions`, options are created with `WriterOptions::new` and set with the method
+/// named after each field. Struct expressions should end with `..WriterOptions::default()`, as
+/// new options may be added in any release.
+///
+/// ```rust
+/// use sgf_parser::*;
+///
+/// let options = WriterOptions::new().pass_format(PassFormat::Tt);
+/// assert_eq!(options.token_order, TokenOrder::Identifier);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct WriterOptions {
+    pub pass_format: PassFormat,
+    pub token_order: TokenOrder,
+    /// Write nodes recorded with `ParserOptions::preserve_format` as they were read when their
+    /// tokens are unchanged, keeping the whitespace between nodes and variations
+    ///
+    /// Only changed and new nodes are written with the other options, so editing a
+    /// hand-maintained file gives a minimal diff.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let input = "(;SZ[9]\n;B[ee] ;W[cc]\n  (;B[gg])\n  (;B[cg]))\n";
+    /// let options = ParserOptions::new().preserve_format(true);
+    /// let mut tree = parse_with_options(input, &options).unwrap().tree;
+    /// let options = WriterOptions::new().preserve_format(true);
+    /// assert_eq!(tree.serialize_with(&options), input);
+    ///
+    /// tree.variations[1].nodes[0].tokens.push(SgfToken::Comment("Better".into()));
+    /// assert_eq!(
+    ///     tree.serialize_with(&options),
+    ///     "(;SZ[9]\n;B[ee] ;W[cc]\n  (;B[gg])\n  (;B[cg]C[Better]))\n"
+    /// );
+    /// ```
+    pub preserve_format: bool,
+}
+
+impl WriterOptions {
+    /// Creates the default options, writing the same text as `String::from`
+    pub fn new() -> Self {
+        WriterOptions::default()
+    }
+
+    /// Sets `pass_format`
+    pub fn pass_format(mut self, pass_format: PassFormat) -> Self {
+        self.pass_format = pass_format;
+        self
+    }
+
+    /// Sets `token_order`
+    pub fn token_order(mut self, token_order: TokenOrder) -> Self {
+        self.token_order = token_order;
+        self
+    }
+
+    /// Sets `preserve_format`
+    pub fn preserve_format(mut self, preserve_format: bool) -> Self {
+        self.preserve_format = preserve_format;
+        self
+    }
+}
+
+impl Action {
+    /// Gets the property value for a pass on a board of `board_size`
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// assert_eq!(Action::pass_repr((19, 19), PassFormat::Tt), "tt");
+    /// assert_eq!(Action::pass_repr((21, 21), PassFormat::Tt), "");
+    /// assert_eq!(Action::pass_repr((19, 19), PassFormat::Empty), "");
+    /// ```
+    pub fn pass_repr(board_size: (u32, u32), format: PassFormat) -> &'static str {
+        match format {
+            PassFormat::Tt if board_size.0 <= 19 && board_size.1 <= 19 => "tt",
+            _ => "",
+        }
+    }
+
+    /// Checks if the action is a pass
+    ///
+    /// `tt` is parsed as a move to `(20, 20)`, use `Action::normalize_pass` to treat it as a
+    /// pass on small boards.
+    pub fn is_pass(&self) -> bool {
+        *self == Action::Pass
+    }
+
+    /// Gets the coordinate of a move, or `None` for a pass
+    pub fn coordinate(&self) -> Option<(u8, u8)> {
+        match *self {
+            Action::Move(x, y) => Some((x, y)),
+            Action::Pass => None,
+        }
+    }
+
+    /// Converts a move to `tt` into a pass on boards up to 19x19
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// assert_eq!(Action::Move(20, 20).normalize_pass((19, 19)), Action::Pass);
+    /// assert_eq!(Action::Move(20, 20).normalize_pass((21, 21)), Action::Move(20, 20));
+    /// ```
+    pub fn normalize_pass(self, board_size: (u32, u32)) -> Action {
+        match self {
+            Action::Move(20, 20) if board_size.0 <= 19 && board_size.1 <= 19 => Action::Pass,
+            action => action,
+        }
+    }
+}
+
+impl GameTree {
+    /// Serializes the tree like `String::from`, using `options`
+    ///
+    /// The board size used for passes is `GameTree::board_size`, falling back to 19x19.
+    ///
+    /// ```rust
+    /// use sgf_parser::*;
+    ///
+    /// let tree: GameTree = parse("(;SZ[9];B[];W[aa](;B[])(;B[bb]))").unwrap();
+    /// let options = WriterOptions::new().pass_format(PassFormat::Tt);
+    /// assert_eq!(tree.serialize_with(&options), "(;SZ[9];B[tt];W[aa](;B[tt])(;B[bb]))");
+    /// assert_eq!(tree.serialize_with(&WriterOptions::default()), tree.to_string());
+    /// ```
+    pub fn serialize_with(&self, options: &WriterOptions) -> String {
+        let board_size = self.board_size().unwrap_or((19, 19));
+        let mut writer = NodeWriter::default();
+        let mut out = String::with_capacity(self.estimated_sgf_len());
+        let mut write_node = |node: &GameNode, out: &mut String| {
+            write_node(node, board_size, options, &mut writer, out)
+        };
+        if options.preserve_format {
+            preserve::write_tree(self, &mut write_node, &mut out);
+        } else {
+            write_tree(self, &mut write_node, &mut out);
+        }
+        out
+    }
+}
+
+fn write_tree(
+    tree: &GameTree,
+    write_node: &mut dyn FnMut(&GameNode, &mut String),
+    out: &mut String,
+) {
+    out.push('(');
+    for node in &tree.nodes {
+        write_node(node, out);
+    }
+    for variation in &tree.variations {
+        write_tree(variation, write_node, out);
+    }
+    out.push(')');
+}
+
+fn write_node(
+    node: &GameNode,
+    board_size: (u32, u32),
+    options: &WriterOptions,
+    writer: &mut NodeWriter,
+    out: &mut String,
+) {
+    let pass = Action::pass_repr(board_size, options.pass_format);
+    let has_pass = node.tokens.iter().any(|token| {
+        matches!(
+            token,
+            SgfToken::Move {
+                action: Action::Pass,
+                ..
+            }
+        )
+    });
+    let result = if pass.is_empty() || !has_pass {
+        writer.write(&node.tokens, options.token_order, out)
+    } else {
+        let tokens: Vec<SgfToken> = node
+            .tokens
+            .iter()
+            .map(|token| match token {
+                SgfToken::Move {
+                    color,
+                    action: Action::Pass,
+                } => SgfToken::Move {
+                    color: *color,
+                    action: Action::Move(20, 20),
+                },
+                token => token.clone(),
+            })
+            .collect();
+        writer.write(&tokens, options.token_order, out)
+    };
+    result.expect("Writing to a String never fails");
+}
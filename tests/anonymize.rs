@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod anonymize_tests {
+    use sgf_parser::*;
+
+    #[test]
+    fn anonymizes_all_variations() {
+        let mut tree: GameTree =
+            parse("(;PB[A]PW[B]WR[3k]EV[Cup]GC[Notes]AN[Someone];B[aa](;C[x]W[bb])(;W[cc]C[y]))")
+                .unwrap();
+        tree.anonymize(&AnonymizeOptions::default());
+        let sgf: String = tree.into();
+        assert_eq!(sgf, "(;PB[Black]PW[White];B[aa](;W[bb])(;W[cc]))");
+    }
+
+    #[test]
+    fn keeps_disabled_categories() {
+        let mut tree: GameTree = parse("(;PB[Shusaku]BR[6d]EV[Cup]AN[Someone];C[Nice])").unwrap();
+        tree.anonymize(&AnonymizeOptions {
+            players: false,
+            comments: false,
+            sources: false,
+            ..AnonymizeOptions::default()
+        });
+        let sgf: String = tree.into();
+        assert_eq!(sgf, "(;AN[Someone]PB[Shusaku];C[Nice])");
+    }
+
+    #[test]
+    fn keeps_board_properties() {
+        let sgf = "(;SZ[9]AB[aa][bb]PB[X]BT[Team]GN[X vs Y]SO[Book]XY[1];AE[aa]PL[W]MA[cc]N[Joseki];W[dd]TB[ee])";
+        let mut tree: GameTree = parse(sgf).unwrap();
+        tree.anonymize(&AnonymizeOptions::default());
+        let anonymized: String = tree.into();
+        assert_eq!(
+            anonymized,
+            "(;AB[aa][bb]PB[Black]SZ[9]XY[1];AE[aa]MA[cc]PL[W];TB[ee]W[dd])"
+        );
+
+        let mut tree: GameTree = parse(sgf).unwrap();
+        tree.anonymize(&AnonymizeOptions {
+            private: true,
+            ..AnonymizeOptions::default()
+        });
+        let anonymized: String = tree.into();
+        assert_eq!(
+            anonymized,
+            "(;AB[aa][bb]PB[Black]SZ[9];AE[aa]MA[cc]PL[W];TB[ee]W[dd])"
+        );
+    }
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "arbitrary")]
+
+mod arbitrary_tests {
+    use arbitrary::{Arbitrary, Unstructured};
+    use sgf_parser::*;
+
+    fn seeds() -> Vec<Vec<u8>> {
+        (0..2048u32)
+            .map(|seed| {
+                let step = seed.wrapping_mul(2_654_435_761) % 251 + 1;
+                (0..512u32)
+                    .map(|i| (i.wrapping_mul(step) ^ seed) as u8)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn serialized_tokens_parse_back_to_the_same_token() {
+        for seed in seeds() {
+            let token = SgfToken::arbitrary(&mut Unstructured::new(&seed)).unwrap();
+            let serialized: String = (&token).into();
+            let tree = parse(&format!("(;{})", serialized))
+                .unwrap_or_else(|err| panic!("{} failed to parse: {}", serialized, err));
+            assert_eq!(tree.nodes[0].tokens, vec![token], "{}", serialized);
+        }
+    }
+
+    #[test]
+    fn serialized_trees_parse_back_to_the_same_tree() {
+        for seed in seeds() {
+            let tree = GameTree::arbitrary(&mut Unstructured::new(&seed)).unwrap();
+            let serialized: String = (&tree).into();
+            let parsed = parse(&serialized)
+                .unwrap_or_else(|err| panic!("{} failed to parse: {}", serialized, err));
+            assert_eq!(parsed, tree, "{}", serialized);
+        }
+    }
+
+    #[test]
+    fn ranks_are_read_back_as_the_same_rank() {
+        for seed in seeds() {
+            let rank = Rank::arbitrary(&mut Unstructured::new(&seed)).unwrap();
+            assert_eq!(Rank::from(rank.to_string().as_str()), rank);
+        }
+    }
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "tokio")]
+
+mod async_io_tests {
+    use sgf_parser::*;
+
+    #[tokio::test]
+    async fn can_parse_file() {
+        let tree = parse_file_async("tests/sgf/ShusakuvsInseki.sgf")
+            .await
+            .unwrap();
+        assert_eq!(
+            tree,
+            parse(include_str!("sgf/ShusakuvsInseki.sgf")).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn decodes_file_with_charset_policy() {
+        let path = std::env::temp_dir().join("sgf_parser_async_latin1.sgf");
+        tokio::fs::write(&path, b"(;CA[ISO-8859-1]PB[Jos\xe9])")
+            .await
+            .unwrap();
+        let err = parse_file_async(&path).await.unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::UnsupportedCharset);
+
+        let options = ParserOptions::new().charset_policy(CharsetPolicy::TranscodeKnown);
+        let tree = parse_file_async_with(&path, &options).await.unwrap();
+        assert_eq!(tree.to_string(), "(;CA[UTF-8]PB[José])");
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn errors_on_missing_file() {
+        let err = parse_file_async("tests/sgf/missing.sgf").await.unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::IoError);
+    }
+
+    #[tokio::test]
+    async fn can_read_events() {
+        let source: &[u8] = b"(;SZ[19];B[aa];W[bb])";
+        let mut reader = AsyncEventReader::new(source);
+        let mut events = vec![];
+        while let Some(chunk) = reader.next().await.unwrap() {
+            events.extend(chunk);
+        }
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[0], Event::StartTree);
+        assert_eq!(events[4], Event::EndTree);
+    }
+}
@@ -0,0 +1,281 @@
+#[cfg(test)]
+mod board_tests {
+    use sgf_parser::*;
+
+    #[test]
+    fn uses_size_from_root_node() {
+        let tree: GameTree = parse("(;SZ[13:9];B[aa])").unwrap();
+        let board = Board::for_tree(&tree).unwrap();
+        assert_eq!(board.size(), (13, 9));
+
+        let tree: GameTree = parse("(;B[aa])").unwrap();
+        let board = Board::for_tree(&tree).unwrap();
+        assert_eq!(board.size(), (19, 19));
+
+        let tree: GameTree = parse("(;SZ[275];B[aa])").unwrap();
+        let err = Board::for_tree(&tree).unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::InvalidTokenValue);
+        assert_eq!(
+            Board::replay(&tree).unwrap_err().kind,
+            SgfErrorKind::InvalidTokenValue
+        );
+    }
+
+    #[test]
+    fn can_capture_group() {
+        let mut board = Board::new(9, 9);
+        board.play(Color::White, (1, 1)).unwrap();
+        board.play(Color::White, (2, 1)).unwrap();
+        board.play(Color::Black, (3, 1)).unwrap();
+        board.play(Color::Black, (1, 2)).unwrap();
+        let captured = board.play(Color::Black, (2, 2)).unwrap();
+
+        assert_eq!(captured, vec![(1, 1), (2, 1)]);
+        assert_eq!(board.get((1, 1)), None);
+        assert_eq!(board.get((2, 1)), None);
+        assert_eq!(board.captures(Color::Black), 2);
+        assert_eq!(board.captures(Color::White), 0);
+    }
+
+    #[test]
+    fn suicide_is_counted_as_opponent_capture() {
+        let mut board = Board::new(9, 9);
+        board.play(Color::Black, (2, 1)).unwrap();
+        board.play(Color::Black, (1, 2)).unwrap();
+        let removed = board.play(Color::White, (1, 1)).unwrap();
+
+        assert_eq!(removed, vec![(1, 1)]);
+        assert_eq!(board.get((1, 1)), None);
+        assert_eq!(board.captures(Color::Black), 1);
+    }
+
+    #[test]
+    fn errors_on_illegal_moves() {
+        let mut board = Board::new(9, 9);
+        board.play(Color::Black, (3, 3)).unwrap();
+        let err = board.play(Color::White, (3, 3)).unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::IllegalMove);
+        let err = board.play(Color::White, (10, 3)).unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::IllegalMove);
+    }
+
+    #[test]
+    fn continues_parsed_game_with_legal_moves() {
+        let mut tree: GameTree = parse("(;SZ[9];B[ee];W[dd];B[ed])").unwrap();
+        let path = TreePath::new(vec![], 3);
+        let mut board = Board::replay(&tree).unwrap();
+        assert!(!board.is_legal(Color::White, (5, 5)));
+        assert!(!board.is_legal(Color::White, (0, 5)));
+        assert!(board.is_legal(Color::White, (6, 5)));
+
+        let err = board
+            .play_action(Color::White, Action::Move(5, 4))
+            .unwrap_err();
+        assert_eq!(err, IllegalMove::Occupied);
+        assert_eq!(SgfError::from(err).kind, SgfErrorKind::IllegalMove);
+
+        assert_eq!(
+            board.play_action(Color::White, Action::Move(6, 5)),
+            Ok(vec![])
+        );
+        let mut sandbox = tree.sandbox_at(&path).unwrap();
+        sandbox.push_move(Color::White, Action::Move(6, 5));
+        assert_eq!(sandbox.board().unwrap(), board);
+        let line = sandbox.finish();
+        tree.commit_sandbox(line).unwrap();
+        assert_eq!(tree.to_string(), "(;SZ[9];B[ee];W[dd];B[ed];W[fe])");
+    }
+
+    #[test]
+    fn pass_ends_ko() {
+        let tree: GameTree =
+            parse("(;SZ[9]AB[ba][ab][bc][cb]AW[ca][db][cc];W[bb];B[]C[Pass];W[gg])").unwrap();
+        let mut cache = tree.board_cache();
+        let ko = cache.board_at(&TreePath::new(vec![], 1)).unwrap();
+        assert!(!ko.is_legal(Color::Black, (3, 2)));
+        for node in 2..=3 {
+            let board = cache.board_at(&TreePath::new(vec![], node)).unwrap();
+            assert!(board.is_legal(Color::Black, (3, 2)));
+        }
+        assert!(Board::replay(&tree).unwrap().is_legal(Color::Black, (3, 2)));
+
+        let tree: GameTree =
+            parse("(;SZ[9]AB[ba][ab][bc][cb]AW[ca][db][cc];W[bb];B[];W[];B[cb])").unwrap();
+        assert_eq!(tree.check_moves(), vec![]);
+    }
+
+    #[test]
+    fn board_cache_matches_replaying_each_line() {
+        let tree: GameTree = parse(include_str!("sgf/ShusakuvsInseki.sgf")).unwrap();
+        let mut cache = tree.board_cache();
+        let last = tree.total_node_count() - 1;
+        for node in [last, 40, 120, 0] {
+            let path = TreePath::new(vec![], node);
+            let mut expected = Board::for_tree(&tree).unwrap();
+            for node in tree.get_line_to(&path).unwrap() {
+                expected.apply_node(node).unwrap();
+            }
+            assert_eq!(*cache.board_at(&path).unwrap(), expected);
+        }
+        assert_eq!(cache.len(), last + 1);
+
+        let tree: GameTree = parse("(;SZ[9];B[ee];C[Tesuji](;W[cc])(;W[gg]))").unwrap();
+        let mut cache = tree.board_cache();
+        let comment = cache.board_at(&TreePath::new(vec![], 2)).unwrap();
+        let mov = cache.get(&TreePath::new(vec![], 1)).unwrap();
+        assert!(std::sync::Arc::ptr_eq(&comment, &mov));
+        assert!(cache.get(&TreePath::new(vec![0], 0)).is_none());
+        assert!(cache.board_at(&TreePath::new(vec![2], 0)).is_err());
+        assert_eq!(
+            cache
+                .board_at(&TreePath::new(vec![1], 0))
+                .unwrap()
+                .get((7, 7)),
+            Some(Color::White)
+        );
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn checks_superko_according_to_rules() {
+        let setup = "SZ[9]AB[ba][ab][bc][cb][ga][fb][gc][bf][ag][bh][cg]\
+                     AW[ca][db][cc][ha][ib][hc][gb][cf][dg][ch]";
+        let moves = ";W[bb];B[hb];W[bg](;B[cb];W[gb];B[cg])(;B[cb];W[gb];B[aa])";
+        let repeat = MoveProblem {
+            path: TreePath::new(vec![0], 2),
+            color: Color::Black,
+            issue: MoveIssue::Superko {
+                repeats: TreePath::new(vec![], 0),
+            },
+        };
+
+        let tree: GameTree = parse(&format!("(;RU[Chinese]{}{})", setup, moves)).unwrap();
+        assert_eq!(tree.check_moves(), vec![repeat.clone()]);
+        let tree: GameTree = parse(&format!("(;RU[AGA]{}{})", setup, moves)).unwrap();
+        assert_eq!(tree.check_moves(), vec![repeat]);
+        let tree: GameTree = parse(&format!("(;RU[Japanese]{}{})", setup, moves)).unwrap();
+        assert!(tree.check_moves().is_empty());
+
+        let tree: GameTree = parse(include_str!("sgf/ShusakuvsInseki.sgf")).unwrap();
+        assert!(tree
+            .check_moves_with(board::KoRule::PositionalSuperko)
+            .is_empty());
+    }
+
+    #[test]
+    fn scores_with_dead_stones_by_rule_set() {
+        let position = "SZ[5]KM[6.5]AB[ca][cb][cc][cd][ce]AW[da][db][dc][dd][de][ab][bd]";
+        let tree: GameTree = parse(&format!("(;RU[Japanese]{};B[];W[])", position)).unwrap();
+        let score = tree.score(&[(1, 2), (2, 4)]).unwrap();
+        assert_eq!(score.dead, vec![(1, 2), (2, 4)]);
+        assert_eq!(score.black_territory.len(), 10);
+        assert_eq!(
+            score.white_territory,
+            vec![(5, 1), (5, 2), (5, 3), (5, 4), (5, 5)]
+        );
+        assert_eq!((score.black, score.white), (12.0, 11.5));
+        assert_eq!(
+            score.outcome,
+            Outcome::WinnerByPoints(Color::Black, Real::new(0.5).unwrap())
+        );
+
+        let tree: GameTree = parse(&format!("(;RU[Chinese]{};B[];W[])", position)).unwrap();
+        let score = tree.score(&[(1, 2), (2, 4)]).unwrap();
+        assert_eq!((score.black, score.white), (15.0, 16.5));
+        assert_eq!(
+            tree.score(&[]).unwrap().outcome,
+            Outcome::WinnerByPoints(Color::White, Real::new(13.5).unwrap())
+        );
+        assert_eq!(
+            tree.score(&[(1, 1)]).unwrap_err().kind,
+            SgfErrorKind::InvalidTokenValue
+        );
+
+        let mut tree = tree;
+        tree.nodes[2].tokens.push(SgfToken::from_pair("TB", "aa"));
+        tree.write_score(&[(1, 2), (2, 4)]).unwrap();
+        tree.write_score(&[(1, 2), (2, 4)]).unwrap();
+        assert_eq!(tree.nodes[2].tokens_for("TB").len(), 10);
+        assert_eq!(tree.nodes[2].tokens_for("TW").len(), 5);
+        assert_eq!(tree.nodes[0].tokens_for("RE").len(), 1);
+    }
+
+    #[test]
+    fn can_estimate_area() {
+        let tree: GameTree = parse("(;SZ[5]AB[ca][cb][cc][cd][ce]AW[da][db][dc][dd][de])").unwrap();
+        let board = Board::replay(&tree).unwrap();
+        assert_eq!(board.estimate_area(), (15, 10));
+    }
+
+    #[test]
+    fn star_points_match_the_constants() {
+        use sgf_parser::board::*;
+
+        assert_eq!(star_points(9, 9), STAR_POINTS_9.to_vec());
+        assert_eq!(star_points(13, 13), STAR_POINTS_13.to_vec());
+        assert_eq!(star_points(19, 19), STAR_POINTS_19.to_vec());
+        assert!(star_points(19, 13).is_empty());
+        assert_eq!(star_points(8, 8).len(), 4);
+
+        let mut stones = handicap_points(13, 9).unwrap();
+        stones.sort_unstable();
+        let mut expected = STAR_POINTS_13.to_vec();
+        expected.sort_unstable();
+        assert_eq!(stones, expected);
+        assert_eq!(handicap_points(19, 7).unwrap()[6], (10, 10));
+        assert_eq!(handicap_points(19, 1), None);
+        assert_eq!(handicap_points(19, 10), None);
+
+        assert!(is_standard_size(13, 13));
+        assert!(!is_standard_size(13, 19));
+        assert!(is_on_board((19, 1), 19, 19));
+        assert!(!is_on_board((0, 1), 19, 19));
+        assert_eq!(default_komi(&RuleSet::Unknown("Ing".to_string())), None);
+    }
+
+    #[test]
+    fn counts_captures_per_node_in_all_variations() {
+        let mut tree: GameTree =
+            parse("(;SZ[9];B[ba];W[aa];B[ab](;W[bb];B[ia];W[ca];B[ac])(;W[ca]PC[x]))").unwrap();
+        assert_eq!(
+            tree.captures_at(&TreePath::new(vec![0], 2)).unwrap(),
+            (1, 0)
+        );
+        assert_eq!(tree.write_captures("XB", "XW").unwrap(), 8);
+        let path = TreePath::new(vec![1], 0);
+        let node = tree.get_node(&path).unwrap();
+        assert_eq!(
+            node.recorded_captures("XB", "XW"),
+            Some(tree.captures_at(&path).unwrap())
+        );
+        assert_eq!(tree.nodes[0].recorded_captures("XB", "XW"), None);
+
+        assert_eq!(tree.write_captures("XB", "XW").unwrap(), 8);
+        assert_eq!(tree.variations[0].nodes[0].tokens.len(), 3);
+    }
+
+    #[test]
+    fn exports_final_position_of_real_games() {
+        let tree: GameTree = parse(include_str!("sgf/ShusakuvsInseki.sgf")).unwrap();
+        let position = tree.final_position_sgf().unwrap();
+        assert_eq!(position.total_node_count(), 1);
+        let root = &position.nodes[0];
+        assert_eq!(root.tokens_for("PB"), tree.nodes[0].tokens_for("PB"));
+        assert!(root.tokens_for("C").is_empty());
+
+        let reparsed: GameTree = parse(&position.to_string()).unwrap();
+        let expected = Board::replay(&tree).unwrap();
+        let actual = Board::replay(&reparsed).unwrap();
+        for x in 1..=19 {
+            for y in 1..=19 {
+                assert_eq!(actual.get((x, y)), expected.get((x, y)));
+            }
+        }
+        let last = TreePath::new(vec![], tree.total_node_count() - 1);
+        assert_eq!(
+            reparsed.color_to_play(&TreePath::default()).unwrap(),
+            tree.color_to_play(&last).unwrap()
+        );
+    }
+}
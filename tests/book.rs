@@ -0,0 +1,26 @@
+#[cfg(test)]
+mod book_tests {
+    use sgf_parser::*;
+
+    #[test]
+    fn counts_moves_per_position() {
+        let collection = parse_collection(
+            "(;RE[B+R];B[pd];W[dp];B[pp])(;RE[W+R];B[pd];W[dp];B[dd])(;RE[Draw];B[pd];W[dd])",
+        )
+        .unwrap();
+        let book = OpeningBook::from_collection(&collection, 3);
+        assert_eq!(book.positions.len(), 3);
+
+        let first = (Color::Black, Action::Move(16, 4));
+        let position = book.get(&[first]).unwrap();
+        assert_eq!(position.candidates.len(), 2);
+        assert_eq!(position.candidates[0].action, Action::Move(4, 16));
+        assert_eq!(position.candidates[0].games, 2);
+        assert_eq!(position.candidates[0].win_rate(Color::White), Some(0.5));
+        assert_eq!(position.candidates[1].win_rate(Color::White), None);
+
+        let second = (Color::White, Action::Move(4, 16));
+        assert_eq!(book.get(&[first, second]).unwrap().candidates.len(), 2);
+        assert!(book.get(&[second]).is_none());
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod charset_tests {
+    use sgf_parser::*;
+
+    fn options(charset_policy: CharsetPolicy) -> ParserOptions {
+        ParserOptions {
+            charset_policy,
+            ..ParserOptions::default()
+        }
+    }
+
+    #[test]
+    fn strict_policy_accepts_only_utf8() {
+        let utf8 = "(;CA[utf8]C[日本])".as_bytes();
+        let tree = parse_bytes_with(utf8, &ParserOptions::default()).unwrap();
+        assert_eq!(tree.nodes[0].tokens[1], SgfToken::Comment("日本".into()));
+
+        let invalid = b"(;C[\xff])";
+        let err = parse_bytes_with(invalid, &options(CharsetPolicy::Strict)).unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::ParseError);
+
+        let latin = b"(;CA[Latin1]C[\xe9])";
+        let err = parse_bytes_with(latin, &options(CharsetPolicy::Strict)).unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::UnsupportedCharset);
+    }
+
+    #[test]
+    fn transcodes_known_charsets() {
+        let policy = options(CharsetPolicy::TranscodeKnown);
+        let tree =
+            parse_bytes_with(b"(;CA[Windows-1252]C[\x93quoted\x94 \x80]B[aa])", &policy).unwrap();
+        assert_eq!(
+            tree.nodes[0].tokens[1],
+            SgfToken::Comment("\u{201c}quoted\u{201d} €".into())
+        );
+        assert!(parse_bytes_with(b"(;CA[US-ASCII]C[abc])", &policy).is_ok());
+        assert_eq!(
+            parse_bytes_with(b"(;CA[US-ASCII]C[\xe9])", &policy)
+                .unwrap_err()
+                .kind,
+            SgfErrorKind::ParseError
+        );
+        assert_eq!(
+            parse_bytes_with(b"(;CA[Shift_JIS]C[\x93\xfa])", &policy)
+                .unwrap_err()
+                .kind,
+            SgfErrorKind::UnsupportedCharset
+        );
+    }
+
+    #[test]
+    fn lossy_policy_replaces_invalid_sequences() {
+        let tree = parse_bytes_with(
+            b"(;CA[Shift_JIS]C[a\xffb])",
+            &options(CharsetPolicy::AssumeUtf8Lossy),
+        )
+        .unwrap();
+        assert_eq!(
+            tree.nodes[0].tokens[1],
+            SgfToken::Comment("a\u{fffd}b".into())
+        );
+        assert_eq!(parse_bytes(b"(;CA[Shift_JIS]C[a\xffb])").unwrap(), tree);
+    }
+}
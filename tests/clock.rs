@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod clock_tests {
+    use sgf_parser::*;
+
+    #[test]
+    fn flags_negative_values() {
+        let tree: GameTree = parse("(;B[aa]BL[50];W[bb]WL[40];B[cc]BL[-3])").unwrap();
+        assert_eq!(
+            tree.check_clock(),
+            vec![ClockProblem {
+                move_number: 3,
+                color: Color::Black,
+                issue: ClockIssue::Negative,
+            }]
+        );
+    }
+
+    #[test]
+    fn allows_overtime_increases() {
+        let tree: GameTree = parse("(;B[aa]BL[5];B[bb]BL[30]OB[5];B[cc]BL[20]OB[4])").unwrap();
+        assert!(tree.check_clock().is_empty());
+
+        let tree: GameTree = parse("(;OT[3x30 byo-yomi];B[aa]BL[5];B[bb]BL[30])").unwrap();
+        assert!(tree.check_clock().is_empty());
+    }
+
+    #[test]
+    fn ignores_games_without_clock() {
+        let tree: GameTree = parse("(;B[aa];W[bb];B[cc])").unwrap();
+        assert!(tree.check_clock().is_empty());
+    }
+
+    #[test]
+    fn repairs_by_interpolation() {
+        let mut tree: GameTree =
+            parse("(;B[aa];B[bb]BL[60];B[cc]BL[-1];B[dd]BL[90];B[ee]BL[30];B[ff])").unwrap();
+        let problems = tree.repair_clock();
+        assert_eq!(problems.len(), 4);
+        assert!(tree.check_clock().is_empty());
+
+        let remaining: Vec<_> = tree
+            .time_series()
+            .black
+            .iter()
+            .map(|entry| entry.remaining)
+            .collect();
+        assert_eq!(
+            remaining,
+            vec![Some(60), Some(60), Some(50), Some(40), Some(30), Some(30)]
+        );
+    }
+}
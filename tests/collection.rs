@@ -0,0 +1,243 @@
+#[cfg(test)]
+mod collection_tests {
+    use sgf_parser::*;
+
+    #[test]
+    fn parses_all_games() {
+        let collection: Collection = "(;B[aa])\n(;B[bb](;W[cc])(;W[dd]))".parse().unwrap();
+        assert_eq!(collection.games.len(), 2);
+        assert_eq!(collection.games[1].count_variations(), 2);
+        assert!(parse_collection("").is_err());
+        assert!(parse_collection("(;B[aa])x").is_err());
+        assert_eq!(
+            parse_collection("(;B[aa])(;SZ[19];SZ[19])")
+                .unwrap_err()
+                .kind,
+            SgfErrorKind::InvalidRootTokenPlacement
+        );
+    }
+
+    #[test]
+    fn parses_all_games_with_options() {
+        let input = "(;SZ[9];B[aa]C[x]C[y])\n(;AP[CGoban:3];B[bb];SZ[9])";
+        assert_eq!(
+            parse_collection(input).unwrap_err().kind,
+            SgfErrorKind::InvalidRootTokenPlacement
+        );
+        let options = ParserOptions::new().root_tokens(RootTokenPolicy::Warn);
+        let outcome = parse_collection_with_options(input, &options).unwrap();
+        assert_eq!(outcome.tree.games.len(), 2);
+        assert_eq!(
+            outcome.warnings,
+            vec![
+                ParseWarning {
+                    game: 0,
+                    variations: vec![],
+                    node: 1,
+                    kind: ParseWarningKind::DuplicateIdentifier("C".to_string()),
+                },
+                ParseWarning {
+                    game: 1,
+                    variations: vec![],
+                    node: 2,
+                    kind: ParseWarningKind::MisplacedRootToken("SZ".to_string()),
+                },
+            ]
+        );
+
+        let options = ParserOptions::new().duplicates(DuplicateMode::Reject);
+        assert_eq!(
+            parse_collection_with_options(input, &options)
+                .unwrap_err()
+                .kind,
+            SgfErrorKind::DuplicateIdentifier
+        );
+
+        let options = ParserOptions::new()
+            .duplicates(DuplicateMode::Merge)
+            .root_tokens(RootTokenPolicy::MoveToRoot);
+        let outcome = parse_collection_with_options(input, &options).unwrap();
+        assert_eq!(
+            outcome.tree.to_string(),
+            "(;SZ[9];B[aa]C[x])(;AP[CGoban:3]SZ[9];B[bb];)"
+        );
+    }
+
+    #[test]
+    fn preserves_formatting_of_all_games() {
+        let input = "(;SZ[9]\n;B[aa] )\n\n( ;B[bb]\n(;W[cc]) )\n";
+        let parser = ParserOptions::new().preserve_format(true);
+        let writer = WriterOptions {
+            preserve_format: true,
+            ..WriterOptions::default()
+        };
+        let collection = parse_collection_with_options(input, &parser).unwrap().tree;
+        let written: String = collection
+            .iter()
+            .map(|game| game.serialize_with(&writer))
+            .collect();
+        assert_eq!(written, input);
+    }
+
+    #[test]
+    fn shares_text_between_all_games() {
+        let input = "(;XR[0.48]PB[Shusaku]C[Nice])(;XR[0.52]PB[Shusaku]C[Nice];XR[0.52])";
+        let options = ParserOptions::new().intern_strings(true);
+        let games = parse_collection_with_options(input, &options)
+            .unwrap()
+            .tree
+            .games;
+        assert_eq!(games, parse_collection(input).unwrap().games);
+
+        let text =
+            |game: &GameTree, node: usize, token: usize| match &game.nodes[node].tokens[token] {
+                SgfToken::Unknown((identifier, value)) => (identifier.clone(), value.clone()),
+                SgfToken::PlayerName { name, .. } | SgfToken::Comment(name) => {
+                    (name.clone(), name.clone())
+                }
+                token => panic!("Unexpected token {:?}", token),
+            };
+        let (first, _) = text(&games[0], 0, 0);
+        let (second, value) = text(&games[1], 0, 0);
+        let (third, repeated) = text(&games[1], 1, 0);
+        assert!(TokenString::ptr_eq(&first, &second));
+        assert!(TokenString::ptr_eq(&second, &third));
+        assert!(TokenString::ptr_eq(&value, &repeated));
+        assert!(TokenString::ptr_eq(
+            &text(&games[0], 0, 1).0,
+            &text(&games[1], 0, 1).0
+        ));
+        assert!(!TokenString::ptr_eq(
+            &text(&games[0], 0, 2).0,
+            &text(&games[1], 0, 2).0
+        ));
+    }
+
+    #[test]
+    fn merges_games_with_variations() {
+        let collection = parse_collection(
+            "(;SZ[9];B[ee](;W[cc])(;W[gg]))(;SZ[9];B[ee];W[gg];B[cc])(;SZ[13];B[ee];W[dd])",
+        )
+        .unwrap();
+        let tree = merge_collection(&collection);
+        assert_eq!(tree.to_string(), "(;;B[ee](;W[cc])(;W[gg];B[cc])(;W[dd]))");
+    }
+
+    #[test]
+    fn merges_empty_collection() {
+        let tree = merge_collection(&Collection::default());
+        assert_eq!(tree.to_string(), "(;)");
+    }
+
+    #[test]
+    fn indexes_games_by_player() {
+        let collection = parse_collection(
+            "(;PB[Shusaku]PW[Gennan])(;PB[ＧＥＮＮＡＮ]PW[Honinbo Shusaku])(;PB[]PW[Gennan Inseki])",
+        )
+        .unwrap();
+        let mut aliases = PlayerAliases::new();
+        aliases
+            .add("Honinbo Shusaku", "Shusaku")
+            .add("Gennan Inseki", "Gennan");
+        let index = collection.index_by_player_with(&aliases);
+
+        assert_eq!(index.names().collect::<Vec<_>>(), vec!["gennan", "shusaku"]);
+        assert_eq!(
+            index.get("SHUSAKU"),
+            &[
+                PlayerGame {
+                    game: 0,
+                    color: Color::Black
+                },
+                PlayerGame {
+                    game: 1,
+                    color: Color::White
+                }
+            ]
+        );
+        assert_eq!(index.get("Gennan Inseki").len(), 3);
+        assert!(index.get("Jowa").is_empty());
+        assert_eq!(collection.index_by_player().players.len(), 4);
+    }
+
+    #[test]
+    fn queries_games() {
+        let collection = parse_collection(
+            "(;DT[1846-09-11,12]PB[Shusaku]PW[Gennan]RE[B+2])\
+             (;DT[1853-01-01]PB[Gennan]PW[Shusaku]RE[W+R])\
+             (;SZ[13]PB[Shusaku]PW[Jowa]RE[Void])",
+        )
+        .unwrap();
+
+        assert_eq!(Query::new().run(&collection).len(), 3);
+        assert_eq!(Query::new().player("SHUSAKU").run(&collection).len(), 3);
+        assert_eq!(
+            Query::new()
+                .player_with_color("Shusaku", Color::White)
+                .run(&collection),
+            vec![&collection.games[1]]
+        );
+        assert_eq!(
+            Query::new()
+                .player("Shusaku")
+                .player("Gennan")
+                .result_winner(Color::Black)
+                .run(&collection),
+            vec![&collection.games[0]]
+        );
+        assert_eq!(
+            Query::new().board_size(13).run(&collection),
+            vec![&collection.games[2]]
+        );
+        assert_eq!(
+            Query::new()
+                .date_range("1846-01-01"..="1846-12-31")
+                .run(&collection),
+            vec![&collection.games[0]]
+        );
+        assert!(Query::new()
+            .date_range(.."1846-01-01")
+            .run(&collection)
+            .is_empty());
+    }
+
+    #[test]
+    fn builds_and_splits_problem_sets() {
+        let trees = vec![
+            parse("(;GN[Ladder]GM[1]SZ[13]AB[cc];B[dd])").unwrap(),
+            parse("(;SZ[9]AW[ee]GC[White to kill])").unwrap(),
+        ];
+        let collection = ProblemSetBuilder::new("Set")
+            .comment("Black to play")
+            .problems(trees)
+            .problem(parse("(;AB[aa])").unwrap())
+            .build()
+            .unwrap();
+        let names: Vec<_> = collection
+            .split_problems()
+            .into_iter()
+            .map(|problem| (problem.name.unwrap(), problem.comment.unwrap()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Ladder".to_string(), "Black to play".to_string()),
+                ("Set 2".to_string(), "White to kill".to_string()),
+                ("Set 3".to_string(), "Black to play".to_string()),
+            ]
+        );
+
+        let reparsed = parse_collection(&collection.to_string()).unwrap();
+        let rebuilt = ProblemSetBuilder::new("Other")
+            .problems(reparsed.split_problems())
+            .build()
+            .unwrap();
+        assert_eq!(rebuilt.to_string(), collection.to_string());
+
+        let err = ProblemSetBuilder::new("Empty")
+            .problem(GameTree::default())
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::NodeNotFound);
+    }
+}
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod coordinate_tests {
+    use sgf_parser::*;
+
+    #[test]
+    fn can_convert_between_conventions() {
+        let coordinate = (1, 1);
+        assert_eq!(CoordinateSystem::SGF.from_sgf(coordinate, 9), Some((1, 1)));
+        assert_eq!(
+            CoordinateSystem::ZERO_BASED.from_sgf(coordinate, 9),
+            Some((0, 0))
+        );
+        assert_eq!(
+            CoordinateSystem::CARTESIAN.from_sgf(coordinate, 9),
+            Some((1, 9))
+        );
+
+        let zero_based_bottom_left = CoordinateSystem {
+            origin: Origin::BottomLeft,
+            zero_based: true,
+        };
+        assert_eq!(zero_based_bottom_left.from_sgf(coordinate, 9), Some((0, 8)));
+        assert_eq!(zero_based_bottom_left.to_sgf((0, 8), 9), Some(coordinate));
+    }
+
+    #[test]
+    fn rejects_coordinates_off_the_board() {
+        assert_eq!(CoordinateSystem::CARTESIAN.from_sgf((1, 19), 9), None);
+        assert_eq!(CoordinateSystem::ZERO_BASED.from_sgf((0, 0), 9), None);
+        assert_eq!(CoordinateSystem::SGF.from_sgf((1, 0), 9), None);
+        assert_eq!(CoordinateSystem::CARTESIAN.to_sgf((1, 10), 9), None);
+        assert_eq!(CoordinateSystem::ZERO_BASED.to_sgf((255, 0), 9), None);
+        assert_eq!(
+            CoordinateSystem::SGF.convert((9, 10), 9, CoordinateSystem::CARTESIAN),
+            None
+        );
+        assert_eq!(to_gtp_vertex((1, 30), 19), None);
+        assert_eq!(to_gtp_vertex((0, 1), 19), None);
+        assert_eq!(Point::new(1, 0).flip_y(9), None);
+        assert_eq!(Point::new(1, 1).flip_y(255), Some(Point::new(1, 255)));
+    }
+
+    #[test]
+    fn converts_whole_games() {
+        let tree: GameTree = parse("(;SZ[13]AB[dd](;B[jj];W[];B[aa])(;B[cc]))").unwrap();
+        assert_eq!(
+            tree.moves_in(CoordinateSystem::CARTESIAN).unwrap(),
+            vec![
+                (Color::Black, Action::Move(10, 4)),
+                (Color::White, Action::Pass),
+                (Color::Black, Action::Move(1, 13)),
+            ]
+        );
+        assert_eq!(
+            tree.stones_in(CoordinateSystem::ZERO_BASED).unwrap(),
+            vec![(Color::Black, (3, 3))]
+        );
+
+        let tree: GameTree = parse("(;SZ[9]AW[jj])").unwrap();
+        assert_eq!(
+            tree.stones_in(CoordinateSystem::SGF).unwrap_err().kind,
+            SgfErrorKind::InvalidTokenValue
+        );
+    }
+
+    #[test]
+    fn conversions_roundtrip() {
+        for x in 1..=13 {
+            for y in 1..=13 {
+                let converted = CoordinateSystem::SGF
+                    .convert((x, y), 13, CoordinateSystem::CARTESIAN)
+                    .unwrap();
+                assert_eq!(
+                    CoordinateSystem::CARTESIAN.convert(converted, 13, CoordinateSystem::SGF),
+                    Some((x, y))
+                );
+                assert_eq!(
+                    Point::new(x, y).flip_y(13).unwrap().flip_y(13),
+                    Some(Point::new(x, y))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn can_convert_gtp_vertices() {
+        assert_eq!(to_gtp_vertex((8, 9), 9), Some("H1".to_string()));
+        assert_eq!(to_gtp_vertex((26, 1), 26), None);
+        assert_eq!(from_gtp_vertex("h1", 9), Some((8, 9)));
+        assert_eq!(from_gtp_vertex("Z", 9), None);
+    }
+
+    #[test]
+    fn coords_match_the_parser() {
+        for x in 1..=52 {
+            for y in [1, 9, 26, 27, 52].iter() {
+                let value = coords::coordinate_to_str((x, *y)).unwrap();
+                assert_eq!(coords::str_to_coordinates(&value).unwrap(), (x, *y));
+                assert_eq!(
+                    SgfToken::from_pair("AB", &value),
+                    SgfToken::Add {
+                        color: Color::Black,
+                        coordinate: (x, *y)
+                    }
+                );
+            }
+        }
+        for value in &["", "aa", "ii", "tt", "ZZ"] {
+            let action = coords::str_to_action(value).unwrap();
+            assert_eq!(coords::action_to_str(action).unwrap(), *value);
+            assert_eq!(
+                SgfToken::from_pair("W", value),
+                SgfToken::Move {
+                    color: Color::White,
+                    action
+                }
+            );
+        }
+        assert_eq!(coords::coordinate_to_str((0, 1)), None);
+        assert_eq!(coords::coordinate_to_str((1, 53)), None);
+        assert_eq!(coords::action_to_str(Action::Move(255, 1)), None);
+        assert!(coords::str_to_action("a").is_err());
+        assert!(coords::str_to_coordinates("a-").is_err());
+    }
+}
@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod corpus_tests {
+    use sgf_parser::testing::*;
+    use sgf_parser::*;
+    use std::path::Path;
+
+    #[test]
+    fn fixture_files_are_clean() {
+        let report = check_corpus("tests/sgf").unwrap();
+        assert_eq!(report.files.len(), 3);
+        assert!(report.is_clean(), "{:#?}", report.failures());
+    }
+
+    #[test]
+    fn reports_broken_files_in_sub_directories() {
+        let report = check_corpus("tests/corpus").unwrap();
+        assert_eq!(report.files.len(), 4);
+
+        let failures = report.failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(
+            failures[0].path,
+            Path::new("tests/corpus/broken/unclosed.sgf")
+        );
+        assert_eq!(
+            failures[0].failure,
+            Some(CorpusFailure::Parse(SgfErrorKind::ParseError))
+        );
+    }
+
+    #[test]
+    fn missing_directory_is_an_error() {
+        let err = check_corpus("tests/does-not-exist").unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::IoError);
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(all(test, feature = "log"))]
+mod diagnostics_tests {
+    use log::{Level, LevelFilter, Log, Metadata, Record};
+    use sgf_parser::*;
+    use std::sync::Mutex;
+
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl Log for Recorder {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.target() == "sgf_parser" && metadata.level() == Level::Debug
+        }
+
+        fn log(&self, record: &Record<'_>) {
+            if self.enabled(record.metadata()) {
+                self.0.lock().unwrap().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static RECORDER: Recorder = Recorder(Mutex::new(vec![]));
+
+    #[test]
+    fn emits_debug_events() {
+        log::set_logger(&RECORDER).unwrap();
+        log::set_max_level(LevelFilter::Debug);
+
+        parse("(;size[19]XX[1];B[zzz])").unwrap();
+        let options = ParserOptions {
+            charset_policy: CharsetPolicy::TranscodeKnown,
+            ..ParserOptions::default()
+        };
+        parse_bytes_with(b"(;CA[latin1]C[caf\xe9])", &options).unwrap();
+
+        let events = RECORDER.0.lock().unwrap();
+        assert!(events.contains(&"malformed identifier size: no uppercase letters".to_string()));
+        assert!(events.contains(&"unknown property XX: [1]".to_string()));
+        assert!(events.contains(&"invalid value for B: [zzz]".to_string()));
+        assert!(events
+            .iter()
+            .any(|event| event.starts_with("parsed 2 nodes from 23 bytes in ")));
+        assert!(events.contains(&"transcoding 20 bytes from latin1".to_string()));
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod dialect_tests {
+    use sgf_parser::*;
+
+    fn result(label: &str) -> SgfToken {
+        let mut tree: GameTree = parse(&format!("(;RE[{}])", label)).unwrap();
+        tree.normalize_dialect(Dialect::Fox);
+        tree.nodes[0].tokens[0].clone()
+    }
+
+    #[test]
+    fn reads_result_labels() {
+        let cases = [
+            ("黑中盘胜", Outcome::WinnerByResign(Color::Black)),
+            ("白中盘胜", Outcome::WinnerByResign(Color::White)),
+            ("白超时负", Outcome::WinnerByTime(Color::Black)),
+            (
+                "黑胜3.5目",
+                Outcome::WinnerByPoints(Color::Black, Real::new(3.5).unwrap()),
+            ),
+            (
+                "白胜1又3/4子",
+                Outcome::WinnerByPoints(Color::White, Real::new(3.5).unwrap()),
+            ),
+            ("和棋", Outcome::Draw),
+            ("흑 불계승", Outcome::WinnerByResign(Color::Black)),
+            (
+                "백 3집반승",
+                Outcome::WinnerByPoints(Color::White, Real::new(3.5).unwrap()),
+            ),
+            (
+                "흑 12집승",
+                Outcome::WinnerByPoints(Color::Black, Real::new(12.0).unwrap()),
+            ),
+            ("백 시간승", Outcome::WinnerByTime(Color::White)),
+        ];
+        for (label, outcome) in cases.iter() {
+            assert_eq!(result(label), SgfToken::Result(*outcome), "{}", label);
+        }
+        assert_eq!(
+            result("黑胜"),
+            SgfToken::Invalid(("RE".into(), "黑胜".into()))
+        );
+    }
+
+    #[test]
+    fn reads_fox_komi() {
+        let mut tree: GameTree = parse("(;RU[Japanese]KM[650])").unwrap();
+        assert_eq!(tree.normalize_dialect(Dialect::Fox), 1);
+        assert_eq!(tree.to_string(), "(;KM[6.5]RU[Japanese])");
+        assert_eq!(
+            tree.serialize_dialect(Dialect::Fox),
+            "(;KM[650]RU[Japanese])"
+        );
+
+        let mut tree: GameTree = parse("(;RU[Chinese]KM[375])").unwrap();
+        tree.normalize_dialect(Dialect::Fox);
+        assert_eq!(tree.to_string(), "(;KM[7.5]RU[Chinese])");
+        assert_eq!(
+            tree.serialize_dialect(Dialect::Fox),
+            "(;KM[375]RU[Chinese])"
+        );
+
+        let mut tree: GameTree = parse("(;KM[375])").unwrap();
+        assert_eq!(tree.normalize_dialect(Dialect::Tygem), 0);
+    }
+
+    #[test]
+    fn roundtrips_dialect_output() {
+        let source = "(;BR[3k]KM[6.5]RE[B+R]RU[Japanese]WR[9p];B[pd];W[dd])";
+        let tree: GameTree = parse(source).unwrap();
+        for dialect in [Dialect::Fox, Dialect::Tygem].iter() {
+            let mut reparsed: GameTree = parse(&tree.serialize_dialect(*dialect)).unwrap();
+            reparsed.normalize_dialect(*dialect);
+            assert_eq!(reparsed.to_string(), source, "{:?}", dialect);
+        }
+        assert_eq!(
+            tree.serialize_dialect(Dialect::Fox),
+            "(;BR[3级]KM[650]RE[黑中盘胜]RU[Japanese]WR[职业9段];B[pd];W[dd])"
+        );
+    }
+}
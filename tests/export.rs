@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod export_tests {
+    use sgf_parser::*;
+
+    const GAMES: &str = "(;PB[Shusaku]BR[4d]PW[Gennan \"Inseki\"]WR[8d]RE[B+2]DT[1846-09-11]\
+                          EV[Castle\ngame];B[qd];W[dc];B[pq];W[])\
+                         (;KM[6.5]HA[2]AB[dd][pp];W[qd])";
+
+    #[test]
+    fn collects_records() {
+        let collection = parse_collection(GAMES).unwrap();
+        let records = collection.records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].white_rank, Some(Rank::Dan(8)));
+        assert_eq!(
+            records[0].result,
+            Some(Outcome::WinnerByPoints(
+                Color::Black,
+                Real::new(2.0).unwrap()
+            ))
+        );
+        assert_eq!(records[0].moves, 4);
+        assert_eq!(
+            records[1],
+            GameRecord {
+                moves: 1,
+                komi: Some(6.5),
+                handicap: Some(2),
+                ..GameRecord::default()
+            }
+        );
+    }
+
+    #[test]
+    fn exports_csv() {
+        let collection = parse_collection(GAMES).unwrap();
+        let csv = collection.to_csv();
+        let lines: Vec<_> = csv.split('\n').collect();
+        assert_eq!(lines[0], GAME_RECORD_COLUMNS.join(","));
+        assert_eq!(
+            lines[1],
+            "Shusaku,\"Gennan \"\"Inseki\"\"\",4d,8d,B+2,1846-09-11,\"Castle"
+        );
+        assert_eq!(lines[2], "game\",4,,");
+        assert_eq!(lines[3], ",,,,,,,1,6.5,2");
+        assert_eq!(Collection::default().to_csv().lines().count(), 1);
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn exports_parquet() {
+        let collection = parse_collection(GAMES).unwrap();
+        let mut output = vec![];
+        collection.write_parquet(&mut output).unwrap();
+        assert!(output.starts_with(b"PAR1"));
+        assert!(output.ends_with(b"PAR1"));
+    }
+}
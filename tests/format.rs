@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod format_tests {
+    use sgf_parser::*;
+
+    #[test]
+    fn formats_real_games_without_changing_them() {
+        let input = include_str!("sgf/ShusakuvsInseki.sgf");
+        let style = FormatStyle {
+            max_width: 60,
+            ..FormatStyle::default()
+        };
+        let formatted = format_sgf(input, &style).unwrap();
+        assert!(formatted.lines().all(|line| line.len() <= 60));
+        assert!(formatted.lines().count() > 10);
+        assert_eq!(
+            parse(&formatted).unwrap().to_string(),
+            parse(input).unwrap().to_string()
+        );
+        assert_eq!(format_sgf(&formatted, &style).unwrap(), formatted);
+    }
+
+    #[test]
+    fn keeps_values_and_order_as_written() {
+        let input = "(;GM[1]C[Two\nlines \\] here]SZ[9];W[cc]XX[a]  [b]\n;B[ee]C[x])(;FF[4])";
+        let style = FormatStyle {
+            max_width: 0,
+            ..FormatStyle::default()
+        };
+        assert_eq!(
+            format_sgf(input, &style).unwrap(),
+            "(;GM[1]C[Two\nlines \\] here]SZ[9]\n;W[cc]XX[a][b];B[ee]C[x])\n(;FF[4])\n"
+        );
+
+        let style = FormatStyle {
+            token_order: Some(TokenOrder::Canonical),
+            indent: 4,
+            max_width: 12,
+        };
+        assert_eq!(
+            format_sgf("(;SZ[9]GM[1];C[Hi]B[ee](;W[cc])(;W[gg]BL[30]))", &style).unwrap(),
+            "(;GM[1]SZ[9]\n;B[ee]C[Hi]\n    (;W[cc])\n    (;W[gg]\n    BL[30]))\n"
+        );
+        assert!(format_sgf("(;B[aa]", &style).is_err());
+    }
+}
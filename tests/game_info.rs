@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod game_info_tests {
+    use sgf_parser::*;
+
+    fn check(source: &str) -> Vec<GameInfoIssue> {
+        parse(source).unwrap().check_game_info()
+    }
+
+    #[test]
+    fn accepts_consistent_handicap() {
+        assert!(check("(;HA[2]KM[0.5]AB[dd][pp];W[pd])").is_empty());
+        assert!(check("(;RU[Chinese]KM[7.5];B[pd])").is_empty());
+        assert!(check("(;B[pd])").is_empty());
+    }
+
+    #[test]
+    fn flags_handicap_problems() {
+        assert_eq!(
+            check("(;HA[3]AB[dd][pp];W[pd])"),
+            vec![GameInfoIssue::HandicapStoneMismatch {
+                handicap: 3,
+                stones: 2
+            }]
+        );
+        assert_eq!(check("(;HA[1])"), vec![GameInfoIssue::InvalidHandicap(1)]);
+    }
+
+    #[test]
+    fn flags_unusual_komi() {
+        assert_eq!(
+            check("(;RU[Japanese]KM[7.5])"),
+            vec![GameInfoIssue::UnusualKomi {
+                komi: 7.5,
+                rules: Some(RuleSet::Japanese),
+                handicap: 0,
+            }]
+        );
+        assert_eq!(check("(;HA[2]AB[dd][pp]KM[6.5])").len(), 1);
+    }
+
+    #[test]
+    fn ignores_other_games() {
+        assert!(check("(;GM[2]HA[4]KM[100])").is_empty());
+    }
+
+    #[test]
+    fn uses_default_size_of_the_game() {
+        let tree: GameTree = parse("(;GM[11];B[aa])").unwrap();
+        assert_eq!(tree.game(), Game::Hex);
+        assert_eq!(Board::for_tree(&tree).unwrap().size(), (11, 11));
+
+        let tree: GameTree = parse("(;GM[40])").unwrap();
+        assert_eq!(tree.game().name(), Some("Kropki"));
+        assert_eq!(tree.board_size(), None);
+        assert_eq!(Board::for_tree(&tree).unwrap().size(), (19, 19));
+    }
+
+    #[test]
+    fn game_codes_round_trip() {
+        for code in 1..=255 {
+            assert_eq!(Game::from_code(code).code(), code);
+        }
+        let token: String = SgfToken::Game(Game::Shogi).into();
+        assert_eq!(token, "GM[8]");
+    }
+}
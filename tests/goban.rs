@@ -0,0 +1,77 @@
+#[cfg(all(test, feature = "goban"))]
+mod goban_tests {
+    use std::convert::TryFrom;
+
+    use goban::pieces::stones::Color as GobanColor;
+    use goban::rules::game::Game as GobanGame;
+    use goban::rules::EndGame;
+    use sgf_parser::*;
+
+    #[test]
+    fn replays_setup_and_moves() {
+        let tree: GameTree = parse("(;SZ[13]KM[0.5]HA[2]AB[dd][jj];W[dj];B[jd];W[])").unwrap();
+        let game = GobanGame::try_from(&tree).unwrap();
+        assert_eq!(game.size(), (13, 13));
+        assert_eq!(game.komi(), 0.5);
+        assert_eq!(game.turn(), GobanColor::Black);
+        assert_eq!(game.goban().get_color((3, 3)), Some(GobanColor::Black));
+        assert_eq!(game.goban().get_color((9, 3)), Some(GobanColor::White));
+        assert_eq!(game.goban().number_of_stones(), (3, 1));
+    }
+
+    #[test]
+    fn keeps_captures_and_results() {
+        let tree: GameTree = parse("(;SZ[9]RE[W+R];B[ba];W[aa];B[ab])").unwrap();
+        let game = GobanGame::try_from(tree).unwrap();
+        assert_eq!(game.goban().get_color((0, 0)), None);
+        assert_eq!(game.goban().number_of_stones(), (2, 0));
+        assert_eq!(
+            game.outcome(),
+            Some(EndGame::WinnerByResign(GobanColor::White))
+        );
+    }
+
+    #[test]
+    fn round_trips_positions() {
+        let tree: GameTree =
+            parse("(;SZ[19]KM[6.5]PL[W]AB[pd][dp]AW[dd];W[pp];B[qq](;W[qp])(;W[cc]))").unwrap();
+        let game = GobanGame::try_from(&tree).unwrap();
+        let position = GameTree::from(&game);
+        assert_eq!(
+            position.to_string(),
+            "(;AB[dp][pd][qq]AW[dd][pp][qp]KM[6.5]PL[B]SZ[19])"
+        );
+        assert_eq!(
+            position.to_string(),
+            tree.final_position_sgf().unwrap().to_string()
+        );
+
+        let replayed = GobanGame::try_from(&position).unwrap();
+        assert_eq!(replayed.goban(), game.goban());
+        assert_eq!(replayed.size(), game.size());
+        assert_eq!(replayed.komi(), game.komi());
+        assert_eq!(replayed.turn(), game.turn());
+        assert_eq!(GameTree::from(replayed).to_string(), position.to_string());
+    }
+
+    #[test]
+    fn rejects_games_goban_cannot_represent() {
+        let errors = [
+            ("(;SZ[25];B[aa])", SgfErrorKind::InvalidTokenValue),
+            ("(;GM[3]SZ[8])", SgfErrorKind::InvalidTokenValue),
+            ("(;SZ[9];B[aa];B[bb])", SgfErrorKind::InvalidTokenValue),
+            ("(;SZ[9];B[aa];AW[bb])", SgfErrorKind::InvalidTokenValue),
+            ("(;SZ[9];B[aa];W[aa])", SgfErrorKind::IllegalMove),
+            ("(;SZ[9];B[];W[];B[aa])", SgfErrorKind::IllegalMove),
+        ];
+        for (sgf, kind) in errors.iter() {
+            let tree: GameTree = parse(sgf).unwrap();
+            assert_eq!(
+                GobanGame::try_from(&tree).unwrap_err().kind,
+                *kind,
+                "{}",
+                sgf
+            );
+        }
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod infer_tests {
+    use sgf_parser::*;
+
+    fn infer(source: &str) -> Option<InferredResult> {
+        parse(source).unwrap().infer_result()
+    }
+
+    #[test]
+    fn reads_results_from_comments() {
+        let result = infer("(;B[aa];W[bb]C[B+R])").unwrap();
+        assert_eq!(result.outcome, Outcome::WinnerByResign(Color::Black));
+
+        let result = infer("(;B[aa]C[Black won by time.];W[bb];B[cc])").unwrap();
+        assert_eq!(result.outcome, Outcome::WinnerByTime(Color::Black));
+
+        let result = infer("(;B[aa];W[bb]C[The game ended in jigo])").unwrap();
+        assert_eq!(result.outcome, Outcome::Draw);
+        assert_eq!(result.confidence, Confidence::High);
+    }
+
+    #[test]
+    fn counts_territory_marks() {
+        let result =
+            infer("(;SZ[3]KM[0.5];B[ba];W[bb];B[ac]TB[aa][ab][bc][cc]TW[ca][cb])").unwrap();
+        assert_eq!(result.confidence, Confidence::Medium);
+        // Black has 6 area points, white has 3, with W[bb] left alive
+        assert_eq!(
+            result.outcome,
+            Outcome::WinnerByPoints(Color::Black, Real::new(2.5).unwrap())
+        );
+    }
+
+    #[test]
+    fn estimates_after_two_passes() {
+        let result = infer("(;SZ[3]KM[0.5];B[bb];W[];B[])").unwrap();
+        assert_eq!(result.confidence, Confidence::Low);
+        assert_eq!(
+            result.outcome,
+            Outcome::WinnerByPoints(Color::Black, Real::new(8.5).unwrap())
+        );
+    }
+
+    #[test]
+    fn returns_none_without_evidence() {
+        assert_eq!(infer("(;B[aa];W[bb]C[Nice game])"), None);
+    }
+}
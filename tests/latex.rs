@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod latex_tests {
+    use sgf_parser::*;
+
+    #[test]
+    fn renders_position_after_captures() {
+        let tree: GameTree = parse("(;SZ[5];B[ba];W[aa];B[ab];W[ee])").unwrap();
+        assert_eq!(
+            tree.to_latex(3).unwrap(),
+            "\\gobansize{5}\n\\cleargoban\n\\black{b5,a4}\n\\showfullgoban\n"
+        );
+        assert_eq!(
+            tree.to_latex(0).unwrap(),
+            "\\gobansize{5}\n\\cleargoban\n\\showfullgoban\n"
+        );
+    }
+
+    #[test]
+    fn renders_numbered_moves() {
+        let tree: GameTree = parse("(;SZ[5];B[ba];W[aa];B[ab];W[cc];B[aa])").unwrap();
+        assert_eq!(
+            tree.to_latex_moves(1..=5).unwrap(),
+            "\\gobansize{5}\n\\cleargoban\n\
+             \\black[1]{b5}\n\\white[2]{a5}\n\\black[3]{a4}\n\\white[4]{c3}\n\
+             \\showfullgoban\n\n5: a5\n"
+        );
+    }
+
+    #[test]
+    fn fails_on_illegal_moves() {
+        let tree: GameTree = parse("(;SZ[5];B[aa];W[aa])").unwrap();
+        assert_eq!(
+            tree.to_latex_moves(1..=2).unwrap_err().kind,
+            SgfErrorKind::IllegalMove
+        );
+    }
+}
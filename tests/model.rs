@@ -10,21 +10,17 @@ mod model_tests {
         assert_eq!(unknowns.len(), 2);
         assert_eq!(
             *unknowns[0],
-            GameNode {
-                tokens: vec![
-                    SgfToken::Move {
-                        color: Color::White,
-                        action: Move(5, 6),
-                    },
-                    SgfToken::Unknown(("AC".to_string(), "23".to_string()))
-                ]
-            }
+            GameNode::from(vec![
+                SgfToken::Move {
+                    color: Color::White,
+                    action: Move(5, 6),
+                },
+                SgfToken::Unknown(("AC".into(), "23".into()))
+            ])
         );
         assert_eq!(
             *unknowns[1],
-            GameNode {
-                tokens: vec![SgfToken::Unknown(("AS".to_string(), "234".to_string()))]
-            }
+            GameNode::from(vec![SgfToken::Unknown(("AS".into(), "234".into()))])
         );
     }
 
@@ -35,15 +31,11 @@ mod model_tests {
         assert_eq!(unknowns.len(), 2);
         assert_eq!(
             *unknowns[0],
-            GameNode {
-                tokens: vec![SgfToken::Invalid(("W".to_string(), "foobar".to_string()))]
-            }
+            GameNode::from(vec![SgfToken::Invalid(("W".into(), "foobar".into()))])
         );
         assert_eq!(
             *unknowns[1],
-            GameNode {
-                tokens: vec![SgfToken::Invalid(("B".to_string(), "234".to_string()))]
-            }
+            GameNode::from(vec![SgfToken::Invalid(("B".into(), "234".into()))])
         );
     }
 
@@ -54,21 +46,17 @@ mod model_tests {
 
         assert_eq!(
             iter.next(),
-            Some(&GameNode {
-                tokens: vec![SgfToken::Move {
-                    color: Color::Black,
-                    action: Move(4, 3),
-                }]
-            })
+            Some(&GameNode::from(vec![SgfToken::Move {
+                color: Color::Black,
+                action: Move(4, 3),
+            }]))
         );
         assert_eq!(
             iter.next(),
-            Some(&GameNode {
-                tokens: vec![SgfToken::Move {
-                    color: Color::White,
-                    action: Move(5, 6),
-                }]
-            })
+            Some(&GameNode::from(vec![SgfToken::Move {
+                color: Color::White,
+                action: Move(5, 6),
+            }]))
         );
         assert_eq!(iter.next(), None);
     }
@@ -80,30 +68,24 @@ mod model_tests {
 
         assert_eq!(
             iter.next(),
-            Some(&GameNode {
-                tokens: vec![SgfToken::Move {
-                    color: Color::Black,
-                    action: Move(4, 3),
-                }]
-            })
+            Some(&GameNode::from(vec![SgfToken::Move {
+                color: Color::Black,
+                action: Move(4, 3),
+            }]))
         );
         assert_eq!(
             iter.next(),
-            Some(&GameNode {
-                tokens: vec![SgfToken::Move {
-                    color: Color::White,
-                    action: Move(5, 6),
-                }]
-            })
+            Some(&GameNode::from(vec![SgfToken::Move {
+                color: Color::White,
+                action: Move(5, 6),
+            }]))
         );
         assert_eq!(
             iter.next(),
-            Some(&GameNode {
-                tokens: vec![SgfToken::Move {
-                    color: Color::Black,
-                    action: Move(1, 1),
-                }]
-            })
+            Some(&GameNode::from(vec![SgfToken::Move {
+                color: Color::Black,
+                action: Move(1, 1),
+            }]))
         );
         assert_eq!(iter.next(), None);
     }
@@ -120,30 +102,24 @@ mod model_tests {
 
         assert_eq!(
             iter.next(),
-            Some(&GameNode {
-                tokens: vec![SgfToken::Move {
-                    color: Color::Black,
-                    action: Move(4, 3),
-                }]
-            })
+            Some(&GameNode::from(vec![SgfToken::Move {
+                color: Color::Black,
+                action: Move(4, 3),
+            }]))
         );
         assert_eq!(
             iter.next(),
-            Some(&GameNode {
-                tokens: vec![SgfToken::Move {
-                    color: Color::White,
-                    action: Move(5, 6),
-                }]
-            })
+            Some(&GameNode::from(vec![SgfToken::Move {
+                color: Color::White,
+                action: Move(5, 6),
+            }]))
         );
         assert_eq!(
             iter.next(),
-            Some(&GameNode {
-                tokens: vec![SgfToken::Move {
-                    color: Color::Black,
-                    action: Move(3, 3),
-                }]
-            })
+            Some(&GameNode::from(vec![SgfToken::Move {
+                color: Color::Black,
+                action: Move(3, 3),
+            }]))
         );
         assert_eq!(iter.next(), None);
     }
@@ -153,4 +129,184 @@ mod model_tests {
         let tree: GameTree = parse("(;B[dc];W[ef](;B[aa])(;B[cc];W[dd]))").unwrap();
         assert_eq!(tree.count_max_nodes(), 4);
     }
+
+    #[test]
+    fn iterator_can_switch_nested_branches() {
+        let tree: GameTree =
+            parse("(;B[aa](;W[bb](;B[cc])(;B[dd];W[ee](;B[ff])(;B[gg])))(;W[hh]))").unwrap();
+        let mut iter = tree.iter();
+
+        iter.next();
+        assert!(iter.pick_variation(0).is_ok());
+        iter.next();
+        assert_eq!(iter.count_variations(), 2);
+        assert!(iter.pick_variation(1).is_ok());
+        iter.next();
+        iter.next();
+        assert!(iter.pick_variation(1).is_ok());
+
+        assert_eq!(
+            iter.next(),
+            Some(&GameNode::from(vec![SgfToken::Move {
+                color: Color::Black,
+                action: Move(7, 7),
+            }]))
+        );
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iterator_previews_variations_and_rejects_missing_ones() {
+        let tree: GameTree = parse("(;B[aa](;W[bb](;B[cc])(;B[dd]))(;W[hh]))").unwrap();
+        let mut iter = tree.iter();
+        let previews = iter.variations();
+        assert_eq!(previews.len(), 2);
+        assert_eq!(previews[0].first_move, Some((Color::White, Move(2, 2))));
+        assert_eq!(previews[1].index, 1);
+        assert_eq!(previews[1].name, None);
+
+        let err = iter.pick_variation(3).unwrap_err();
+        assert_eq!(
+            err.kind,
+            SgfErrorKind::VariationOutOfRange {
+                requested: 3,
+                available: 2
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Variation 3 picked, but there are 2 variations"
+        );
+
+        iter.next();
+        iter.next();
+        assert_eq!(
+            iter.variations()[1].first_move,
+            Some((Color::Black, Move(4, 4)))
+        );
+        let leaf: GameTree = parse("(;B[aa])").unwrap();
+        assert!(leaf.iter().variations().is_empty());
+    }
+
+    #[test]
+    fn iterator_uses_first_sub_branch_by_default() {
+        let tree: GameTree = parse("(;B[aa](;W[bb](;B[cc])(;B[dd]))(;W[hh]))").unwrap();
+        let mut iter = tree.iter();
+        assert!(iter.pick_variation(0).is_ok());
+
+        let nodes = iter.collect::<Vec<_>>();
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(
+            *nodes[2],
+            GameNode::from(vec![SgfToken::Move {
+                color: Color::Black,
+                action: Move(3, 3),
+            }])
+        );
+    }
+
+    #[test]
+    fn iterator_follows_path_then_chooser() {
+        let tree: GameTree =
+            parse("(;B[aa](;W[bb](;B[cc])(;B[dd](;W[ee])(;W[ff])))(;W[hh]))").unwrap();
+        let mut iter = tree
+            .iter()
+            .with_path(&[0, 1])
+            .unwrap()
+            .with_chooser(|previews| previews.len() - 1);
+        let last = iter.by_ref().last().unwrap();
+        assert_eq!(
+            *last,
+            GameNode::from(vec![SgfToken::Move {
+                color: Color::White,
+                action: Move(6, 6),
+            }])
+        );
+        assert_eq!(iter.variations_taken(), &[0, 1, 1]);
+
+        let mut iter = tree.iter().with_chooser(|_| 5);
+        assert_eq!(iter.by_ref().count(), 1);
+        assert!(iter.variations_taken().is_empty());
+    }
+
+    #[test]
+    fn picked_variation_overrides_path() {
+        let tree: GameTree = parse("(;B[aa](;W[bb])(;W[hh]))").unwrap();
+        let mut iter = tree.iter().with_path(&[0]).unwrap();
+        assert!(iter.pick_variation(1).is_ok());
+        assert_eq!(iter.by_ref().count(), 2);
+        assert_eq!(iter.variations_taken(), &[1]);
+    }
+
+    #[test]
+    fn can_iterate_in_reverse() {
+        let tree: GameTree = parse("(;B[dc];W[ef](;B[aa])(;B[cc];W[ee]))").unwrap();
+        let forward = tree.iter().collect::<Vec<_>>();
+        let mut backward = tree.iter_rev().collect::<Vec<_>>();
+        backward.reverse();
+        assert_eq!(forward, backward);
+    }
+
+    #[test]
+    fn can_iterate_suffix_from_path() {
+        let tree: GameTree = parse("(;B[dc];W[ef](;B[aa])(;B[cc];W[ee]))").unwrap();
+        let mut iter = tree.iter_from(&TreePath::new(vec![], 1)).unwrap();
+        assert!(iter.pick_variation(1).is_ok());
+        assert_eq!(iter.count(), 3);
+
+        assert_eq!(
+            tree.iter_from(&TreePath::new(vec![2], 0))
+                .err()
+                .unwrap()
+                .kind,
+            SgfErrorKind::VariationNotFound
+        );
+        assert_eq!(
+            tree.iter_from(&TreePath::new(vec![0], 1))
+                .err()
+                .unwrap()
+                .kind,
+            SgfErrorKind::NodeNotFound
+        );
+    }
+
+    #[test]
+    fn can_get_line_to_path() {
+        let tree: GameTree = parse("(;B[dc];W[ef](;B[aa])(;B[cc];W[ee]))").unwrap();
+        let line = tree.get_line_to(&TreePath::new(vec![1], 0)).unwrap();
+        assert_eq!(line.len(), 3);
+        assert_eq!(
+            *line[2],
+            GameNode::from(vec![SgfToken::Move {
+                color: Color::Black,
+                action: Move(3, 3),
+            }])
+        );
+    }
+
+    #[test]
+    fn count_nodes_in_all_variations() {
+        let tree: GameTree = parse("(;B[dc];W[ef](;B[aa](;W[bb])(;W[cc]))(;B[cc];W[dd]))").unwrap();
+        assert_eq!(tree.total_node_count(), 7);
+        assert_eq!(tree.leaf_count(), 3);
+        assert_eq!(tree.max_depth(), 2);
+        assert_eq!(tree.variation_count_recursive(), 4);
+        assert_eq!(GameTree::default().total_node_count(), 0);
+        assert_eq!(GameTree::default().max_depth(), 0);
+        assert_eq!(GameTree::default().variation_count_recursive(), 0);
+    }
+
+    #[test]
+    fn count_deeply_nested_variations() {
+        let tree: GameTree = parse(
+            "(;SZ[9];B[ee](;W[cc];B[gg](;W[gc](;B[cg])(;B[dg];W[eg]))(;W[dd]))(;W[gg])(;W[cg]))",
+        )
+        .unwrap();
+        assert_eq!(tree.total_node_count(), 11);
+        assert_eq!(tree.leaf_count(), 5);
+        assert_eq!(tree.max_depth(), 3);
+        assert_eq!(tree.variation_count_recursive(), 7);
+        assert_eq!(tree.count_variations(), 3);
+        assert_eq!(tree.count_max_nodes(), 7);
+    }
 }
@@ -4,41 +4,119 @@ mod node_tests {
 
     #[test]
     fn can_convert_node_to_string() {
-        let node = GameNode {
-            tokens: vec![
-                SgfToken::PlayerName {
-                    color: Color::Black,
-                    name: "black".to_string(),
-                },
-                SgfToken::PlayerName {
-                    color: Color::White,
-                    name: "white".to_string(),
-                },
-            ],
-        };
+        let node = GameNode::from(vec![
+            SgfToken::PlayerName {
+                color: Color::Black,
+                name: "black".into(),
+            },
+            SgfToken::PlayerName {
+                color: Color::White,
+                name: "white".into(),
+            },
+        ]);
         let string_node: String = node.into();
         assert_eq!(string_node, ";PB[black]PW[white]");
     }
 
     #[test]
     fn can_convert_node_with_multiple_of_same_property_to_string() {
-        let node = GameNode {
-            tokens: vec![
-                SgfToken::Add {
-                    color: Color::Black,
-                    coordinate: (1, 1),
-                },
-                SgfToken::PlayerName {
-                    color: Color::White,
-                    name: "white".to_string(),
-                },
-                SgfToken::Add {
-                    color: Color::Black,
-                    coordinate: (2, 2),
-                },
-            ],
-        };
+        let node = GameNode::from(vec![
+            SgfToken::Add {
+                color: Color::Black,
+                coordinate: (1, 1),
+            },
+            SgfToken::PlayerName {
+                color: Color::White,
+                name: "white".into(),
+            },
+            SgfToken::Add {
+                color: Color::Black,
+                coordinate: (2, 2),
+            },
+        ]);
         let string_node: String = node.into();
         assert_eq!(string_node, ";AB[aa][bb]PW[white]");
     }
+
+    #[test]
+    fn can_build_and_iterate_node() {
+        let mut node = GameNode::from(vec![SgfToken::from_pair("B", "aa")]);
+        node.extend(vec![SgfToken::from_pair("C", "comment")]);
+        assert_eq!((&node).into_iter().count(), 2);
+
+        let tokens = node.into_iter().collect::<Vec<_>>();
+        assert_eq!(tokens[1], SgfToken::Comment("comment".into()));
+        assert_eq!(GameNode::default().tokens.len(), 0);
+    }
+
+    #[test]
+    fn keeps_extras_out_of_sgf() {
+        #[derive(Debug, PartialEq)]
+        struct Eval {
+            score: f32,
+            visits: u32,
+        }
+
+        let mut tree: GameTree = parse("(;SZ[9];B[ee];W[cc])").unwrap();
+        tree.nodes[2].extras_mut().insert(
+            "eval",
+            Eval {
+                score: -1.5,
+                visits: 800,
+            },
+        );
+        let copy = tree.clone();
+        assert_eq!(
+            copy.nodes[2].extras().get::<Eval>("eval"),
+            Some(&Eval {
+                score: -1.5,
+                visits: 800
+            })
+        );
+        assert_eq!(tree, parse("(;SZ[9];B[ee];W[cc])").unwrap());
+        assert_eq!(format!("{:?}", tree.nodes[2].extras()), "{\"eval\"}");
+
+        let node = &mut tree.nodes[2];
+        assert!(!node.write_extra("eval", "XE", |eval: &f32| eval.to_string()));
+        assert!(node.write_extra("eval", "XE", |eval: &Eval| eval.visits.to_string()));
+        assert!(node.write_extra("eval", "XE", |eval: &Eval| eval.score.to_string()));
+        assert_eq!(tree.to_string(), "(;SZ[9];B[ee];W[cc]XE[-1.5])");
+
+        assert!(tree.nodes[2].extras_mut().remove("eval"));
+        assert!(tree.nodes[2].extras().is_empty());
+    }
+
+    #[test]
+    fn writes_canonical_order_regardless_of_construction() {
+        let tokens = vec![
+            SgfToken::from_pair("LB", "aa:A"),
+            SgfToken::from_pair("XX", "private"),
+            SgfToken::from_pair("C", "Joseki"),
+            SgfToken::from_pair("W", "bb"),
+            SgfToken::from_pair("WL", "12"),
+            SgfToken::from_pair("TR", "cc"),
+            SgfToken::from_pair("PW", "Lee"),
+        ];
+        let expected = ";PW[Lee]W[bb]WL[12]C[Joseki]LB[aa:A]TR[cc]XX[private]";
+        let mut reversed: GameNode = tokens.iter().rev().cloned().collect();
+        let mut node = GameNode::from(tokens);
+
+        node.sort_tokens(TokenOrder::Canonical);
+        reversed.sort_tokens(TokenOrder::Canonical);
+        assert_eq!(node.tokens, reversed.tokens);
+
+        let tree = GameTree {
+            nodes: vec![node],
+            variations: vec![],
+        };
+        let options = WriterOptions {
+            token_order: TokenOrder::Canonical,
+            ..WriterOptions::default()
+        };
+        assert_eq!(tree.serialize_with(&options), format!("({})", expected));
+        assert_eq!(
+            tree.to_string(),
+            "(;C[Joseki]LB[aa:A]PW[Lee]TR[cc]WL[12]W[bb]XX[private])"
+        );
+    }
 }
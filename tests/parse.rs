@@ -2,13 +2,14 @@
 mod parser_tests {
     use sgf_parser::Action::Move;
     use sgf_parser::*;
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn errors_on_invalid_root_token_placement() {
         let sgf = parse("(;KM[6.5];SZ[19])");
         match sgf {
             Err(ref e) => assert_eq!(e.kind, SgfErrorKind::InvalidRootTokenPlacement),
-            _ => assert!(false),
+            _ => panic!("expected an InvalidRootTokenPlacement error"),
         }
     }
 
@@ -20,9 +21,9 @@ mod parser_tests {
         assert_eq!(
             sgf,
             GameTree {
-                nodes: vec![GameNode {
-                    tokens: vec![SgfToken::Komi(6.5f32)]
-                }],
+                nodes: vec![GameNode::from(vec![SgfToken::Komi(
+                    Real::new(6.5).unwrap()
+                )])],
                 variations: vec![],
             }
         );
@@ -36,9 +37,7 @@ mod parser_tests {
         assert_eq!(
             sgf,
             GameTree {
-                nodes: vec![GameNode {
-                    tokens: vec![SgfToken::Copyright("2017".to_string())],
-                }],
+                nodes: vec![GameNode::from(vec![SgfToken::Copyright("2017".into())])],
                 variations: vec![],
             }
         );
@@ -52,18 +51,16 @@ mod parser_tests {
         assert_eq!(
             sgf,
             GameTree {
-                nodes: vec![GameNode {
-                    tokens: vec![
-                        SgfToken::Move {
-                            color: Color::Black,
-                            action: Move(4, 3),
-                        },
-                        SgfToken::Time {
-                            color: Color::Black,
-                            time: 3498,
-                        }
-                    ],
-                }],
+                nodes: vec![GameNode::from(vec![
+                    SgfToken::Move {
+                        color: Color::Black,
+                        action: Move(4, 3),
+                    },
+                    SgfToken::Time {
+                        color: Color::Black,
+                        time: 3498,
+                    }
+                ])],
                 variations: vec![],
             }
         );
@@ -78,18 +75,14 @@ mod parser_tests {
             sgf,
             GameTree {
                 nodes: vec![
-                    GameNode {
-                        tokens: vec![SgfToken::Move {
-                            color: Color::Black,
-                            action: Move(4, 3),
-                        }],
-                    },
-                    GameNode {
-                        tokens: vec![SgfToken::Move {
-                            color: Color::White,
-                            action: Move(5, 6),
-                        }],
-                    }
+                    GameNode::from(vec![SgfToken::Move {
+                        color: Color::Black,
+                        action: Move(4, 3),
+                    }]),
+                    GameNode::from(vec![SgfToken::Move {
+                        color: Color::White,
+                        action: Move(5, 6),
+                    }])
                 ],
                 variations: vec![],
             }
@@ -104,29 +97,23 @@ mod parser_tests {
         assert_eq!(
             sgf,
             GameTree {
-                nodes: vec![GameNode {
-                    tokens: vec![SgfToken::Move {
-                        color: Color::Black,
-                        action: Move(1, 1),
-                    }],
-                },],
+                nodes: vec![GameNode::from(vec![SgfToken::Move {
+                    color: Color::Black,
+                    action: Move(1, 1),
+                }]),],
                 variations: vec![
                     GameTree {
-                        nodes: vec![GameNode {
-                            tokens: vec![SgfToken::Move {
-                                color: Color::White,
-                                action: Move(2, 2),
-                            }],
-                        },],
+                        nodes: vec![GameNode::from(vec![SgfToken::Move {
+                            color: Color::White,
+                            action: Move(2, 2),
+                        }]),],
                         variations: vec![],
                     },
                     GameTree {
-                        nodes: vec![GameNode {
-                            tokens: vec![SgfToken::Move {
-                                color: Color::White,
-                                action: Move(3, 3),
-                            }],
-                        },],
+                        nodes: vec![GameNode::from(vec![SgfToken::Move {
+                            color: Color::White,
+                            action: Move(3, 3),
+                        }]),],
                         variations: vec![],
                     }
                 ],
@@ -143,26 +130,22 @@ mod parser_tests {
             sgf,
             GameTree {
                 nodes: vec![
-                    GameNode {
-                        tokens: vec![
-                            SgfToken::Event("event".to_string()),
-                            SgfToken::PlayerName {
-                                color: Color::Black,
-                                name: "black".to_string(),
-                            },
-                            SgfToken::PlayerName {
-                                color: Color::White,
-                                name: "white".to_string(),
-                            },
-                            SgfToken::Comment("comment".to_string()),
-                        ],
-                    },
-                    GameNode {
-                        tokens: vec![SgfToken::Move {
+                    GameNode::from(vec![
+                        SgfToken::Event("event".into()),
+                        SgfToken::PlayerName {
                             color: Color::Black,
-                            action: Move(1, 1),
-                        }],
-                    }
+                            name: "black".into(),
+                        },
+                        SgfToken::PlayerName {
+                            color: Color::White,
+                            name: "white".into(),
+                        },
+                        SgfToken::Comment("comment".into()),
+                    ]),
+                    GameNode::from(vec![SgfToken::Move {
+                        color: Color::Black,
+                        action: Move(1, 1),
+                    }])
                 ],
                 variations: vec![],
             }
@@ -178,21 +161,15 @@ mod parser_tests {
             sgf,
             GameTree {
                 nodes: vec![
-                    GameNode {
-                        tokens: vec![SgfToken::Move {
-                            color: Color::Black,
-                            action: Move(4, 3),
-                        }],
-                    },
-                    GameNode {
-                        tokens: vec![SgfToken::Unknown(("FO".to_string(), "asdf".to_string())),],
-                    },
-                    GameNode {
-                        tokens: vec![SgfToken::Move {
-                            color: Color::White,
-                            action: Move(5, 6),
-                        }],
-                    }
+                    GameNode::from(vec![SgfToken::Move {
+                        color: Color::Black,
+                        action: Move(4, 3),
+                    }]),
+                    GameNode::from(vec![SgfToken::Unknown(("FO".into(), "asdf".into())),]),
+                    GameNode::from(vec![SgfToken::Move {
+                        color: Color::White,
+                        action: Move(5, 6),
+                    }])
                 ],
                 variations: vec![],
             }
@@ -207,9 +184,9 @@ mod parser_tests {
         assert_eq!(
             sgf,
             GameTree {
-                nodes: vec![GameNode {
-                    tokens: vec![SgfToken::Comment("a [wrapped\\] comment".to_string()),],
-                },],
+                nodes: vec![GameNode::from(vec![SgfToken::Comment(
+                    "a [wrapped\\] comment".into()
+                ),]),],
                 variations: vec![],
             }
         );
@@ -223,24 +200,347 @@ mod parser_tests {
         assert_eq!(
             sgf,
             GameTree {
-                nodes: vec![GameNode {
-                    tokens: vec![
-                        SgfToken::Add {
-                            color: Color::Black,
-                            coordinate: (1, 1)
-                        },
-                        SgfToken::Add {
-                            color: Color::Black,
-                            coordinate: (1, 2)
-                        },
-                        SgfToken::Add {
-                            color: Color::Black,
-                            coordinate: (3, 3)
-                        },
-                    ],
-                },],
+                nodes: vec![GameNode::from(vec![
+                    SgfToken::Add {
+                        color: Color::Black,
+                        coordinate: (1, 1)
+                    },
+                    SgfToken::Add {
+                        color: Color::Black,
+                        coordinate: (1, 2)
+                    },
+                    SgfToken::Add {
+                        color: Color::Black,
+                        coordinate: (3, 3)
+                    },
+                ]),],
                 variations: vec![],
             }
         );
     }
+
+    #[test]
+    fn reports_progress_in_document_order() {
+        let input = "(;SZ[9](;B[aa];W[bb])(;B[cc]))";
+        let reports = Arc::new(Mutex::new(vec![]));
+        let recorded = Arc::clone(&reports);
+        let options = ParserOptions::new().progress(move |progress| {
+            recorded.lock().unwrap().push(progress);
+        });
+        let tree = parse_with_options(input, &options).unwrap().tree;
+        assert_eq!(tree, parse(input).unwrap());
+        let reports = reports.lock().unwrap();
+        assert_eq!(
+            reports.iter().map(|p| p.nodes).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(
+            reports.iter().map(|p| p.bytes).collect::<Vec<_>>(),
+            vec![7, 14, 20, 28]
+        );
+        assert!(reports.iter().all(|p| p.total_bytes == input.len()));
+    }
+
+    #[test]
+    fn reports_progress_to_options() {
+        let input = "(;SZ[9](;B[aa];W[bb])(;B[cc]))";
+        let reports = Arc::new(Mutex::new(vec![]));
+        let recorded = Arc::clone(&reports);
+        let options = ParserOptions {
+            progress: Some(Arc::new(move |progress| {
+                recorded.lock().unwrap().push(progress)
+            })),
+            ..ParserOptions::default()
+        };
+        let outcome = parse_with_options(input, &options).unwrap();
+        assert_eq!(outcome.tree, parse(input).unwrap());
+        let reports = reports.lock().unwrap();
+        assert_eq!(
+            reports
+                .iter()
+                .map(|p| (p.nodes, p.bytes))
+                .collect::<Vec<_>>(),
+            vec![(1, 7), (2, 14), (3, 20), (4, 28)]
+        );
+    }
+
+    #[test]
+    fn parses_fragments() {
+        let node = parse_node(" ;SZ[19]AB[aa][bb] ").unwrap();
+        assert_eq!(node.tokens.len(), 3);
+
+        let nodes = parse_sequence(";B[dd]C[hello];W[pp]").unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(
+            nodes[1].tokens,
+            vec![SgfToken::Move {
+                color: Color::White,
+                action: Action::Move(16, 16),
+            }]
+        );
+        assert_eq!(serialize_sequence(&nodes), ";B[dd]C[hello];W[pp]");
+    }
+
+    #[test]
+    fn rejects_invalid_fragments() {
+        assert!(parse_node(";B[dd];W[pp]").is_err());
+        assert!(parse_node("(;B[dd])").is_err());
+        assert!(parse_sequence("").is_err());
+        assert!(parse_sequence(";B[dd])").is_err());
+    }
+
+    #[test]
+    fn roundtrips_empty_nodes_and_values() {
+        for input in &[
+            "(;)",
+            "(;;B[aa];)",
+            "(;(;)(;))",
+            "(;VW[];DD[]TB[]TW[])",
+            "(;C[];B[])",
+        ] {
+            let tree: GameTree = parse(input).unwrap();
+            let output: String = (&tree).into();
+            assert_eq!(&output, input);
+            assert_eq!(parse(&output).unwrap(), tree);
+        }
+        let tree: GameTree = parse("(;VW[];B[aa])").unwrap();
+        assert_eq!(tree.nodes[0].tokens, vec![SgfToken::EmptyList("VW".into())]);
+        assert!(tree.nodes[0].tokens[0].identifier() == "VW");
+    }
+
+    #[test]
+    fn escapes_values_when_serializing() {
+        let tree: GameTree = parse(r"(;C[a\]b];C[ends with \\];N[\\\]])").unwrap();
+        let output: String = (&tree).into();
+        assert_eq!(output, r"(;C[a\]b];C[ends with \\];N[\\\]])");
+        assert_eq!(parse_bytes(output.as_bytes()).unwrap(), tree);
+
+        let node = GameNode::from(vec![
+            SgfToken::Comment("a]b\\".into()),
+            SgfToken::Unknown(("XX".into(), "[]".into())),
+        ]);
+        let output: String = (&node).into();
+        assert_eq!(output, r";C[a\]b\\]XX[[\]]");
+        assert_eq!(
+            parse_node(&output).unwrap().tokens[0],
+            SgfToken::Comment(r"a\]b\\".into())
+        );
+    }
+
+    #[test]
+    fn folds_identifier_case() {
+        let options = ParserOptions {
+            identifier_case: IdentifierCase::Fold,
+            ..ParserOptions::default()
+        };
+        let input = "(;Ff[4]size[19]GaMe[1];w[dd])";
+        let tree = parse_with_options(input, &options).unwrap().tree;
+        assert_eq!(tree.nodes[0].tokens[0], SgfToken::FileFormat(4));
+        assert_eq!(
+            tree.nodes[0].tokens[1],
+            SgfToken::Invalid(("size".into(), "19".into()))
+        );
+        assert_eq!(tree.nodes[0].tokens[2], SgfToken::Game(Game::Go));
+        assert_eq!(tree.nodes[1].tokens[0], SgfToken::from_pair("W", "dd"));
+        assert_eq!(parse_bytes_with(input.as_bytes(), &options).unwrap(), tree);
+
+        let tree = parse(input).unwrap();
+        assert_eq!(
+            tree.nodes[1].tokens[0],
+            SgfToken::Invalid(("w".into(), "dd".into()))
+        );
+    }
+
+    #[test]
+    fn keeps_lowercase_identifiers_as_invalid() {
+        let outcome = parse_with_warnings("(;ab[cd]C[ok];b[aa][bb])").unwrap();
+        let tree = outcome.tree;
+        assert_eq!(
+            tree.nodes[0].tokens[0],
+            SgfToken::Invalid(("ab".into(), "cd".into()))
+        );
+        assert_eq!(
+            tree.nodes[1].tokens,
+            vec![
+                SgfToken::Invalid(("b".into(), "aa".into())),
+                SgfToken::Invalid(("b".into(), "bb".into())),
+            ]
+        );
+        assert_eq!(tree.get_invalid_nodes().len(), 2);
+        assert_eq!(tree.to_string(), "(;C[ok]ab[cd];b[aa][bb])");
+        assert_eq!(
+            outcome.warnings[0].kind,
+            ParseWarningKind::MalformedIdentifier("ab".to_string())
+        );
+        assert_eq!(
+            parse_bytes(b"(;ab[cd])").unwrap(),
+            parse("(;ab[cd])").unwrap()
+        );
+    }
+
+    #[test]
+    fn applies_root_token_policy() {
+        let input = "(;SZ[19];B[ee]SZ[9](;W[cc]CA[UTF-8])(;W[gg]))";
+        let with = |root_tokens| ParserOptions {
+            root_tokens,
+            ..ParserOptions::default()
+        };
+
+        let outcome = parse_with_options(input, &with(RootTokenPolicy::Ignore)).unwrap();
+        assert_eq!(outcome.tree.nodes[1].tokens.len(), 2);
+        assert!(outcome.warnings.is_empty());
+
+        let outcome = parse_with_options(input, &with(RootTokenPolicy::Warn)).unwrap();
+        let warnings: Vec<_> = outcome
+            .warnings
+            .iter()
+            .map(|warning| {
+                (
+                    warning.variations.clone(),
+                    warning.node,
+                    warning.kind.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![
+                (
+                    vec![0],
+                    0,
+                    ParseWarningKind::MisplacedRootToken("CA".to_string())
+                ),
+                (
+                    vec![],
+                    1,
+                    ParseWarningKind::MisplacedRootToken("SZ".to_string())
+                ),
+            ]
+        );
+
+        let options = with(RootTokenPolicy::MoveToRoot);
+        let outcome = parse_with_options(input, &options).unwrap();
+        assert_eq!(
+            outcome.tree.to_string(),
+            "(;CA[UTF-8]SZ[19];B[ee](;W[cc])(;W[gg]))"
+        );
+        assert_eq!(
+            parse_bytes_with(input.as_bytes(), &options).unwrap(),
+            outcome.tree
+        );
+
+        let err = parse_with_options(input, &with(RootTokenPolicy::Error)).unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::InvalidRootTokenPlacement);
+        assert!(parse_bytes_with(input.as_bytes(), &with(RootTokenPolicy::Error)).is_err());
+    }
+
+    #[test]
+    fn applies_application_quirks() {
+        let input = "(;AP[Foxwq:3.1]CA[gb2312]KM[375]RU[cn];B[pd]\n;W[tt])";
+        let options = ParserOptions {
+            apply_quirks: true,
+            ..ParserOptions::default()
+        };
+        let outcome = parse_with_options(input, &options).unwrap();
+        assert_eq!(
+            outcome.tree.to_string(),
+            "(;AP[Foxwq:3.1]CA[UTF-8]KM[7.5]RU[Chinese];B[pd];W[tt])"
+        );
+        let kinds: Vec<_> = outcome.warnings.iter().map(|w| w.kind.clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ParseWarningKind::UnknownCharset("gb2312".to_string()),
+                ParseWarningKind::AppliedQuirk(Quirk::FoxDialect),
+            ]
+        );
+
+        let input = "(;AP[MultiGo:4.4.4]CA[gb2312]SZ[19];B[pd]\n;W[tt])";
+        let options = ParserOptions {
+            preserve_format: true,
+            ..options
+        };
+        let outcome = parse_with_options(input, &options).unwrap();
+        assert_eq!(
+            outcome.warnings[1].kind,
+            ParseWarningKind::AppliedQuirk(Quirk::MislabeledCharset)
+        );
+        let written = outcome.tree.serialize_with(&WriterOptions {
+            preserve_format: true,
+            ..WriterOptions::default()
+        });
+        assert_eq!(written, "(;AP[MultiGo:4.4.4]CA[UTF-8]SZ[19];B[pd]\n;W[tt])");
+        assert!(parse_with_options(input, &ParserOptions::default())
+            .unwrap()
+            .warnings
+            .iter()
+            .all(|w| !matches!(w.kind, ParseWarningKind::AppliedQuirk(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "localized-results")]
+    fn can_parse_localized_results() {
+        let cases = [
+            ("黒中押し勝ち", Outcome::WinnerByResign(Color::Black)),
+            ("白の中押し勝ち", Outcome::WinnerByResign(Color::White)),
+            ("W+中押し", Outcome::WinnerByResign(Color::White)),
+            (
+                "黒3目半勝ち",
+                Outcome::WinnerByPoints(Color::Black, Real::new(3.5).unwrap()),
+            ),
+            ("持碁", Outcome::Draw),
+            ("白中盘胜", Outcome::WinnerByResign(Color::White)),
+            ("B+中盘", Outcome::WinnerByResign(Color::Black)),
+            (
+                "黑胜2又1/4子",
+                Outcome::WinnerByPoints(Color::Black, Real::new(4.5).unwrap()),
+            ),
+            ("흑 불계승", Outcome::WinnerByResign(Color::Black)),
+            ("B+불계", Outcome::WinnerByResign(Color::Black)),
+            (
+                "백 6집반승",
+                Outcome::WinnerByPoints(Color::White, Real::new(6.5).unwrap()),
+            ),
+        ];
+        for (label, outcome) in cases.iter() {
+            let tree = parse(&format!("(;RE[{}])", label)).unwrap();
+            assert_eq!(
+                tree.nodes[0].tokens,
+                vec![SgfToken::Result(*outcome)],
+                "{}",
+                label
+            );
+        }
+        let tree = parse("(;RE[黒勝ち])").unwrap();
+        assert_eq!(
+            tree.nodes[0].tokens,
+            vec![SgfToken::Invalid(("RE".into(), "黒勝ち".into()))]
+        );
+    }
+
+    #[test]
+    fn parses_syntax_without_interpreting_properties() {
+        let input = "(;SZ[9]\n  AB[aa] [bb]  \n;foo[not a move]\n)\n(;B[tt]KM[x])";
+        let trees = parse_syntax(input).unwrap();
+        assert_eq!(trees.len(), 2);
+        assert_eq!(trees[0].span, 0..42);
+        assert_eq!(&input[trees[1].span.clone()], "(;B[tt]KM[x])");
+
+        let root = &trees[0].nodes[0];
+        assert_eq!(&input[root.span.clone()], ";SZ[9]\n  AB[aa] [bb]");
+        let setup = &root.properties[1];
+        assert_eq!(&input[setup.span.clone()], "AB[aa] [bb]");
+        let values: Vec<_> = setup.values.iter().map(|v| &input[v.clone()]).collect();
+        assert_eq!(values, vec!["aa", "bb"]);
+
+        let node = &trees[0].nodes[1];
+        assert_eq!(&input[node.properties[0].identifier.clone()], "foo");
+        assert_eq!(&input[node.properties[0].values[0].clone()], "not a move");
+
+        assert_eq!(
+            parse_syntax("(;B[aa]").unwrap_err().kind,
+            SgfErrorKind::ParseError
+        );
+        assert_eq!(parse_syntax("(;)").unwrap()[0].nodes[0].span, 1..2);
+    }
 }
@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod props_tests {
+    use sgf_parser::props::*;
+    use sgf_parser::{parse, token, Action, Color, FindToken, GameTree, Outcome};
+
+    #[test]
+    fn finds_typed_values() {
+        let tree: GameTree = parse("(;SZ[13]RE[W+R]HA[2];B[aa]LB[bb:x][cc:y];W[])").unwrap();
+        assert_eq!(tree.first::<Size>(), Some((13, 13)));
+        assert_eq!(
+            tree.first::<Result>(),
+            Some(Outcome::WinnerByResign(Color::White))
+        );
+        assert_eq!(token!(tree, Handicap), Some(2));
+        assert_eq!(token!(tree, Komi), None);
+
+        let node = &tree.nodes[1];
+        assert_eq!(token!(node, BlackMove), Some(Action::Move(1, 1)));
+        assert_eq!(node.all::<Label>().len(), 2);
+        assert!(!node.has::<WhiteMove>());
+
+        let node = &tree.nodes[2];
+        assert_eq!(node.first::<Move>(), Some((Color::White, Action::Pass)));
+    }
+
+    #[test]
+    fn empty_tree_has_no_values() {
+        assert!(!GameTree::default().has::<Size>());
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(all(test, feature = "svg"))]
+mod render_tests {
+    use sgf_parser::render::svg::{render, render_with, SvgOptions};
+    use sgf_parser::*;
+
+    #[test]
+    fn renders_position_at_path() {
+        let tree: GameTree = parse("(;SZ[9];B[cc](;W[gg])(;W[cg]SQ[cc]CR[ee]LB[aa:<1>]))").unwrap();
+        let main = render(&tree, &TreePath::new(vec![0], 0)).unwrap();
+        assert_eq!(main.matches("stroke=\"black\"/>").count(), 18 + 2);
+        assert!(!main.contains("<rect x="));
+
+        let options = SvgOptions {
+            cell_size: 20,
+            ..SvgOptions::default()
+        };
+        let variation = render_with(&tree, &TreePath::new(vec![1], 0), &options).unwrap();
+        assert!(variation.contains(
+            "<rect x=\"55.5\" y=\"55.5\" width=\"9\" height=\"9\" fill=\"none\" stroke=\"white\""
+        ));
+        assert!(variation.contains("<circle cx=\"100\" cy=\"100\" r=\"6\" fill=\"none\""));
+        assert!(variation.contains(">&lt;1&gt;</text>"));
+        assert!(variation.contains("<circle cx=\"60\" cy=\"140\" r=\"5\" fill=\"none\""));
+        assert!(variation.ends_with("</svg>"));
+    }
+
+    #[test]
+    fn renders_largest_boards() {
+        let tree: GameTree = parse("(;SZ[52])").unwrap();
+        let options = SvgOptions {
+            cell_size: 10,
+            ..SvgOptions::default()
+        };
+        let svg = render_with(&tree, &TreePath::new(vec![], 0), &options).unwrap();
+        assert!(svg.contains("width=\"530\" height=\"530\""));
+
+        let tree: GameTree = parse("(;SZ[255])").unwrap();
+        assert_eq!(
+            render(&tree, &TreePath::new(vec![], 0)).unwrap_err().kind,
+            SgfErrorKind::InvalidTokenValue
+        );
+    }
+
+    #[test]
+    fn skips_marks_when_disabled() {
+        let tree: GameTree = parse("(;SZ[9];B[cc]TR[cc])").unwrap();
+        let options = SvgOptions {
+            marks: false,
+            highlight_last_move: false,
+            ..SvgOptions::default()
+        };
+        let svg = render_with(&tree, &TreePath::new(vec![], 1), &options).unwrap();
+        assert!(!svg.contains("<polygon"));
+        assert!(!svg.contains("fill=\"none\""));
+        assert_eq!(
+            render(&tree, &TreePath::new(vec![], 5)).unwrap_err().kind,
+            SgfErrorKind::NodeNotFound
+        );
+    }
+}
@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod review_tests {
+    use sgf_parser::*;
+
+    #[test]
+    fn reviews_chosen_path() {
+        let tree: GameTree =
+            parse("(;SZ[5]AB[aa];B[ba](;W[cc]C[Main])(;W[ab]SQ[ab]TR[aa]C[Say \"hi\"\n]))")
+                .unwrap();
+        let review = tree.review_to(&TreePath::new(vec![1], 0)).unwrap();
+        assert_eq!(review.frames.len(), 3);
+        let last = &review.frames[2];
+        assert_eq!(last.move_number, 2);
+        assert_eq!(last.marks.len(), 2);
+        assert_eq!(last.board.get((1, 2)), Some(Color::White));
+        assert_eq!(
+            review.to_json(),
+            "{\"size\":[5,5],\"frames\":[\
+             {\"move_number\":0,\"move\":null,\"black\":[\"aa\"],\"white\":[],\"comment\":null,\"marks\":[]},\
+             {\"move_number\":1,\"move\":\"B[ba]\",\"black\":[\"aa\",\"ba\"],\"white\":[],\"comment\":null,\"marks\":[]},\
+             {\"move_number\":2,\"move\":\"W[ab]\",\"black\":[\"aa\",\"ba\"],\"white\":[\"ab\"],\"comment\":\"Say \\\"hi\\\"\\n\",\
+             \"marks\":[{\"type\":\"square\",\"point\":\"ab\"},{\"type\":\"triangle\",\"point\":\"aa\"}]}]}"
+        );
+    }
+
+    #[test]
+    fn fails_on_missing_path_and_illegal_moves() {
+        let tree: GameTree = parse("(;B[aa];W[aa])").unwrap();
+        assert_eq!(
+            tree.review_to(&TreePath::new(vec![2], 0)).unwrap_err().kind,
+            SgfErrorKind::VariationNotFound
+        );
+        assert_eq!(tree.review().unwrap_err().kind, SgfErrorKind::IllegalMove);
+        assert_eq!(
+            GameTree::default().review().unwrap().to_json(),
+            "{\"size\":[0,0],\"frames\":[]}"
+        );
+    }
+}
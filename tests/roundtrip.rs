@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod roundtrip_tests {
+    use sgf_parser::*;
+
+    #[test]
+    fn sgf_files_roundtrip_without_loss() {
+        let report = roundtrip(include_str!("sgf/ShusakuvsInseki.sgf")).unwrap();
+        assert!(report.is_lossless(), "{:?}", report.differences);
+
+        let report = roundtrip(include_str!("sgf/empty_node.sgf")).unwrap();
+        assert!(report.is_lossless(), "{:?}", report.differences);
+    }
+
+    #[test]
+    fn errors_on_invalid_input() {
+        let err = roundtrip("(;B[aa]").unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::ParseError);
+    }
+
+    #[test]
+    fn reports_token_differences() {
+        let tree = GameTree {
+            nodes: vec![GameNode::from(vec![SgfToken::Size(19, 19)])],
+            variations: vec![GameTree {
+                nodes: vec![GameNode::from(vec![SgfToken::Label {
+                    label: "".into(),
+                    coordinate: (1, 1),
+                }])],
+                variations: vec![],
+            }],
+        };
+        let report = roundtrip_tree(&tree);
+        assert_eq!(
+            report.differences,
+            vec![RoundtripDifference::Tokens {
+                variations: vec![0],
+                node: 0,
+                original: vec![SgfToken::Label {
+                    label: "".into(),
+                    coordinate: (1, 1),
+                }],
+                reparsed: vec![SgfToken::Invalid(("LB".into(), "aa:".into()))],
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_reparse_failures() {
+        let tree = GameTree {
+            nodes: vec![
+                GameNode::from(vec![]),
+                GameNode::from(vec![SgfToken::Size(19, 19)]),
+            ],
+            variations: vec![],
+        };
+        let report = roundtrip_tree(&tree);
+        assert_eq!(
+            report.differences,
+            vec![RoundtripDifference::ReparseFailed(
+                SgfErrorKind::InvalidRootTokenPlacement
+            )]
+        );
+    }
+
+    #[test]
+    fn preserves_formatting_of_unchanged_nodes() {
+        let input = "(;FF[4]SZ[19]\r\n  PB[Black]\n\n;B[pd]\n;W[dp]  C[Nice]\n(\n  ;B[pp]\n)\n( ;B[dd] )\n)\n";
+        let parser = ParserOptions {
+            preserve_format: true,
+            ..ParserOptions::default()
+        };
+        let writer = WriterOptions {
+            preserve_format: true,
+            ..WriterOptions::default()
+        };
+        let mut tree = parse_with_options(input, &parser).unwrap().tree;
+        assert_eq!(tree.serialize_with(&writer), input);
+        assert_eq!(
+            parse_bytes_with(input.as_bytes(), &parser)
+                .unwrap()
+                .serialize_with(&writer),
+            input
+        );
+
+        tree.nodes[2]
+            .tokens
+            .retain(|token| token.identifier() != "C");
+        tree.variations.remove(0);
+        tree.variations[0]
+            .nodes
+            .push(GameNode::from(vec![SgfToken::from_pair("W", "cc")]));
+        assert_eq!(
+            tree.serialize_with(&writer),
+            "(;FF[4]SZ[19]\r\n  PB[Black]\n\n;B[pd]\n;W[dp]\n( ;B[dd];W[cc] )\n)\n"
+        );
+
+        let tree = parse(input).unwrap();
+        assert_eq!(tree.serialize_with(&writer), tree.to_string());
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod sandbox_tests {
+    use sgf_parser::*;
+
+    #[test]
+    fn extends_sequence_without_variations() {
+        let mut tree: GameTree = parse("(;SZ[9];B[ee])").unwrap();
+        let mut sandbox = tree.sandbox_at(&TreePath::new(vec![], 1)).unwrap();
+        sandbox
+            .push_move(Color::White, Action::Move(3, 3))
+            .push_move(Color::Black, Action::Pass);
+        assert_eq!(sandbox.line().len(), 4);
+        let line = sandbox.finish();
+        assert_eq!(tree.commit_sandbox(line).unwrap(), TreePath::new(vec![], 3));
+        assert_eq!(tree.to_string(), "(;SZ[9];B[ee];W[cc];B[])");
+    }
+
+    #[test]
+    fn follows_existing_nodes() {
+        let mut tree: GameTree = parse("(;SZ[9];B[ee](;W[cc];B[gg])(;W[gc]))").unwrap();
+        let mut sandbox = tree.sandbox_at(&TreePath::new(vec![], 0)).unwrap();
+        sandbox
+            .push_move(Color::Black, Action::Move(5, 5))
+            .push_move(Color::White, Action::Move(7, 3))
+            .push_move(Color::Black, Action::Move(3, 7));
+        let line = sandbox.finish();
+        assert_eq!(
+            tree.commit_sandbox(line).unwrap(),
+            TreePath::new(vec![1], 1)
+        );
+        assert_eq!(
+            tree.to_string(),
+            "(;SZ[9];B[ee](;W[cc];B[gg])(;W[gc];B[cg]))"
+        );
+    }
+
+    #[test]
+    fn splits_sequence_where_line_leaves_it() {
+        let mut tree: GameTree = parse("(;SZ[9];B[ee];W[cc](;B[gg])(;B[gc]))").unwrap();
+        let mut sandbox = tree.sandbox_at(&TreePath::new(vec![], 1)).unwrap();
+        sandbox.push_move(Color::White, Action::Move(3, 7));
+        let line = sandbox.finish();
+        assert_eq!(
+            tree.commit_sandbox(line).unwrap(),
+            TreePath::new(vec![1], 0)
+        );
+        assert_eq!(
+            tree.to_string(),
+            "(;SZ[9];B[ee](;W[cc](;B[gg])(;B[gc]))(;W[cg]))"
+        );
+    }
+
+    #[test]
+    fn discarding_leaves_tree_unchanged() {
+        let tree: GameTree = parse("(;SZ[9];B[ee])").unwrap();
+        let mut sandbox = tree.sandbox_at(&TreePath::new(vec![], 1)).unwrap();
+        sandbox.push_move(Color::White, Action::Move(3, 3));
+        assert!(sandbox.pop().is_some());
+        assert!(sandbox.nodes().is_empty());
+        sandbox.discard();
+        assert_eq!(tree.to_string(), "(;SZ[9];B[ee])");
+    }
+
+    #[test]
+    fn rejects_missing_node() {
+        let tree: GameTree = parse("(;SZ[9];B[ee])").unwrap();
+        let err = tree.sandbox_at(&TreePath::new(vec![], 2)).unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::NodeNotFound);
+    }
+
+    #[test]
+    fn infers_colors_from_the_line() {
+        let tree: GameTree = parse("(;HA[2]AB[cc][gg])").unwrap();
+        let mut sandbox = tree.sandbox_at(&TreePath::new(vec![], 0)).unwrap();
+        sandbox.play_auto((5, 5));
+        sandbox.push_node(parse_node(";PL[W]").unwrap());
+        sandbox.play_auto((3, 7)).play_auto((7, 3));
+        let colors: Vec<Color> = sandbox
+            .nodes()
+            .iter()
+            .flat_map(|node| node.tokens.iter())
+            .filter_map(|token| match token {
+                SgfToken::Move { color, .. } => Some(*color),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(colors, vec![Color::White, Color::White, Color::Black]);
+    }
+}
@@ -29,17 +29,16 @@ mod sgf_files_test {
     }
 
     fn has_iso_node(tree: &GameTree) -> bool {
-        tree.nodes[0].tokens.iter().any(|token| match token {
-            SgfToken::Charset(Encoding::Other(enc)) if enc == "ISO-8859" => true,
-            _ => false,
-        })
+        tree.nodes[0].tokens.iter().any(
+            |token| matches!(token, SgfToken::Charset(Encoding::Other(enc)) if enc == "ISO-8859"),
+        )
     }
 
     fn has_utf_node(tree: &GameTree) -> bool {
-        tree.nodes[0].tokens.iter().any(|token| match token {
-            SgfToken::Charset(Encoding::UTF8) => true,
-            _ => false,
-        })
+        tree.nodes[0]
+            .tokens
+            .iter()
+            .any(|token| matches!(token, SgfToken::Charset(Encoding::UTF8)))
     }
 
     #[test]
@@ -0,0 +1,109 @@
+#[cfg(all(test, feature = "cli"))]
+mod sgf_tool_tests {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    fn sgf_tool(args: &[&str]) -> (i32, String) {
+        let output = Command::new(env!("CARGO_BIN_EXE_sgf-tool"))
+            .args(args)
+            .output()
+            .unwrap();
+        (
+            output.status.code().unwrap(),
+            String::from_utf8(output.stdout).unwrap(),
+        )
+    }
+
+    /// Writes `content` to a file in a temporary directory only used by `test`
+    fn temp_file(test: &str, name: &str, content: &[u8]) -> PathBuf {
+        let directory = env::temp_dir().join(format!("sgf-tool-{}", test));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    const LATIN1: &[u8] = b"(;CA[ISO-8859-1]SZ[9]PB[Jos\xe9];B[ee](;W[cc])(;W[gg]))";
+
+    #[test]
+    fn shows_game_info() {
+        let (code, output) = sgf_tool(&["info", "tests/sgf/ShusakuvsInseki.sgf"]);
+        assert_eq!(code, 0);
+        assert!(output.contains("black: Shusaku\n"));
+        assert!(output.contains("moves: 325\n"));
+    }
+
+    #[test]
+    fn decodes_files_with_their_charset() {
+        let path = temp_file("charset", "game.sgf", LATIN1);
+        let (code, output) = sgf_tool(&["to-json", path.to_str().unwrap()]);
+        assert_eq!(code, 0);
+        assert!(output.starts_with(r#"{"nodes":[{"CA":["UTF-8"],"SZ":["9"],"PB":["José"]}"#));
+
+        let (code, _) = sgf_tool(&["info", "tests/sgf/ShusakuvsInseki-iso.sgf"]);
+        assert_eq!(code, 2);
+    }
+
+    #[test]
+    fn lint_fails_on_warnings() {
+        let path = temp_file("lint", "game.sgf", LATIN1);
+        let (code, output) = sgf_tool(&["lint", path.to_str().unwrap()]);
+        assert_eq!(code, 1);
+        assert!(output.contains("UnknownCharset"));
+
+        let (code, output) = sgf_tool(&["lint", "tests/sgf/ShusakuvsInseki.sgf"]);
+        assert_eq!(code, 0);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn writes_mainline_and_normalized_games() {
+        let path = temp_file(
+            "mainline",
+            "game.sgf",
+            b"(;SZ[9];B[ee]W[cc](;B[gg])(;B[cg]))",
+        );
+        let (code, output) = sgf_tool(&["mainline", path.to_str().unwrap()]);
+        assert_eq!(code, 0);
+        assert_eq!(output, "(;SZ[9];B[ee]W[cc];B[gg])\n");
+
+        let normalized = path.with_file_name("normalized.sgf");
+        let args = [
+            "normalize",
+            path.to_str().unwrap(),
+            normalized.to_str().unwrap(),
+        ];
+        assert_eq!(sgf_tool(&args), (0, String::new()));
+        assert_eq!(
+            fs::read_to_string(normalized).unwrap(),
+            "(;SZ[9];B[ee];W[cc](;B[gg])(;B[cg]))"
+        );
+    }
+
+    #[test]
+    fn splits_collections() {
+        let path = temp_file(
+            "split",
+            "games.sgf",
+            b"(;PB[Shusaku];B[aa])(;PB[Inseki];W[bb])",
+        );
+        let directory = path.with_file_name("games");
+        let (code, output) =
+            sgf_tool(&["split", path.to_str().unwrap(), directory.to_str().unwrap()]);
+        assert_eq!(code, 0);
+        assert_eq!(output.lines().count(), 2);
+        assert_eq!(
+            fs::read_to_string(directory.join("games-2.sgf")).unwrap(),
+            "(;PB[Inseki];W[bb])"
+        );
+    }
+
+    #[test]
+    fn prints_usage_on_invalid_arguments() {
+        let (code, _) = sgf_tool(&["frobnicate"]);
+        assert_eq!(code, 2);
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod shared_tests {
+    use sgf_parser::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn converts_both_ways() {
+        let source = "(;SZ[9];B[ee](;W[cc];B[gg])(;W[gc]))";
+        let tree: GameTree = parse(source).unwrap();
+        let shared = ArcGameTree::from(tree.clone());
+        assert_eq!(shared.to_string(), source);
+        assert_eq!(GameTree::from(&shared), tree);
+    }
+
+    #[test]
+    fn modifications_only_copy_touched_nodes() {
+        let mut tree = ArcGameTree::from(parse("(;SZ[9];B[ee](;W[cc])(;W[gc]))").unwrap());
+        let snapshot = tree.snapshot();
+        tree.node_mut(&TreePath::new(vec![1], 0))
+            .unwrap()
+            .tokens
+            .push(SgfToken::Comment("Engine: 55%".into()));
+
+        assert!(Arc::ptr_eq(&tree.nodes[0], &snapshot.nodes[0]));
+        assert!(Arc::ptr_eq(&tree.variations[0], &snapshot.variations[0]));
+        assert!(!Arc::ptr_eq(
+            &tree.variations[1].nodes[0],
+            &snapshot.variations[1].nodes[0]
+        ));
+        assert_eq!(snapshot.to_string(), "(;SZ[9];B[ee](;W[cc])(;W[gc]))");
+    }
+
+    #[test]
+    fn readers_see_consistent_snapshots() {
+        let mut tree = ArcGameTree::from(parse("(;SZ[19];B[pd])").unwrap());
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let snapshot = tree.snapshot();
+                thread::spawn(move || GameTree::from(&snapshot).count_max_nodes())
+            })
+            .collect();
+        for _ in 0..10 {
+            tree.push_move(Color::White, Action::Pass).unwrap();
+        }
+        for reader in readers {
+            assert_eq!(reader.join().unwrap(), 2);
+        }
+        assert_eq!(GameTree::from(&tree).count_max_nodes(), 12);
+    }
+
+    #[test]
+    fn add_variation_checks_path_and_tokens() {
+        let mut tree = ArcGameTree::from(parse("(;SZ[9];B[ee])").unwrap());
+        let err = tree
+            .add_variation(&TreePath::new(vec![], 2), vec![GameNode::default()])
+            .unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::NodeNotFound);
+        let err = tree
+            .add_variation(
+                &TreePath::new(vec![], 1),
+                vec![parse_node(";SZ[9]").unwrap()],
+            )
+            .unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::InvalidRootTokenPlacement);
+        let path = tree
+            .add_variation(
+                &TreePath::new(vec![], 1),
+                vec![parse_node(";W[cc]").unwrap()],
+            )
+            .unwrap();
+        assert_eq!(
+            tree.get_node(&path).unwrap(),
+            &parse_node(";W[cc]").unwrap()
+        );
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod stream_tests {
+    use sgf_parser::Action::Move;
+    use sgf_parser::*;
+
+    #[test]
+    fn emits_nodes_once_complete() {
+        let mut parser = Parser::new();
+        assert_eq!(parser.feed(b"(;B[aa]").unwrap(), vec![Event::StartTree]);
+        assert_eq!(
+            parser.feed(b";").unwrap(),
+            vec![Event::Node(GameNode::from(vec![SgfToken::Move {
+                color: Color::Black,
+                action: Move(1, 1),
+            }]))]
+        );
+    }
+
+    #[test]
+    fn matches_full_parse() {
+        let source = "(;SZ[19]C[a \\] comment];B[aa](;W[bb])(;W[cc]AB[dd][ee]))";
+        let mut events = vec![];
+        let mut parser = Parser::new();
+        for chunk in source.as_bytes().chunks(3) {
+            events.extend(parser.feed(chunk).unwrap());
+        }
+        events.extend(parser.finish().unwrap());
+
+        let tree = parse(source).unwrap();
+        let nodes = events
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Node(node) => Some(node),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(nodes[..2], tree.nodes[..]);
+        assert_eq!(nodes[2], tree.variations[0].nodes[0]);
+        assert_eq!(nodes[3], tree.variations[1].nodes[0]);
+    }
+
+    #[test]
+    fn handles_multibyte_characters_split_across_chunks() {
+        let source = "(;C[æøå])".as_bytes();
+        let mut parser = Parser::new();
+        let mut events = vec![];
+        for chunk in source.chunks(1) {
+            events.extend(parser.feed(chunk).unwrap());
+        }
+        assert_eq!(
+            events[1],
+            Event::Node(GameNode::from(vec![SgfToken::Comment("æøå".into())]))
+        );
+    }
+
+    #[test]
+    fn errors_on_unfinished_input() {
+        let mut parser = Parser::new();
+        parser.feed(b"(;B[aa]").unwrap();
+        assert_eq!(parser.finish().unwrap_err().kind, SgfErrorKind::ParseError);
+    }
+
+    #[test]
+    fn errors_on_invalid_input() {
+        let mut parser = Parser::new();
+        assert!(parser.feed(b"(;B[aa]];").is_err());
+    }
+
+    #[test]
+    fn parse_bytes_matches_parse() {
+        let source = include_str!("sgf/ShusakuvsInseki.sgf");
+        assert_eq!(
+            parse_bytes(source.as_bytes()).unwrap(),
+            parse(source).unwrap()
+        );
+
+        let source = include_str!("sgf/empty_node.sgf");
+        assert_eq!(
+            parse_bytes(source.as_bytes()).unwrap(),
+            parse(source).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_bytes_validates_root_tokens() {
+        assert_eq!(
+            parse_bytes(b"(;KM[6.5];SZ[19])").unwrap_err().kind,
+            SgfErrorKind::InvalidRootTokenPlacement
+        );
+        assert_eq!(
+            parse_bytes(b"(;B[aa](;W[bb]);B[cc])").unwrap_err().kind,
+            SgfErrorKind::ParseError
+        );
+    }
+
+    #[test]
+    fn builder_collects_all_trees() {
+        let mut parser = Parser::new();
+        let mut builder = TreeBuilder::new();
+        for event in parser.feed(b"(;B[aa])(;B[bb])").unwrap() {
+            builder.push(event).unwrap();
+        }
+        let trees = builder.finish().unwrap();
+        assert_eq!(trees.len(), 2);
+    }
+
+    #[test]
+    fn builder_shares_repeated_identifiers_between_trees() {
+        fn identifier(tree: &GameTree) -> &TokenString {
+            match &tree.nodes[0].tokens[0] {
+                SgfToken::Unknown((identifier, _)) => identifier,
+                token => panic!("Unexpected token {:?}", token),
+            }
+        }
+
+        let input = b"(;XR[0.48]PB[Shusaku])(;XR[0.52]PB[Shusaku])";
+        let build = |options: &ParserOptions| {
+            let mut parser = Parser::with_options(options);
+            let mut builder = TreeBuilder::with_options(options);
+            for event in parser.feed(input).unwrap() {
+                builder.push(event).unwrap();
+            }
+            builder.finish().unwrap()
+        };
+
+        let trees = build(&ParserOptions::new().intern_strings(true));
+        assert!(TokenString::ptr_eq(
+            identifier(&trees[0]),
+            identifier(&trees[1])
+        ));
+        assert_eq!(
+            trees[0].nodes[0].tokens[1],
+            SgfToken::PlayerName {
+                color: Color::Black,
+                name: "Shusaku".into(),
+            }
+        );
+        assert_eq!(trees, build(&ParserOptions::default()));
+
+        let trees = build(&ParserOptions::default());
+        assert!(!TokenString::ptr_eq(
+            identifier(&trees[0]),
+            identifier(&trees[1])
+        ));
+    }
+}
@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod time_tests {
+    use sgf_parser::*;
+
+    #[test]
+    fn collects_entries_for_each_color() {
+        let tree: GameTree =
+            parse("(;TM[60];B[aa]BL[50];W[bb];B[cc]BL[30]OB[5];W[dd]WL[40])").unwrap();
+        let series = tree.time_series();
+
+        assert_eq!(
+            series.get(Color::Black),
+            &[
+                TimeEntry {
+                    move_number: 1,
+                    remaining: Some(50),
+                    used: Some(10),
+                    moves_remaining: None,
+                },
+                TimeEntry {
+                    move_number: 3,
+                    remaining: Some(30),
+                    used: Some(20),
+                    moves_remaining: Some(5),
+                },
+            ]
+        );
+        let white = series.get(Color::White);
+        assert_eq!(white[0].remaining, None);
+        assert_eq!(white[0].used, None);
+        assert_eq!(white[1].move_number, 4);
+        assert_eq!(white[1].used, Some(20));
+    }
+
+    #[test]
+    fn clock_reset_has_no_used_time() {
+        let tree: GameTree = parse("(;B[aa]BL[5];B[bb]BL[30];B[cc]BL[25])").unwrap();
+        let used: Vec<_> = tree.time_series().black.iter().map(|e| e.used).collect();
+        assert_eq!(used, vec![None, None, Some(5)]);
+    }
+}
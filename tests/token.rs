@@ -88,7 +88,10 @@ mod token_tests {
         );
         assert_eq!(
             SgfToken::from_pair("RE", "B+35.0"),
-            SgfToken::Result(Outcome::WinnerByPoints(Color::Black, 35.0))
+            SgfToken::Result(Outcome::WinnerByPoints(
+                Color::Black,
+                Real::new(35.0).unwrap()
+            ))
         );
         assert_eq!(
             SgfToken::from_pair("RE", "W+R"),
@@ -96,7 +99,10 @@ mod token_tests {
         );
         assert_eq!(
             SgfToken::from_pair("RE", "W+55.5"),
-            SgfToken::Result(Outcome::WinnerByPoints(Color::White, 55.5))
+            SgfToken::Result(Outcome::WinnerByPoints(
+                Color::White,
+                Real::new(55.5).unwrap()
+            ))
         );
         assert_eq!(
             SgfToken::from_pair("RE", "W+T"),
@@ -177,7 +183,7 @@ mod token_tests {
             token,
             SgfToken::PlayerName {
                 color: Color::Black,
-                name: "Honinbo Shusai".to_string(),
+                name: "Honinbo Shusai".into(),
             }
         );
         let string_token: String = token.into();
@@ -188,7 +194,7 @@ mod token_tests {
             token,
             SgfToken::PlayerName {
                 color: Color::White,
-                name: "Cho Chikun".to_string(),
+                name: "Cho Chikun".into(),
             }
         );
         let string_token: String = token.into();
@@ -218,12 +224,39 @@ mod token_tests {
         );
         let string_token: String = token.into();
         assert_eq!(string_token, "WR[5 kyu]");
+
+        let token = SgfToken::from_pair("BR", "3k?");
+        let string_token: String = token.into();
+        assert_eq!(string_token, "BR[3k?]");
+    }
+
+    #[test]
+    fn can_parse_rank_forms() {
+        assert_eq!(Rank::from("9段"), Rank::Dan(9));
+        assert_eq!(Rank::from("2 dan"), Rank::Dan(2));
+        assert_eq!(Rank::from("15級"), Rank::Kyu(15));
+        assert_eq!(Rank::from("1P"), Rank::Pro(1));
+        assert_eq!(Rank::from("31k"), Rank::Other("31k".to_string()));
+        assert_eq!(Rank::from("amateur"), Rank::Other("amateur".to_string()));
+    }
+
+    #[test]
+    fn can_compare_ranks() {
+        assert!(Rank::Dan(5) > Rank::Dan(4));
+        assert!(Rank::Dan(1) > Rank::Kyu(1));
+        assert!(Rank::Kyu(1) > Rank::Kyu(2));
+        assert!(Rank::Pro(1) > Rank::Dan(9));
+        assert!(Rank::from("5d") >= Rank::Dan(5));
+        assert!(Rank::Other("?".to_string()) > Rank::Pro(9));
+        assert!(Rank::Other("3k?".to_string()) < Rank::Other("?".to_string()));
+        assert_eq!(Rank::Kyu(1).to_numeric(), Some(0));
+        assert_eq!(Rank::Dan(3).to_rating(), Some(2300));
     }
 
     #[test]
     fn can_parse_komi_tokens() {
         let token = SgfToken::from_pair("KM", "4.5");
-        assert_eq!(token, SgfToken::Komi(4.5));
+        assert_eq!(token, SgfToken::Komi(Real::new(4.5).unwrap()));
         let string_token: String = token.into();
         assert_eq!(string_token, "KM[4.5]");
     }
@@ -255,7 +288,7 @@ mod token_tests {
     #[test]
     fn can_parse_event_tokens() {
         let token = SgfToken::from_pair("EV", "event");
-        assert_eq!(token, SgfToken::Event("event".to_string()));
+        assert_eq!(token, SgfToken::Event("event".into()));
         let string_token: String = token.into();
         assert_eq!(string_token, "EV[event]");
     }
@@ -263,7 +296,7 @@ mod token_tests {
     #[test]
     fn can_parse_comment_tokens() {
         let token = SgfToken::from_pair("C", "comment");
-        assert_eq!(token, SgfToken::Comment("comment".to_string()));
+        assert_eq!(token, SgfToken::Comment("comment".into()));
         let string_token: String = token.into();
         assert_eq!(string_token, "C[comment]");
     }
@@ -271,10 +304,7 @@ mod token_tests {
     #[test]
     fn can_parse_comment_token_with_escpaed_chars() {
         let token = SgfToken::from_pair("C", "a [wrapped\\] comment");
-        assert_eq!(
-            token,
-            SgfToken::Comment("a [wrapped\\] comment".to_string())
-        );
+        assert_eq!(token, SgfToken::Comment("a [wrapped\\] comment".into()));
         let string_token: String = token.into();
         assert_eq!(string_token, "C[a [wrapped\\] comment]");
     }
@@ -282,23 +312,31 @@ mod token_tests {
     #[test]
     fn can_parse_game_name_tokens() {
         let token = SgfToken::from_pair("GN", "game name");
-        assert_eq!(token, SgfToken::GameName("game name".to_string()));
+        assert_eq!(token, SgfToken::GameName("game name".into()));
         let string_token: String = token.into();
         assert_eq!(string_token, "GN[game name]");
     }
 
     #[test]
     fn can_parse_copyright_tokens() {
-        let token = SgfToken::from_pair("CR", "copyright");
-        assert_eq!(token, SgfToken::Copyright("copyright".to_string()));
+        let token = SgfToken::from_pair("CP", "copyright");
+        assert_eq!(token, SgfToken::Copyright("copyright".into()));
+        let string_token: String = token.into();
+        assert_eq!(string_token, "CP[copyright]");
+    }
+
+    #[test]
+    fn can_parse_circle_tokens() {
+        let token = SgfToken::from_pair("CR", "aa");
+        assert_eq!(token, SgfToken::Circle { coordinate: (1, 1) });
         let string_token: String = token.into();
-        assert_eq!(string_token, "CR[copyright]");
+        assert_eq!(string_token, "CR[aa]");
     }
 
     #[test]
     fn can_parse_date_tokens() {
         let token = SgfToken::from_pair("DT", "2019-02-02");
-        assert_eq!(token, SgfToken::Date("2019-02-02".to_string()));
+        assert_eq!(token, SgfToken::Date("2019-02-02".into()));
         let string_token: String = token.into();
         assert_eq!(string_token, "DT[2019-02-02]");
     }
@@ -306,7 +344,7 @@ mod token_tests {
     #[test]
     fn can_parse_place_tokens() {
         let token = SgfToken::from_pair("PC", "place");
-        assert_eq!(token, SgfToken::Place("place".to_string()));
+        assert_eq!(token, SgfToken::Place("place".into()));
         let string_token: String = token.into();
         assert_eq!(string_token, "PC[place]");
     }
@@ -333,7 +371,7 @@ mod token_tests {
         assert_eq!(
             token,
             SgfToken::Label {
-                label: "foo".to_string(),
+                label: "foo".into(),
                 coordinate: (11, 11),
             }
         );
@@ -346,11 +384,15 @@ mod token_tests {
         assert_eq!(SgfToken::from_pair("GM", "1"), SgfToken::Game(Game::Go));
         assert_eq!(
             SgfToken::from_pair("GM", "2"),
-            SgfToken::Game(Game::Other(2))
+            SgfToken::Game(Game::Othello)
+        );
+        assert_eq!(
+            SgfToken::from_pair("GM", "41"),
+            SgfToken::Game(Game::Other(41))
         );
         assert_eq!(
             SgfToken::from_pair("GM", "error"),
-            SgfToken::Invalid(("GM".to_string(), "error".to_string()))
+            SgfToken::Invalid(("GM".into(), "error".into()))
         );
         let token = SgfToken::from_pair("GM", "1");
         let string_token: String = token.into();
@@ -435,8 +477,8 @@ mod token_tests {
         assert_eq!(
             token,
             SgfToken::Application {
-                name: "CGoban".to_string(),
-                version: "1.6.2".to_string(),
+                name: "CGoban".into(),
+                version: "1.6.2".into(),
             }
         );
         let string_token: String = token.into();
@@ -446,7 +488,7 @@ mod token_tests {
     #[test]
     fn can_parse_overtime_token() {
         let token = SgfToken::from_pair("OT", "15/300 Canadian");
-        assert_eq!(token, SgfToken::Overtime("15/300 Canadian".to_string()));
+        assert_eq!(token, SgfToken::Overtime("15/300 Canadian".into()));
         let string_token: String = token.into();
         assert_eq!(string_token, "OT[15/300 Canadian]");
     }
@@ -506,9 +548,202 @@ mod token_tests {
         assert_eq!(string_token, "FF[3]");
 
         let token = SgfToken::from_pair("FF", "5");
+        assert_eq!(token, SgfToken::Invalid(("FF".into(), "5".into())));
+    }
+
+    #[test]
+    fn invalid_coordinate_characters_are_invalid_tokens() {
+        let token = SgfToken::from_pair("B", "!!");
+        assert_eq!(token, SgfToken::Invalid(("B".into(), "!!".into())));
+
+        let token = SgfToken::from_pair("LB", "a\u{9a}:A");
+        assert_eq!(token, SgfToken::Invalid(("LB".into(), "a\u{9a}:A".into())));
+    }
+
+    #[test]
+    fn can_parse_annotation_tokens() {
+        let token = SgfToken::from_pair("DM", "1");
+        assert_eq!(token, SgfToken::Even(Emphasis::Normal));
+        let string_token: String = token.into();
+        assert_eq!(string_token, "DM[1]");
+
+        let token = SgfToken::from_pair("GW", "2");
         assert_eq!(
             token,
-            SgfToken::Invalid(("FF".to_string(), "5".to_string()))
+            SgfToken::GoodFor {
+                color: Color::White,
+                emphasis: Emphasis::Emphasized,
+            }
+        );
+        let string_token: String = token.into();
+        assert_eq!(string_token, "GW[2]");
+
+        let token = SgfToken::from_pair("TE", "2");
+        assert_eq!(token, SgfToken::Tesuji(Emphasis::Emphasized));
+        let string_token: String = token.into();
+        assert_eq!(string_token, "TE[2]");
+
+        let token = SgfToken::from_pair("HO", "3");
+        assert_eq!(token, SgfToken::Invalid(("HO".into(), "3".into())));
+    }
+
+    #[test]
+    fn can_construct_validated_tokens() {
+        assert_eq!(
+            SgfToken::add(Color::White, (9, 9), (9, 9)).unwrap(),
+            SgfToken::Add {
+                color: Color::White,
+                coordinate: (9, 9),
+            }
+        );
+        assert!(SgfToken::add(Color::White, (0, 9), (9, 9)).is_err());
+        assert!(SgfToken::move_to(Color::White, (10, 9), (9, 9)).is_err());
+
+        assert_eq!(SgfToken::size(19, 13).unwrap(), SgfToken::Size(19, 13));
+        assert_eq!(
+            SgfToken::size(53, 19).unwrap_err().kind,
+            SgfErrorKind::InvalidTokenValue
         );
+
+        assert!(SgfToken::komi(f32::INFINITY).is_err());
+        assert!(SgfToken::label((53, 1), "A").is_err());
+    }
+
+    #[test]
+    fn tokens_can_be_hashed() {
+        use std::collections::HashSet;
+
+        let mut tokens = HashSet::new();
+        tokens.insert(SgfToken::from_pair("KM", "6.5"));
+        tokens.insert(SgfToken::from_pair("KM", "6.50"));
+        tokens.insert(SgfToken::Komi(Real::new(0.0).unwrap()));
+        tokens.insert(SgfToken::Komi(Real::new(-0.0).unwrap()));
+        tokens.insert(SgfToken::from_pair("RE", "B+0.5"));
+        assert_eq!(tokens.len(), 3);
+    }
+
+    #[test]
+    fn tokens_are_ordered_by_variant_and_value() {
+        let a = SgfToken::Charset(Encoding::UTF8);
+        let b = SgfToken::Charset(Encoding::Other("ISO-8859-1".to_string()));
+        assert_ne!(a.cmp(&b), std::cmp::Ordering::Equal);
+        assert!(SgfToken::from_pair("B", "aa") < SgfToken::from_pair("W", "aa"));
+        assert!(SgfToken::Komi(Real::new(-1.0).unwrap()) < SgfToken::Komi(Real::new(0.0).unwrap()));
+    }
+
+    #[test]
+    fn non_finite_numbers_are_invalid() {
+        assert_eq!(
+            SgfToken::from_pair("KM", "NaN"),
+            SgfToken::Invalid(("KM".into(), "NaN".into()))
+        );
+        assert_eq!(
+            SgfToken::from_pair("RE", "B+inf"),
+            SgfToken::Invalid(("RE".into(), "B+inf".into()))
+        );
+    }
+
+    #[test]
+    fn grouped_tokens_convert_back() {
+        let tree = parse(include_str!("sgf/ShusakuvsInseki.sgf")).unwrap();
+        for node in tree.iter() {
+            for token in &node.tokens {
+                let grouped = token.clone().grouped();
+                assert_eq!(SgfToken::from(grouped), *token);
+            }
+        }
+    }
+
+    #[test]
+    fn tokens_are_grouped_by_category() {
+        assert_eq!(
+            SgfToken::from_pair("OB", "3").grouped(),
+            GroupedToken::Timing(TimingProp::MovesRemaining {
+                color: Color::Black,
+                moves: 3
+            })
+        );
+        assert_eq!(
+            SgfToken::from_pair("SZ", "9").grouped(),
+            GroupedToken::Root(RootProp::Size(9, 9))
+        );
+        assert_eq!(
+            GroupedToken::from(AnnotationProp::Tesuji(Emphasis::Normal)),
+            SgfToken::from_pair("TE", "1").grouped()
+        );
+        assert_eq!(
+            SgfToken::from_pair("TE", "1").grouped().category(),
+            TokenCategory::Annotation
+        );
+    }
+
+    #[test]
+    fn identifier_matches_serialization() {
+        let tree = parse(include_str!("sgf/ShusakuvsInseki.sgf")).unwrap();
+        for node in tree.iter() {
+            for token in &node.tokens {
+                let serialized: String = token.into();
+                assert!(serialized.starts_with(&format!("{}[", token.identifier())));
+            }
+        }
+    }
+
+    #[test]
+    fn outcome_and_rule_set_accessors() {
+        let outcome = Outcome::WinnerByPoints(Color::White, Real::new(2.5).unwrap());
+        assert_eq!(outcome.get_points(), Some(2.5));
+        assert!(!outcome.is_draw());
+        assert!(Outcome::Draw.is_draw());
+        assert!(RuleSet::Japanese.is_known());
+        assert!(!RuleSet::from("Ing").is_known());
+    }
+
+    #[test]
+    fn reparses_invalid_tokens_with_relaxed_rules() {
+        let hints = ReparseHints::default();
+        let reparse =
+            |identifier, value| SgfToken::from_pair(identifier, value).try_reparse(&hints);
+        assert_eq!(
+            reparse("AB", "\tdd"),
+            Some(SgfToken::Add {
+                color: Color::Black,
+                coordinate: (4, 4)
+            })
+        );
+        assert_eq!(reparse("HA", " 2\n"), Some(SgfToken::Handicap(2)));
+        assert_eq!(
+            reparse("KM", " 0,5 "),
+            Some(SgfToken::Komi(Real::new(0.5).unwrap()))
+        );
+        assert_eq!(
+            reparse("LB", " dd:A"),
+            Some(SgfToken::Label {
+                label: "A".into(),
+                coordinate: (4, 4)
+            })
+        );
+        assert_eq!(reparse("XX", " a "), None);
+        assert_eq!(reparse("B", "dd"), None);
+
+        let hints = ReparseHints {
+            decimal_comma: false,
+            ..ReparseHints::default()
+        };
+        assert_eq!(SgfToken::from_pair("KM", "6,5").try_reparse(&hints), None);
+    }
+
+    #[test]
+    fn cloned_trees_share_token_text() {
+        let tree: GameTree = parse("(;PB[Honinbo Shusaku]C[Ear-reddening move])").unwrap();
+        let copy = tree.clone();
+        for (token, copied) in tree.nodes[0].tokens.iter().zip(&copy.nodes[0].tokens) {
+            match (token, copied) {
+                (SgfToken::PlayerName { name: a, .. }, SgfToken::PlayerName { name: b, .. })
+                | (SgfToken::Comment(a), SgfToken::Comment(b)) => {
+                    assert!(TokenString::ptr_eq(a, b))
+                }
+                _ => panic!("Unexpected tokens {:?}", (token, copied)),
+            }
+        }
     }
 }
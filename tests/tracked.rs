@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tracked_tests {
+    use sgf_parser::*;
+
+    fn tracked(source: &str) -> TrackedGameTree {
+        TrackedGameTree::new(parse(source).unwrap())
+    }
+
+    #[test]
+    fn records_all_mutations() {
+        let mut tree = tracked("(;SZ[9];B[aa](;W[bb])(;W[cc]))");
+        let path = TreePath::new(vec![1], 0);
+        tree.insert_node(&path, parse_node(";C[before]").unwrap())
+            .unwrap();
+        tree.set_token(&TreePath::new(vec![], 1), 0, SgfToken::from_pair("B", "dd"))
+            .unwrap();
+        assert_eq!(
+            tree.remove_token(&TreePath::new(vec![1], 1), 0).unwrap(),
+            SgfToken::from_pair("W", "cc")
+        );
+        tree.remove_variation(&[0]).unwrap();
+
+        let sgf: String = tree.tree().into();
+        assert_eq!(sgf, "(;SZ[9];B[dd](;C[before];))");
+
+        let events: Vec<String> = tree.events().iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            events,
+            vec![
+                "node-added 1:0 ;C[before]",
+                "token-changed :1 0 B[aa] B[dd]",
+                "token-removed 1:1 0 W[cc]",
+                "variation-removed 0 (;W[bb])",
+            ]
+        );
+    }
+
+    #[test]
+    fn inverse_mutations_restore_the_tree() {
+        let original = parse("(;SZ[9];B[aa](;W[bb])(;W[cc]))").unwrap();
+        let mut tree = TrackedGameTree::new(original.clone());
+        tree.add_variation(&[], parse("(;W[dd];B[ee])").unwrap())
+            .unwrap();
+        tree.remove_node(&TreePath::new(vec![0], 0)).unwrap();
+        tree.add_token(&TreePath::new(vec![1], 0), SgfToken::from_pair("C", "x"))
+            .unwrap();
+
+        let mut restored = tree.tree().clone();
+        for event in tree.take_events().iter().rev() {
+            event.inverse().apply(&mut restored).unwrap();
+        }
+        assert_eq!(restored, original);
+        assert!(tree.events().is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_changes() {
+        let mut tree = tracked("(;SZ[9];B[aa])");
+        let err = tree
+            .add_token(&TreePath::new(vec![], 1), SgfToken::Size(9, 9))
+            .unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::InvalidRootTokenPlacement);
+
+        let err = tree.remove_token(&TreePath::new(vec![], 1), 3).unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::InvalidTokenValue);
+
+        let err = tree.remove_variation(&[0]).unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::VariationNotFound);
+
+        let stale = Mutation::TokenRemoved {
+            path: TreePath::new(vec![], 1),
+            index: 0,
+            token: SgfToken::from_pair("B", "bb"),
+        };
+        assert!(tree.apply(stale).is_err());
+        assert!(tree.events().is_empty());
+    }
+
+    #[test]
+    fn undo_and_redo_single_changes() {
+        let original = parse("(;SZ[9];B[aa])").unwrap();
+        let mut tree = TrackedGameTree::new(original.clone());
+        assert!(!tree.can_undo());
+
+        tree.push_move(Color::White, Action::Move(2, 2)).unwrap();
+        tree.add_token(&TreePath::new(vec![], 2), SgfToken::from_pair("C", "x"))
+            .unwrap();
+        assert!(tree.undo().unwrap());
+        let sgf: String = tree.tree().into();
+        assert_eq!(sgf, "(;SZ[9];B[aa];W[bb])");
+
+        assert!(tree.undo().unwrap());
+        assert_eq!(tree.tree(), &original);
+        assert!(!tree.undo().unwrap());
+
+        assert!(tree.redo().unwrap());
+        assert!(tree.can_redo());
+        tree.push_move(Color::Black, Action::Pass).unwrap();
+        assert!(!tree.can_redo());
+        assert!(!tree.redo().unwrap());
+
+        let sgf: String = tree.tree().into();
+        assert_eq!(sgf, "(;SZ[9];B[aa];W[bb];B[])");
+        assert_eq!(tree.events().len(), 6);
+    }
+
+    #[test]
+    fn undo_commits_open_transaction() {
+        let original = parse("(;SZ[9];B[aa](;W[bb])(;W[cc]))").unwrap();
+        let mut tree = TrackedGameTree::new(original.clone());
+        tree.begin_transaction();
+        tree.remove_variation(&[1]).unwrap();
+        tree.push_move(Color::Black, Action::Move(4, 4)).unwrap();
+        assert!(tree.can_undo());
+
+        assert!(tree.undo().unwrap());
+        assert_eq!(tree.tree(), &original);
+
+        tree.begin_transaction();
+        tree.commit();
+        assert!(tree.can_redo());
+    }
+
+    #[test]
+    fn builds_tree_from_empty() {
+        let mut tree = TrackedGameTree::default();
+        tree.push_node(parse_node(";SZ[9]").unwrap()).unwrap();
+        tree.push_move(Color::Black, Action::Move(1, 1)).unwrap();
+        let sgf: String = tree.into_inner().into();
+        assert_eq!(sgf, "(;SZ[9];B[aa])");
+    }
+}
@@ -7,30 +7,24 @@ mod tree_tests {
     fn can_convert_game_tree_without_variations() {
         let tree = GameTree {
             nodes: vec![
-                GameNode {
-                    tokens: vec![
-                        SgfToken::PlayerName {
-                            color: Color::Black,
-                            name: "black".to_string(),
-                        },
-                        SgfToken::PlayerName {
-                            color: Color::White,
-                            name: "white".to_string(),
-                        },
-                    ],
-                },
-                GameNode {
-                    tokens: vec![SgfToken::Move {
+                GameNode::from(vec![
+                    SgfToken::PlayerName {
                         color: Color::Black,
-                        action: Move(3, 3),
-                    }],
-                },
-                GameNode {
-                    tokens: vec![SgfToken::Move {
+                        name: "black".into(),
+                    },
+                    SgfToken::PlayerName {
                         color: Color::White,
-                        action: Move(16, 16),
-                    }],
-                },
+                        name: "white".into(),
+                    },
+                ]),
+                GameNode::from(vec![SgfToken::Move {
+                    color: Color::Black,
+                    action: Move(3, 3),
+                }]),
+                GameNode::from(vec![SgfToken::Move {
+                    color: Color::White,
+                    action: Move(16, 16),
+                }]),
             ],
             variations: vec![],
         };
@@ -42,48 +36,38 @@ mod tree_tests {
     fn can_convert_game_tree_with_variations() {
         let tree = GameTree {
             nodes: vec![
-                GameNode {
-                    tokens: vec![
-                        SgfToken::PlayerName {
-                            color: Color::Black,
-                            name: "black".to_string(),
-                        },
-                        SgfToken::PlayerName {
-                            color: Color::White,
-                            name: "white".to_string(),
-                        },
-                    ],
-                },
-                GameNode {
-                    tokens: vec![SgfToken::Move {
+                GameNode::from(vec![
+                    SgfToken::PlayerName {
                         color: Color::Black,
-                        action: Move(3, 3),
-                    }],
-                },
-                GameNode {
-                    tokens: vec![SgfToken::Move {
+                        name: "black".into(),
+                    },
+                    SgfToken::PlayerName {
                         color: Color::White,
-                        action: Move(16, 16),
-                    }],
-                },
+                        name: "white".into(),
+                    },
+                ]),
+                GameNode::from(vec![SgfToken::Move {
+                    color: Color::Black,
+                    action: Move(3, 3),
+                }]),
+                GameNode::from(vec![SgfToken::Move {
+                    color: Color::White,
+                    action: Move(16, 16),
+                }]),
             ],
             variations: vec![
                 GameTree {
-                    nodes: vec![GameNode {
-                        tokens: vec![SgfToken::Move {
-                            color: Color::Black,
-                            action: Move(4, 16),
-                        }],
-                    }],
+                    nodes: vec![GameNode::from(vec![SgfToken::Move {
+                        color: Color::Black,
+                        action: Move(4, 16),
+                    }])],
                     variations: vec![],
                 },
                 GameTree {
-                    nodes: vec![GameNode {
-                        tokens: vec![SgfToken::Move {
-                            color: Color::Black,
-                            action: Move(16, 4),
-                        }],
-                    }],
+                    nodes: vec![GameNode::from(vec![SgfToken::Move {
+                        color: Color::Black,
+                        action: Move(16, 4),
+                    }])],
                     variations: vec![],
                 },
             ],
@@ -99,31 +83,25 @@ mod tree_tests {
     fn can_validate_valid_game_tree() {
         let tree = GameTree {
             nodes: vec![
-                GameNode {
-                    tokens: vec![
-                        SgfToken::PlayerName {
-                            color: Color::Black,
-                            name: "black".to_string(),
-                        },
-                        SgfToken::PlayerName {
-                            color: Color::White,
-                            name: "white".to_string(),
-                        },
-                        SgfToken::Size(19, 19),
-                    ],
-                },
-                GameNode {
-                    tokens: vec![SgfToken::Move {
+                GameNode::from(vec![
+                    SgfToken::PlayerName {
                         color: Color::Black,
-                        action: Move(3, 3),
-                    }],
-                },
-                GameNode {
-                    tokens: vec![SgfToken::Move {
+                        name: "black".into(),
+                    },
+                    SgfToken::PlayerName {
                         color: Color::White,
-                        action: Move(16, 16),
-                    }],
-                },
+                        name: "white".into(),
+                    },
+                    SgfToken::Size(19, 19),
+                ]),
+                GameNode::from(vec![SgfToken::Move {
+                    color: Color::Black,
+                    action: Move(3, 3),
+                }]),
+                GameNode::from(vec![SgfToken::Move {
+                    color: Color::White,
+                    action: Move(16, 16),
+                }]),
             ],
             variations: vec![],
         };
@@ -134,33 +112,27 @@ mod tree_tests {
     fn can_invalidate_invalid_main_varation() {
         let tree = GameTree {
             nodes: vec![
-                GameNode {
-                    tokens: vec![
-                        SgfToken::PlayerName {
-                            color: Color::Black,
-                            name: "black".to_string(),
-                        },
-                        SgfToken::PlayerName {
-                            color: Color::White,
-                            name: "white".to_string(),
-                        },
-                    ],
-                },
-                GameNode {
-                    tokens: vec![SgfToken::Move {
+                GameNode::from(vec![
+                    SgfToken::PlayerName {
                         color: Color::Black,
-                        action: Move(3, 3),
-                    }],
-                },
-                GameNode {
-                    tokens: vec![
-                        SgfToken::Move {
-                            color: Color::White,
-                            action: Move(16, 16),
-                        },
-                        SgfToken::Size(19, 19),
-                    ],
-                },
+                        name: "black".into(),
+                    },
+                    SgfToken::PlayerName {
+                        color: Color::White,
+                        name: "white".into(),
+                    },
+                ]),
+                GameNode::from(vec![SgfToken::Move {
+                    color: Color::Black,
+                    action: Move(3, 3),
+                }]),
+                GameNode::from(vec![
+                    SgfToken::Move {
+                        color: Color::White,
+                        action: Move(16, 16),
+                    },
+                    SgfToken::Size(19, 19),
+                ]),
             ],
             variations: vec![],
         };
@@ -170,19 +142,17 @@ mod tree_tests {
     #[test]
     fn single_node_tree_is_valid() {
         let tree = GameTree {
-            nodes: vec![GameNode {
-                tokens: vec![
-                    SgfToken::PlayerName {
-                        color: Color::Black,
-                        name: "black".to_string(),
-                    },
-                    SgfToken::PlayerName {
-                        color: Color::White,
-                        name: "white".to_string(),
-                    },
-                    SgfToken::Size(19, 19),
-                ],
-            }],
+            nodes: vec![GameNode::from(vec![
+                SgfToken::PlayerName {
+                    color: Color::Black,
+                    name: "black".into(),
+                },
+                SgfToken::PlayerName {
+                    color: Color::White,
+                    name: "white".into(),
+                },
+                SgfToken::Size(19, 19),
+            ])],
             variations: vec![],
         };
         assert!(tree.is_valid());
@@ -191,24 +161,702 @@ mod tree_tests {
     #[test]
     fn charset_converted_to_utf8_on_string_conversion() {
         let tree = GameTree {
-            nodes: vec![GameNode {
-                tokens: vec![
-                    SgfToken::Charset(Encoding::Other("ISO-8859".to_string())),
-                    SgfToken::PlayerName {
-                        color: Color::Black,
-                        name: "black".to_string(),
-                    },
-                    SgfToken::PlayerName {
-                        color: Color::White,
-                        name: "white".to_string(),
-                    },
-                    SgfToken::Size(19, 19),
-                ],
-            }],
+            nodes: vec![GameNode::from(vec![
+                SgfToken::Charset(Encoding::Other("ISO-8859".to_string())),
+                SgfToken::PlayerName {
+                    color: Color::Black,
+                    name: "black".into(),
+                },
+                SgfToken::PlayerName {
+                    color: Color::White,
+                    name: "white".into(),
+                },
+                SgfToken::Size(19, 19),
+            ])],
             variations: vec![],
         };
         let output: String = tree.into();
 
         assert_eq!(output, "(;CA[UTF-8]PB[black]PW[white]SZ[19])");
     }
+
+    #[test]
+    fn can_debug_print_nested_variations() {
+        let tree: GameTree = parse("(;SZ[9];B[aa](;W[bb](;B[cc])(;B[dd]))(;W[ee]))").unwrap();
+        assert_eq!(
+            tree.debug_tree().to_string(),
+            ";\n;B[aa]\n├─ ;W[bb]\n│  ├─ ;B[cc]\n│  └─ ;B[dd]\n└─ ;W[ee]\n"
+        );
+    }
+
+    #[test]
+    fn can_debug_print_empty_tree() {
+        assert_eq!(GameTree::default().debug_tree().to_string(), "");
+    }
+
+    #[test]
+    fn debug_print_keeps_comments_on_one_line() {
+        let tree: GameTree = parse(
+            "(;SZ[9]PB[Shusaku]AB[cc][gg];W[ee]TR[ee]C[Ear-reddening move.\n\nBlack wins here.]\
+             (;B[ce]C[Short])(;B[ec]))",
+        )
+        .unwrap();
+        assert_eq!(
+            tree.debug_tree().to_string(),
+            ";AB[cc][gg]\n\
+             ;W[ee] \"Ear-reddening move. Bla…\"\n\
+             ├─ ;B[ce] \"Short\"\n\
+             └─ ;B[ec]\n"
+        );
+    }
+
+    #[test]
+    fn can_export_dot_with_move_numbers() {
+        let tree: GameTree =
+            parse("(;C[say \"hi\"]AB[cc];B[aa]C[Long\ncomment](;W[bb];AW[dd])(;W[cc]B[dd];W[ee]))")
+                .unwrap();
+        assert_eq!(
+            tree.to_dot(),
+            "digraph {\n    n0 [label=\"\"];\n    n1 [label=\"1: B[aa]\"];\n    n0 -> n1;\n    \
+             n2 [label=\"2: W[bb]\"];\n    n1 -> n2;\n    n3 [label=\"\"];\n    n2 -> n3;\n    \
+             n4 [label=\"2: W[cc] 3: B[dd]\"];\n    n1 -> n4;\n    \
+             n5 [label=\"4: W[ee]\"];\n    n4 -> n5;\n}\n"
+        );
+    }
+
+    #[test]
+    fn shrink_to_fit_releases_unused_capacity() {
+        let mut comment = String::with_capacity(64);
+        comment.push_str("comment");
+        let mut tokens = Vec::with_capacity(8);
+        tokens.push(SgfToken::Comment(comment.into()));
+        let mut tree = GameTree {
+            nodes: vec![GameNode::from(tokens)],
+            variations: Vec::with_capacity(4),
+        };
+
+        let usage = tree.memory_usage();
+        assert_eq!(usage.nodes, 1);
+        assert_eq!(usage.tokens, 1);
+        assert!(usage.unused_bytes >= 57);
+
+        tree.shrink_to_fit();
+        let shrunk = tree.memory_usage();
+        assert_eq!(shrunk.unused_bytes, 0);
+        assert!(shrunk.allocated_bytes < usage.allocated_bytes);
+    }
+
+    #[test]
+    fn can_iterate_over_tree_reference() {
+        let tree: GameTree = parse("(;B[dc];W[ef](;B[aa])(;B[cc]))").unwrap();
+        let mut count = 0;
+        for node in &tree {
+            assert_eq!(node.tokens.len(), 1);
+            count += 1;
+        }
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn display_matches_string_conversion() {
+        let tree: GameTree = parse("(;CA[ISO-8859-1]PB[black];B[aa](;W[bb])(;W[cc]))").unwrap();
+        let string_tree: String = (&tree).into();
+        assert_eq!(tree.to_string(), string_tree);
+        assert_eq!(tree.nodes[0].to_string(), ";CA[UTF-8]PB[black]");
+        assert_eq!(tree.nodes[0].tokens[1].to_string(), "PB[black]");
+    }
+
+    #[test]
+    fn push_node_appends_to_main_variation() {
+        let mut tree: GameTree = parse("(;SZ[9];B[aa](;W[bb])(;W[cc]))").unwrap();
+        assert_eq!(tree.push_move(Color::Black, Action::Pass).unwrap(), ";B[]");
+        let text = tree
+            .push_node(GameNode::from(vec![SgfToken::Comment("hello".into())]))
+            .unwrap();
+        assert_eq!(text, ";C[hello]");
+
+        let sgf: String = tree.clone().into();
+        assert_eq!(sgf, "(;SZ[9];B[aa](;W[bb];B[];C[hello])(;W[cc]))");
+
+        let err = tree.push_node(GameNode::from(vec![SgfToken::Size(19, 19)]));
+        assert_eq!(
+            err.unwrap_err().kind,
+            SgfErrorKind::InvalidRootTokenPlacement
+        );
+    }
+
+    #[test]
+    fn push_node_to_empty_tree_allows_root_tokens() {
+        let mut tree = GameTree::default();
+        assert_eq!(
+            tree.push_node(GameNode::from(vec![SgfToken::Size(19, 19)]))
+                .unwrap(),
+            ";SZ[19]"
+        );
+        assert_eq!(tree.nodes.len(), 1);
+    }
+
+    #[test]
+    fn writes_passes_for_older_readers() {
+        let options = WriterOptions {
+            pass_format: PassFormat::Tt,
+            ..WriterOptions::default()
+        };
+        let tree: GameTree = parse("(;B[];W[tt])").unwrap();
+        let output = tree.serialize_with(&options);
+        assert_eq!(output, "(;B[tt];W[tt])");
+        let reparsed: GameTree = parse(&output).unwrap();
+        let actions: Vec<Action> = reparsed
+            .iter()
+            .filter_map(|node| match node.tokens[0] {
+                SgfToken::Move { action, .. } => Some(action.normalize_pass((19, 19))),
+                _ => None,
+            })
+            .collect();
+        assert!(actions.iter().all(Action::is_pass));
+
+        let large: GameTree = parse("(;SZ[25];B[])").unwrap();
+        assert_eq!(large.serialize_with(&options), "(;SZ[25];B[])");
+        assert_eq!(Action::Move(3, 4).coordinate(), Some((3, 4)));
+        assert_eq!(Action::Pass.coordinate(), None);
+    }
+
+    #[test]
+    fn names_variations() {
+        let mut tree: GameTree =
+            parse("(;SZ[9];B[ee](;N[Joseki]W[cc](;N[a\\]b]B[dd])(;B[gg]))(;W[gg]))").unwrap();
+        assert_eq!(tree.variation_names(), vec![Some("Joseki"), None]);
+        assert_eq!(
+            tree.variations[0].variation_names(),
+            vec![Some("a\\]b"), None]
+        );
+        assert_eq!(tree.nodes[1].tokens, vec![SgfToken::from_pair("B", "ee")]);
+        assert_eq!(
+            tree.variations[0].nodes[0].tokens[0],
+            SgfToken::NodeName("Joseki".into())
+        );
+
+        tree.set_variation_name(0, Some("Mistake")).unwrap();
+        assert_eq!(tree.variation_names(), vec![Some("Mistake"), None]);
+        let err = tree.set_variation_name(2, Some("x")).unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::VariationNotFound);
+        assert_eq!(
+            tree.to_string(),
+            "(;SZ[9];B[ee](;N[Mistake]W[cc](;B[dd]N[a\\]b])(;B[gg]))(;W[gg]))"
+        );
+    }
+
+    #[test]
+    fn lists_annotations_in_all_variations() {
+        let tree: GameTree =
+            parse("(;SZ[9]HO[1];B[ee](;W[cc]GW[2]UC[1])(;W[gg]HO[2];B[cc]GB[1]))").unwrap();
+        assert_eq!(
+            tree.hotspots(),
+            vec![
+                (TreePath::new(vec![], 0), Emphasis::Normal),
+                (TreePath::new(vec![1], 0), Emphasis::Emphasized),
+            ]
+        );
+        assert_eq!(
+            tree.judged_positions(),
+            vec![
+                (
+                    TreePath::new(vec![0], 0),
+                    Judgement::GoodFor(Color::White, Emphasis::Emphasized)
+                ),
+                (
+                    TreePath::new(vec![0], 0),
+                    Judgement::Unclear(Emphasis::Normal)
+                ),
+                (
+                    TreePath::new(vec![1], 1),
+                    Judgement::GoodFor(Color::Black, Emphasis::Normal)
+                ),
+            ]
+        );
+        assert!(parse("(;B[aa])").unwrap().hotspots().is_empty());
+    }
+
+    #[test]
+    fn converts_single_line_to_moves() {
+        let tree: GameTree = parse("(;SZ[9]AB[cc];B[ee](;W[gg];B[])(;W[gc]))").unwrap();
+        let err = tree.try_into_moves().unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::UnexpectedVariation);
+        let branch = std::error::Error::source(&err)
+            .and_then(|source| source.downcast_ref::<BranchPoint>())
+            .unwrap();
+        assert_eq!(branch.path, TreePath::new(vec![], 1));
+        assert_eq!(branch.variations, 2);
+        assert_eq!(branch.to_string(), "2 variations after node :1");
+
+        let tree: GameTree = parse("(;SZ[9];B[ee](;W[gg](;B[cc])(;B[gc])))").unwrap();
+        let err = tree.try_into_moves().unwrap_err();
+        let branch = std::error::Error::source(&err)
+            .and_then(|source| source.downcast_ref::<BranchPoint>())
+            .unwrap();
+        assert_eq!(branch.path, TreePath::new(vec![0], 0));
+
+        let tree: GameTree = parse("(;SZ[9];B[ee](;W[gg];B[]))").unwrap();
+        assert_eq!(
+            tree.try_into_moves().unwrap(),
+            vec![
+                (Color::Black, Action::Move(5, 5)),
+                (Color::White, Action::Move(7, 7)),
+                (Color::Black, Action::Pass),
+            ]
+        );
+    }
+
+    #[test]
+    fn normalizes_moves_in_all_variations() {
+        let mut tree: GameTree =
+            parse("(;SZ[9]B[ee]W[cc]BL[10]OB[3];WL[20](;B[gg]W[gc]WL[15])(;B[cg];C[x]))").unwrap();
+        assert_eq!(tree.normalize_moves(), 3);
+        assert_eq!(
+            tree.to_string(),
+            "(;BL[10]B[ee]OB[3]SZ[9];WL[20]W[cc](;B[gg];WL[15]W[gc])(;B[cg];C[x]))"
+        );
+        assert_eq!(tree.normalize_moves(), 0);
+
+        let mut tree: GameTree = parse("(;SZ[9];BL[30];B[ee];WL[30])").unwrap();
+        assert_eq!(tree.normalize_moves(), 0);
+    }
+
+    #[test]
+    fn splits_setup_and_moves_in_all_variations() {
+        let mut tree: GameTree =
+            parse("(;SZ[9]KM[6.5]AB[cc]B[ee]C[Start](;AW[gg]PL[W]W[gc]BL[10])(;W[cg]))").unwrap();
+        assert_eq!(tree.mixed_setup_nodes().len(), 2);
+        tree.nodes[0].extras_mut().insert("engine", 1u8);
+        assert_eq!(tree.split_setup_moves(), 2);
+        assert_eq!(
+            tree.to_string(),
+            "(;AB[cc]KM[6.5]SZ[9];B[ee]C[Start](;AW[gg]PL[W];BL[10]W[gc])(;W[cg]))"
+        );
+        assert!(tree.nodes[0].extras().contains_key("engine"));
+        assert!(tree.nodes[1].extras().is_empty());
+        assert_eq!(tree.split_setup_moves(), 0);
+    }
+
+    #[test]
+    fn repairs_all_variations_once() {
+        let mut tree: GameTree = parse("(;SZ[9];B[tt]TR[aa][aa](;AB[cc]W[ee])(;W[gg]))").unwrap();
+        tree.variations[1].nodes[0]
+            .tokens
+            .push(SgfToken::Size(13, 13));
+        tree.variations[1].nodes[0]
+            .tokens
+            .push(SgfToken::Game(Game::Go));
+
+        let repaired = tree.repair(&RepairOptions::default());
+        assert_eq!(
+            repaired.changes,
+            vec![
+                Repair::DroppedRootToken {
+                    path: TreePath::new(vec![1], 0),
+                    identifier: "SZ".to_string(),
+                },
+                Repair::LiftedRootToken {
+                    path: TreePath::new(vec![1], 0),
+                    identifier: "GM".to_string(),
+                },
+                Repair::MergedDuplicates {
+                    path: TreePath::new(vec![], 1),
+                    dropped: vec![],
+                },
+                Repair::ConvertedPass(TreePath::new(vec![], 1)),
+                Repair::SplitSetup(TreePath::new(vec![0], 0)),
+            ]
+        );
+        assert_eq!(
+            repaired.tree.to_string(),
+            "(;GM[1]SZ[9];B[]TR[aa](;AB[cc];W[ee])(;W[gg]))"
+        );
+        assert!(repaired
+            .tree
+            .repair(&RepairOptions::default())
+            .is_unchanged());
+
+        let options = RepairOptions {
+            split_setup_moves: false,
+            convert_tt_passes: false,
+            ..RepairOptions::default()
+        };
+        let repaired = tree.repair(&options);
+        assert_eq!(repaired.changes.len(), 3);
+        assert_eq!(
+            repaired.tree.to_string(),
+            "(;GM[1]SZ[9];B[tt]TR[aa](;AB[cc]W[ee])(;W[gg]))"
+        );
+    }
+
+    #[test]
+    fn restores_iterators_from_snapshots() {
+        let tree: GameTree = parse("(;B[aa];W[bb](;B[cc](;W[dd])(;W[ee]))(;B[ff]))").unwrap();
+        let mut iter = tree.iter_from(&TreePath::new(vec![0], 0)).unwrap();
+        iter.next();
+        iter.pick_variation(1).unwrap();
+        let state = iter.snapshot();
+        assert_eq!(state.to_string(), "0:1/1");
+
+        let rest: Vec<&GameNode> = tree.restore_iter(&state).unwrap().collect();
+        assert_eq!(rest, iter.collect::<Vec<_>>());
+        assert_eq!(rest, vec![&tree.variations[0].variations[1].nodes[0]]);
+
+        let mut iter = tree.iter();
+        let nodes: Vec<&GameNode> = iter.by_ref().collect();
+        assert_eq!(nodes.len(), 4);
+        let state = iter.snapshot();
+        assert_eq!(state.path, TreePath::new(vec![0, 0], 1));
+        assert_eq!(tree.restore_iter(&state).unwrap().count(), 0);
+
+        assert_eq!("0.0:1/0".parse::<IteratorState>().unwrap(), state);
+        assert_eq!(
+            ":0/0".parse::<IteratorState>().unwrap(),
+            IteratorState::default()
+        );
+        for malformed in ["", "0:1", "0/1", "a:0/0", "0..1:0/0"].iter() {
+            let err = malformed.parse::<IteratorState>().unwrap_err();
+            assert_eq!(err.kind, SgfErrorKind::ParseError, "{}", malformed);
+        }
+
+        let stale: IteratorState = "1.0:0/0".parse().unwrap();
+        let err = tree.restore_iter(&stale).err().unwrap();
+        assert_eq!(err.kind, SgfErrorKind::VariationNotFound);
+        let stale: IteratorState = "1:2/0".parse().unwrap();
+        let err = tree.restore_iter(&stale).err().unwrap();
+        assert_eq!(err.kind, SgfErrorKind::NodeNotFound);
+    }
+
+    #[test]
+    fn finds_branch_points_in_document_order() {
+        let tree: GameTree = parse(
+            "(;SZ[9](;B[ee];W[cc](;B[gg])(;B[cg]))(;B[gc](;W[cc])(;W[gg];B[ee](;W[aa])(;W[bb]))))",
+        )
+        .unwrap();
+        let branches: Vec<(String, usize, usize)> = tree
+            .branch_points()
+            .iter()
+            .map(|branch| {
+                (
+                    branch.path.to_string(),
+                    branch.move_number,
+                    branch.variations,
+                )
+            })
+            .collect();
+        assert_eq!(
+            branches,
+            vec![
+                (":0".to_string(), 0, 2),
+                ("0:1".to_string(), 2, 2),
+                ("1:0".to_string(), 1, 2),
+                ("1.1:1".to_string(), 3, 2),
+            ]
+        );
+
+        let tree: GameTree = parse("(;SZ[9];B[ee](;W[cc]))").unwrap();
+        assert!(tree.branch_points().is_empty());
+    }
+
+    #[test]
+    fn finds_longest_and_shortest_lines() {
+        let tree: GameTree =
+            parse("(;SZ[9](;B[ee];W[cc](;B[gg])(;B[cg];W[gc]))(;B[gc];W[cc];B[gg];W[ee]))")
+                .unwrap();
+        let (path, nodes) = tree.longest_line();
+        assert_eq!(path, TreePath::new(vec![0, 1], 1));
+        assert_eq!(nodes.len(), 5);
+        assert_eq!(nodes, tree.get_line_to(&path).unwrap());
+
+        let (path, nodes) = tree.shortest_line();
+        assert_eq!(path, TreePath::new(vec![0, 0], 0));
+        assert_eq!(nodes.len(), 4);
+
+        let empty = GameTree::default();
+        let (path, nodes) = empty.longest_line();
+        assert_eq!(path, TreePath::default());
+        assert!(nodes.is_empty());
+    }
+
+    #[test]
+    fn strips_tokens_by_category_in_all_variations() {
+        let mut tree: GameTree = parse(
+            "(;SZ[9]KM[6.5]AB[cc]XX[app];B[ee]BL[30]C[Start](;W[gg]TR[gg]OW[2])(;W[cg]GB[1];B[gc]BM[1]))",
+        )
+        .unwrap();
+        let keep = TokenCategory::Root | TokenCategory::GameInfo | TokenCategory::Move;
+        let strip = [
+            TokenCategory::Setup,
+            TokenCategory::Timing,
+            TokenCategory::Annotation,
+            TokenCategory::Markup,
+            TokenCategory::Unknown,
+            TokenCategory::Invalid,
+        ]
+        .iter()
+        .fold(TokenCategories::empty(), |categories, category| {
+            categories | *category
+        });
+        assert_eq!(strip | keep, TokenCategories::all());
+
+        assert_eq!(tree.strip(strip), 8);
+        assert_eq!(
+            tree.to_string(),
+            "(;KM[6.5]SZ[9];B[ee](;W[gg])(;W[cg];B[gc]))"
+        );
+        assert_eq!(tree.strip(TokenCategories::empty()), 0);
+        assert_eq!(tree.strip(TokenCategory::Move), 4);
+        assert_eq!(tree.total_node_count(), 5);
+    }
+
+    #[test]
+    fn estimates_sgf_length_of_real_games() {
+        let tree: GameTree = parse(include_str!("sgf/ShusakuvsInseki.sgf")).unwrap();
+        let length = tree.to_string().len();
+        let estimate = tree.estimated_sgf_len();
+        assert!(estimate >= length, "{} < {}", estimate, length);
+        assert!(
+            estimate - length < length / 20,
+            "{} vs {}",
+            estimate,
+            length
+        );
+        assert_eq!(
+            tree.token_count(),
+            tree.iter().map(|node| node.tokens.len()).sum::<usize>()
+        );
+
+        let tree: GameTree =
+            parse("(;SZ[9:13]HA[2]KM[0.5]RE[W+R]AB[aa][bb];B[];W[aa]LB[aa:A]TR[bb])").unwrap();
+        assert_eq!(tree.estimated_sgf_len(), tree.to_string().len() + 2);
+    }
+
+    #[test]
+    fn attaches_variations_from_move_lists() {
+        let mut tree: GameTree = parse("(;SZ[9];B[ee](;W[cc])(;W[gg]))").unwrap();
+        let pv = [
+            (Color::White, Action::Move(3, 7)),
+            (Color::Black, Action::Pass),
+        ];
+        let path = tree
+            .attach_variation_at(&TreePath::new(vec![], 1), &pv, None)
+            .unwrap();
+        assert_eq!(path, TreePath::new(vec![2], 0));
+        assert_eq!(
+            tree.to_string(),
+            "(;SZ[9];B[ee](;W[cc])(;W[gg])(;W[cg];B[]))"
+        );
+
+        let path = tree
+            .attach_variation_at(&TreePath::new(vec![0], 0), &pv[1..], Some("Pass"))
+            .unwrap();
+        assert_eq!(path, TreePath::new(vec![0, 0], 0));
+        assert_eq!(tree.get_node(&path).unwrap().to_string(), ";B[]C[Pass]");
+
+        let err = tree
+            .attach_variation_at(&TreePath::new(vec![], 1), &[], None)
+            .unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::NodeNotFound);
+        assert!(tree
+            .attach_variation_at(&TreePath::new(vec![5], 0), &pv, None)
+            .is_err());
+    }
+
+    #[test]
+    fn reports_consecutive_moves_of_one_color() {
+        let tree: GameTree =
+            parse("(;SZ[9];W[ee];W[cc];B[gg](;B[gc];PL[W];W[cg])(;AB[ce];W[ec];PL[W];W[eg]))")
+                .unwrap();
+        assert_eq!(
+            tree.same_color_moves(),
+            vec![TreePath::new(vec![], 2), TreePath::new(vec![0], 0)]
+        );
+        assert!(parse("(;SZ[9];B[ee];W[cc])")
+            .unwrap()
+            .same_color_moves()
+            .is_empty());
+    }
+
+    #[test]
+    fn orders_paths_in_document_order() {
+        let tree: GameTree =
+            parse("(;SZ[9](;B[ee];W[cc](;B[gg])(;B[cg];W[gc]))(;B[gc];W[cc]))").unwrap();
+        let paths = vec![
+            TreePath::new(vec![], 0),
+            TreePath::new(vec![0], 0),
+            TreePath::new(vec![0], 1),
+            TreePath::new(vec![0, 0], 0),
+            TreePath::new(vec![0, 1], 0),
+            TreePath::new(vec![0, 1], 1),
+            TreePath::new(vec![1], 0),
+            TreePath::new(vec![1], 1),
+        ];
+        let mut sorted = paths.clone();
+        sorted.reverse();
+        sorted.sort();
+        assert_eq!(sorted, paths);
+        assert!(paths.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let a = TreePath::new(vec![0, 1], 1);
+        let b = TreePath::new(vec![0, 0], 0);
+        assert_eq!(
+            tree.common_ancestor(&a, &b).unwrap(),
+            TreePath::new(vec![0], 1)
+        );
+        assert_eq!(
+            tree.common_ancestor(&a, &TreePath::new(vec![1], 1))
+                .unwrap(),
+            TreePath::new(vec![], 0)
+        );
+        assert_eq!(
+            tree.common_ancestor(&a, &TreePath::new(vec![0], 0))
+                .unwrap(),
+            TreePath::new(vec![0], 0)
+        );
+        assert!(TreePath::new(vec![0], 0).is_ancestor_of(&a));
+        assert!(!b.is_ancestor_of(&a));
+        assert!(tree
+            .common_ancestor(&a, &TreePath::new(vec![2], 0))
+            .is_err());
+    }
+
+    #[test]
+    fn promotes_unknown_tokens_in_all_variations() {
+        let mut tree: GameTree =
+            parse("(;SZ[9]XT[300];B[ee](;W[cc]XC[One])(;W[gg]XC[Two]XX[keep]))").unwrap();
+        let mut seen = vec![];
+        let promoted = tree.promote_unknown(|identifier, value| {
+            seen.push(identifier.to_string());
+            match identifier {
+                "XT" => value.parse().ok().map(SgfToken::TimeLimit),
+                "XC" => Some(SgfToken::Comment(value.into())),
+                _ => None,
+            }
+        });
+        assert_eq!(promoted, 3);
+        assert_eq!(seen, vec!["XT", "XC", "XC", "XX"]);
+        assert_eq!(
+            tree.to_string(),
+            "(;SZ[9]TM[300];B[ee](;C[One]W[cc])(;C[Two]W[gg]XX[keep]))"
+        );
+        assert_eq!(tree.get_unknown_nodes().len(), 1);
+        assert_eq!(tree.promote_unknown(|_, _| None), 0);
+    }
+
+    #[test]
+    fn repair_reparses_invalid_tokens_first() {
+        let tree: GameTree = parse("(;SZ[9];B[ee];W[ cc ]SZ[ 9 ];B[ tt ])").unwrap();
+        let repaired = tree.repair(&RepairOptions::default());
+        assert_eq!(
+            repaired.changes[..3],
+            [
+                Repair::ReparsedToken {
+                    path: TreePath::new(vec![], 2),
+                    identifier: "W".to_string(),
+                },
+                Repair::ReparsedToken {
+                    path: TreePath::new(vec![], 2),
+                    identifier: "SZ".to_string(),
+                },
+                Repair::ReparsedToken {
+                    path: TreePath::new(vec![], 3),
+                    identifier: "B".to_string(),
+                },
+            ]
+        );
+        assert_eq!(repaired.tree.to_string(), "(;SZ[9];B[ee];W[cc];B[])");
+        assert!(repaired.tree.get_invalid_nodes().is_empty());
+    }
+
+    #[test]
+    fn maps_and_filters_into_new_trees() {
+        let mut tree: GameTree =
+            parse("(;SZ[9];B[ee](;PL[W](;W[cc])(;W[gc]))(;W[gg]C[Hi]))").unwrap();
+        tree.nodes[1].extras_mut().insert("visits", 12u32);
+        let original = tree.clone();
+
+        let filtered = tree.filter_nodes(|node| node.tokens_for("PL").is_empty());
+        assert_eq!(
+            filtered.to_string(),
+            "(;SZ[9];B[ee](;W[cc])(;W[gc])(;C[Hi]W[gg]))"
+        );
+        assert_eq!(filtered.nodes[1].extras().get::<u32>("visits"), Some(&12));
+
+        let mut visited = vec![];
+        let stripped = tree.map_tokens(|token| {
+            visited.push(token.identifier().to_string());
+            match token {
+                SgfToken::Comment(_) => SgfToken::Comment(String::new().into()),
+                token => token.clone(),
+            }
+        });
+        assert_eq!(visited, vec!["SZ", "B", "PL", "W", "W", "W", "C"]);
+        assert_eq!(
+            stripped.variations[1].nodes[0].tokens_for("C")[0],
+            &SgfToken::Comment(String::new().into())
+        );
+        assert_eq!(stripped.nodes[1].extras().get::<u32>("visits"), Some(&12));
+        assert_eq!(tree.map_nodes(GameNode::clone), original);
+        assert_eq!(tree, original);
+    }
+
+    #[test]
+    fn crops_games_restoring_captures_from_outside() {
+        let tree: GameTree =
+            parse("(;SZ[9]VW[];B[cc];W[bc];B[aa];W[cb];B[ab];W[dc]TR[cc][dd];B[ba];W[cd])")
+                .unwrap();
+        let cropped = tree.crop((1, 1), (3, 3)).unwrap();
+        assert_eq!(
+            cropped.to_string(),
+            "(;SZ[9]VW[aa:cc];B[cc];W[bc];B[aa];W[cb];B[ab];TR[cc];B[ba];AE[cc])"
+        );
+        let tree: GameTree =
+            parse("(;SZ[9];B[ca];W[ba];B[da];W[db];B[aa];W[ea];B[ab];W[cb])").unwrap();
+        let cropped = tree.crop((1, 1), (3, 3)).unwrap();
+        assert_eq!(
+            cropped.to_string(),
+            "(;SZ[9]VW[aa:cc];B[ca];W[ba];B[aa];B[ab];W[cb];AE[ca])"
+        );
+
+        let err = tree.crop((3, 3), (1, 1)).unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::InvalidTokenValue);
+        assert!(tree.crop((1, 1), (10, 9)).is_err());
+        assert!(tree.crop((0, 1), (9, 9)).is_err());
+    }
+
+    #[test]
+    fn can_collect_and_extend_lines() {
+        let moves = [
+            (Color::Black, Move(4, 4)),
+            (Color::White, Move(16, 16)),
+            (Color::Black, Action::Pass),
+        ];
+        let nodes = moves
+            .iter()
+            .map(|(color, action)| {
+                GameNode::from(vec![SgfToken::Move {
+                    color: *color,
+                    action: *action,
+                }])
+            })
+            .collect::<Vec<_>>();
+
+        let line: GameTree = nodes.iter().cloned().collect();
+        assert_eq!(line.nodes, nodes);
+        assert!(line.variations.is_empty());
+        let empty: GameTree = Vec::new().into_iter().collect();
+        assert_eq!(empty, GameTree::default());
+
+        let mut tree: GameTree = parse("(;SZ[19];B[aa](;W[bb](;B[cc])(;B[dd]))(;W[ee]))").unwrap();
+        tree.extend(nodes);
+        assert_eq!(
+            tree.to_string(),
+            "(;SZ[19];B[aa](;W[bb](;B[cc];B[dd];W[pp];B[])(;B[dd]))(;W[ee]))"
+        );
+
+        let mut empty = GameTree::default();
+        empty.extend(line.nodes.clone());
+        assert_eq!(empty, line);
+    }
 }
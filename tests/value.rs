@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod value_tests {
+    use sgf_parser::*;
+
+    #[test]
+    fn knows_properties_without_tokens() {
+        assert_eq!(SgfToken::value_type_of("DM"), Some(ValueType::Double));
+        assert_eq!(
+            SgfToken::value_type_of("TB"),
+            Some(ValueType::EList(Box::new(ValueType::Point)))
+        );
+        assert_eq!(SgfToken::value_type_of("KO"), Some(ValueType::None));
+        assert_eq!(SgfToken::value_type_of("XX"), None);
+    }
+
+    #[test]
+    fn can_validate_values() {
+        assert!(ValueType::Number.is_valid("-12"));
+        assert!(!ValueType::Number.is_valid("1.5"));
+        assert!(ValueType::Real.is_valid("1.5"));
+        assert!(!ValueType::Real.is_valid("1."));
+        assert!(ValueType::Double.is_valid("2"));
+        assert!(!ValueType::Double.is_valid("3"));
+        assert!(ValueType::Color.is_valid("W"));
+        assert!(ValueType::Move.is_valid(""));
+        assert!(!ValueType::Point.is_valid(""));
+    }
+
+    #[test]
+    fn can_validate_composed_values() {
+        let size = SgfToken::value_type_of("SZ").unwrap();
+        assert!(size.is_valid("19"));
+        assert!(size.is_valid("19:13"));
+        assert!(!size.is_valid("19:x"));
+
+        let application = SgfToken::value_type_of("AP").unwrap();
+        assert!(application.is_valid("name\\:with colon:1.0"));
+
+        let dim = SgfToken::value_type_of("DD").unwrap();
+        assert!(dim.is_valid(""));
+        assert!(dim.is_valid("aa"));
+    }
+
+    #[test]
+    fn all_properties_have_a_value_type() {
+        for info in properties() {
+            assert!(
+                SgfToken::value_type_of(info.identifier).is_some(),
+                "{}",
+                info.identifier
+            );
+        }
+        assert_eq!(properties().len(), 67);
+    }
+
+    #[test]
+    fn tokens_agree_with_the_registry() {
+        let copyright = SgfToken::from_pair("CP", "2019");
+        assert_eq!(copyright, SgfToken::Copyright("2019".into()));
+        assert_eq!(
+            property_info(copyright.identifier()).unwrap().name,
+            "Copyright"
+        );
+        let circle = SgfToken::from_pair("CR", "aa");
+        assert_eq!(property_info(circle.identifier()).unwrap().name, "Circle");
+
+        let mut node = parse_node(";CP[one]CR[aa]CP[two]CR[bb]").unwrap();
+        assert_eq!(node.merge_duplicates(), vec!["CP".to_string()]);
+        assert_eq!(node.to_string(), ";CP[one]CR[aa][bb]");
+    }
+
+    #[test]
+    fn property_info_describes_inheritance() {
+        let info = property_info("DD").unwrap();
+        assert!(info.inherit);
+        assert_eq!(info.multiplicity(), Multiplicity::EList);
+        assert_eq!(
+            property_info("SZ").unwrap().property_type,
+            PropertyType::Root
+        );
+        assert_eq!(
+            property_info("KM").unwrap().multiplicity(),
+            Multiplicity::Single
+        );
+    }
+
+    #[test]
+    fn formats_reals_deterministically() {
+        let cases = [
+            (375.5, "375.5"),
+            (0.5, "0.5"),
+            (7.0, "7"),
+            (-3.5, "-3.5"),
+            (0.25, "0.25"),
+            (6.1, "6.1"),
+            (-0.0, "0"),
+            (1e-7, "0.0000001"),
+            (f32::NAN, "0"),
+        ];
+        for (value, expected) in cases.iter() {
+            assert_eq!(format_real(*value), *expected, "{}", value);
+            assert!(SgfToken::value_type_of("KM")
+                .unwrap()
+                .is_valid(&format_real(*value)));
+        }
+        assert_eq!(
+            SgfToken::Komi(Real::new(-0.0).unwrap()).to_string(),
+            "KM[0]"
+        );
+        assert_eq!(
+            SgfToken::Result(Outcome::WinnerByPoints(
+                Color::White,
+                Real::new(375.5).unwrap()
+            ))
+            .to_string(),
+            "RE[W+375.5]"
+        );
+        let tree: GameTree = parse("(;KM[7.0]RE[B+0.50])").unwrap();
+        assert_eq!(tree.to_string(), "(;KM[7]RE[B+0.5])");
+    }
+}
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod verify_tests {
+    use sgf_parser::*;
+
+    #[test]
+    fn flags_missing_result() {
+        let games = vec![parse("(;SZ[9];B[ee])").unwrap()];
+        let report = verify_results(&games);
+        assert_eq!(report.games[0].issues, vec![ResultIssue::MissingResult]);
+    }
+
+    #[test]
+    fn flags_winner_mismatch() {
+        let games =
+            vec![
+                parse("(;SZ[5]KM[0.5]RE[W+4.5]AB[ca][cb][cc][cd][ce]AW[da][db][dc][dd][de])")
+                    .unwrap(),
+            ];
+        let report = verify_results(&games);
+        let check = &report.games[0];
+        assert_eq!(check.estimated_score, Some(4.5));
+        assert_eq!(
+            check.issues,
+            vec![ResultIssue::WinnerMismatch {
+                recorded: Some(Color::White),
+                estimated: Some(Color::Black),
+            }]
+        );
+    }
+
+    #[test]
+    fn records_captures() {
+        let games = vec![parse("(;SZ[9]RE[B+R];W[aa];B[ba];W[ii];B[ab])").unwrap()];
+        let report = verify_results(&games);
+        assert!(report.is_clean());
+        assert_eq!(report.games[0].captures, (1, 0));
+    }
+
+    #[test]
+    fn flags_replay_failures() {
+        let games = vec![parse("(;SZ[9]RE[B+R];B[aa];W[aa])").unwrap()];
+        let report = verify_results(&games);
+        assert_eq!(
+            report.games[0].issues,
+            vec![ResultIssue::ReplayFailed(SgfErrorKind::IllegalMove)]
+        );
+        assert_eq!(report.games[0].estimated_score, None);
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod warning_tests {
+    use sgf_parser::*;
+
+    #[test]
+    fn clean_input_has_no_warnings() {
+        let outcome = parse_with_warnings("(;SZ[19]CA[UTF-8];B[aa];W[])").unwrap();
+        assert!(outcome.warnings.is_empty());
+    }
+
+    #[test]
+    fn warns_about_empty_values() {
+        let outcome = parse_with_warnings("(;KM[];B[])").unwrap();
+        assert_eq!(
+            outcome.warnings,
+            vec![ParseWarning {
+                game: 0,
+                variations: vec![],
+                node: 0,
+                kind: ParseWarningKind::EmptyValue {
+                    identifier: "KM".to_string()
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn warns_about_normalized_identifiers() {
+        let outcome = parse_with_warnings("(;PlaCe[Tokyo]FooBar[1])").unwrap();
+        assert_eq!(
+            outcome.warnings,
+            vec![ParseWarning {
+                game: 0,
+                variations: vec![],
+                node: 0,
+                kind: ParseWarningKind::NormalizedIdentifier {
+                    original: "PlaCe".to_string(),
+                    normalized: "PC".to_string(),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn warns_about_deprecated_identifiers_in_variations() {
+        let outcome = parse_with_warnings("(;B[aa](;W[bb])(;W[cc];B[dd]L[ee]))").unwrap();
+        assert_eq!(
+            outcome.warnings,
+            vec![ParseWarning {
+                game: 0,
+                variations: vec![1],
+                node: 1,
+                kind: ParseWarningKind::DeprecatedIdentifier("L".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn converts_ff3_long_identifiers_and_labels() {
+        let input = "(;Size[9]Komi[6.5]BlackRank[3d];B[ee]L[aa][cc])";
+        let outcome = parse_with_warnings(input).unwrap();
+        assert_eq!(
+            outcome.tree.to_string(),
+            "(;BR[3d]KM[6.5]SZ[9];B[ee]LB[aa:a][cc:b])"
+        );
+        let kinds: Vec<_> = outcome.warnings.into_iter().map(|w| w.kind).collect();
+        assert_eq!(
+            kinds[..3],
+            [
+                ParseWarningKind::DeprecatedLongIdentifier {
+                    original: "Size".to_string(),
+                    normalized: "SZ".to_string(),
+                },
+                ParseWarningKind::DeprecatedLongIdentifier {
+                    original: "Komi".to_string(),
+                    normalized: "KM".to_string(),
+                },
+                ParseWarningKind::DeprecatedLongIdentifier {
+                    original: "BlackRank".to_string(),
+                    normalized: "BR".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            kinds[3],
+            ParseWarningKind::DeprecatedIdentifier("L".to_string())
+        );
+
+        let mut parser = Parser::new();
+        let mut builder = TreeBuilder::new();
+        for event in parser.feed(input.as_bytes()).unwrap() {
+            builder.push(event).unwrap();
+        }
+        assert_eq!(builder.finish().unwrap()[0], outcome.tree);
+    }
+
+    #[test]
+    fn warns_about_duplicate_identifiers() {
+        let outcome = parse_with_warnings("(;AB[aa][bb];AB[cc]AW[dd]AB[ee]AB[ff])").unwrap();
+        assert_eq!(
+            outcome.warnings,
+            vec![ParseWarning {
+                game: 0,
+                variations: vec![],
+                node: 1,
+                kind: ParseWarningKind::DuplicateIdentifier("AB".to_string()),
+            }]
+        );
+        assert_eq!(outcome.tree.nodes[1].tokens_for("AB").len(), 3);
+    }
+
+    #[test]
+    fn merges_or_rejects_duplicate_identifiers() {
+        let input = "(;SZ[19];B[aa]TR[aa]XX[1]TR[aa][bb]XX[2]XX[1])";
+        let merge = ParserOptions::new().duplicates(DuplicateMode::Merge);
+        let reject = ParserOptions::new().duplicates(DuplicateMode::Reject);
+        let merged = parse_with_options(input, &merge).unwrap();
+        assert!(merged.warnings.is_empty());
+        assert_eq!(merged.tree.to_string(), "(;SZ[19];B[aa]TR[aa][bb]XX[1][2])");
+
+        let err = parse_with_options(input, &reject).unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::DuplicateIdentifier);
+
+        let first_only = parse_with_options("(;B[aa]B[bb])", &merge).unwrap();
+        assert_eq!(first_only.tree.to_string(), "(;B[aa])");
+        assert!(parse_with_options("(;AB[aa][bb])", &reject).is_ok());
+    }
+
+    #[test]
+    fn handles_duplicate_identifiers_with_options() {
+        let input = "(;SZ[19];B[aa]TR[aa]XX[1]TR[aa][bb]XX[2]XX[1])";
+        let options = ParserOptions::new().duplicates(DuplicateMode::Merge);
+        let merged = parse_with_options(input, &options).unwrap();
+        assert!(merged.warnings.is_empty());
+        assert_eq!(merged.tree.to_string(), "(;SZ[19];B[aa]TR[aa][bb]XX[1][2])");
+
+        let options = ParserOptions {
+            duplicates: DuplicateMode::Reject,
+            ..ParserOptions::default()
+        };
+        let err = parse_with_options(input, &options).unwrap_err();
+        assert_eq!(err.kind, SgfErrorKind::DuplicateIdentifier);
+
+        let warned = parse_with_options(input, &ParserOptions::default()).unwrap();
+        assert_eq!(warned.warnings.len(), 2);
+        assert_eq!(warned.tree, parse(input).unwrap());
+    }
+}